@@ -0,0 +1,165 @@
+//! Move generation, action, perft, and search throughput on the standard board, as in
+//! [`Preset::CaptureAll`], and a large one.
+//!
+//! Run with `cargo bench`, every benchmark runs once as a smoke test under `cargo test --benches`.
+
+use kapto::collections::{HashMap, HashSet};
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use kapto::engine::Engine;
+use kapto::eval::ParamEvaluator;
+use kapto::game::playout::RandomPolicy;
+use kapto::game::Game;
+use kapto::movegen::{legal_actions, perft};
+use kapto::ruleset::board_type::goal_locations::GoalLocations;
+use kapto::ruleset::board_type::BoardType;
+use kapto::ruleset::presets::Preset;
+use kapto::ruleset::standard::standard_pieces;
+use kapto::ruleset::starting_positions::placement_area::PlacementArea;
+use kapto::ruleset::starting_positions::StartingPositions;
+use kapto::ruleset::victory_condition::VictoryCondition;
+use kapto::ruleset::Ruleset;
+use kapto::search::mcts::{EvaluatorModel, Mcts, MctsConfig};
+
+/// Whether this is a `cargo bench` run rather than the smoke test, which runs unoptimized.
+fn measuring() -> bool {
+    std::env::args().any(|arg| arg == "--bench")
+}
+
+/// A 14×14 board with 24 pieces a side.
+fn large_rules() -> Ruleset {
+    let mut piece_counts = HashMap::new();
+    piece_counts.insert(0, 8);
+    piece_counts.insert(1, 16);
+    let mut victory_conditions = HashSet::new();
+    victory_conditions.insert(VictoryCondition::AllCaptured);
+    Ruleset {
+        pieces: standard_pieces(),
+        board_type: BoardType::Rectangular {
+            rows: 14,
+            columns: 14,
+            goal_depth: 1,
+            red_goals: GoalLocations::Centered { width: 4 },
+            blue_goals: GoalLocations::Centered { width: 4 },
+        },
+        starting_positions: StartingPositions::Random {
+            seed: 1,
+            piece_counts,
+            area: PlacementArea::Half,
+        },
+        victory_conditions,
+        stalemate_rule: Default::default(),
+        pass_rule: Default::default(),
+        scoring: Default::default(),
+        game_length_rule: Default::default(),
+        chance_rule: Default::default(),
+    }
+}
+
+/// The game after `plies` random actions, so boards have gaps to move and jump into.
+fn midgame(ruleset: Ruleset, plies: usize) -> Game {
+    let mut game = Game::new(ruleset).expect("Benchmark rulesets verify");
+    let mut policy = RandomPolicy::new(7);
+    for _ in 0..plies {
+        match policy.choose_action(&game) {
+            Some(action) => {
+                game.apply(&action).expect("Legal actions apply");
+            }
+            None => break,
+        }
+    }
+    game
+}
+
+/// Both benchmark boards ten plies in.
+fn games() -> Vec<(&'static str, Game)> {
+    vec![
+        (
+            "standard",
+            midgame(Preset::CaptureAll.ruleset().expect("Presets verify"), 10),
+        ),
+        ("large", midgame(large_rules(), 10)),
+    ]
+}
+
+fn movegen(c: &mut Criterion) {
+    let mut group = c.benchmark_group("legal_actions");
+    for (name, game) in games() {
+        let actions = legal_actions(game.board(), game.to_move()).len();
+        group.throughput(Throughput::Elements(actions as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &game, |b, game| {
+            b.iter(|| legal_actions(black_box(game.board()), game.to_move()))
+        });
+    }
+    group.finish();
+}
+
+fn apply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("apply");
+    for (name, game) in games() {
+        let actions = game.legal_actions();
+        group.throughput(Throughput::Elements(actions.len() as u64));
+        // Games cannot undo, applying to a clone is how engines try actions
+        group.bench_with_input(BenchmarkId::new("game", name), &game, |b, game| {
+            b.iter(|| {
+                for action in actions.iter() {
+                    let mut after = game.clone();
+                    black_box(after.apply(action).expect("Legal actions apply"));
+                }
+            })
+        });
+        // Dropping a speculative board undoes its action
+        group.bench_with_input(BenchmarkId::new("undo", name), &game, |b, game| {
+            b.iter(|| {
+                for action in actions.iter() {
+                    black_box(
+                        game.board()
+                            .apply_speculative(action)
+                            .expect("Legal actions apply"),
+                    );
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn perft_depth(c: &mut Criterion) {
+    let depth = if measuring() { 3 } else { 1 };
+    let mut group = c.benchmark_group(format!("perft_{}", depth));
+    group.sample_size(10);
+    for (name, game) in games() {
+        group.throughput(Throughput::Elements(perft(&game, depth)));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &game, |b, game| {
+            b.iter(|| perft(black_box(game), depth))
+        });
+    }
+    group.finish();
+}
+
+fn search(c: &mut Criterion) {
+    let simulations = if measuring() { 200 } else { 8 };
+    let mut group = c.benchmark_group("mcts_nodes");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(simulations as u64));
+    for (name, game) in games() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &game, |b, game| {
+            b.iter(|| {
+                let mut mcts = Mcts::new(
+                    MctsConfig {
+                        simulations,
+                        ..MctsConfig::default()
+                    },
+                    EvaluatorModel::new(ParamEvaluator::default()),
+                );
+                mcts.search(black_box(game))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, movegen, apply, perft_depth, search);
+criterion_main!(benches);
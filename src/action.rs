@@ -1,29 +1,319 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::action::jump_directions::JumpDirections;
 use crate::coordinate::Coordinate;
 use crate::direction::Direction;
-use crate::game_board::Piece;
+use crate::game_board::{Color, PieceInstance};
+
+pub mod builder;
+pub mod jump_directions;
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub struct Action {
     pub start_pos: Coordinate,
     pub action_type: ActionType,
 }
+impl Action {
+    /// Passes the turn, see [`PassRule`](crate::ruleset::pass_rule::PassRule).
+    pub fn pass() -> Self {
+        Self {
+            start_pos: Coordinate::new(0, 0),
+            action_type: ActionType::Pass,
+        }
+    }
+
+    /// Where the moving piece ends up, `start_pos` for passes.
+    pub fn end_pos(&self) -> Coordinate {
+        match &self.action_type {
+            ActionType::Move(direction) => direction.offset() + self.start_pos,
+            ActionType::Jump(directions) => directions
+                .iter()
+                .fold(self.start_pos, |position, direction| {
+                    direction.offset() * 2 + position
+                }),
+            ActionType::Pass => self.start_pos,
+        }
+    }
+
+    /// This action's index in `0..dims.actions()`, such as for a policy vector.
+    ///
+    /// Moves and jumps are grouped by their start square in row major order, passing is the last
+    /// index. `None` if the start is off the board or the jump chain is longer than
+    /// [`BoardDims::max_jumps`].
+    pub fn to_index(&self, dims: BoardDims) -> Option<usize> {
+        if let ActionType::Pass = self.action_type {
+            return Some(dims.actions() - 1);
+        }
+        let position = self.start_pos;
+        if position.row < 0
+            || position.column < 0
+            || position.row as usize >= dims.rows
+            || position.column as usize >= dims.columns
+        {
+            return None;
+        }
+        let square = position.row as usize * dims.columns + position.column as usize;
+        let offset = match &self.action_type {
+            ActionType::Move(direction) => direction_index(*direction),
+            ActionType::Jump(directions) => {
+                if directions.is_empty() || directions.len() > dims.max_jumps {
+                    return None;
+                }
+                // Shorter chains first, then the chain's directions as base 8 digits
+                let shorter: usize = (1..directions.len())
+                    .map(|length| DIRECTIONS.pow(length as u32))
+                    .sum();
+                let digits = directions.iter().fold(0, |digits, direction| {
+                    digits * DIRECTIONS + direction_index(*direction)
+                });
+                DIRECTIONS + shorter + digits
+            }
+            ActionType::Pass => unreachable!(),
+        };
+        Some(square * dims.per_square() + offset)
+    }
+
+    /// The action at `index` of [`Action::to_index`], `None` past the end.
+    ///
+    /// The action need not be legal in any position.
+    pub fn from_index(index: usize, dims: BoardDims) -> Option<Action> {
+        let actions = dims.actions();
+        if index >= actions {
+            return None;
+        }
+        if index == actions - 1 {
+            return Some(Action::pass());
+        }
+        let square = index / dims.per_square();
+        let start_pos = Coordinate::new(
+            (square / dims.columns) as i16,
+            (square % dims.columns) as i16,
+        );
+        let mut offset = index % dims.per_square();
+        let directions: Vec<Direction> = Direction::into_enum_iter().collect();
+        if offset < DIRECTIONS {
+            return Some(Action {
+                start_pos,
+                action_type: ActionType::Move(directions[offset]),
+            });
+        }
+        offset -= DIRECTIONS;
+        let mut length = 1;
+        while offset >= DIRECTIONS.pow(length as u32) {
+            offset -= DIRECTIONS.pow(length as u32);
+            length += 1;
+        }
+        let mut chain = vec![Direction::North; length];
+        for direction in chain.iter_mut().rev() {
+            *direction = directions[offset % DIRECTIONS];
+            offset /= DIRECTIONS;
+        }
+        Some(Action {
+            start_pos,
+            action_type: ActionType::Jump(chain.into()),
+        })
+    }
+}
+
+/// Number of [`Direction`]s.
+const DIRECTIONS: usize = 8;
+
+fn direction_index(direction: Direction) -> usize {
+    Direction::into_enum_iter()
+        .position(|other| other == direction)
+        .expect("Every direction is in the iterator")
+}
+
+/// The shape of the action space of [`Action::to_index`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardDims {
+    pub rows: usize,
+    pub columns: usize,
+    /// The longest jump chain with an index, each extra jump multiplies the space by 8 so this
+    /// should stay small.
+    pub max_jumps: usize,
+}
+impl BoardDims {
+    pub fn new(rows: usize, columns: usize, max_jumps: usize) -> Self {
+        Self {
+            rows,
+            columns,
+            max_jumps,
+        }
+    }
 
-#[derive(Debug)]
+    /// Indices for each start square, one per move direction and per jump chain.
+    pub fn per_square(&self) -> usize {
+        (0..=self.max_jumps)
+            .map(|length| DIRECTIONS.pow(length as u32))
+            .sum::<usize>()
+            + DIRECTIONS
+            - 1
+    }
+    /// Size of the whole action space.
+    pub fn actions(&self) -> usize {
+        self.rows * self.columns * self.per_square() + 1
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum ActionType {
     Move(Direction),
-    Jump(Vec<Direction>),
+    Jump(JumpDirections),
+    /// Leaves the board unchanged, `start_pos` is ignored.
+    Pass,
+}
+impl ActionType {
+    pub fn is_move(&self) -> bool {
+        matches!(self, ActionType::Move(_))
+    }
+
+    pub fn is_jump(&self) -> bool {
+        matches!(self, ActionType::Jump(_))
+    }
+
+    pub fn is_pass(&self) -> bool {
+        matches!(self, ActionType::Pass)
+    }
+}
+
+/// What happened when an action was applied.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct ActionOutcome {
+    /// Captured pieces and the positions they were captured from, in capture order.
+    pub captures: Vec<(Coordinate, PieceInstance)>,
+    /// The moved piece and the goal square it ended on, if it moved onto one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub goal_entries: Vec<(Coordinate, PieceInstance)>,
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ActionError {
     InvalidStartPosition,
     NoPieceAtStart,
-    PieceOnMove(Piece),
+    PieceOnMove(PieceInstance),
     MoveOffBoard,
     EmptyJump,
-    PieceOnJump(Piece),
+    PieceOnJump(PieceInstance),
     NoPieceJumped,
     JumpOffBoard,
     JumpedBackToPrevPosition,
-    MultipleJumpsForSmall,
+    /// The piece's [`JumpLimit`](crate::ruleset::piece_definition::JumpLimit) is `Cannot`.
+    CannotJump,
+    /// The jump chain is longer than the piece's limit.
+    JumpLimitExceeded(usize),
+    JumpDirectionNotAllowed(Direction),
+    /// A jump over the same piece along the same line as an earlier jump in the chain.
+    RepeatedJump,
+    /// The jump chain ends where the piece's
+    /// [`ChainContinuationRule`](crate::ruleset::piece_definition::ChainContinuationRule) has it
+    /// keep jumping.
+    ChainUnfinished,
+    /// The piece is [`GoalMovementRule::Locked`](crate::ruleset::piece_definition::GoalMovementRule::Locked) in the goal it is in.
+    GoalLocked,
+    /// The piece may only move from a goal to another goal.
+    LeftGoal,
+    /// The piece may not enter its own color's goal.
+    EnteredOwnGoal,
+    /// The piece at the start position belongs to this color which is not the one to move.
+    WrongColor(Color),
+    /// The game already has a result.
+    GameFinished,
+    /// Pieces are still being placed.
+    PlacementInProgress,
+    /// The ruleset's [`PassRule`](crate::ruleset::pass_rule::PassRule) does not allow passing now.
+    PassNotAllowed,
+    /// The piece would land on a [`BoardSpace::Blocked`](crate::game_board::BoardSpace::Blocked) space.
+    Blocked,
+    /// The jump chain continues after landing on a [`BoardSpace::Sticky`](crate::game_board::BoardSpace::Sticky) space.
+    StuckOnSticky,
+    /// An [`ActionBuilder`](builder::ActionBuilder) step after the action already moved.
+    MoveAfterStep,
+    /// A [`ValidatedAction`](crate::game_board::validated::ValidatedAction) applied to a board
+    /// other than the one it was validated on.
+    StaleValidation,
+    /// The face the ruleset's [`ChanceRule`](crate::ruleset::chance_rule::ChanceRule) rolled
+    /// this turn does not allow the action.
+    NotRolled,
+    /// A piece with [`CaptureRequirement::Forced`](crate::ruleset::piece_definition::CaptureRequirement::Forced)
+    /// must capture instead, see [`legal_actions`](crate::movegen::legal_actions).
+    CaptureForced,
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use crate::action::{Action, ActionType, BoardDims};
+    use crate::collections::HashSet;
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn index_test() {
+        let dims = BoardDims::new(3, 2, 2);
+        assert_eq!(dims.per_square(), 8 + 8 + 64);
+        assert_eq!(dims.actions(), 6 * 80 + 1);
+        for index in 0..dims.actions() {
+            let action = Action::from_index(index, dims).unwrap();
+            assert_eq!(action.to_index(dims), Some(index));
+        }
+        assert_eq!(Action::from_index(dims.actions(), dims), None);
+        assert_eq!(Action::pass().to_index(dims), Some(dims.actions() - 1));
+        let too_long = Action {
+            start_pos: Coordinate::new(0, 0),
+            action_type: ActionType::Jump([Direction::North; 3].into()),
+        };
+        assert_eq!(too_long.to_index(dims), None);
+        let off_board = Action {
+            start_pos: Coordinate::new(3, 0),
+            action_type: ActionType::Move(Direction::North),
+        };
+        assert_eq!(off_board.to_index(dims), None);
+
+        // Legal actions of real games within the jump bound get distinct indices
+        let mut playout = Game::new(small_ruleset(3))
+            .unwrap()
+            .playout(RandomPolicy::new(1));
+        loop {
+            let board = &playout.game().board().board;
+            let dims = BoardDims::new(board.rows, board.columns, 4);
+            let actions: Vec<Action> = playout
+                .game()
+                .legal_actions()
+                .into_iter()
+                .filter(|action| match &action.action_type {
+                    ActionType::Jump(directions) => directions.len() <= dims.max_jumps,
+                    _ => true,
+                })
+                .collect();
+            let indices: HashSet<usize> = actions
+                .iter()
+                .map(|action| action.to_index(dims).unwrap())
+                .collect();
+            assert_eq!(indices.len(), actions.len());
+            let decoded: Vec<Action> = actions
+                .iter()
+                .map(|action| Action::from_index(action.to_index(dims).unwrap(), dims).unwrap())
+                .collect();
+            assert_eq!(decoded, actions);
+            if playout.next().is_none() || playout.game().history().len() > 60 {
+                break;
+            }
+        }
+    }
 }
@@ -0,0 +1,234 @@
+use alloc::vec::Vec;
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::action::{Action, ActionError, ActionType};
+use crate::coordinate::Coordinate;
+use crate::direction::Direction;
+use crate::game_board::board::Board;
+use crate::game_board::{Color, GameBoard, JumpChain, JumpStep};
+
+/// A way [`ActionBuilder`] can continue the action it is building.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Continuation {
+    /// A single step to `to`, ending the action.
+    Move {
+        direction: Direction,
+        to: Coordinate,
+    },
+    /// Another jump of the chain.
+    Hop(JumpStep),
+}
+
+/// Builds an action one step at a time, such as from clicks in a UI.
+///
+/// Every step is checked against the board when it is taken, so the action committed is always
+/// valid on it.
+#[derive(Clone, Debug)]
+pub struct ActionBuilder<'a> {
+    board: &'a GameBoard,
+    color: Color,
+    chain: Option<JumpChain>,
+    /// The direction of the action if it is a move.
+    step: Option<Direction>,
+}
+impl<'a> ActionBuilder<'a> {
+    /// Builds an action for `color` on `board`.
+    pub fn new(board: &'a GameBoard, color: Color) -> Self {
+        Self {
+            board,
+            color,
+            chain: None,
+            step: None,
+        }
+    }
+
+    /// Starts over from the piece at `position`, which must be one of `color`'s.
+    pub fn select(&mut self, position: Coordinate) -> Result<(), ActionError> {
+        let piece = match self.board.piece(position) {
+            Ok(Some(piece)) => piece,
+            Ok(None) => return Err(ActionError::NoPieceAtStart),
+            Err(_) => return Err(ActionError::InvalidStartPosition),
+        };
+        if piece.color() != self.color {
+            return Err(ActionError::WrongColor(piece.color()));
+        }
+        self.chain = Some(JumpChain::new(piece, position));
+        self.step = None;
+        Ok(())
+    }
+    /// The start of the action, `None` until a piece is selected.
+    pub fn selected(&self) -> Option<Coordinate> {
+        self.chain.as_ref().map(JumpChain::start)
+    }
+    /// Where the selected piece would be after the steps so far.
+    pub fn position(&self) -> Option<Coordinate> {
+        let chain = self.chain.as_ref()?;
+        Some(match self.step {
+            Some(direction) => self.board.portal_exit(direction.offset() + chain.start()),
+            None => chain.position(),
+        })
+    }
+
+    /// Moves the selected piece one square, only before any jump.
+    pub fn step(&mut self, direction: Direction) -> Result<(), ActionError> {
+        let chain = self
+            .chain
+            .as_ref()
+            .ok_or(ActionError::InvalidStartPosition)?;
+        if self.step.is_some() || !chain.steps().is_empty() {
+            return Err(ActionError::MoveAfterStep);
+        }
+        self.board.is_valid_move(chain.start(), direction)?;
+        self.step = Some(direction);
+        Ok(())
+    }
+    /// Adds a jump in `direction` to the chain, returning it.
+    pub fn hop(&mut self, direction: Direction) -> Result<JumpStep, ActionError> {
+        if self.step.is_some() {
+            return Err(ActionError::MoveAfterStep);
+        }
+        let chain = self
+            .chain
+            .as_mut()
+            .ok_or(ActionError::InvalidStartPosition)?;
+        let step = self.board.next_jump(chain, direction)?;
+        chain.push(step);
+        Ok(step)
+    }
+    /// Takes back the last move or jump, `false` if there was none.
+    pub fn undo(&mut self) -> bool {
+        if self.step.take().is_some() {
+            return true;
+        }
+        self.chain
+            .as_mut()
+            .is_some_and(|chain| chain.pop().is_some())
+    }
+
+    /// Every step the action can continue with, empty once it is a move.
+    pub fn legal_continuations(&self) -> Vec<Continuation> {
+        let chain = match &self.chain {
+            Some(chain) if self.step.is_none() => chain,
+            _ => return Vec::new(),
+        };
+        let mut out = Vec::new();
+        if chain.steps().is_empty() {
+            for direction in Direction::into_enum_iter() {
+                if self.board.is_valid_move(chain.start(), direction).is_ok() {
+                    out.push(Continuation::Move {
+                        direction,
+                        to: self.board.portal_exit(direction.offset() + chain.start()),
+                    });
+                }
+            }
+        }
+        for direction in Direction::into_enum_iter() {
+            if let Ok(step) = self.board.next_jump(chain, direction) {
+                out.push(Continuation::Hop(step));
+            }
+        }
+        out
+    }
+
+    /// The action built so far, at least one move or jump must have been taken.
+    pub fn commit(&self) -> Result<Action, ActionError> {
+        let chain = self
+            .chain
+            .as_ref()
+            .ok_or(ActionError::InvalidStartPosition)?;
+        let action_type = match self.step {
+            Some(direction) => ActionType::Move(direction),
+            None if chain.steps().is_empty() => return Err(ActionError::EmptyJump),
+            None => ActionType::Jump(chain.directions()),
+        };
+        Ok(Action {
+            start_pos: chain.start(),
+            action_type,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::builder::{ActionBuilder, Continuation};
+    use crate::action::{ActionError, ActionType};
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game_board::board::Board;
+    use crate::game_board::{Color, GameBoard, PieceInstance};
+    use crate::movegen::actions_from;
+
+    #[test]
+    fn builder_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        let start = Coordinate::new(3, 0);
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(4, 3)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+
+        let mut builder = ActionBuilder::new(&board, Color::Red);
+        assert!(matches!(
+            builder.commit(),
+            Err(ActionError::InvalidStartPosition)
+        ));
+        assert!(matches!(
+            builder.select(Coordinate::new(1, 1)),
+            Err(ActionError::NoPieceAtStart)
+        ));
+        assert!(matches!(
+            builder.select(Coordinate::new(3, 1)),
+            Err(ActionError::WrongColor(Color::Blue))
+        ));
+        builder.select(start).unwrap();
+        assert!(matches!(builder.commit(), Err(ActionError::EmptyJump)));
+
+        let continuations = builder.legal_continuations();
+        let legal = actions_from(&board, start);
+        let first_steps = legal
+            .iter()
+            .filter(|action| match &action.action_type {
+                ActionType::Jump(directions) => directions.len() == 1,
+                _ => true,
+            })
+            .count();
+        assert_eq!(continuations.len(), first_steps);
+
+        // Builds every legal chain hop by hop
+        for action in legal.iter() {
+            builder.select(start).unwrap();
+            match &action.action_type {
+                ActionType::Move(direction) => {
+                    builder.step(*direction).unwrap();
+                    assert!(builder.legal_continuations().is_empty());
+                    assert!(matches!(
+                        builder.hop(Direction::East),
+                        Err(ActionError::MoveAfterStep)
+                    ));
+                }
+                ActionType::Jump(directions) => {
+                    for direction in directions {
+                        assert!(builder.legal_continuations().iter().any(
+                            |continuation| matches!(continuation, Continuation::Hop(step) if step.direction == *direction)
+                        ));
+                        builder.hop(*direction).unwrap();
+                    }
+                    assert!(matches!(
+                        builder.step(Direction::North),
+                        Err(ActionError::MoveAfterStep)
+                    ));
+                }
+                ActionType::Pass => unreachable!(),
+            }
+            assert_eq!(&builder.commit().unwrap(), action);
+            assert_eq!(builder.position(), board.destination(action));
+        }
+
+        builder.select(start).unwrap();
+        assert!(builder.hop(Direction::South).is_err());
+        builder.hop(Direction::East).unwrap();
+        assert!(builder.undo());
+        assert!(!builder.undo());
+        assert_eq!(builder.position(), Some(start));
+    }
+}
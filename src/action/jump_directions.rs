@@ -0,0 +1,214 @@
+//! Jump chain directions stored without allocating for common chain lengths.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::ops::{Deref, DerefMut};
+
+use crate::direction::Direction;
+
+/// Chains up to this long are stored inline.
+pub const INLINE_JUMPS: usize = 15;
+
+/// The direction of every jump in a chain, in order.
+///
+/// Behaves as a `[Direction]` and serializes as a list like `Vec<Direction>`, chains longer
+/// than [`INLINE_JUMPS`] move to the heap.
+#[derive(Clone)]
+pub struct JumpDirections(Repr);
+#[derive(Clone)]
+enum Repr {
+    Inline {
+        len: u8,
+        directions: [Direction; INLINE_JUMPS],
+    },
+    Heap(Vec<Direction>),
+}
+impl JumpDirections {
+    pub fn new() -> Self {
+        Self(Repr::Inline {
+            len: 0,
+            directions: [Direction::North; INLINE_JUMPS],
+        })
+    }
+    pub fn push(&mut self, direction: Direction) {
+        match &mut self.0 {
+            Repr::Inline { len, directions } if (*len as usize) < INLINE_JUMPS => {
+                directions[*len as usize] = direction;
+                *len += 1;
+            }
+            Repr::Inline { directions, .. } => {
+                let mut heap = Vec::with_capacity(INLINE_JUMPS * 2);
+                heap.extend_from_slice(directions);
+                heap.push(direction);
+                self.0 = Repr::Heap(heap);
+            }
+            Repr::Heap(heap) => heap.push(direction),
+        }
+    }
+    pub fn pop(&mut self) -> Option<Direction> {
+        match &mut self.0 {
+            Repr::Inline { len: 0, .. } => None,
+            Repr::Inline { len, directions } => {
+                *len -= 1;
+                Some(directions[*len as usize])
+            }
+            Repr::Heap(heap) => heap.pop(),
+        }
+    }
+    /// Whether the chain is stored on the heap.
+    pub fn spilled(&self) -> bool {
+        matches!(self.0, Repr::Heap(_))
+    }
+    pub fn as_slice(&self) -> &[Direction] {
+        self
+    }
+}
+impl Default for JumpDirections {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Deref for JumpDirections {
+    type Target = [Direction];
+
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            Repr::Inline { len, directions } => &directions[..*len as usize],
+            Repr::Heap(heap) => heap,
+        }
+    }
+}
+impl DerefMut for JumpDirections {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.0 {
+            Repr::Inline { len, directions } => &mut directions[..*len as usize],
+            Repr::Heap(heap) => heap,
+        }
+    }
+}
+impl PartialEq for JumpDirections {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+impl Eq for JumpDirections {}
+impl Hash for JumpDirections {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+impl Debug for JumpDirections {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+impl FromIterator<Direction> for JumpDirections {
+    fn from_iter<T: IntoIterator<Item = Direction>>(iter: T) -> Self {
+        let mut out = Self::new();
+        out.extend(iter);
+        out
+    }
+}
+impl Extend<Direction> for JumpDirections {
+    fn extend<T: IntoIterator<Item = Direction>>(&mut self, iter: T) {
+        for direction in iter {
+            self.push(direction);
+        }
+    }
+}
+impl From<&[Direction]> for JumpDirections {
+    fn from(from: &[Direction]) -> Self {
+        from.iter().copied().collect()
+    }
+}
+impl From<Vec<Direction>> for JumpDirections {
+    fn from(from: Vec<Direction>) -> Self {
+        if from.len() > INLINE_JUMPS {
+            Self(Repr::Heap(from))
+        } else {
+            from.as_slice().into()
+        }
+    }
+}
+impl<const N: usize> From<[Direction; N]> for JumpDirections {
+    fn from(from: [Direction; N]) -> Self {
+        from.iter().copied().collect()
+    }
+}
+impl From<JumpDirections> for Vec<Direction> {
+    fn from(from: JumpDirections) -> Self {
+        match from.0 {
+            Repr::Heap(heap) => heap,
+            Repr::Inline { .. } => from.to_vec(),
+        }
+    }
+}
+impl<'a> IntoIterator for &'a JumpDirections {
+    type Item = &'a Direction;
+    type IntoIter = core::slice::Iter<'a, Direction>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for JumpDirections {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for JumpDirections {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Direction>::deserialize(deserializer).map(Self::from)
+    }
+}
+#[cfg(feature = "formats")]
+impl schemars::JsonSchema for JumpDirections {
+    fn is_referenceable() -> bool {
+        <Vec<Direction>>::is_referenceable()
+    }
+    fn schema_name() -> alloc::string::String {
+        <Vec<Direction>>::schema_name()
+    }
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        <Vec<Direction>>::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use crate::action::jump_directions::{JumpDirections, INLINE_JUMPS};
+    use crate::direction::Direction;
+
+    #[test]
+    fn spill_test() {
+        let mut directions = JumpDirections::new();
+        let mut expected = Vec::new();
+        for index in 0..INLINE_JUMPS + 2 {
+            assert_eq!(directions.spilled(), index > INLINE_JUMPS);
+            let direction = if index % 2 == 0 {
+                Direction::East
+            } else {
+                Direction::SouthWest
+            };
+            directions.push(direction);
+            expected.push(direction);
+            assert_eq!(*directions, *expected);
+        }
+        assert_eq!(directions, JumpDirections::from(expected.clone()));
+        assert_eq!(Vec::from(directions.clone()), expected);
+        assert_eq!(directions.pop(), expected.pop());
+
+        let short = JumpDirections::from([Direction::North, Direction::West]);
+        assert!(!short.spilled());
+        assert_eq!(short, JumpDirections::from(short.to_vec()));
+        assert_eq!(format!("{:?}", short), "[North, West]");
+    }
+}
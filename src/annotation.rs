@@ -0,0 +1,213 @@
+//! Automatic annotation of recorded games by an engine's scores.
+//!
+//! Every position is analyzed before and after its action, an action losing more than a
+//! threshold of [`Candidate::score`] against the engine's best is a mistake or a blunder, an
+//! action that was much better than every alternative is good.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::engine::Engine;
+use crate::game::record::GameRecord;
+use crate::game::{GameError, GameOutcome, GameResult};
+use crate::game_board::Color;
+use crate::notation::{format_action, CoordinateScheme};
+
+/// Score differences for each annotation, in the `-1.0..=1.0` range of [`Candidate::score`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnotationThresholds {
+    /// Least loss against the best action for a mistake.
+    pub mistake: f32,
+    /// Least loss against the best action for a blunder.
+    pub blunder: f32,
+    /// Least lead of the best action over the second for playing it to be good.
+    pub good: f32,
+}
+impl Default for AnnotationThresholds {
+    fn default() -> Self {
+        Self {
+            mistake: 0.2,
+            blunder: 0.5,
+            good: 0.3,
+        }
+    }
+}
+
+/// How an action compares to the engine's choice.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Annotation {
+    Good,
+    Mistake,
+    Blunder,
+}
+impl Annotation {
+    /// The usual symbol, `!`, `?`, or `??`.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Good => "!",
+            Self::Mistake => "?",
+            Self::Blunder => "??",
+        }
+    }
+}
+
+/// [`annotate_with`] the default thresholds.
+pub fn annotate(record: &GameRecord, engine: &mut impl Engine) -> GameResult<GameRecord> {
+    annotate_with(record, engine, &AnnotationThresholds::default())
+}
+
+/// `record` with a comment on every annotated action, replacing its comments.
+///
+/// Comments start with the annotation's symbol followed by the score after the action and the
+/// engine's alternative, `?? -0.62, best was b2:d4 (+0.10)`.
+pub fn annotate_with(
+    record: &GameRecord,
+    engine: &mut impl Engine,
+    thresholds: &AnnotationThresholds,
+) -> GameResult<GameRecord> {
+    let mut game = GameRecord {
+        actions: Vec::new(),
+        ..record.clone()
+    }
+    .replay()?;
+    engine.new_game(&game);
+    let mut comments = Vec::with_capacity(record.actions.len());
+    let mut candidates = engine.analyze(&game, 2);
+    for (ply, action) in record.actions.iter().enumerate() {
+        let mover = game.to_move();
+        game.apply(action)
+            .map_err(|error| GameError::IllegalAction { ply, error })?;
+        let next = match game.result() {
+            Some(_) => Vec::new(),
+            None => engine.analyze(&game, 2),
+        };
+        let score = match (game.result(), next.first()) {
+            (Some(GameOutcome::Win(color)), _) if color == mover => 1.0,
+            (Some(GameOutcome::Win(_)), _) => -1.0,
+            (Some(GameOutcome::Draw), _) | (None, None) => 0.0,
+            (None, Some(best)) => perspective(best.score, game.to_move(), mover),
+        };
+
+        let annotation = match candidates.as_slice() {
+            [best, second, ..] if &best.action == action => {
+                (best.score - second.score >= thresholds.good).then_some((Annotation::Good, second))
+            }
+            [best, ..] if &best.action != action => {
+                let loss = best.score - score;
+                if loss >= thresholds.blunder {
+                    Some((Annotation::Blunder, best))
+                } else if loss >= thresholds.mistake {
+                    Some((Annotation::Mistake, best))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        let comment = annotation.map(|(annotation, alternative)| {
+            format!(
+                "{} {:+.2}, {} was {} ({:+.2})",
+                annotation.symbol(),
+                score,
+                match annotation {
+                    Annotation::Good => "next best",
+                    Annotation::Mistake | Annotation::Blunder => "best",
+                },
+                format_action(&alternative.action, CoordinateScheme::Algebraic),
+                alternative.score
+            )
+        });
+        comments.push(comment);
+        candidates = next;
+    }
+    while comments.last() == Some(&None) {
+        comments.pop();
+    }
+    Ok(GameRecord {
+        comments,
+        ..record.clone()
+    })
+}
+
+/// `score` for `color` turned to the view of `perspective`.
+fn perspective(score: f32, color: Color, perspective: Color) -> f32 {
+    if color == perspective {
+        score
+    } else {
+        -score
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use crate::action::Action;
+    use crate::annotation::{annotate, Annotation};
+    use crate::engine::{Candidate, Engine};
+    use crate::eval::{Evaluator, ParamEvaluator};
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::testing::small_ruleset;
+
+    /// Scores every action by the evaluation right after it.
+    struct OnePly;
+    impl Engine for OnePly {
+        fn choose_action(&mut self, game: &Game) -> Option<Action> {
+            self.analyze(game, 1)
+                .pop()
+                .map(|candidate| candidate.action)
+        }
+        fn analyze(&mut self, game: &Game, multipv: usize) -> Vec<Candidate> {
+            let mut candidates: Vec<Candidate> = game
+                .legal_actions()
+                .into_iter()
+                .map(|action| {
+                    let mut after = game.clone();
+                    after.apply(&action).unwrap();
+                    let score = ParamEvaluator::default().evaluate(&after, game.to_move());
+                    Candidate {
+                        pv: vec![action.clone()],
+                        action,
+                        score: (score as f32 / 300.0).clamp(-1.0, 1.0),
+                    }
+                })
+                .collect();
+            candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+            candidates.truncate(multipv);
+            candidates
+        }
+    }
+
+    #[test]
+    fn annotate_test() {
+        let mut playout = Game::new(small_ruleset(3))
+            .unwrap()
+            .playout(RandomPolicy::new(8));
+        playout.by_ref().take(80).for_each(drop);
+        let record = playout.game().record();
+        let annotated = annotate(&record, &mut OnePly).unwrap();
+        assert_eq!(annotated.actions, record.actions);
+        assert!(annotated.comments.len() <= record.actions.len());
+        assert!(annotated.comments.iter().flatten().count() > 0);
+
+        let mut game = Game::new(small_ruleset(3)).unwrap();
+        for (ply, action) in record.actions.iter().enumerate() {
+            let comment = annotated.comments.get(ply).cloned().flatten();
+            let best = OnePly.choose_action(&game).unwrap();
+            if let Some(comment) = comment {
+                let symbol = comment.split(' ').next().unwrap();
+                if &best == action {
+                    assert_eq!(symbol, Annotation::Good.symbol());
+                } else {
+                    assert!([Annotation::Mistake, Annotation::Blunder]
+                        .iter()
+                        .any(|annotation| annotation.symbol() == symbol));
+                }
+            }
+            game.apply(action).unwrap();
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Streams for live spectators.
+//!
+//! A [`Broadcast`] observes a [`Match`](crate::series::Match) and records a stream of
+//! [`BroadcastFrame`]s, a [`SpectatorView`] snapshot every few actions with the actions and
+//! clocks between them. Spectators can join at any frame with [`Broadcast::replay_from`].
+
+use alloc::vec::Vec;
+
+use crate::action::Action;
+use crate::coordinate::Coordinate;
+use crate::game::event::{GameEvent, Phase};
+use crate::game::placement::Placement;
+use crate::game::Game;
+use crate::game_board::{BoardDelta, Color, GameBoard, PieceInstance};
+use crate::series::{MatchObserver, MatchState};
+
+/// One step of a broadcast, frames after a [`BroadcastFrame::GameStarted`] are of that game.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BroadcastFrame {
+    /// Game `game` of the match started with player `red` as Red.
+    GameStarted {
+        game: usize,
+        red: usize,
+    },
+    /// Everything a spectator shows, replacing what came before.
+    Snapshot(SpectatorView),
+    Placed(Placement),
+    /// An action and the changes it made to the board.
+    Action {
+        action: Action,
+        deltas: Vec<BoardDelta>,
+    },
+    /// A piece removed by [`Game::huff`], the same color keeps moving.
+    Huffed {
+        position: Coordinate,
+        piece: PieceInstance,
+    },
+    Clock {
+        color: Color,
+        remaining_ms: u64,
+    },
+    PhaseChanged(Phase),
+}
+
+/// What a spectator sees of a game.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpectatorView {
+    /// The game's index in the match.
+    pub game: usize,
+    pub board: GameBoard,
+    pub to_move: Color,
+    pub phase: Phase,
+    /// Actions played so far.
+    pub moves: usize,
+    /// The last clock reported, for the color deciding.
+    pub clock: Option<(Color, u64)>,
+}
+impl SpectatorView {
+    /// The view of `game`, game `index` of its match.
+    pub fn new(index: usize, game: &Game) -> Self {
+        Self {
+            game: index,
+            board: game
+                .placement()
+                .map_or(game.board(), |phase| phase.board())
+                .clone(),
+            to_move: game.to_move(),
+            phase: game.phase(),
+            moves: game.history().len(),
+            clock: None,
+        }
+    }
+    /// The view after `frames`, `None` if they have no snapshot or one does not apply.
+    pub fn replay(frames: &[BroadcastFrame]) -> Option<Self> {
+        let start = frames
+            .iter()
+            .position(|frame| matches!(frame, BroadcastFrame::Snapshot(_)))?;
+        let mut out = match &frames[start] {
+            BroadcastFrame::Snapshot(view) => view.clone(),
+            _ => unreachable!("Found as a snapshot"),
+        };
+        for frame in frames[start + 1..].iter() {
+            out.apply(frame)?;
+        }
+        Some(out)
+    }
+    /// Updates the view with the next frame, `None` if its deltas do not match the board.
+    pub fn apply(&mut self, frame: &BroadcastFrame) -> Option<()> {
+        match frame {
+            BroadcastFrame::GameStarted { game, .. } => self.game = *game,
+            BroadcastFrame::Snapshot(view) => *self = view.clone(),
+            BroadcastFrame::Placed(placement) => {
+                self.board.apply_deltas(&[BoardDelta::Appeared {
+                    position: placement.position,
+                    piece: PieceInstance::new(placement.piece_index, placement.color),
+                }])?;
+                self.clock = None;
+            }
+            BroadcastFrame::Action { deltas, .. } => {
+                self.board.apply_deltas(deltas)?;
+                self.to_move = self.to_move.opponent();
+                self.moves += 1;
+                self.clock = None;
+            }
+            BroadcastFrame::Huffed { position, piece } => {
+                self.board.apply_deltas(&[BoardDelta::Disappeared {
+                    position: *position,
+                    piece: *piece,
+                }])?;
+            }
+            BroadcastFrame::Clock {
+                color,
+                remaining_ms,
+            } => self.clock = Some((*color, *remaining_ms)),
+            BroadcastFrame::PhaseChanged(phase) => self.phase = *phase,
+        }
+        Some(())
+    }
+}
+
+/// Records a [`BroadcastFrame`] stream of every game it observes.
+#[derive(Clone, Debug)]
+pub struct Broadcast {
+    /// Actions between snapshots, at least 1.
+    snapshot_interval: usize,
+    frames: Vec<BroadcastFrame>,
+    view: Option<SpectatorView>,
+}
+impl Broadcast {
+    /// A broadcast taking a snapshot every `snapshot_interval` actions.
+    pub fn new(snapshot_interval: usize) -> Self {
+        Self {
+            snapshot_interval: snapshot_interval.max(1),
+            frames: Vec::new(),
+            view: None,
+        }
+    }
+    pub fn frames(&self) -> &[BroadcastFrame] {
+        &self.frames
+    }
+    /// What is live now, `None` before the first game.
+    pub fn view(&self) -> Option<&SpectatorView> {
+        self.view.as_ref()
+    }
+    /// The frames to send a spectator joining at `frame`, starting at the last snapshot at or
+    /// before it.
+    pub fn replay_from(&self, frame: usize) -> &[BroadcastFrame] {
+        let frame = frame.min(self.frames.len().saturating_sub(1));
+        let start = self.frames[..self.frames.len().min(frame + 1)]
+            .iter()
+            .rposition(|frame| matches!(frame, BroadcastFrame::Snapshot(_)))
+            .unwrap_or(0);
+        &self.frames[start..]
+    }
+
+    fn push(&mut self, frame: BroadcastFrame) {
+        if let Some(view) = &mut self.view {
+            view.apply(&frame)
+                .expect("Frames are made from the view's board");
+        }
+        self.frames.push(frame);
+    }
+    fn action(&mut self, action: &Action) {
+        let view = match &self.view {
+            Some(view) => view,
+            None => return,
+        };
+        let (after, _) = view
+            .board
+            .apply_action(action)
+            .expect("Observed actions were legal");
+        let deltas = view.board.diff(&after);
+        self.push(BroadcastFrame::Action {
+            action: action.clone(),
+            deltas,
+        });
+        let view = self.view.as_ref().expect("Checked above");
+        if view.moves.is_multiple_of(self.snapshot_interval) {
+            self.frames.push(BroadcastFrame::Snapshot(view.clone()));
+        }
+    }
+    fn phase_changed(&mut self, phase: Phase) {
+        if self.view.as_ref().is_some_and(|view| view.phase != phase) {
+            self.push(BroadcastFrame::PhaseChanged(phase));
+        }
+    }
+}
+impl MatchObserver for Broadcast {
+    fn game_started(&mut self, index: usize, red: usize, game: &Game) {
+        let view = SpectatorView::new(index, game);
+        self.frames
+            .push(BroadcastFrame::GameStarted { game: index, red });
+        self.frames.push(BroadcastFrame::Snapshot(view.clone()));
+        self.view = Some(view);
+    }
+    fn event(&mut self, _index: usize, event: &GameEvent) {
+        match event {
+            GameEvent::PhaseChanged(phase) => self.phase_changed(*phase),
+            GameEvent::Placed(placement) => self.push(BroadcastFrame::Placed(*placement)),
+            GameEvent::Revealed(placements) => {
+                for placement in placements {
+                    self.push(BroadcastFrame::Placed(*placement));
+                }
+            }
+            GameEvent::ClockUpdated {
+                color,
+                remaining_ms,
+            } => self.push(BroadcastFrame::Clock {
+                color: *color,
+                remaining_ms: *remaining_ms,
+            }),
+            GameEvent::Action { action, .. } => self.action(action),
+            GameEvent::Huffed { position, piece } => self.push(BroadcastFrame::Huffed {
+                position: *position,
+                piece: *piece,
+            }),
+            GameEvent::ReserveChanged { .. } => {}
+        }
+    }
+    fn game_finished(&mut self, _index: usize, state: &MatchState) {
+        if let Some(outcome) = state.records.last().and_then(|record| record.result) {
+            self.phase_changed(Phase::Finished(outcome));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use crate::broadcast::{Broadcast, BroadcastFrame, SpectatorView};
+    use crate::engine::{GreedyCapturePlayer, RandomPlayer};
+    use crate::series::{Match, MatchConfig};
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn broadcast_test() {
+        let mut broadcast = Broadcast::new(4);
+        let state = Match::new(
+            small_ruleset(0),
+            MatchConfig {
+                games: 1,
+                max_plies: 30,
+                ..MatchConfig::default()
+            },
+            Box::new(GreedyCapturePlayer::new(0)),
+            Box::new(RandomPlayer::new(1)),
+        )
+        .with_observer(Box::new(&mut broadcast))
+        .run()
+        .unwrap()
+        .clone();
+        let record = &state.records[0];
+        let frames = broadcast.frames();
+        assert!(matches!(
+            frames[0],
+            BroadcastFrame::GameStarted { game: 0, .. }
+        ));
+        let actions = frames
+            .iter()
+            .filter(|frame| matches!(frame, BroadcastFrame::Action { .. }))
+            .count();
+        assert_eq!(actions, record.actions.len());
+        let snapshots = frames
+            .iter()
+            .filter(|frame| matches!(frame, BroadcastFrame::Snapshot(_)))
+            .count();
+        assert_eq!(snapshots, 1 + actions / 4);
+
+        let live = broadcast.view().unwrap();
+        assert_eq!(live.moves, actions);
+        for frame in 0..frames.len() {
+            let replay = broadcast.replay_from(frame);
+            assert!(replay.len() + frame >= frames.len());
+            let view = SpectatorView::replay(replay).unwrap();
+            assert_eq!(view.moves, live.moves);
+            assert_eq!(view.phase, live.phase);
+            assert!(view.board.diff(&live.board).is_empty());
+        }
+    }
+}
@@ -1,11 +1,22 @@
-use std::ops::MulAssign;
-use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
-
-use matrix::Position;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::ops::MulAssign;
+use core::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+use core::str::FromStr;
 
+use crate::direction::Direction;
+use crate::game_board::Color;
+use crate::grid::Position;
+use crate::notation::{CoordinateScheme, NotationError};
 use crate::ruleset::board_type::BoardType;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+/// A position on the board.
+///
+/// Coordinates are always canonical, see [`Orientation`].
+/// Ordering is row major.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub struct Coordinate {
     pub row: i16,
     pub column: i16,
@@ -15,6 +26,60 @@ impl Coordinate {
         Self { row, column }
     }
 }
+/// Squares in `scheme`, see [`Coordinate::display`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CoordinateDisplay {
+    coordinate: Coordinate,
+    scheme: CoordinateScheme,
+}
+impl Display for CoordinateDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.scheme.format_square(self.coordinate))
+    }
+}
+impl Coordinate {
+    /// This square written in `scheme`, it must be on the board.
+    pub fn display(self, scheme: CoordinateScheme) -> CoordinateDisplay {
+        CoordinateDisplay {
+            coordinate: self,
+            scheme,
+        }
+    }
+}
+/// [`CoordinateScheme::Algebraic`], or `(row,column)` for squares above or left of the board.
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.row < 0 || self.column < 0 {
+            write!(f, "({},{})", self.row, self.column)
+        } else {
+            self.display(CoordinateScheme::Algebraic).fmt(f)
+        }
+    }
+}
+/// Either [`CoordinateScheme::Algebraic`] like `c4` or a row and column like `(3,2)` or `3,2`.
+///
+/// Parenthesized rows and columns may be negative, so every [`Display`]ed coordinate parses.
+impl FromStr for Coordinate {
+    type Err = NotationError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let signed = text
+            .strip_prefix('(')
+            .and_then(|pair| pair.strip_suffix(')'))
+            .and_then(|pair| pair.split_once(','))
+            .and_then(|(row, column)| {
+                Some((row.trim().parse().ok()?, column.trim().parse().ok()?))
+            });
+        if let Some((row, column)) = signed {
+            return Ok(Coordinate::new(row, column));
+        }
+        if text.contains(',') {
+            CoordinateScheme::RowColumn.parse_square(text)
+        } else {
+            CoordinateScheme::Algebraic.parse_square(text)
+        }
+    }
+}
 impl Position for Coordinate {
     fn row(&self) -> usize {
         self.row as usize
@@ -32,7 +97,7 @@ impl Add for Coordinate {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self::new(self.row + rhs.row, self.column)
+        Self::new(self.row + rhs.row, self.column + rhs.column)
     }
 }
 impl AddAssign for Coordinate {
@@ -68,12 +133,216 @@ impl MulAssign<i16> for Coordinate {
     }
 }
 
+/// A view of the board relative to the canonical orientation.
+///
+/// The canonical orientation has `(0, 0)` in the top left corner with Red on top.
+/// Rows increase towards the south (Blue) and columns increase towards the east.
+/// [`Direction::North`] is therefore a row offset of -1 and [`Direction::East`] a column offset of +1.
+///
+/// Every orientation is its own inverse, so converting to and from canonical is the same operation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    /// The canonical orientation.
+    Standard,
+    /// Mirrored about the horizontal center, north and south are swapped.
+    Flipped,
+    /// Mirrored about the vertical center, east and west are swapped.
+    Mirrored,
+    /// Rotated 180 degrees, both axes are swapped.
+    Rotated,
+}
+impl Orientation {
+    pub const ALL: [Orientation; 4] = [
+        Orientation::Standard,
+        Orientation::Flipped,
+        Orientation::Mirrored,
+        Orientation::Rotated,
+    ];
+
+    /// The orientation a player of `color` sees, with their own side at the bottom.
+    pub fn viewed_by(color: Color) -> Self {
+        match color {
+            Color::Red => Orientation::Rotated,
+            Color::Blue => Orientation::Standard,
+        }
+    }
+
+    fn flips_rows(self) -> bool {
+        matches!(self, Orientation::Flipped | Orientation::Rotated)
+    }
+    fn flips_columns(self) -> bool {
+        matches!(self, Orientation::Mirrored | Orientation::Rotated)
+    }
+
+    /// Converts a canonical coordinate on a board of `rows` by `columns` into this orientation.
+    pub fn from_canonical(self, rows: usize, columns: usize, coordinate: Coordinate) -> Coordinate {
+        let row = if self.flips_rows() {
+            rows as i16 - coordinate.row - 1
+        } else {
+            coordinate.row
+        };
+        let column = if self.flips_columns() {
+            columns as i16 - coordinate.column - 1
+        } else {
+            coordinate.column
+        };
+        Coordinate::new(row, column)
+    }
+    /// Converts a coordinate in this orientation on a board of `rows` by `columns` into canonical.
+    pub fn to_canonical(self, rows: usize, columns: usize, coordinate: Coordinate) -> Coordinate {
+        self.from_canonical(rows, columns, coordinate)
+    }
+
+    /// Converts a canonical direction into this orientation.
+    pub fn direction_from_canonical(self, direction: Direction) -> Direction {
+        let (mut north, mut east) = direction.components();
+        if self.flips_rows() {
+            north = -north;
+        }
+        if self.flips_columns() {
+            east = -east;
+        }
+        Direction::from_components(north, east).expect("Components come from a direction")
+    }
+    /// Converts a direction in this orientation into canonical.
+    pub fn direction_to_canonical(self, direction: Direction) -> Direction {
+        self.direction_from_canonical(direction)
+    }
+}
+
+/// Mirrors a coordinate about the board's horizontal center.
 pub fn flip_coordinate(board: &BoardType, coordinate: Coordinate) -> Coordinate {
-    Coordinate::new(board.rows() as i16 - coordinate.row - 1, coordinate.column)
+    Orientation::Flipped.from_canonical(board.rows(), board.columns(), coordinate)
 }
+/// Rotates a coordinate 180 degrees about the board's center.
 pub fn rotate_coordinate(board: &BoardType, coordinate: Coordinate) -> Coordinate {
-    Coordinate::new(
-        board.rows() as i16 - coordinate.row - 1,
-        board.columns() as i16 - coordinate.column - 1,
-    )
+    Orientation::Rotated.from_canonical(board.rows(), board.columns(), coordinate)
+}
+
+#[cfg(test)]
+mod test {
+    use enum_iterator::IntoEnumIterator;
+
+    use alloc::string::ToString;
+
+    use crate::coordinate::{Coordinate, Orientation};
+    use crate::direction::Direction;
+    use crate::game_board::Color;
+    use crate::notation::CoordinateScheme;
+
+    const ROWS: usize = 5;
+    const COLUMNS: usize = 4;
+
+    fn all_coordinates() -> impl Iterator<Item = Coordinate> {
+        (0..ROWS as i16)
+            .flat_map(|row| (0..COLUMNS as i16).map(move |column| Coordinate::new(row, column)))
+    }
+
+    #[test]
+    fn canonical_offsets_test() {
+        assert_eq!(Direction::North.offset(), Coordinate::new(-1, 0));
+        assert_eq!(Direction::South.offset(), Coordinate::new(1, 0));
+        assert_eq!(Direction::East.offset(), Coordinate::new(0, 1));
+        assert_eq!(Direction::West.offset(), Coordinate::new(0, -1));
+        assert_eq!(Direction::NorthWest.offset(), Coordinate::new(-1, -1));
+        assert_eq!(Direction::NorthEast.offset(), Coordinate::new(-1, 1));
+        assert_eq!(Direction::SouthWest.offset(), Coordinate::new(1, -1));
+        assert_eq!(Direction::SouthEast.offset(), Coordinate::new(1, 1));
+    }
+
+    #[test]
+    fn add_test() {
+        assert_eq!(
+            Coordinate::new(1, 2) + Coordinate::new(3, 4),
+            Coordinate::new(4, 6)
+        );
+    }
+
+    #[test]
+    fn orientation_involution_test() {
+        for orientation in Orientation::ALL.iter().copied() {
+            for coordinate in all_coordinates() {
+                let converted = orientation.from_canonical(ROWS, COLUMNS, coordinate);
+                assert!(converted.row >= 0 && (converted.row as usize) < ROWS);
+                assert!(converted.column >= 0 && (converted.column as usize) < COLUMNS);
+                assert_eq!(
+                    orientation.to_canonical(ROWS, COLUMNS, converted),
+                    coordinate
+                );
+            }
+            for direction in Direction::into_enum_iter() {
+                let converted = orientation.direction_from_canonical(direction);
+                assert_eq!(orientation.direction_to_canonical(converted), direction);
+            }
+        }
+    }
+
+    #[test]
+    fn orientation_step_test() {
+        for orientation in Orientation::ALL.iter().copied() {
+            for coordinate in all_coordinates() {
+                for direction in Direction::into_enum_iter() {
+                    let stepped =
+                        orientation.from_canonical(ROWS, COLUMNS, coordinate + direction.offset());
+                    let oriented = orientation.from_canonical(ROWS, COLUMNS, coordinate)
+                        + orientation.direction_from_canonical(direction).offset();
+                    assert_eq!(stepped, oriented);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn viewed_by_test() {
+        let red_corner = Coordinate::new(0, 0);
+        let blue_corner = Coordinate::new(ROWS as i16 - 1, COLUMNS as i16 - 1);
+        for color in Color::into_enum_iter() {
+            let own_corner = match color {
+                Color::Red => red_corner,
+                Color::Blue => blue_corner,
+            };
+            let viewed = Orientation::viewed_by(color).from_canonical(ROWS, COLUMNS, own_corner);
+            assert_eq!(viewed, blue_corner);
+        }
+    }
+
+    #[test]
+    fn parse_display_test() {
+        assert_eq!("c4".parse::<Coordinate>().unwrap(), Coordinate::new(3, 2));
+        assert_eq!(
+            "(3,2)".parse::<Coordinate>().unwrap(),
+            Coordinate::new(3, 2)
+        );
+        assert_eq!(
+            "( 3, 2 )".parse::<Coordinate>().unwrap(),
+            Coordinate::new(3, 2)
+        );
+        assert_eq!("3,2".parse::<Coordinate>().unwrap(), Coordinate::new(3, 2));
+        assert_eq!(
+            "(-1,0)".parse::<Coordinate>().unwrap(),
+            Coordinate::new(-1, 0)
+        );
+        for text in ["", "c", "4", "(3,2", "3,", "-1,0", "C4"] {
+            assert!(text.parse::<Coordinate>().is_err(), "{}", text);
+        }
+
+        for coordinate in all_coordinates().chain([Coordinate::new(-1, 2), Coordinate::new(0, -3)])
+        {
+            let text = coordinate.to_string();
+            assert_eq!(text.parse::<Coordinate>().unwrap(), coordinate);
+        }
+        assert_eq!(Coordinate::new(3, 2).to_string(), "c4");
+        let position = Coordinate::new(1, 3);
+        assert_eq!(
+            position.display(CoordinateScheme::RowColumn).to_string(),
+            "1,3"
+        );
+        assert_eq!(
+            position
+                .display(CoordinateScheme::Numeric { columns: COLUMNS })
+                .to_string(),
+            "8"
+        );
+    }
 }
@@ -0,0 +1,310 @@
+//! Training data for machine learning from recorded games.
+//!
+//! Every position before an action becomes one sample of `0`/`1` input planes, all
+//! `rows * columns` of the board including the goal rows:
+//!
+//! - Plane `2 * k` holds Red's pieces of definition `k`, plane `2 * k + 1` Blue's.
+//! - The last plane is all ones when Red is to move and all zeros when Blue is.
+//!
+//! Each sample's value is the final result from the view of the color to move, `1` for a win,
+//! `0` for a draw, and `-1` for a loss.
+//!
+//! [`Dataset::to_npz`] stores both as an uncompressed NPZ archive that `numpy.load` reads
+//! directly, `inputs.npy` is a `uint8` array of shape `(samples, planes, rows, columns)` and
+//! `values.npy` an `int8` array of shape `(samples,)`.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::game::record::GameRecord;
+use crate::game::{Game, GameError, GameOutcome};
+use crate::game_board::Color;
+use crate::ruleset::Ruleset;
+
+/// Samples from records of games on boards of the same shape.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dataset {
+    pub rows: usize,
+    pub columns: usize,
+    /// Input planes per sample, two per piece definition and the side to move.
+    pub planes: usize,
+    /// Every sample's planes in order, `samples * planes * rows * columns` values.
+    pub inputs: Vec<u8>,
+    /// Every sample's value.
+    pub values: Vec<i8>,
+}
+impl Dataset {
+    /// An empty dataset shaped for games of `ruleset`.
+    pub fn new(ruleset: &Ruleset) -> DatasetResult<Self> {
+        let game = Game::new(ruleset.clone())?;
+        Ok(Self {
+            rows: game.board().board.rows,
+            columns: game.board().board.columns,
+            planes: 2 * ruleset.pieces.len() + 1,
+            inputs: Vec::new(),
+            values: Vec::new(),
+        })
+    }
+
+    /// Adds every position of the finished game in `record`, returning the number of samples.
+    pub fn push(&mut self, record: &GameRecord) -> DatasetResult<usize> {
+        let final_game = record.replay()?;
+        let result = final_game.result().ok_or(DatasetError::Unfinished)?;
+        let board = &final_game.board().board;
+        let planes = 2 * record.ruleset.pieces.len() + 1;
+        if (board.rows, board.columns, planes) != (self.rows, self.columns, self.planes) {
+            return Err(DatasetError::ShapeMismatch {
+                rows: board.rows,
+                columns: board.columns,
+                planes,
+            });
+        }
+
+        let mut game = GameRecord {
+            actions: Vec::new(),
+            ..record.clone()
+        }
+        .replay()?;
+        for action in record.actions.iter() {
+            self.push_position(&game, result);
+            game.apply(action)
+                .expect("Actions were checked by the first replay");
+        }
+        Ok(record.actions.len())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The dataset as an NPZ archive, see the [module docs](self) for its arrays.
+    ///
+    /// The archive is not ZIP64, so each array must stay below 4 GiB.
+    pub fn to_npz(&self) -> Vec<u8> {
+        let shape = format!(
+            "({}, {}, {}, {})",
+            self.len(),
+            self.planes,
+            self.rows,
+            self.columns
+        );
+        let inputs = npy("|u1", &shape, &self.inputs);
+        let values = self
+            .values
+            .iter()
+            .map(|&value| value as u8)
+            .collect::<Vec<_>>();
+        let values = npy("|i1", &format!("({},)", self.len()), &values);
+        zip_stored(&[("inputs.npy", &inputs), ("values.npy", &values)])
+    }
+    #[cfg(feature = "std")]
+    pub fn write_npz(&self, mut writer: impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.to_npz())
+    }
+
+    fn push_position(&mut self, game: &Game, result: GameOutcome) {
+        let area = self.rows * self.columns;
+        let start = self.inputs.len();
+        self.inputs.resize(start + self.planes * area, 0);
+        let inputs = &mut self.inputs[start..];
+        for (index, space) in game.board().board.values.iter().enumerate() {
+            if let Some(Some(piece)) = space.piece() {
+                let plane = 2 * piece.definition_index() + (piece.color() == Color::Blue) as usize;
+                // Grids are stored column major, samples are row major
+                let (row, column) = (index % self.rows, index / self.rows);
+                inputs[plane * area + row * self.columns + column] = 1;
+            }
+        }
+        if game.to_move() == Color::Red {
+            inputs[(self.planes - 1) * area..].fill(1);
+        }
+        self.values.push(match result {
+            GameOutcome::Win(color) if color == game.to_move() => 1,
+            GameOutcome::Win(_) => -1,
+            GameOutcome::Draw => 0,
+        });
+    }
+}
+
+/// A version 1.0 `.npy` file of a C ordered array.
+fn npy(descr: &str, shape: &str, data: &[u8]) -> Vec<u8> {
+    let mut header = String::from("{'descr': '");
+    header.push_str(descr);
+    header.push_str("', 'fortran_order': False, 'shape': ");
+    header.push_str(shape);
+    header.push_str(", }");
+    // The magic, version, and length take 10 bytes, the header ends in a newline and pads the
+    // data to 64 bytes
+    while (10 + header.len() + 1) % 64 != 0 {
+        header.push(' ');
+    }
+    header.push('\n');
+
+    let mut out = Vec::with_capacity(10 + header.len() + data.len());
+    out.extend_from_slice(b"\x93NUMPY\x01\x00");
+    out.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    out.extend_from_slice(header.as_bytes());
+    out.extend_from_slice(data);
+    out
+}
+
+/// A ZIP archive storing `files` without compression.
+fn zip_stored(files: &[(&str, &[u8])]) -> Vec<u8> {
+    // Stored entries need version 2.0, dated 1980-01-01
+    const VERSION: u16 = 20;
+    const DATE: u16 = (1 << 5) | 1;
+    let mut out = Vec::new();
+    let mut directory = Vec::new();
+    for (name, data) in files {
+        let offset = out.len() as u32;
+        let crc = crc32(data);
+        let mut fields = Vec::new();
+        for value in [VERSION, 0, 0, 0, DATE] {
+            fields.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [crc, data.len() as u32, data.len() as u32] {
+            fields.extend_from_slice(&value.to_le_bytes());
+        }
+        fields.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        fields.extend_from_slice(&0u16.to_le_bytes());
+
+        out.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        out.extend_from_slice(&fields);
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        directory.extend_from_slice(&VERSION.to_le_bytes());
+        directory.extend_from_slice(&fields);
+        // Comment length, disk, and internal and external attributes
+        directory.extend_from_slice(&[0; 10]);
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+    }
+    let directory_offset = out.len() as u32;
+    out.extend_from_slice(&directory);
+    out.extend_from_slice(&0x06054b50u32.to_le_bytes());
+    out.extend_from_slice(&[0; 4]);
+    for _ in 0..2 {
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    }
+    out.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+    out.extend_from_slice(&directory_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+pub type DatasetResult<T> = Result<T, DatasetError>;
+#[derive(Clone, Debug)]
+pub enum DatasetError {
+    GameError(GameError),
+    /// The record's game has no result to learn from.
+    Unfinished,
+    /// The record's board or pieces give samples of a different shape.
+    ShapeMismatch {
+        rows: usize,
+        columns: usize,
+        planes: usize,
+    },
+}
+impl Display for DatasetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for DatasetError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::GameError(error) => Some(error),
+            Self::Unfinished | Self::ShapeMismatch { .. } => None,
+        }
+    }
+}
+impl From<GameError> for DatasetError {
+    fn from(from: GameError) -> Self {
+        Self::GameError(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dataset::{crc32, Dataset, DatasetError};
+    use crate::game::playout::RandomPolicy;
+    use crate::game::{Game, GameOutcome};
+    use crate::game_board::Color;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn crc32_test() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+    }
+
+    #[test]
+    fn dataset_test() {
+        let ruleset = small_ruleset(0);
+        let first_color = Game::new(ruleset.clone()).unwrap().to_move();
+        let mut dataset = Dataset::new(&ruleset).unwrap();
+        let mut playout = Game::new(ruleset).unwrap().playout(RandomPolicy::new(0));
+        playout.by_ref().take(10).for_each(drop);
+        assert!(matches!(
+            dataset.push(&playout.game().record()),
+            Err(DatasetError::Unfinished)
+        ));
+        playout.by_ref().for_each(drop);
+        let game = playout.into_game();
+        let record = game.record();
+        let samples = dataset.push(&record).unwrap();
+        assert_eq!(samples, record.actions.len());
+        assert_eq!(dataset.len(), samples);
+
+        let area = dataset.rows * dataset.columns;
+        let sample = dataset.planes * area;
+        assert_eq!(dataset.inputs.len(), samples * sample);
+        // The side to move plane alternates
+        let red_plane =
+            |index: usize| dataset.inputs[index * sample - area..index * sample].to_vec();
+        assert_eq!(red_plane(1), vec![(first_color == Color::Red) as u8; area]);
+        assert_eq!(red_plane(2), vec![(first_color == Color::Blue) as u8; area]);
+        assert_eq!(dataset.values[0], -dataset.values[1]);
+        let first = match game.result().unwrap() {
+            GameOutcome::Win(color) if color == first_color => 1,
+            GameOutcome::Win(_) => -1,
+            GameOutcome::Draw => 0,
+        };
+        assert_eq!(dataset.values[0], first);
+
+        let npz = dataset.to_npz();
+        assert_eq!(&npz[..4], b"PK\x03\x04");
+        assert_eq!(&npz[npz.len() - 22..npz.len() - 18], b"PK\x05\x06");
+        let header = &npz[30 + "inputs.npy".len()..];
+        assert_eq!(&header[..6], b"\x93NUMPY");
+        let header_len = u16::from_le_bytes([header[8], header[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        assert!(core::str::from_utf8(&header[10..10 + header_len])
+            .unwrap()
+            .contains(&format!(
+                "'shape': ({}, {}, {}, {})",
+                samples, dataset.planes, dataset.rows, dataset.columns
+            )));
+    }
+}
@@ -1,11 +1,18 @@
-use std::collections::HashSet;
-use std::hash::Hash;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::hash::Hash;
+use core::iter::FromIterator;
 
 use bitflags::bitflags;
+use enum_iterator::IntoEnumIterator;
 
+use crate::collections::HashSet;
 use crate::coordinate::Coordinate;
 
 bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
     pub struct Directions: u8 {
         const NORTH         = 0b00000001;
         const SOUTH         = 0b00000010;
@@ -36,35 +43,75 @@ impl From<Direction> for Directions {
     }
 }
 impl Directions {
-    fn run_for_all(self, mut function: impl FnMut(Direction)) {
-        if self.contains(Directions::NORTH) {
-            function(Direction::North);
-        }
-        if self.contains(Directions::SOUTH) {
-            function(Direction::South);
-        }
-        if self.contains(Directions::EAST) {
-            function(Direction::East);
-        }
-        if self.contains(Directions::WEST) {
-            function(Direction::West);
-        }
-        if self.contains(Directions::NORTH_WEST) {
-            function(Direction::NorthWest);
-        }
-        if self.contains(Directions::NORTH_EAST) {
-            function(Direction::NorthEast);
-        }
-        if self.contains(Directions::SOUTH_WEST) {
-            function(Direction::SouthWest);
+    /// Number of directions in the set.
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+}
+impl IntoIterator for Directions {
+    type Item = Direction;
+    type IntoIter = DirectionsIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DirectionsIter(self)
+    }
+}
+impl FromIterator<Direction> for Directions {
+    fn from_iter<T: IntoIterator<Item = Direction>>(iter: T) -> Self {
+        iter.into_iter()
+            .fold(Directions::NONE, |out, direction| out | direction.into())
+    }
+}
+/// The contained flags, `NORTH | SOUTH_EAST`, or `NONE` when empty.
+impl Display for Directions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("NONE");
         }
-        if self.contains(Directions::SOUTH_EAST) {
-            function(Direction::SouthEast);
+        for (index, direction) in self.into_iter().enumerate() {
+            if index > 0 {
+                f.write_str(" | ")?;
+            }
+            f.write_str(match direction {
+                Direction::North => "NORTH",
+                Direction::South => "SOUTH",
+                Direction::East => "EAST",
+                Direction::West => "WEST",
+                Direction::NorthWest => "NORTH_WEST",
+                Direction::NorthEast => "NORTH_EAST",
+                Direction::SouthWest => "SOUTH_WEST",
+                Direction::SouthEast => "SOUTH_EAST",
+            })?;
         }
+        Ok(())
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+/// The directions of a [`Directions`] in [`Direction`] order.
+#[derive(Copy, Clone, Debug)]
+pub struct DirectionsIter(Directions);
+impl Iterator for DirectionsIter {
+    type Item = Direction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let direction =
+            Direction::into_enum_iter().find(|&direction| self.0.contains(direction.into()))?;
+        self.0.remove(direction.into());
+        Some(direction)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+impl ExactSizeIterator for DirectionsIter {}
+
+/// A direction on the board, in the canonical orientation (see [`Orientation`]).
+///
+/// [`Orientation`]: crate::coordinate::Orientation
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, IntoEnumIterator)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum Direction {
     North,
     South,
@@ -76,32 +123,82 @@ pub enum Direction {
     SouthEast,
 }
 impl Direction {
-    pub fn offset(&self) -> Coordinate {
+    /// The (north, east) steps of this direction, each -1, 0, or 1.
+    pub fn components(&self) -> (i16, i16) {
         match self {
-            Direction::North => Coordinate::new(0, -1),
-            Direction::South => Coordinate::new(0, 1),
-            Direction::East => Coordinate::new(1, 0),
-            Direction::West => Coordinate::new(-1, 0),
-            Direction::NorthWest => Coordinate::new(-1, -1),
-            Direction::NorthEast => Coordinate::new(1, -1),
-            Direction::SouthWest => Coordinate::new(-1, 1),
-            Direction::SouthEast => Coordinate::new(1, 1),
+            Direction::North => (1, 0),
+            Direction::South => (-1, 0),
+            Direction::East => (0, 1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (1, -1),
+            Direction::NorthEast => (1, 1),
+            Direction::SouthWest => (-1, -1),
+            Direction::SouthEast => (-1, 1),
         }
     }
+    /// Inverse of [`Direction::components`], `None` for `(0, 0)` or out of range components.
+    pub fn from_components(north: i16, east: i16) -> Option<Self> {
+        match (north, east) {
+            (1, 0) => Some(Direction::North),
+            (-1, 0) => Some(Direction::South),
+            (0, 1) => Some(Direction::East),
+            (0, -1) => Some(Direction::West),
+            (1, -1) => Some(Direction::NorthWest),
+            (1, 1) => Some(Direction::NorthEast),
+            (-1, -1) => Some(Direction::SouthWest),
+            (-1, 1) => Some(Direction::SouthEast),
+            _ => None,
+        }
+    }
+
+    /// The coordinate offset of a single step, north is towards row 0.
+    pub fn offset(&self) -> Coordinate {
+        let (north, east) = self.components();
+        Coordinate::new(-north, east)
+    }
 }
 impl From<Directions> for HashSet<Direction> {
     fn from(from: Directions) -> Self {
-        let mut out = HashSet::new();
-        from.run_for_all(|direction| {
-            out.insert(direction);
-        });
-        out
+        from.into_iter().collect()
     }
 }
 impl From<Directions> for Vec<Direction> {
     fn from(from: Directions) -> Self {
-        let mut out = Self::new();
-        from.run_for_all(|direction| out.push(direction));
-        out
+        from.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    use enum_iterator::IntoEnumIterator;
+
+    use crate::direction::{Direction, Directions};
+
+    #[test]
+    fn directions_test() {
+        assert_eq!(Directions::NONE.len(), 0);
+        assert_eq!(Directions::CARDINAL.len(), 4);
+        assert_eq!(Directions::ALL.into_iter().len(), 8);
+        let all: Vec<Direction> = Directions::ALL.into_iter().collect();
+        assert_eq!(all, Direction::into_enum_iter().collect::<Vec<_>>());
+        assert_eq!(
+            [Direction::North, Direction::SouthEast, Direction::North]
+                .iter()
+                .copied()
+                .collect::<Directions>(),
+            Directions::NORTH | Directions::SOUTH_EAST
+        );
+        assert_eq!(
+            Directions::DIAGONAL.into_iter().collect::<Directions>(),
+            Directions::DIAGONAL
+        );
+        assert_eq!(Directions::NONE.to_string(), "NONE");
+        assert_eq!(
+            (Directions::WEST | Directions::NORTH).to_string(),
+            "NORTH | WEST"
+        );
     }
 }
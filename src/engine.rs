@@ -0,0 +1,374 @@
+//! Players that choose actions on their own.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::action::Action;
+use crate::eval::ParamEvaluator;
+use crate::game::placement::{Placement, PlacementPhase, PlacementResult};
+use crate::game::playout::{Policy, RandomPolicy};
+use crate::game::Game;
+use crate::game_board::Color;
+use crate::rng::{ChaChaRng, GameRng, ENGINE_STREAM};
+#[cfg(feature = "std")]
+use crate::search::control::{SearchControl, SearchHandle};
+
+/// Chooses actions for whichever color is to move.
+pub trait Engine {
+    /// Called before each new game.
+    fn new_game(&mut self, _game: &Game) {}
+    /// The action to play for [`Game::to_move`], `None` resigns.
+    ///
+    /// Only called while the game is in progress, the action must be legal.
+    fn choose_action(&mut self, game: &Game) -> Option<Action>;
+    /// The piece to place for the color placing in [`Game::placement`], `None` finishes its
+    /// placing.
+    ///
+    /// Only called during the placement phase, the placement must be legal. The default plays
+    /// [`PlacementPlanner`]'s choice.
+    fn choose_placement(&mut self, game: &Game) -> Option<Placement> {
+        PlacementPlanner::default().choose(game)
+    }
+    /// Up to `multipv` of the best actions for [`Game::to_move`], best first.
+    ///
+    /// Engines that cannot compare actions return only their choice, scored `0.0`.
+    fn analyze(&mut self, game: &Game, multipv: usize) -> Vec<Candidate> {
+        match self.choose_action(game) {
+            Some(action) if multipv > 0 => vec![Candidate {
+                pv: vec![action.clone()],
+                action,
+                score: 0.0,
+            }],
+            _ => Vec::new(),
+        }
+    }
+
+    /// [`Engine::choose_action`] that returns early once `control` asks it to stop.
+    ///
+    /// Engines should report their best action to `control` as they search, and ignore their
+    /// limits while it is pondering. The default cannot be interrupted.
+    #[cfg(feature = "std")]
+    fn think(&mut self, game: &Game, _control: &SearchControl) -> Option<Action> {
+        self.choose_action(game)
+    }
+    /// Searches `game` on a new thread.
+    #[cfg(feature = "std")]
+    fn start_search(self, game: &Game) -> SearchHandle<Self>
+    where
+        Self: Sized + Send + 'static,
+    {
+        SearchHandle::spawn(self, game, SearchControl::new())
+    }
+    /// [`Engine::start_search`] reporting to `control`, see [`SearchControl::on_info`].
+    #[cfg(feature = "std")]
+    fn start_search_with(self, game: &Game, control: SearchControl) -> SearchHandle<Self>
+    where
+        Self: Sized + Send + 'static,
+    {
+        SearchHandle::spawn(self, game, control)
+    }
+    /// Searches `game`, the position after the expected reply, until stopped or hit.
+    #[cfg(feature = "std")]
+    fn start_pondering(self, game: &Game) -> SearchHandle<Self>
+    where
+        Self: Sized + Send + 'static,
+    {
+        SearchHandle::spawn(self, game, SearchControl::pondering())
+    }
+}
+/// An action found by [`Engine::analyze`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Candidate {
+    pub action: Action,
+    /// Expected result for the color to move, between `-1.0` for a loss and `1.0` for a win.
+    pub score: f32,
+    /// The line expected after the action, starting with it.
+    pub pv: Vec<Action>,
+}
+
+/// Plays a uniformly random legal action, reproducible from its seed.
+///
+/// A baseline for tournaments and tests, the same as [`RandomPolicy`] as an [`Engine`].
+#[derive(Clone, Debug)]
+pub struct RandomPlayer(pub RandomPolicy);
+impl RandomPlayer {
+    pub fn new(seed: u64) -> Self {
+        Self(RandomPolicy::new(seed))
+    }
+}
+impl Engine for RandomPlayer {
+    fn choose_action(&mut self, game: &Game) -> Option<Action> {
+        self.0.choose_action(game)
+    }
+    fn choose_placement(&mut self, game: &Game) -> Option<Placement> {
+        self.0.choose_placement(game)
+    }
+}
+
+/// Plays the legal action capturing the most enemy pieces, less any of its own it captures.
+///
+/// Ties, including between actions capturing nothing, are broken at random from its seed.
+#[derive(Clone, Debug)]
+pub struct GreedyCapturePlayer(pub ChaChaRng);
+impl GreedyCapturePlayer {
+    pub fn new(seed: u64) -> Self {
+        Self(ChaChaRng::with_stream(seed, ENGINE_STREAM))
+    }
+}
+impl Engine for GreedyCapturePlayer {
+    fn choose_action(&mut self, game: &Game) -> Option<Action> {
+        let color = game.to_move();
+        let mut best = Vec::new();
+        let mut best_gain = isize::MIN;
+        for action in game.legal_actions() {
+            let mut after = game.clone();
+            let outcome = after.apply(&action).expect("Legal actions must apply");
+            let gain = outcome
+                .captures
+                .iter()
+                .map(|(_, piece)| if piece.color() == color { -1 } else { 1 })
+                .sum();
+            if gain > best_gain {
+                best_gain = gain;
+                best.clear();
+            }
+            if gain == best_gain {
+                best.push(action);
+            }
+        }
+        if best.is_empty() {
+            return None;
+        }
+        let index = self.0.below(best.len());
+        Some(best.swap_remove(index))
+    }
+}
+
+impl Engine for RandomPolicy {
+    fn choose_action(&mut self, game: &Game) -> Option<Action> {
+        let mut actions = game.legal_actions();
+        if actions.is_empty() {
+            return None;
+        }
+        let index = self.choose(game, &actions);
+        Some(actions.swap_remove(index))
+    }
+    fn choose_placement(&mut self, game: &Game) -> Option<Placement> {
+        let mut placements = game.placement()?.legal_placements();
+        if placements.is_empty() {
+            return None;
+        }
+        let index = self.0.below(placements.len());
+        Some(placements.swap_remove(index))
+    }
+}
+
+/// Places pieces by scoring the board with a [`ParamEvaluator`] after every line of
+/// placements up to `depth` placements long, both colors placing their best.
+///
+/// Limits and areas are those of [`PlacementPhase::legal_placements`], ties go to the first
+/// placement in its order.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PlacementPlanner {
+    pub evaluator: ParamEvaluator,
+    /// Placements searched including the one chosen, at least 1.
+    pub depth: usize,
+}
+impl Default for PlacementPlanner {
+    fn default() -> Self {
+        Self {
+            evaluator: ParamEvaluator::default(),
+            depth: 2,
+        }
+    }
+}
+impl PlacementPlanner {
+    /// The best placement for the color placing, `None` if it has none or placing is over.
+    pub fn choose(&self, game: &Game) -> Option<Placement> {
+        let phase = game.placement()?;
+        let color = phase.to_place()?;
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "placement_search",
+            depth = self.depth,
+            reached = tracing::field::Empty
+        )
+        .entered();
+        let mut best = None;
+        let mut best_score = i32::MIN;
+        let mut reached = 0;
+        for placement in phase.legal_placements() {
+            let mut after = phase.clone();
+            after
+                .place(placement.piece_index, placement.position)
+                .expect("Legal placements must place");
+            let depth = self.depth.max(1) - 1;
+            let score = self.search(&after, color, depth, best_score, i32::MAX, &mut reached);
+            if score > best_score {
+                best_score = score;
+                best = Some(placement);
+            }
+        }
+        #[cfg(feature = "tracing")]
+        span.record("reached", reached);
+        best
+    }
+
+    /// The score for `perspective` of `phase` with `depth` more placements, exact only
+    /// between `alpha` and `beta`.
+    ///
+    /// Raises `reached` to the placements searched so far if this line is the longest.
+    fn search(
+        &self,
+        phase: &PlacementPhase,
+        perspective: Color,
+        depth: usize,
+        mut alpha: i32,
+        mut beta: i32,
+        reached: &mut usize,
+    ) -> i32 {
+        *reached = (*reached).max(self.depth.max(1) - depth);
+        let color = match phase.to_place() {
+            Some(color) if depth > 0 => color,
+            _ => return self.evaluator.evaluate_board(phase.board(), perspective),
+        };
+        let placements = phase.legal_placements();
+        if placements.is_empty() {
+            let mut after = phase.clone();
+            after
+                .finish_placing()
+                .expect("The color to place can finish");
+            return self.search(&after, perspective, depth - 1, alpha, beta, reached);
+        }
+        let maximizing = color == perspective;
+        let mut best = if maximizing { i32::MIN } else { i32::MAX };
+        for placement in placements {
+            let mut after = phase.clone();
+            after
+                .place(placement.piece_index, placement.position)
+                .expect("Legal placements must place");
+            let score = self.search(&after, perspective, depth - 1, alpha, beta, reached);
+            if maximizing {
+                best = best.max(score);
+                alpha = alpha.max(score);
+            } else {
+                best = best.min(score);
+                beta = beta.min(score);
+            }
+            if alpha >= beta {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(depth, alpha, beta, "cutoff");
+                break;
+            }
+        }
+        best
+    }
+}
+
+/// Plays `engine`'s placement in `game`, finishing its placing if it chooses none.
+pub fn play_placement(engine: &mut (impl Engine + ?Sized), game: &mut Game) -> PlacementResult<()> {
+    match engine.choose_placement(game) {
+        Some(placement) => game.place(placement.piece_index, placement.position),
+        None => game.finish_placing(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+
+    use crate::collections::HashSet;
+
+    use crate::engine::{
+        play_placement, Engine, GreedyCapturePlayer, PlacementPlanner, RandomPlayer,
+    };
+    use crate::game::placement::PlacementPhase;
+    use crate::game::Game;
+    use crate::game_board::Color;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::series::{Match, MatchConfig};
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn baseline_players_test() {
+        for seed in 0..4 {
+            let mut game = Game::new(small_ruleset(seed)).unwrap();
+            let mut players = (RandomPlayer::new(seed), GreedyCapturePlayer::new(seed));
+            while game.result().is_none() && game.history().len() < 300 {
+                let greedy = game.history().len() % 2 == 1;
+                let action = if greedy {
+                    players.1.choose_action(&game)
+                } else {
+                    players.0.choose_action(&game)
+                };
+                let action = match action {
+                    Some(action) => action,
+                    None => {
+                        assert!(game.legal_actions().is_empty());
+                        break;
+                    }
+                };
+                let captures_available = game.legal_actions().iter().any(|action| {
+                    let mut after = game.clone();
+                    !after.apply(action).unwrap().captures.is_empty()
+                });
+                let outcome = game.apply(&action).unwrap();
+                if greedy && captures_available {
+                    assert!(!outcome.captures.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn placement_test() {
+        let mut ruleset = small_ruleset(0);
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 3 });
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::TurnsCount { per_turn_count: 1 },
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        let mut game = Game::new(ruleset.clone()).unwrap();
+        let planner = PlacementPlanner {
+            depth: 1,
+            ..PlacementPlanner::default()
+        };
+        // Only material differs between the first placements, the big piece is worth most
+        let first = planner.choose(&game).unwrap();
+        assert_eq!(first.piece_index, 0);
+        let mut players = (GreedyCapturePlayer::new(2), RandomPlayer::new(3));
+        while let Some(color) = game.placement().and_then(PlacementPhase::to_place) {
+            match color {
+                Color::Red => play_placement(&mut players.0, &mut game).unwrap(),
+                Color::Blue => play_placement(&mut players.1, &mut game).unwrap(),
+            }
+        }
+        assert!(!game.placements().is_empty());
+        assert!(!game.legal_actions().is_empty());
+
+        let mut series = Match::new(
+            ruleset,
+            MatchConfig {
+                games: 1,
+                max_plies: 60,
+                ..MatchConfig::default()
+            },
+            Box::new(GreedyCapturePlayer::new(0)),
+            Box::new(RandomPlayer::new(1)),
+        );
+        let state = series.run().unwrap();
+        assert_eq!(state.games(), 1);
+        assert_eq!(state.forfeits, [0, 0]);
+        assert!(state
+            .records
+            .iter()
+            .all(|record| !record.placements.is_empty()));
+    }
+}
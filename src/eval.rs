@@ -0,0 +1,208 @@
+//! Position evaluation for engines.
+
+use alloc::vec::Vec;
+
+use crate::action::ActionType;
+use crate::collections::HashSet;
+use crate::game::{Game, GameOutcome};
+use crate::game_board::board::Board;
+use crate::game_board::{Color, GameBoard};
+use crate::movegen::legal_actions;
+
+/// Score for a won game, every other score is strictly between this and its negation.
+pub const WIN_SCORE: i32 = 1_000_000;
+
+/// Scores a game from one color's point of view, higher is better for `perspective`.
+pub trait Evaluator {
+    fn evaluate(&self, game: &Game, perspective: Color) -> i32;
+}
+impl<F> Evaluator for F
+where
+    F: Fn(&Game, Color) -> i32,
+{
+    fn evaluate(&self, game: &Game, perspective: Color) -> i32 {
+        self(game, perspective)
+    }
+}
+
+/// A model such as a neural network giving priors and a value for many positions at once.
+///
+/// Search engines collect positions into batches so models on accelerators are called as rarely
+/// as possible, as `search::mcts::Mcts` does.
+pub trait ExternalEvaluator {
+    /// The policy and value of every game in order.
+    ///
+    /// A policy holds one prior per [`Game::legal_actions`] in order, policies of another length
+    /// are replaced by uniform priors. Values are between `-1.0` and `1.0` for [`Game::to_move`].
+    fn evaluate_batch(&mut self, games: &[Game]) -> Vec<(Vec<f32>, f32)>;
+}
+
+/// Weights for [`ParamEvaluator`], each term is the difference between the two colors.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EvalParams {
+    /// Value of each piece of the first definition, the large piece in the standard rules.
+    pub large_material: i32,
+    /// Value of each piece of every other definition.
+    pub small_material: i32,
+    /// Value of each legal action.
+    pub mobility: i32,
+    /// Penalty per step between each piece and the nearest goal it can score in.
+    pub goal_distance: i32,
+    /// Value of each enemy piece that can be jumped this turn.
+    pub jump_threats: i32,
+}
+impl EvalParams {
+    /// The weights in field order, matching [`ParamEvaluator::features`].
+    pub fn weights(&self) -> [i32; 5] {
+        [
+            self.large_material,
+            self.small_material,
+            self.mobility,
+            self.goal_distance,
+            self.jump_threats,
+        ]
+    }
+    pub fn from_weights(weights: [i32; 5]) -> Self {
+        let [large_material, small_material, mobility, goal_distance, jump_threats] = weights;
+        Self {
+            large_material,
+            small_material,
+            mobility,
+            goal_distance,
+            jump_threats,
+        }
+    }
+}
+impl Default for EvalParams {
+    fn default() -> Self {
+        Self {
+            large_material: 300,
+            small_material: 100,
+            mobility: 2,
+            goal_distance: 10,
+            jump_threats: 40,
+        }
+    }
+}
+
+/// Evaluator with linear weights that can be tuned externally.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamEvaluator {
+    pub params: EvalParams,
+}
+impl ParamEvaluator {
+    pub fn new(params: EvalParams) -> Self {
+        Self { params }
+    }
+
+    /// The raw terms for `color` in the order of the [`EvalParams`] fields.
+    pub fn features(board: &GameBoard, color: Color) -> [i32; 5] {
+        let pieces = board.pieces_of_color(color);
+        let large = pieces
+            .iter()
+            .filter(|(_, piece)| piece.definition_index() == 0)
+            .count() as i32;
+        let small = pieces.len() as i32 - large;
+        let distance: i32 = pieces
+            .iter()
+            .filter_map(|&(position, _)| board.goal_distance(position, color.opponent()))
+            .map(|distance| distance as i32)
+            .sum();
+
+        let actions = legal_actions(board, color);
+        let mut threatened = HashSet::new();
+        for action in actions.iter() {
+            if let ActionType::Jump(directions) = &action.action_type {
+                let middle = directions[0].offset() + action.start_pos;
+                if let Ok(Some(piece)) = board.piece(middle) {
+                    if piece.color() != color {
+                        threatened.insert(middle);
+                    }
+                }
+            }
+        }
+
+        [
+            large,
+            small,
+            actions.len() as i32,
+            -distance,
+            threatened.len() as i32,
+        ]
+    }
+}
+impl Evaluator for ParamEvaluator {
+    fn evaluate(&self, game: &Game, perspective: Color) -> i32 {
+        match game.result() {
+            Some(GameOutcome::Win(color)) if color == perspective => return WIN_SCORE,
+            Some(GameOutcome::Win(_)) => return -WIN_SCORE,
+            Some(GameOutcome::Draw) => return 0,
+            None => {}
+        }
+        self.evaluate_board(game.board(), perspective)
+    }
+}
+impl ParamEvaluator {
+    /// The score of `board` ignoring game results, such as a board still being placed.
+    pub fn evaluate_board(&self, board: &GameBoard, perspective: Color) -> i32 {
+        let own = Self::features(board, perspective);
+        let opponent = Self::features(board, perspective.opponent());
+        let score: i32 = self
+            .params
+            .weights()
+            .iter()
+            .zip(own.iter().zip(opponent.iter()))
+            .map(|(weight, (own, opponent))| weight * (own - opponent))
+            .sum();
+        score.clamp(-WIN_SCORE + 1, WIN_SCORE - 1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::eval::{EvalParams, Evaluator, ParamEvaluator, WIN_SCORE};
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::game_board::Color;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn symmetric_test() {
+        let evaluator = ParamEvaluator::default();
+        let game = Game::new(small_ruleset(2)).unwrap();
+        assert_eq!(evaluator.evaluate(&game, Color::Red), 0);
+
+        let mut playout = game.playout(RandomPolicy::new(5));
+        playout.by_ref().take(7).for_each(drop);
+        let game = playout.game();
+        assert_eq!(
+            evaluator.evaluate(game, Color::Red),
+            -evaluator.evaluate(game, Color::Blue)
+        );
+    }
+
+    #[test]
+    fn material_test() {
+        let evaluator = ParamEvaluator::new(EvalParams {
+            large_material: 1,
+            small_material: 0,
+            mobility: 0,
+            goal_distance: 0,
+            jump_threats: 0,
+        });
+        let mut playout = Game::new(small_ruleset(2))
+            .unwrap()
+            .playout(RandomPolicy::new(1));
+        playout.by_ref().take(1_000).for_each(drop);
+        let game = playout.game();
+        let score = evaluator.evaluate(game, Color::Red);
+        if game.is_finished() {
+            assert_eq!(score.abs(), WIN_SCORE);
+        } else {
+            let large = |color| ParamEvaluator::features(game.board(), color)[0];
+            assert_eq!(score, large(Color::Red) - large(Color::Blue));
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! SVG replays of recorded games.
+//!
+//! Enabled by the `export` feature.
+//!
+//! Every frame is a standalone SVG document, players can step through them or
+//! convert them to an animation with any SVG capable tool.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::action::{Action, ActionOutcome};
+use crate::coordinate::Coordinate;
+use crate::game::record::GameRecord;
+use crate::game::{GameError, GameResult};
+use crate::game_board::{BoardSpace, Color, GameBoard};
+use crate::notation::CoordinateScheme;
+
+/// How frames are drawn.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SvgOptions {
+    /// Width and height of every square in pixels.
+    pub square_size: u32,
+    /// Labels every square in this scheme, `None` leaves squares unlabeled.
+    pub labels: Option<CoordinateScheme>,
+}
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            square_size: 40,
+            labels: None,
+        }
+    }
+}
+
+/// One frame for the starting position and one after every action of `record`.
+///
+/// Frames after an action outline its start and end squares and cross out the pieces it captured.
+pub fn svg_frames(record: &GameRecord, options: &SvgOptions) -> GameResult<Vec<String>> {
+    let mut game = GameRecord {
+        actions: Vec::new(),
+        ..record.clone()
+    }
+    .replay()?;
+    let mut out = Vec::with_capacity(record.actions.len() + 1);
+    out.push(svg_frame(game.board(), None, options));
+    for (ply, action) in record.actions.iter().enumerate() {
+        let outcome = game
+            .apply(action)
+            .map_err(|error| GameError::IllegalAction { ply, error })?;
+        out.push(svg_frame(game.board(), Some((action, &outcome)), options));
+    }
+    Ok(out)
+}
+
+/// `board` as an SVG document, highlighting `last` if it was the action that led to it.
+pub fn svg_frame(
+    board: &GameBoard,
+    last: Option<(&Action, &ActionOutcome)>,
+    options: &SvgOptions,
+) -> String {
+    let size = options.square_size as usize;
+    let (rows, columns) = (board.board.rows, board.board.columns);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+        columns * size,
+        rows * size,
+        columns * size,
+        rows * size
+    );
+    for row in 0..rows {
+        for column in 0..columns {
+            let position = Coordinate::new(row as i16, column as i16);
+            let (x, y) = (column * size, row * size);
+            let (fill, piece) = match board.board[position] {
+                BoardSpace::Invalid => continue,
+                BoardSpace::Normal(piece) if (row + column) % 2 == 0 => ("#eeeeee", piece),
+                BoardSpace::Normal(piece) => ("#d8d8d8", piece),
+                BoardSpace::Goal {
+                    goal_for: Color::Red,
+                    piece,
+                } => ("#f4c7c3", piece),
+                BoardSpace::Goal {
+                    goal_for: Color::Blue,
+                    piece,
+                } => ("#c6d4f4", piece),
+                BoardSpace::Blocked => ("#606060", None),
+                BoardSpace::Sticky(piece) => ("#e6d9a2", piece),
+                BoardSpace::Portal { piece, .. } => ("#cdb8e8", piece),
+            };
+            let _ = writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                x, y, size, size, fill
+            );
+            if let Some(piece) = piece {
+                let _ = writeln!(
+                    out,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                    x + size / 2,
+                    y + size / 2,
+                    piece_radius(size, piece.definition_index()),
+                    color_fill(piece.color())
+                );
+            }
+            if let Some(scheme) = options.labels {
+                let _ = writeln!(
+                    out,
+                    r##"<text x="{}" y="{}" font-size="{}" fill="#555555">{}</text>"##,
+                    x + 2,
+                    y + size / 4 + 2,
+                    size / 4,
+                    scheme.format_square(position)
+                );
+            }
+        }
+    }
+    if let Some((action, outcome)) = last {
+        if !action.action_type.is_pass() {
+            for position in [action.start_pos, action.end_pos()] {
+                let _ = writeln!(
+                    out,
+                    r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#e0b000" stroke-width="3"/>"##,
+                    position.column as usize * size + 1,
+                    position.row as usize * size + 1,
+                    size - 2,
+                    size - 2
+                );
+            }
+        }
+        for (position, piece) in outcome.captures.iter() {
+            let (x, y) = (
+                position.column as usize * size,
+                position.row as usize * size,
+            );
+            let _ = writeln!(
+                out,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}" opacity="0.35"/>"#,
+                x + size / 2,
+                y + size / 2,
+                piece_radius(size, piece.definition_index()),
+                color_fill(piece.color())
+            );
+            let (near, far) = (size / 4, size - size / 4);
+            let _ = writeln!(
+                out,
+                r##"<path d="M{} {}L{} {}M{} {}L{} {}" stroke="#202020" stroke-width="3"/>"##,
+                x + near,
+                y + near,
+                x + far,
+                y + far,
+                x + far,
+                y + near,
+                x + near,
+                y + far
+            );
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// The large piece is drawn bigger than every other piece.
+fn piece_radius(size: usize, definition_index: usize) -> usize {
+    if definition_index == 0 {
+        size * 2 / 5
+    } else {
+        size * 3 / 10
+    }
+}
+
+fn color_fill(color: Color) -> &'static str {
+    match color {
+        Color::Red => "#d03030",
+        Color::Blue => "#3060d0",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::export::{svg_frames, SvgOptions};
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::notation::CoordinateScheme;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn svg_frames_test() {
+        let mut playout = Game::new(small_ruleset(1))
+            .unwrap()
+            .playout(RandomPolicy::new(1));
+        playout.by_ref().take(60).for_each(drop);
+        let game = playout.into_game();
+        let record = game.record();
+
+        let options = SvgOptions {
+            labels: Some(CoordinateScheme::Algebraic),
+            ..SvgOptions::default()
+        };
+        let frames = svg_frames(&record, &options).unwrap();
+        assert_eq!(frames.len(), record.actions.len() + 1);
+        assert!(frames
+            .iter()
+            .all(|frame| frame.starts_with("<svg") && frame.ends_with("</svg>\n")));
+        assert!(frames[0].contains(">a2</text>"));
+        assert!(!frames[0].contains("stroke=\"#e0b000\""));
+        assert!(frames[1].contains("stroke=\"#e0b000\""));
+
+        let captures: usize = game
+            .history()
+            .iter()
+            .map(|(_, outcome)| outcome.captures.len())
+            .sum();
+        let crossed: usize = frames
+            .iter()
+            .map(|frame| frame.matches("<path").count())
+            .sum();
+        assert!(captures > 0);
+        assert_eq!(crossed, captures);
+    }
+}
@@ -0,0 +1,256 @@
+//! Reference matches between random bots for checking engine changes keep the rules the same.
+//!
+//! Every fixture plays seeded random games and compares aggregate statistics against
+//! tolerance ranges. Ranges rather than exact values let optimizations reorder
+//! legal actions, which changes the individual games but not the statistics.
+
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::RangeInclusive;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::collections::{HashMap, HashSet};
+use crate::game::playout::RandomPolicy;
+use crate::game::{Game, GameError, GameOutcome, GameResult};
+use crate::game_board::Color;
+use crate::ruleset::board_type::goal_locations::GoalLocations;
+use crate::ruleset::board_type::BoardType;
+use crate::ruleset::piece_definition::GoalMovementRule;
+use crate::ruleset::standard::standard_pieces;
+use crate::ruleset::starting_positions::placement_area::PlacementArea;
+use crate::ruleset::starting_positions::StartingPositions;
+use crate::ruleset::victory_condition::VictoryCondition;
+use crate::ruleset::Ruleset;
+
+/// A seeded set of random games with the statistics they are expected to produce.
+#[derive(Clone, Debug)]
+pub struct Fixture {
+    pub name: &'static str,
+    pub ruleset: Ruleset,
+    /// Game `n` is played by [`RandomPolicy::new`] with `seed + n`.
+    pub seed: u64,
+    pub games: usize,
+    /// Games reaching this many plies are drawn.
+    pub max_plies: usize,
+    pub expected: ExpectedStats,
+}
+impl Fixture {
+    /// Plays every game of the fixture.
+    pub fn run(&self) -> GameResult<FixtureStats> {
+        let mut out = FixtureStats::default();
+        for game_index in 0..self.games {
+            let policy = RandomPolicy::new(self.seed.wrapping_add(game_index as u64));
+            let mut playout = Game::new(self.ruleset.clone())?.playout(policy);
+            for (_, outcome) in playout.by_ref().take(self.max_plies) {
+                out.plies += 1;
+                out.captures += outcome.captures.len();
+            }
+            match playout.game().result() {
+                Some(GameOutcome::Win(Color::Red)) => out.red_wins += 1,
+                Some(GameOutcome::Win(Color::Blue)) => out.blue_wins += 1,
+                Some(GameOutcome::Draw) | None => out.draws += 1,
+            }
+        }
+        Ok(out)
+    }
+
+    /// Runs the fixture, erroring on the first statistic outside of its expected range.
+    pub fn check(&self) -> FixtureResult<FixtureStats> {
+        let stats = self.run()?;
+        let checks = [
+            ("red_wins", stats.red_wins, &self.expected.red_wins),
+            ("draws", stats.draws, &self.expected.draws),
+            ("plies", stats.plies, &self.expected.plies),
+            ("captures", stats.captures, &self.expected.captures),
+        ];
+        for (stat, value, range) in checks.iter() {
+            if !range.contains(value) {
+                return Err(FixtureError::OutOfRange {
+                    fixture: self.name,
+                    stat,
+                    value: *value,
+                    expected: (*range).clone(),
+                });
+            }
+        }
+        Ok(stats)
+    }
+}
+
+/// Tolerance ranges for the totals of a [`Fixture`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpectedStats {
+    pub red_wins: RangeInclusive<usize>,
+    pub draws: RangeInclusive<usize>,
+    pub plies: RangeInclusive<usize>,
+    pub captures: RangeInclusive<usize>,
+}
+
+/// Totals over every game of a [`Fixture`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FixtureStats {
+    pub red_wins: usize,
+    pub blue_wins: usize,
+    /// Games with a drawn result or that reached the ply limit.
+    pub draws: usize,
+    pub plies: usize,
+    pub captures: usize,
+}
+
+/// The curated fixtures, covering each victory condition and goal rule.
+pub fn fixtures() -> Vec<Fixture> {
+    vec![
+        Fixture {
+            name: "small_all_captured",
+            ruleset: random_ruleset(6, 6, 1, VictoryCondition::AllCaptured),
+            seed: 0,
+            games: 40,
+            max_plies: 300,
+            expected: ExpectedStats {
+                red_wins: 8..=32,
+                draws: 0..=12,
+                plies: 900..=1_700,
+                captures: 220..=340,
+            },
+        },
+        Fixture {
+            name: "goal_count",
+            ruleset: random_ruleset(
+                8,
+                6,
+                2,
+                VictoryCondition::GoalCount {
+                    amount: 1,
+                    valid_pieces: vec![0, 1],
+                },
+            ),
+            seed: 100,
+            games: 40,
+            max_plies: 300,
+            expected: ExpectedStats {
+                red_wins: 8..=32,
+                draws: 0..=14,
+                plies: 2_200..=3_700,
+                captures: 200..=320,
+            },
+        },
+        Fixture {
+            name: "no_own_goal",
+            ruleset: {
+                let mut ruleset = random_ruleset(
+                    8,
+                    6,
+                    3,
+                    VictoryCondition::GoalCount {
+                        amount: 1,
+                        valid_pieces: vec![0, 1],
+                    },
+                );
+                for piece in ruleset.pieces.iter_mut() {
+                    piece.goal_move_rule = GoalMovementRule::NoOwnGoal;
+                }
+                ruleset
+            },
+            seed: 200,
+            games: 40,
+            max_plies: 300,
+            expected: ExpectedStats {
+                red_wins: 8..=32,
+                draws: 0..=10,
+                plies: 1_800..=3_200,
+                captures: 200..=320,
+            },
+        },
+    ]
+}
+
+/// Checks every fixture in [`fixtures`].
+pub fn check_all() -> FixtureResult<Vec<FixtureStats>> {
+    fixtures().iter().map(Fixture::check).collect()
+}
+
+fn random_ruleset(
+    rows: u8,
+    columns: u8,
+    seed: u64,
+    victory_condition: VictoryCondition,
+) -> Ruleset {
+    let mut victory_conditions = HashSet::new();
+    victory_conditions.insert(victory_condition);
+    let mut piece_counts = HashMap::new();
+    piece_counts.insert(0, 2);
+    piece_counts.insert(1, 3);
+    Ruleset {
+        pieces: standard_pieces(),
+        board_type: BoardType::Rectangular {
+            rows,
+            columns,
+            goal_depth: 1,
+            red_goals: GoalLocations::Centered { width: 2 },
+            blue_goals: GoalLocations::Centered { width: 2 },
+        },
+        starting_positions: StartingPositions::Random {
+            seed,
+            piece_counts,
+            area: PlacementArea::Half,
+        },
+        victory_conditions,
+        stalemate_rule: Default::default(),
+        pass_rule: Default::default(),
+        scoring: Default::default(),
+        game_length_rule: Default::default(),
+        chance_rule: Default::default(),
+    }
+}
+
+pub type FixtureResult<T> = Result<T, FixtureError>;
+#[derive(Clone, Debug)]
+pub enum FixtureError {
+    GameError(GameError),
+    OutOfRange {
+        fixture: &'static str,
+        stat: &'static str,
+        value: usize,
+        expected: RangeInclusive<usize>,
+    },
+}
+impl Display for FixtureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for FixtureError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::GameError(error) => Some(error),
+            Self::OutOfRange { .. } => None,
+        }
+    }
+}
+impl From<GameError> for FixtureError {
+    fn from(from: GameError) -> Self {
+        Self::GameError(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixtures::{check_all, fixtures};
+
+    #[test]
+    fn fixtures_test() {
+        let stats = check_all().unwrap();
+        assert_eq!(stats.len(), fixtures().len());
+        for (fixture, stats) in fixtures().iter().zip(stats) {
+            assert_eq!(
+                stats.red_wins + stats.blue_wins + stats.draws,
+                fixture.games
+            );
+        }
+    }
+}
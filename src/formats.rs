@@ -0,0 +1,385 @@
+//! Save formats, compatibility self tests, and schemas for third party readers.
+//!
+//! Enabled by the `formats` feature.
+
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+use std::error::Error;
+use std::string::{String, ToString};
+use std::vec;
+use std::vec::Vec;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::collections::{HashMap, HashSet};
+use crate::coordinate::Coordinate;
+use crate::direction::Directions;
+use crate::game::playout::RandomPolicy;
+use crate::game::record::GameRecord;
+use crate::game::scenario::Scenario;
+use crate::game::Game;
+use crate::game_board::Color;
+use crate::grid::Grid;
+use crate::ruleset::board_type::goal_locations::GoalLocations;
+use crate::ruleset::board_type::space::Space;
+use crate::ruleset::board_type::BoardType;
+use crate::ruleset::chance_rule::{ChanceFace, ChanceRule};
+use crate::ruleset::game_length_rule::{GameLengthRule, Tiebreak};
+use crate::ruleset::pass_rule::PassRule;
+use crate::ruleset::scoring::Scoring;
+use crate::ruleset::stalemate_rule::StalemateRule;
+use crate::ruleset::standard::standard_pieces;
+use crate::ruleset::starting_positions::alteration_type::AlternationType;
+use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+use crate::ruleset::starting_positions::placement_area::PlacementArea;
+use crate::ruleset::starting_positions::StartingPositions;
+use crate::ruleset::victory_condition::{PieceFilter, VictoryCondition};
+use crate::ruleset::Ruleset;
+
+/// A supported serialization format.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Format {
+    Json,
+}
+impl Format {
+    pub const ALL: [Format; 1] = [Format::Json];
+
+    pub fn serialize<T: Serialize>(&self, value: &T) -> FormatResult<Vec<u8>> {
+        match self {
+            Format::Json => serde_json::to_vec(value).map_err(|error| FormatError::Serialize {
+                format: *self,
+                message: error.to_string(),
+            }),
+        }
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> FormatResult<T> {
+        match self {
+            Format::Json => {
+                serde_json::from_slice(bytes).map_err(|error| FormatError::Deserialize {
+                    format: *self,
+                    message: error.to_string(),
+                })
+            }
+        }
+    }
+
+    /// Serializes and deserializes `value`, erroring if the result is not equal.
+    pub fn round_trip<T>(&self, value: &T) -> FormatResult<()>
+    where
+        T: Serialize + DeserializeOwned + PartialEq,
+    {
+        let out: T = self.deserialize(&self.serialize(value)?)?;
+        if out == *value {
+            Ok(())
+        } else {
+            Err(FormatError::Mismatch {
+                format: *self,
+                type_name: core::any::type_name::<T>(),
+            })
+        }
+    }
+}
+
+/// Round trips samples of every serializable type through every [`Format`].
+///
+/// Samples cover every variant of the ruleset types along with a played game.
+pub fn selftest() -> FormatResult<()> {
+    let rulesets = sample_rulesets();
+    let record = sample_record();
+    for format in Format::ALL.iter() {
+        for ruleset in rulesets.iter() {
+            format.round_trip(ruleset)?;
+        }
+        format.round_trip(&record)?;
+        #[cfg(feature = "net")]
+        for message in sample_messages(&record) {
+            format.round_trip(&message)?;
+        }
+    }
+    Ok(())
+}
+
+/// A type with a published schema.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SchemaKind {
+    Ruleset,
+    GameRecord,
+    Scenario,
+}
+impl SchemaKind {
+    pub const ALL: [SchemaKind; 3] = [
+        SchemaKind::Ruleset,
+        SchemaKind::GameRecord,
+        SchemaKind::Scenario,
+    ];
+}
+/// The JSON Schema of the [`Format::Json`] encoding of `kind`.
+pub fn json_schema(kind: SchemaKind) -> String {
+    let schema = match kind {
+        SchemaKind::Ruleset => schemars::schema_for!(Ruleset),
+        SchemaKind::GameRecord => schemars::schema_for!(GameRecord),
+        SchemaKind::Scenario => schemars::schema_for!(Scenario),
+    };
+    serde_json::to_string_pretty(&schema).expect("Schemas always serialize")
+}
+
+fn sample_rulesets() -> Vec<Ruleset> {
+    let board_type = BoardType::Rectangular {
+        rows: 6,
+        columns: 6,
+        goal_depth: 1,
+        red_goals: GoalLocations::Centered { width: 2 },
+        blue_goals: GoalLocations::Strip { start: 1, width: 3 },
+    };
+    let positions: HashMap<usize, Vec<Coordinate>> = vec![
+        (0, vec![Coordinate::new(1, 1), Coordinate::new(1, 2)]),
+        (1, vec![Coordinate::new(2, 0)]),
+    ]
+    .into_iter()
+    .collect();
+    let own_area: HashSet<Coordinate> = positions.values().flatten().copied().collect();
+
+    let mut victory_conditions = HashSet::new();
+    victory_conditions.insert(VictoryCondition::GoalCount {
+        amount: 2,
+        valid_pieces: vec![0, 1],
+    });
+    victory_conditions.insert(VictoryCondition::AllCaptured);
+    victory_conditions.insert(VictoryCondition::PointDifference(3));
+    victory_conditions.insert(VictoryCondition::PiecesInGoal {
+        count: 2,
+        piece_filter: PieceFilter::Except(vec![1]),
+    });
+    victory_conditions.insert(VictoryCondition::ScoreAtLeast(10));
+    victory_conditions.insert(VictoryCondition::Any(vec![
+        VictoryCondition::All(vec![
+            VictoryCondition::PointDifference(1),
+            VictoryCondition::ScoreAtLeast(6),
+        ]),
+        VictoryCondition::CaptureCount {
+            count: 4,
+            piece_filter: PieceFilter::Any,
+        },
+    ]));
+    let scoring = Scoring {
+        capture_points: vec![2, 1],
+        goal_entry_points: vec![3],
+    };
+    let mut piece_limits = HashSet::new();
+    piece_limits.insert(PieceLimit::TotalLimit { limit: 6 });
+    piece_limits.insert(PieceLimit::TypeCountLimit {
+        limits: vec![(0, 2)].into_iter().collect(),
+    });
+    piece_limits.insert(PieceLimit::PointLimit {
+        point_values: vec![(0, 3), (1, 1)].into_iter().collect(),
+        point_limit: 8,
+        square_costs: vec![(1, vec![Coordinate::new(2, 1)].into_iter().collect())]
+            .into_iter()
+            .collect(),
+    });
+
+    let starting_positions = vec![
+        StartingPositions::MirroredFlipped(positions.clone()),
+        StartingPositions::MirroredRotated(positions.clone()),
+        StartingPositions::NotMirrored(
+            vec![(Color::Red, positions.clone()), (Color::Blue, positions)]
+                .into_iter()
+                .collect(),
+        ),
+        StartingPositions::Placement {
+            first_color: Color::Blue,
+            alternation_type: AlternationType::TurnsPoints {
+                per_turn_points: 2,
+                hard_limit: true,
+            },
+            placement_area: PlacementArea::NonMirrored(
+                vec![
+                    (Color::Red, own_area.clone()),
+                    (Color::Blue, own_area.clone()),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            piece_limits: piece_limits.clone(),
+        },
+        StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::TurnsCount { per_turn_count: 1 },
+            placement_area: PlacementArea::MirroredFlipped(own_area.clone()),
+            piece_limits,
+        },
+        StartingPositions::Random {
+            seed: 7,
+            piece_counts: vec![(0, 1), (1, 2)].into_iter().collect(),
+            area: PlacementArea::MirroredRotated(own_area),
+        },
+    ];
+
+    let mut out: Vec<Ruleset> = starting_positions
+        .into_iter()
+        .map(|starting_positions| Ruleset {
+            pieces: standard_pieces(),
+            board_type: board_type.clone(),
+            starting_positions,
+            victory_conditions: victory_conditions.clone(),
+            stalemate_rule: Default::default(),
+            pass_rule: Default::default(),
+            scoring: scoring.clone(),
+            game_length_rule: Default::default(),
+            chance_rule: Default::default(),
+        })
+        .collect();
+    let mut custom = Grid::new((4, 3));
+    custom[(0, 1)] = Space::Goal(Color::Red);
+    custom[(3, 1)] = Space::Goal(Color::Blue);
+    custom[(1, 0)] = Space::Invalid;
+    out.push(Ruleset {
+        pieces: standard_pieces(),
+        board_type: BoardType::Custom(custom),
+        starting_positions: StartingPositions::Random {
+            seed: 0,
+            piece_counts: vec![(1, 1)].into_iter().collect(),
+            area: PlacementArea::Half,
+        },
+        victory_conditions,
+        stalemate_rule: StalemateRule::Pass,
+        pass_rule: PassRule::WhenBlocked,
+        scoring,
+        game_length_rule: GameLengthRule {
+            max_turns: Some(80),
+            tiebreak: Tiebreak::ClosestToGoal,
+        },
+        chance_rule: ChanceRule {
+            faces: vec![
+                ChanceFace {
+                    pieces: Some(vec![0]),
+                    directions: None,
+                },
+                ChanceFace {
+                    pieces: None,
+                    directions: Some(Directions::CARDINAL),
+                },
+            ],
+        },
+    });
+    out
+}
+
+fn sample_record() -> GameRecord {
+    let mut victory_conditions = HashSet::new();
+    victory_conditions.insert(VictoryCondition::AllCaptured);
+    let ruleset = Ruleset {
+        pieces: standard_pieces(),
+        board_type: BoardType::Rectangular {
+            rows: 6,
+            columns: 6,
+            goal_depth: 1,
+            red_goals: GoalLocations::Columns(vec![2, 3].into_iter().collect()),
+            blue_goals: GoalLocations::Centered { width: 2 },
+        },
+        starting_positions: StartingPositions::Random {
+            seed: 3,
+            piece_counts: vec![(0, 2), (1, 3)].into_iter().collect(),
+            area: PlacementArea::Half,
+        },
+        victory_conditions,
+        stalemate_rule: StalemateRule::Draw,
+        pass_rule: PassRule::Always,
+        scoring: Default::default(),
+        game_length_rule: Default::default(),
+        chance_rule: Default::default(),
+    };
+    let mut playout = Game::new(ruleset)
+        .expect("Sample ruleset must be valid")
+        .playout(RandomPolicy::new(3));
+    playout.by_ref().take(40).for_each(drop);
+    playout.game().record()
+}
+
+#[cfg(feature = "net")]
+fn sample_messages(record: &GameRecord) -> Vec<crate::net::Message> {
+    use crate::action::{Action, ActionType};
+    use crate::direction::Direction;
+    use crate::net::{ClockState, GameId, Hello, Message, RejectReason};
+
+    let action = record.actions.first().cloned().unwrap_or(Action {
+        start_pos: Coordinate::new(1, 1),
+        action_type: ActionType::Jump([Direction::South, Direction::East].into()),
+    });
+    vec![
+        Message::Hello(Hello {
+            protocol_version: crate::net::PROTOCOL_VERSION,
+            ruleset_fingerprint: record.ruleset.fingerprint(),
+            game_id: GameId(9),
+            color: Some(Color::Red),
+        }),
+        Message::Reject(RejectReason::ProtocolVersion { ours: 1, theirs: 2 }),
+        Message::Action {
+            sequence: 0,
+            action,
+        },
+        Message::Action {
+            sequence: 1,
+            action: Action::pass(),
+        },
+        Message::ClockSync {
+            clocks: ClockState {
+                red_remaining_ms: 1,
+                blue_remaining_ms: 2,
+            },
+            sent_at_ms: 3,
+        },
+        Message::Resume {
+            game_id: GameId(9),
+            last_sequence: None,
+        },
+        Message::ResumeActions {
+            first_sequence: 0,
+            actions: record.actions.clone(),
+        },
+    ]
+}
+
+pub type FormatResult<T> = Result<T, FormatError>;
+#[derive(Clone, Debug)]
+pub enum FormatError {
+    Serialize {
+        format: Format,
+        message: String,
+    },
+    Deserialize {
+        format: Format,
+        message: String,
+    },
+    /// A value did not survive the round trip unchanged.
+    Mismatch {
+        format: Format,
+        type_name: &'static str,
+    },
+}
+impl Display for FormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+impl Error for FormatError {}
+
+#[cfg(test)]
+mod test {
+    use crate::formats::{json_schema, selftest, SchemaKind};
+
+    #[test]
+    fn selftest_test() {
+        selftest().unwrap();
+    }
+
+    #[test]
+    fn schema_test() {
+        for kind in SchemaKind::ALL.iter() {
+            let schema: serde_json::Value = serde_json::from_str(&json_schema(*kind)).unwrap();
+            assert!(schema["definitions"]["PieceDefinition"].is_object());
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Turns limited by the ruleset's die, see [`ChanceRule`].
+//!
+//! The face for each turn is rolled from [`Game::seed`] when the turn starts and kept in
+//! [`Game::rolls`], records carry the rolls so replays roll the same faces.
+//!
+//! [`ChanceRule`]: crate::ruleset::chance_rule::ChanceRule
+
+use alloc::vec::Vec;
+
+use crate::action::Action;
+use crate::game::{Game, GameError, GameResult};
+use crate::game_board::board::Board;
+use crate::movegen::legal_actions;
+use crate::ruleset::chance_rule::ChanceFace;
+
+impl<B: Board + Clone> Game<B> {
+    /// The face rolled for the current turn, `None` without a
+    /// [`ChanceRule`](crate::ruleset::chance_rule::ChanceRule).
+    pub fn roll(&self) -> Option<&ChanceFace> {
+        self.rolls
+            .get(self.history.len())
+            .and_then(|&face| self.ruleset.chance_rule.faces.get(face))
+    }
+    /// The face rolled for each turn by ply, including the current one.
+    pub fn rolls(&self) -> &[usize] {
+        &self.rolls
+    }
+
+    /// [`Game::set_seed`] for a game about to be replayed.
+    pub(crate) fn with_seed(mut self, seed: u64) -> Self {
+        self.set_seed(seed);
+        self
+    }
+    /// Uses `rolls` for the turns they cover, for replaying a record.
+    pub(crate) fn with_rolls(mut self, rolls: &[usize]) -> GameResult<Self> {
+        let faces = self.ruleset.chance_rule.faces.len();
+        if let Some((ply, &face)) = rolls.iter().enumerate().find(|(_, &face)| face >= faces) {
+            return Err(GameError::IllegalRoll { ply, face });
+        }
+        self.rolls = rolls.to_vec();
+        self.roll_chance();
+        Ok(self)
+    }
+    /// Rolls the current turn's face if it was not rolled yet.
+    pub(crate) fn roll_chance(&mut self) {
+        while self.rolls.len() <= self.history.len() {
+            match self.ruleset.chance_rule.roll(self.seed, self.rolls.len()) {
+                Some(face) => self.rolls.push(face),
+                None => return,
+            }
+        }
+    }
+    /// The part of the color to move's `actions` the roll allows, all of them if it allows none.
+    pub(crate) fn rolled(&self, actions: Vec<Action>) -> Vec<Action> {
+        let face = match self.roll() {
+            Some(face) => face,
+            None => return actions,
+        };
+        let allowed: Vec<Action> = actions
+            .iter()
+            .filter(|action| face.allows(&self.board, action))
+            .cloned()
+            .collect();
+        if allowed.is_empty() {
+            actions
+        } else {
+            allowed
+        }
+    }
+    /// Whether the roll allows the color to move to play `action`.
+    pub(crate) fn is_rolled(&self, action: &Action) -> bool {
+        match self.roll() {
+            Some(face) => {
+                face.allows(&self.board, action)
+                    || !legal_actions(&self.board, self.to_move)
+                        .iter()
+                        .any(|action| face.allows(&self.board, action))
+            }
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+
+use crate::action::{Action, ActionOutcome};
+use crate::coordinate::Coordinate;
+use crate::game::placement::Placement;
+use crate::game::GameOutcome;
+use crate::game_board::{Color, PieceInstance};
+
+/// The stage a [`Game`](crate::game::Game) is in.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase {
+    Placement,
+    Play,
+    Finished(GameOutcome),
+}
+
+/// A change to a game, in the order it happened.
+///
+/// Applying every event in order reproduces what a spectator needs to show,
+/// see [`Game::take_events`](crate::game::Game::take_events).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEvent {
+    /// The game entered a phase, the first event of every game.
+    PhaseChanged(Phase),
+    Placed(Placement),
+    /// Pieces `color` can still place, `None` if no limit applies.
+    ReserveChanged {
+        color: Color,
+        piece_index: usize,
+        remaining: Option<usize>,
+    },
+    /// Time left on the current decision, sent at the configured interval.
+    ClockUpdated {
+        color: Color,
+        remaining_ms: u64,
+    },
+    Action {
+        action: Action,
+        outcome: ActionOutcome,
+    },
+    /// The color to move removed `piece` for ignoring a capture, see
+    /// [`Game::huff`](crate::game::Game::huff).
+    Huffed {
+        position: Coordinate,
+        piece: PieceInstance,
+    },
+    /// The opponent's placements an [`EventFog`](crate::game::fog::EventFog) withheld, once
+    /// placing ends. Games never report this themselves.
+    Revealed(Vec<Placement>),
+}
+
+/// Pending events and the clock interval they are reported at.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EventQueue {
+    pub events: Vec<GameEvent>,
+    pub clock_interval_ms: Option<u64>,
+    /// Clock time since the last [`GameEvent::ClockUpdated`].
+    pub clock_since_ms: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collections::HashSet;
+    use crate::game::event::{GameEvent, Phase};
+    use crate::game::placement::{PlacementPhase, TimeoutPolicy};
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::game_board::Color;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::ruleset::verified::VerifiedRuleset;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn event_stream_test() {
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 2 });
+        let mut ruleset = small_ruleset(0);
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::TurnsCount { per_turn_count: 1 },
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        let phase = PlacementPhase::new(&VerifiedRuleset::new(ruleset.clone()).unwrap())
+            .unwrap()
+            .with_clock(100, TimeoutPolicy::AutoPlace { seed: 1 });
+        let mut game = Game::resume_placement(ruleset, phase).unwrap();
+        game.set_clock_interval(Some(30));
+
+        let events = game.take_events();
+        assert_eq!(events[0], GameEvent::PhaseChanged(Phase::Placement));
+        assert!(events.contains(&GameEvent::ReserveChanged {
+            color: Color::Blue,
+            piece_index: 1,
+            remaining: Some(2),
+        }));
+
+        game.tick_placement(20);
+        assert!(game.take_events().is_empty());
+        game.tick_placement(20);
+        assert_eq!(
+            game.take_events(),
+            vec![GameEvent::ClockUpdated {
+                color: Color::Red,
+                remaining_ms: 60,
+            }]
+        );
+
+        while game.placement().is_some() {
+            game.tick_placement(100);
+        }
+        let events = game.take_events();
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| matches!(event, GameEvent::Placed(_)))
+                .count(),
+            4
+        );
+        assert!(events.contains(&GameEvent::ReserveChanged {
+            color: Color::Red,
+            piece_index: 0,
+            remaining: Some(0),
+        }));
+        assert!(events.contains(&GameEvent::PhaseChanged(Phase::Play)));
+
+        let mut playout = game.playout(RandomPolicy::new(2));
+        let plies = playout.by_ref().take(1_000).count();
+        let events = playout.into_game().take_events();
+        let actions = events
+            .iter()
+            .filter(|event| matches!(event, GameEvent::Action { .. }))
+            .count();
+        assert_eq!(actions, plies);
+    }
+}
@@ -0,0 +1,196 @@
+//! What each color may see while [`AlternationType::Hidden`] placements are in progress.
+//!
+//! Servers send each client its [`Game::fogged`] view and its events through an [`EventFog`],
+//! the opponent's placements are revealed together once placing ends.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::game::event::{GameEvent, Phase};
+use crate::game::placement::Placement;
+use crate::game::Game;
+use crate::game_board::board::Board;
+use crate::game_board::{Color, GameBoard};
+use crate::ruleset::starting_positions::alteration_type::AlternationType;
+
+/// A game as one color may see it.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FoggedGameState {
+    pub viewer: Color,
+    /// The board without the opponent's hidden pieces.
+    pub board: GameBoard,
+    /// The placements `viewer` may see, in order.
+    pub placements: Vec<Placement>,
+    /// How many of the opponent's placements are hidden.
+    pub hidden: usize,
+    pub phase: Phase,
+    pub to_move: Color,
+}
+impl FoggedGameState {
+    pub fn is_fogged(&self) -> bool {
+        self.hidden > 0
+    }
+    /// Whether `board` could be the real board as far as the viewer knows, that is whether it
+    /// is in the viewer's information set.
+    ///
+    /// Placement areas and limits of the hidden pieces are not checked.
+    pub fn is_consistent(&self, board: &GameBoard) -> bool {
+        let opponent = self.viewer.opponent();
+        let visible = self.board.pieces_of_color(opponent);
+        board.pieces_of_color(self.viewer) == self.board.pieces_of_color(self.viewer)
+            && board.pieces_of_color(opponent).len() == visible.len() + self.hidden
+            && visible
+                .iter()
+                .all(|&(position, piece)| board.piece(position).ok().flatten() == Some(piece))
+    }
+}
+
+impl Game {
+    /// Whether placements are hidden from the opponent, during an
+    /// [`AlternationType::Hidden`] placement phase.
+    pub fn is_fogged(&self) -> bool {
+        self.placement()
+            .is_some_and(|phase| matches!(phase.alternation_type(), AlternationType::Hidden))
+    }
+    /// What `viewer` may see of this game.
+    pub fn fogged(&self, viewer: Color) -> FoggedGameState {
+        let (mut board, placements) = match self.placement() {
+            Some(phase) => (phase.board().clone(), phase.placements()),
+            None => (self.board().clone(), self.placements()),
+        };
+        let mut visible = Vec::with_capacity(placements.len());
+        let mut hidden = 0;
+        for &placement in placements {
+            if self.is_fogged() && placement.color != viewer {
+                *board
+                    .piece_mut(placement.position)
+                    .expect("Placements are on the board") = None;
+                hidden += 1;
+            } else {
+                visible.push(placement);
+            }
+        }
+        FoggedGameState {
+            viewer,
+            board,
+            placements: visible,
+            hidden,
+            phase: self.phase(),
+            to_move: self.to_move(),
+        }
+    }
+}
+
+/// Withholds the opponent's placements and reserves from a stream of [`GameEvent`]s while
+/// placements are hidden, revealing the placements once the phase changes.
+#[derive(Clone, Debug)]
+pub struct EventFog {
+    viewer: Color,
+    hidden: bool,
+    withheld: Vec<Placement>,
+}
+impl EventFog {
+    /// Filters the events of `game` for `viewer`, made before taking any.
+    pub fn new(game: &Game, viewer: Color) -> Self {
+        Self {
+            viewer,
+            hidden: game.is_fogged(),
+            withheld: Vec::new(),
+        }
+    }
+    /// The events `viewer` may see in place of `event`.
+    pub fn filter(&mut self, event: GameEvent) -> Vec<GameEvent> {
+        if !self.hidden {
+            return vec![event];
+        }
+        match event {
+            GameEvent::Placed(placement) if placement.color != self.viewer => {
+                self.withheld.push(placement);
+                Vec::new()
+            }
+            GameEvent::ReserveChanged { color, .. } if color != self.viewer => Vec::new(),
+            GameEvent::PhaseChanged(phase) if phase != Phase::Placement => {
+                self.hidden = false;
+                vec![
+                    GameEvent::Revealed(core::mem::take(&mut self.withheld)),
+                    event,
+                ]
+            }
+            event => vec![event],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collections::HashSet;
+    use crate::game::event::{GameEvent, Phase};
+    use crate::game::fog::EventFog;
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::game_board::Color;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn fog_test() {
+        let mut ruleset = small_ruleset(0);
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 2 });
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::Hidden,
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        let mut game = Game::new(ruleset).unwrap();
+        let mut fog = EventFog::new(&game, Color::Blue);
+        assert!(game.is_fogged());
+
+        let mut seen = Vec::new();
+        while let Some(phase) = game.placement() {
+            match phase.legal_placements().first() {
+                Some(placement) => game
+                    .place(placement.piece_index, placement.position)
+                    .unwrap(),
+                None => game.finish_placing().unwrap(),
+            }
+            let view = game.fogged(Color::Blue);
+            if let Some(phase) = game.placement() {
+                assert!(view.is_consistent(phase.board()));
+                assert_eq!(
+                    view.hidden,
+                    phase
+                        .placements()
+                        .iter()
+                        .filter(|placement| placement.color == Color::Red)
+                        .count()
+                );
+                assert!(view.board.pieces_of_color(Color::Red).is_empty());
+            }
+            for event in game.take_events() {
+                seen.extend(fog.filter(event));
+            }
+        }
+        assert!(!game.is_fogged());
+        assert!(!game.fogged(Color::Blue).is_fogged());
+        assert_eq!(game.fogged(Color::Blue).placements, game.placements());
+
+        assert!(seen.iter().all(
+            |event| !matches!(event, GameEvent::Placed(placement) if placement.color == Color::Red)
+        ));
+        let revealed = seen
+            .iter()
+            .position(|event| matches!(event, GameEvent::Revealed(_)))
+            .unwrap();
+        match &seen[revealed] {
+            GameEvent::Revealed(placements) => assert_eq!(placements.len(), 2),
+            _ => unreachable!(),
+        }
+        assert_eq!(seen[revealed + 1], GameEvent::PhaseChanged(Phase::Play));
+    }
+}
@@ -0,0 +1,261 @@
+//! The huffing penalty for ignored captures, see [`Game::huff`].
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::action::Action;
+use crate::coordinate::Coordinate;
+use crate::game::event::GameEvent;
+use crate::game::Game;
+use crate::game_board::board::Board;
+use crate::game_board::PieceInstance;
+use crate::movegen::{captures, legal_actions};
+use crate::ruleset::piece_definition::CaptureRequirement;
+
+/// A piece removed by [`Game::huff`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct Huff {
+    /// Actions played before the huff, it answers the action at `ply - 1`.
+    pub ply: usize,
+    pub position: Coordinate,
+}
+
+impl<B: Board + Clone> Game<B> {
+    /// Squares of the opponent's pieces the color to move may [`huff`](Game::huff).
+    ///
+    /// Pieces with [`CaptureRequirement::Huff`] that could have captured when the last action
+    /// captured nothing, empty once the color to move acts or huffs.
+    pub fn huffable(&self) -> &[Coordinate] {
+        &self.huffable
+    }
+    /// Pieces removed by huffing, in the order they were huffed.
+    pub fn huffs(&self) -> &[Huff] {
+        &self.huffs
+    }
+    /// Removes the opponent's piece at `position` for ignoring a capture.
+    ///
+    /// Takes the place of forced captures in rulesets using [`CaptureRequirement::Huff`]. The
+    /// color to move may huff one piece before its action and still plays that action.
+    pub fn huff(&mut self, position: Coordinate) -> HuffResult<PieceInstance> {
+        if self.is_finished() {
+            return Err(HuffError::GameFinished);
+        }
+        if !self.huffable.contains(&position) {
+            return Err(HuffError::NotHuffable(position));
+        }
+        let piece = self
+            .board
+            .piece(position)
+            .ok()
+            .flatten()
+            .expect("Huffable squares hold a piece");
+        self.board
+            .set_piece(position, None)
+            .expect("Huffable squares are on the board");
+        if let Some(cache) = &mut self.move_cache {
+            cache.update(&self.board, &[position]);
+        }
+        self.huffable.clear();
+        self.huffs.push(Huff {
+            ply: self.history.len(),
+            position,
+        });
+        self.events
+            .events
+            .push(GameEvent::Huffed { position, piece });
+        self.update_result();
+        Ok(piece)
+    }
+
+    /// Pieces of the color to move that huff and have a capture, before it plays `action`.
+    ///
+    /// Returns where each would be after `action`, empty if no piece huffs.
+    pub(crate) fn huff_candidates(&self, action: &Action) -> Vec<Coordinate> {
+        let huffs = |piece: PieceInstance| {
+            self.ruleset
+                .pieces
+                .get(piece.definition_index())
+                .is_some_and(|piece| piece.capture_requirement == CaptureRequirement::Huff)
+        };
+        let mut out = Vec::new();
+        if !self
+            .ruleset
+            .pieces
+            .iter()
+            .any(|piece| piece.capture_requirement == CaptureRequirement::Huff)
+        {
+            return out;
+        }
+        for capture in legal_actions(&self.board, self.to_move) {
+            if !capture.action_type.is_jump() || out.contains(&capture.start_pos) {
+                continue;
+            }
+            let huffed =
+                matches!(self.board.piece(capture.start_pos), Ok(Some(piece)) if huffs(piece));
+            if huffed && captures(&self.board, &capture) {
+                out.push(capture.start_pos);
+            }
+        }
+        let moved_to = self.board.destination(action);
+        for position in out.iter_mut() {
+            if *position == action.start_pos {
+                if let Some(moved_to) = moved_to {
+                    *position = moved_to;
+                }
+            }
+        }
+        out
+    }
+}
+
+pub type HuffResult<T> = Result<T, HuffError>;
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HuffError {
+    /// The game already has a result.
+    GameFinished,
+    /// No piece there ignored a capture last action, see [`Game::huffable`].
+    NotHuffable(Coordinate),
+}
+impl Display for HuffError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for HuffError {}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use crate::action::{Action, ActionError, ActionType};
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game::event::GameEvent;
+    use crate::game::huff::{Huff, HuffError};
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::game_board::{Color, PieceInstance};
+    use crate::notation::{format_move_list, parse_move_list, CoordinateScheme};
+    use crate::ruleset::piece_definition::CaptureRequirement;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn huff_test() {
+        let mut ruleset = small_ruleset(0);
+        for piece in ruleset.pieces.iter_mut() {
+            piece.capture_requirement = CaptureRequirement::Huff;
+        }
+        let mut position = crate::collections::HashMap::new();
+        position.insert(
+            Color::Red,
+            vec![(1, vec![Coordinate::new(3, 2), Coordinate::new(2, 5)])]
+                .into_iter()
+                .collect(),
+        );
+        position.insert(
+            Color::Blue,
+            vec![(1, vec![Coordinate::new(4, 2), Coordinate::new(5, 5)])]
+                .into_iter()
+                .collect(),
+        );
+        ruleset.starting_positions = StartingPositions::NotMirrored(position);
+        let mut game = Game::new(ruleset).unwrap();
+
+        // Red steps away from its capture
+        let ignore = Action {
+            start_pos: Coordinate::new(3, 2),
+            action_type: ActionType::Move(Direction::North),
+        };
+        game.apply(&ignore).unwrap();
+        assert_eq!(game.huffable(), &[Coordinate::new(2, 2)]);
+        assert!(matches!(
+            game.huff(Coordinate::new(2, 5)),
+            Err(HuffError::NotHuffable(_))
+        ));
+        game.take_events();
+        assert_eq!(
+            game.huff(Coordinate::new(2, 2)).unwrap(),
+            PieceInstance::new(1, Color::Red)
+        );
+        assert_eq!(game.board().piece(Coordinate::new(2, 2)).unwrap(), None);
+        assert!(game.huffable().is_empty());
+        assert_eq!(
+            game.take_events(),
+            vec![GameEvent::Huffed {
+                position: Coordinate::new(2, 2),
+                piece: PieceInstance::new(1, Color::Red),
+            }]
+        );
+        assert_eq!(game.to_move(), Color::Blue);
+
+        let step = Action {
+            start_pos: Coordinate::new(5, 5),
+            action_type: ActionType::Move(Direction::West),
+        };
+        game.apply(&step).unwrap();
+        assert!(game.huffable().is_empty());
+
+        let record = game.record();
+        assert_eq!(
+            record.huffs,
+            vec![Huff {
+                ply: 1,
+                position: Coordinate::new(2, 2),
+            }]
+        );
+        let replayed = record.replay().unwrap();
+        assert_eq!(replayed.board().board, game.board().board);
+
+        let text = format_move_list(&record, CoordinateScheme::Algebraic);
+        assert!(text.contains(" huff:c3"));
+        let parsed = parse_move_list(&text, CoordinateScheme::Algebraic).unwrap();
+        assert_eq!(parsed.actions, record.actions);
+        assert_eq!(parsed.huffs, record.huffs);
+    }
+
+    #[test]
+    fn forced_or_huff_test() {
+        let with_requirement = |capture_requirement| {
+            let mut ruleset = small_ruleset(0);
+            for piece in ruleset.pieces.iter_mut() {
+                piece.capture_requirement = capture_requirement;
+            }
+            let mut position = crate::collections::HashMap::new();
+            position.insert(
+                Color::Red,
+                vec![(1, vec![Coordinate::new(3, 2)])].into_iter().collect(),
+            );
+            position.insert(
+                Color::Blue,
+                vec![(1, vec![Coordinate::new(4, 2)])].into_iter().collect(),
+            );
+            ruleset.starting_positions = StartingPositions::NotMirrored(position);
+            Game::new(ruleset).unwrap()
+        };
+        let ignore = Action {
+            start_pos: Coordinate::new(3, 2),
+            action_type: ActionType::Move(Direction::North),
+        };
+
+        let mut forced = with_requirement(CaptureRequirement::Forced(0));
+        assert!(!forced.legal_actions().contains(&ignore));
+        assert!(matches!(
+            forced.apply(&ignore),
+            Err(ActionError::CaptureForced)
+        ));
+        assert!(forced.huffable().is_empty());
+
+        let mut huff = with_requirement(CaptureRequirement::Huff);
+        assert!(huff.legal_actions().contains(&ignore));
+        huff.apply(&ignore).unwrap();
+        assert_eq!(huff.huffable(), &[Coordinate::new(2, 2)]);
+    }
+}
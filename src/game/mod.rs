@@ -0,0 +1,714 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use alloc::vec::Vec;
+
+use crate::action::{Action, ActionError, ActionOutcome};
+use crate::coordinate::Coordinate;
+use crate::game::event::{EventQueue, GameEvent, Phase};
+use crate::game::huff::{Huff, HuffError};
+use crate::game::placement::{Placement, PlacementError, PlacementPhase, PlacementResult, Timeout};
+use crate::game::simultaneous::Round;
+use crate::game::statistics::GameStatistics;
+use crate::game_board::board::Board;
+use crate::game_board::{Color, GameBoard, PieceInstance};
+#[cfg(feature = "metrics")]
+use crate::metrics;
+#[cfg(feature = "metrics")]
+use crate::metrics::{Counter, ErrorKind};
+use crate::movegen::{is_forced_allowed, legal_actions, MoveCache};
+use crate::ruleset::pass_rule::PassRule;
+use crate::ruleset::scoring::Scores;
+use crate::ruleset::stalemate_rule::StalemateRule;
+use crate::ruleset::starting_positions::{StartingPositions, StartingPositionsError};
+use crate::ruleset::verified::RulesetHandle;
+use crate::ruleset::victory_condition::{VictoryCondition, VictoryState};
+use crate::ruleset::{Ruleset, RulesetError};
+
+pub mod chance;
+pub mod event;
+pub mod fog;
+pub mod huff;
+pub mod placement;
+pub mod playout;
+pub mod preview;
+pub mod record;
+pub mod save;
+pub mod scenario;
+pub mod simultaneous;
+pub mod statistics;
+pub mod team;
+
+/// A game in progress under a ruleset.
+///
+/// Play works on any [`Board`], starting, placing, and saving need a [`GameBoard`], see
+/// [`Game::with_board`].
+#[derive(Clone, Debug)]
+pub struct Game<B = GameBoard> {
+    ruleset: RulesetHandle,
+    board: B,
+    to_move: Color,
+    history: Vec<(Action, ActionOutcome)>,
+    scores: Scores,
+    result: Option<GameOutcome>,
+    placement: Option<PlacementPhase>,
+    placements: Vec<Placement>,
+    events: EventQueue,
+    move_cache: Option<MoveCache>,
+    /// The color whose draw offer is pending.
+    draw_offer: Option<Color>,
+    /// Pieces the color to move may huff, see [`Game::huffable`].
+    huffable: Vec<Coordinate>,
+    huffs: Vec<Huff>,
+    /// Actions waiting for [`Game::resolve`], by [`Color::index`].
+    submissions: [Option<Action>; 2],
+    rounds: Vec<Round>,
+    /// See [`Game::set_seed`].
+    seed: u64,
+    rolls: Vec<usize>,
+}
+impl Game {
+    /// Starts a game from the ruleset's starting positions.
+    ///
+    /// [`StartingPositions::Placement`] rulesets start with a [`PlacementPhase`],
+    /// actions can be played once it finishes with `first_color` to move.
+    pub fn new(ruleset: Ruleset) -> GameResult<Self> {
+        Self::count_error(
+            RulesetHandle::new(ruleset)
+                .map_err(GameError::from)
+                .and_then(Self::new_unrecorded),
+        )
+    }
+    /// Starts a game from an already verified ruleset, see [`Game::new`].
+    pub fn from_handle(ruleset: impl Into<RulesetHandle>) -> GameResult<Self> {
+        Self::count_error(Self::new_unrecorded(ruleset.into()))
+    }
+    fn count_error(out: GameResult<Self>) -> GameResult<Self> {
+        #[cfg(feature = "metrics")]
+        if out.is_err() {
+            metrics::increment_counter(Counter::Errors(ErrorKind::Ruleset), 1);
+        }
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &out {
+            tracing::debug!(?error, "could not start game");
+        }
+        out
+    }
+    fn new_unrecorded(ruleset: RulesetHandle) -> GameResult<Self> {
+        let mut board = ruleset.starting_positions.build_board(&ruleset)?;
+        board.index_pieces();
+        let to_move = match &ruleset.starting_positions {
+            StartingPositions::Placement { first_color, .. } => *first_color,
+            _ => Color::Red,
+        };
+        let placement = PlacementPhase::new(&ruleset);
+        let mut out = Self {
+            ruleset,
+            board,
+            to_move,
+            history: Vec::new(),
+            scores: Scores::default(),
+            result: None,
+            placement,
+            placements: Vec::new(),
+            events: EventQueue::default(),
+            move_cache: None,
+            draw_offer: None,
+            huffable: Vec::new(),
+            huffs: Vec::new(),
+            submissions: [None, None],
+            rounds: Vec::new(),
+            seed: 0,
+            rolls: Vec::new(),
+        };
+        out.roll_chance();
+        out.push_start_events();
+        Ok(out)
+    }
+    /// Resumes a game whose placement phase was interrupted.
+    pub fn resume_placement(ruleset: Ruleset, placement: PlacementPhase) -> GameResult<Self> {
+        let mut out = Self::new(ruleset)?;
+        out.board = placement.board().clone();
+        out.board.index_pieces();
+        out.placements = placement.placements().to_vec();
+        out.placement = Some(placement);
+        out.events.events.clear();
+        out.push_start_events();
+        out.finish_placement();
+        Ok(out)
+    }
+    fn push_start_events(&mut self) {
+        self.events
+            .events
+            .push(GameEvent::PhaseChanged(self.phase()));
+        if let Some(placement) = &self.placement {
+            for (color, piece_index, remaining) in reserves(placement) {
+                self.events.events.push(GameEvent::ReserveChanged {
+                    color,
+                    piece_index,
+                    remaining,
+                });
+            }
+        }
+    }
+}
+
+impl<B: Board + Clone> Game<B> {
+    pub fn ruleset(&self) -> &Ruleset {
+        &self.ruleset
+    }
+    /// The shared ruleset, for starting more games without verifying it again.
+    pub fn ruleset_handle(&self) -> &RulesetHandle {
+        &self.ruleset
+    }
+    pub fn board(&self) -> &B {
+        &self.board
+    }
+    pub fn to_move(&self) -> Color {
+        self.to_move
+    }
+    /// Every action played so far with its outcome.
+    pub fn history(&self) -> &[(Action, ActionOutcome)] {
+        &self.history
+    }
+    /// Points scored so far under the ruleset's [`Scoring`](crate::ruleset::scoring::Scoring).
+    pub fn scores(&self) -> Scores {
+        self.scores
+    }
+    /// The result, `None` while the game is in progress.
+    pub fn result(&self) -> Option<GameOutcome> {
+        self.result
+    }
+    pub fn is_finished(&self) -> bool {
+        self.result.is_some()
+    }
+    pub fn phase(&self) -> Phase {
+        match (self.result, &self.placement) {
+            (Some(result), _) => Phase::Finished(result),
+            (None, Some(_)) => Phase::Placement,
+            (None, None) => Phase::Play,
+        }
+    }
+    /// The placement phase while it is in progress.
+    pub fn placement(&self) -> Option<&PlacementPhase> {
+        self.placement.as_ref()
+    }
+    /// Every placement made before play started.
+    pub fn placements(&self) -> &[Placement] {
+        &self.placements
+    }
+
+    /// Removes and returns every event since the last call.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        core::mem::take(&mut self.events.events)
+    }
+    /// Reports [`GameEvent::ClockUpdated`] every `interval_ms` of clock time, `None` to stop.
+    pub fn set_clock_interval(&mut self, interval_ms: Option<u64>) {
+        self.events.clock_interval_ms = interval_ms;
+        self.events.clock_since_ms = 0;
+    }
+    /// The seed of the game's random rules, see [`Game::set_seed`].
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+    /// Seeds the game's random rules, rerolling the current turn's [`roll`](Game::roll) and
+    /// every one after it.
+    ///
+    /// Games start seeded with 0, so apps wanting different games seed them before playing.
+    /// Random starting positions are seeded by their ruleset instead.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rolls.truncate(self.history.len());
+        self.roll_chance();
+    }
+}
+
+impl Game {
+    /// Piece counts, mobility, goals, pending captures, and scores for both colors.
+    pub fn statistics(&self) -> GameStatistics {
+        let mut out = GameStatistics::new(&self.board, self.ruleset.pieces.len());
+        out.red.score = self.scores.red;
+        out.blue.score = self.scores.blue;
+        out
+    }
+    /// The game played on another board representation, such as a
+    /// [`StandardBoard`](crate::game_board::fixed::StandardBoard).
+    ///
+    /// `None` during placement, which places on a [`GameBoard`], or if the board does not
+    /// convert.
+    pub fn with_board<C>(&self) -> Option<Game<C>>
+    where
+        C: for<'a> TryFrom<&'a GameBoard>,
+    {
+        if self.placement.is_some() {
+            return None;
+        }
+        Some(Game {
+            ruleset: self.ruleset.clone(),
+            board: C::try_from(&self.board).ok()?,
+            to_move: self.to_move,
+            history: self.history.clone(),
+            scores: self.scores,
+            result: self.result,
+            placement: None,
+            placements: self.placements.clone(),
+            events: self.events.clone(),
+            move_cache: self.move_cache.clone(),
+            draw_offer: self.draw_offer,
+            huffable: self.huffable.clone(),
+            huffs: self.huffs.clone(),
+            submissions: self.submissions.clone(),
+            rounds: self.rounds.clone(),
+            seed: self.seed,
+            rolls: self.rolls.clone(),
+        })
+    }
+
+    /// Places a piece during the placement phase, see [`PlacementPhase::place`].
+    pub fn place(&mut self, piece_index: usize, position: Coordinate) -> PlacementResult<()> {
+        let placement = self
+            .placement
+            .as_mut()
+            .ok_or(PlacementError::PhaseFinished)?;
+        let before = reserves(placement);
+        placement.place(piece_index, position)?;
+        let placed = *placement.placements().last().unwrap();
+        self.push_placed(placed, before);
+        self.finish_placement();
+        Ok(())
+    }
+    /// Ends placing for the color to place, see [`PlacementPhase::finish_placing`].
+    pub fn finish_placing(&mut self) -> PlacementResult<()> {
+        self.placement
+            .as_mut()
+            .ok_or(PlacementError::PhaseFinished)?
+            .finish_placing()?;
+        self.finish_placement();
+        Ok(())
+    }
+    /// Advances the placement clock, see [`PlacementPhase::tick`].
+    pub fn tick_placement(&mut self, elapsed_ms: u64) -> Option<Timeout> {
+        let phase = self.placement.as_mut()?;
+        let before = reserves(phase);
+        let timeout = phase.tick(elapsed_ms);
+        match timeout {
+            Some(Timeout::AutoPlaced(placement)) => self.push_placed(placement, before),
+            Some(Timeout::Forfeited(_)) => {}
+            None => self.push_clock(elapsed_ms),
+        }
+        self.finish_placement();
+        timeout
+    }
+    fn push_placed(&mut self, placed: Placement, before: Vec<(Color, usize, Option<usize>)>) {
+        self.placements.push(placed);
+        self.events.events.push(GameEvent::Placed(placed));
+        self.events.clock_since_ms = 0;
+        let after = self.placement.as_ref().map(reserves).unwrap_or_default();
+        for (before, (color, piece_index, remaining)) in before.into_iter().zip(after) {
+            if before.2 != remaining {
+                self.events.events.push(GameEvent::ReserveChanged {
+                    color,
+                    piece_index,
+                    remaining,
+                });
+            }
+        }
+    }
+    fn push_clock(&mut self, elapsed_ms: u64) {
+        let interval_ms = match self.events.clock_interval_ms {
+            Some(interval_ms) => interval_ms,
+            None => return,
+        };
+        self.events.clock_since_ms += elapsed_ms;
+        if self.events.clock_since_ms < interval_ms {
+            return;
+        }
+        self.events.clock_since_ms %= interval_ms.max(1);
+        let phase = match &self.placement {
+            Some(phase) => phase,
+            None => return,
+        };
+        if let (Some(color), Some(clock)) = (phase.to_place(), phase.clock()) {
+            self.events.events.push(GameEvent::ClockUpdated {
+                color,
+                remaining_ms: clock.limit_ms.saturating_sub(clock.elapsed_ms),
+            });
+        }
+    }
+    fn finish_placement(&mut self) {
+        let placement = match self.placement.take() {
+            Some(placement) if placement.is_finished() => placement,
+            placement => {
+                self.placement = placement;
+                return;
+            }
+        };
+        if let Some(color) = placement.forfeited() {
+            self.result = Some(GameOutcome::Win(color.opponent()));
+        }
+        self.to_move = placement.first_color();
+        self.board = placement.into_board();
+        self.board.index_pieces();
+        if let Some(cache) = &mut self.move_cache {
+            *cache = MoveCache::new(&self.board);
+        }
+        if self.result.is_none() {
+            self.result = self.stalemate_result();
+        }
+        self.events
+            .events
+            .push(GameEvent::PhaseChanged(Phase::Play));
+        if let Some(result) = self.result {
+            self.events
+                .events
+                .push(GameEvent::PhaseChanged(Phase::Finished(result)));
+        } else {
+            self.pass_stalemate();
+        }
+    }
+}
+
+impl<B: Board + Clone> Game<B> {
+    /// All legal actions for the color to move, empty during placement and once the game is finished.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        if self.is_finished() || self.placement.is_some() {
+            return Vec::new();
+        }
+        self.actions_for(self.to_move)
+    }
+    /// [`Game::legal_actions`] from the move cache if enabled.
+    pub fn legal_actions_cached(&self) -> Vec<Action> {
+        let cache = match &self.move_cache {
+            Some(cache) => cache,
+            None => return self.legal_actions(),
+        };
+        if self.is_finished() || self.placement.is_some() {
+            return Vec::new();
+        }
+        self.with_pass(self.rolled(cache.legal_actions(&self.board, self.to_move)))
+    }
+    /// Keeps a [`MoveCache`] updated by [`Game::apply`] for [`Game::legal_actions_cached`].
+    pub fn set_move_cache(&mut self, enabled: bool) {
+        self.move_cache = if enabled {
+            Some(MoveCache::new(&self.board))
+        } else {
+            None
+        };
+    }
+    /// The board's actions for `color` the roll allows and a pass if the [`PassRule`] allows one.
+    fn actions_for(&self, color: Color) -> Vec<Action> {
+        let actions = legal_actions(&self.board, color);
+        if color == self.to_move {
+            self.with_pass(self.rolled(actions))
+        } else {
+            self.with_pass(actions)
+        }
+    }
+    fn with_pass(&self, mut out: Vec<Action>) -> Vec<Action> {
+        let pass = match self.ruleset.pass_rule {
+            PassRule::Never => false,
+            PassRule::WhenBlocked => out.is_empty(),
+            PassRule::Always => true,
+        };
+        if pass {
+            out.push(Action::pass());
+        }
+        out
+    }
+
+    /// Plays `action` for the color to move and passes the turn.
+    pub fn apply(&mut self, action: &Action) -> Result<ActionOutcome, ActionError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("apply", ply = self.history.len()).entered();
+        let out = self.apply_unrecorded(action);
+        #[cfg(feature = "tracing")]
+        match &out {
+            Ok(outcome) => tracing::trace!(?action, captures = outcome.captures.len(), "applied"),
+            Err(error) => tracing::debug!(?action, ?error, "rejected action"),
+        }
+        #[cfg(feature = "metrics")]
+        match out {
+            Ok(_) => metrics::increment_counter(Counter::Moves, 1),
+            Err(_) => metrics::increment_counter(Counter::Errors(ErrorKind::Action), 1),
+        }
+        out
+    }
+    /// Checks what the board does not, whether `color` may act now and owns the piece.
+    fn check_turn(&self, color: Color, action: &Action) -> Result<(), ActionError> {
+        if self.is_finished() {
+            return Err(ActionError::GameFinished);
+        }
+        if self.placement.is_some() {
+            return Err(ActionError::PlacementInProgress);
+        }
+        if action.action_type.is_pass() {
+            let allowed = self
+                .actions_for(color)
+                .iter()
+                .any(|action| action.action_type.is_pass());
+            if !allowed {
+                return Err(ActionError::PassNotAllowed);
+            }
+        } else if let Ok(Some(piece)) = self.board.piece(action.start_pos) {
+            if piece.color() != color {
+                return Err(ActionError::WrongColor(piece.color()));
+            }
+        }
+        if self.board.is_valid_action(action).is_ok()
+            && !is_forced_allowed(&self.board, color, action)
+        {
+            return Err(ActionError::CaptureForced);
+        }
+        if color == self.to_move && !self.is_rolled(action) {
+            return Err(ActionError::NotRolled);
+        }
+        Ok(())
+    }
+    fn apply_unrecorded(&mut self, action: &Action) -> Result<ActionOutcome, ActionError> {
+        self.check_turn(self.to_move, action)?;
+        self.play(action)
+    }
+    /// Plays `action` for the color to move without [`Game::check_turn`].
+    fn play(&mut self, action: &Action) -> Result<ActionOutcome, ActionError> {
+        let huff_candidates = self.huff_candidates(action);
+        let destination = self
+            .move_cache
+            .as_ref()
+            .and_then(|_| self.board.destination(action));
+        let mut board = self.board.clone();
+        let outcome = board.apply_with_outcome(action)?;
+        *self.scores.color_mut(self.to_move) +=
+            self.ruleset.scoring.points(&self.board, action, &outcome);
+        self.board = board;
+        if let Some(cache) = &mut self.move_cache {
+            let mut changed: Vec<_> = outcome
+                .captures
+                .iter()
+                .map(|(position, _)| *position)
+                .collect();
+            if let Some(destination) = destination {
+                changed.push(action.start_pos);
+                changed.push(destination);
+            }
+            cache.update(&self.board, &changed);
+        }
+
+        if self.draw_offer == Some(self.to_move.opponent()) {
+            self.draw_offer = None;
+        }
+        self.history.push((action.clone(), outcome.clone()));
+        self.events.events.push(GameEvent::Action {
+            action: action.clone(),
+            outcome: outcome.clone(),
+        });
+        self.to_move = self.to_move.opponent();
+        self.roll_chance();
+        self.huffable = if outcome.captures.is_empty() {
+            huff_candidates
+        } else {
+            Vec::new()
+        };
+        self.update_result();
+        Ok(outcome)
+    }
+    /// Decides the result after the board changed, see [`Game::evaluate_result`].
+    fn update_result(&mut self) {
+        self.result = self.evaluate_result();
+        if let Some(result) = self.result {
+            self.huffable.clear();
+            self.events
+                .events
+                .push(GameEvent::PhaseChanged(Phase::Finished(result)));
+        } else {
+            self.pass_stalemate();
+        }
+    }
+
+    /// Offers a draw from `color`, standing until the opponent accepts, declines, or plays an
+    /// action. Ignored once the game is finished.
+    pub fn offer_draw(&mut self, color: Color) {
+        if !self.is_finished() {
+            self.draw_offer = Some(color);
+        }
+    }
+    /// The color whose draw offer is pending.
+    pub fn draw_offer(&self) -> Option<Color> {
+        self.draw_offer
+    }
+    /// Draws the game if an offer is pending, returns whether it did.
+    pub fn accept_draw(&mut self) -> bool {
+        if self.draw_offer.take().is_none() || self.is_finished() {
+            return false;
+        }
+        self.result = Some(GameOutcome::Draw);
+        self.events
+            .events
+            .push(GameEvent::PhaseChanged(Phase::Finished(GameOutcome::Draw)));
+        true
+    }
+    pub fn decline_draw(&mut self) {
+        self.draw_offer = None;
+    }
+
+    /// Checks in order, the first to decide the game ends it:
+    /// the mover's victory conditions, the opponent's, two passes in a row drawing,
+    /// the [`GameLengthRule`](crate::ruleset::game_length_rule::GameLengthRule) tiebreak at the turn limit,
+    /// then the [`StalemateRule`] if the color to move has no legal action.
+    fn evaluate_result(&mut self) -> Option<GameOutcome> {
+        let mover = self.to_move.opponent();
+        // Only capture counts need the pieces captured so far
+        let captured: Vec<PieceInstance> = if self
+            .ruleset
+            .victory_conditions
+            .iter()
+            .flat_map(VictoryCondition::leaves)
+            .any(|condition| matches!(condition, VictoryCondition::CaptureCount { .. }))
+        {
+            self.history
+                .iter()
+                .flat_map(|(_, outcome)| outcome.captures.iter().map(|(_, piece)| *piece))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let state = VictoryState {
+            board: &self.board,
+            captured: &captured,
+            scores: self.scores,
+        };
+        for color in [mover, self.to_move] {
+            if self
+                .ruleset
+                .victory_conditions
+                .iter()
+                .any(|condition| condition.is_met_in(&state, color))
+            {
+                return Some(GameOutcome::Win(color));
+            }
+        }
+        let passes = self
+            .history
+            .iter()
+            .rev()
+            .take(2)
+            .filter(|(action, _)| action.action_type.is_pass())
+            .count();
+        if passes == 2 {
+            return Some(GameOutcome::Draw);
+        }
+        let length_rule = &self.ruleset.game_length_rule;
+        if length_rule.is_reached(self.history.len()) {
+            return Some(length_rule.tiebreak.decide(&self.board, self.scores));
+        }
+        self.stalemate_result()
+    }
+
+    /// Applies the ruleset's [`StalemateRule`] if the color to move has no legal action,
+    /// [`Game::pass_stalemate`] passes for it under [`StalemateRule::Pass`].
+    fn stalemate_result(&self) -> Option<GameOutcome> {
+        if !self.actions_for(self.to_move).is_empty() {
+            return None;
+        }
+        match self.ruleset.stalemate_rule {
+            StalemateRule::Lose => Some(GameOutcome::Win(self.to_move.opponent())),
+            StalemateRule::Draw => Some(GameOutcome::Draw),
+            StalemateRule::Pass => {
+                if self.actions_for(self.to_move.opponent()).is_empty() {
+                    Some(GameOutcome::Draw)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+    /// Plays a pass ply for the color to move if it has no legal action under
+    /// [`StalemateRule::Pass`], recorded and evented like any action.
+    fn pass_stalemate(&mut self) {
+        if self.ruleset.stalemate_rule == StalemateRule::Pass
+            && self.actions_for(self.to_move).is_empty()
+        {
+            self.play(&Action::pass()).expect("Boards apply passes");
+        }
+    }
+}
+
+/// Every piece's reserve for both colors, in a stable order.
+fn reserves(placement: &PlacementPhase) -> Vec<(Color, usize, Option<usize>)> {
+    let mut out = Vec::new();
+    for color in [Color::Red, Color::Blue] {
+        for piece_index in 0..placement.piece_count() {
+            out.push((color, piece_index, placement.reserve(color, piece_index)));
+        }
+    }
+    out
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum GameOutcome {
+    Win(Color),
+    Draw,
+}
+
+pub type GameResult<T> = Result<T, GameError>;
+#[derive(Clone, Debug)]
+pub enum GameError {
+    RulesetError(RulesetError),
+    StartingPositionsError(StartingPositionsError),
+    /// A recorded action could not be replayed.
+    IllegalAction {
+        ply: usize,
+        error: ActionError,
+    },
+    /// A recorded placement could not be replayed.
+    IllegalPlacement {
+        index: usize,
+        error: PlacementError,
+    },
+    /// A recorded huff could not be replayed.
+    IllegalHuff {
+        index: usize,
+        error: HuffError,
+    },
+    /// A recorded roll is not a face of the ruleset's die.
+    IllegalRoll {
+        ply: usize,
+        face: usize,
+    },
+    /// A recorded round has no recorded actions to play.
+    IllegalRound {
+        index: usize,
+    },
+}
+impl Display for GameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for GameError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::RulesetError(error) => Some(error),
+            Self::StartingPositionsError(error) => Some(error),
+            Self::IllegalAction { .. } => None,
+            Self::IllegalPlacement { error, .. } => Some(error),
+            Self::IllegalHuff { error, .. } => Some(error),
+            Self::IllegalRoll { .. } | Self::IllegalRound { .. } => None,
+        }
+    }
+}
+impl From<RulesetError> for GameError {
+    fn from(from: RulesetError) -> Self {
+        Self::RulesetError(from)
+    }
+}
+impl From<StartingPositionsError> for GameError {
+    fn from(from: StartingPositionsError) -> Self {
+        Self::StartingPositionsError(from)
+    }
+}
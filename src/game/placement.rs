@@ -0,0 +1,606 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use alloc::vec::Vec;
+use enum_iterator::IntoEnumIterator;
+
+use crate::collections::{HashMap, HashSet};
+use crate::coordinate::Coordinate;
+use crate::game_board::board::Board;
+use crate::game_board::{Color, GameBoard, PieceInstance};
+use crate::rng::{ChaChaRng, GameRng, PLACEMENT_STREAM};
+use crate::ruleset::starting_positions::alteration_type::AlternationType;
+use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+use crate::ruleset::starting_positions::StartingPositions;
+use crate::ruleset::verified::VerifiedRuleset;
+
+/// A piece placed during the placement phase.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct Placement {
+    pub color: Color,
+    pub piece_index: usize,
+    pub position: Coordinate,
+}
+
+/// What happens when a placement decision runs out of time.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeoutPolicy {
+    /// A legal placement is picked for the player, reproducible from the seed.
+    AutoPlace { seed: u64 },
+    /// The player loses the game.
+    Forfeit,
+}
+
+/// Time allowed for each placement decision.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecisionClock {
+    pub limit_ms: u64,
+    pub policy: TimeoutPolicy,
+    /// Time spent on the current decision.
+    pub elapsed_ms: u64,
+}
+
+/// The result of a timed out decision, see [`PlacementPhase::tick`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Timeout {
+    AutoPlaced(Placement),
+    Forfeited(Color),
+}
+
+/// Players placing their pieces before play starts, for [`StartingPositions::Placement`].
+///
+/// [`AlternationType::Hidden`] alternates like [`AlternationType::WholePlacement`],
+/// see [`fog`](crate::game::fog) for hiding the placements.
+/// Serializable so an interrupted phase can be resumed.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlacementPhase {
+    first_color: Color,
+    alternation_type: AlternationType,
+    piece_limits: HashSet<PieceLimit>,
+    areas: HashMap<Color, Vec<Coordinate>>,
+    piece_count: usize,
+    /// [`PieceDefinition::owner`](crate::ruleset::piece_definition::PieceDefinition::owner) of
+    /// each piece.
+    #[cfg_attr(feature = "serde", serde(default))]
+    owners: Vec<Option<Color>>,
+    board: GameBoard,
+    to_place: Option<Color>,
+    /// Pieces or points placed in the current turn.
+    placed_this_turn: usize,
+    counts: HashMap<Color, HashMap<usize, usize>>,
+    /// Points spent on square costs.
+    #[cfg_attr(feature = "serde", serde(default))]
+    square_points: HashMap<Color, usize>,
+    done: HashSet<Color>,
+    placements: Vec<Placement>,
+    clock: Option<DecisionClock>,
+    forfeited: Option<Color>,
+}
+impl PlacementPhase {
+    /// The placement phase for a ruleset, `None` if pieces do not start with a placement.
+    pub fn new(ruleset: &VerifiedRuleset) -> Option<Self> {
+        let (first_color, alternation_type, placement_area, piece_limits) =
+            match &ruleset.starting_positions {
+                StartingPositions::Placement {
+                    first_color,
+                    alternation_type,
+                    placement_area,
+                    piece_limits,
+                } => (first_color, alternation_type, placement_area, piece_limits),
+                _ => return None,
+            };
+        let areas = Color::into_enum_iter()
+            .map(|color| {
+                let mut positions: Vec<Coordinate> = placement_area
+                    .positions_for(color, &ruleset.board_type)
+                    .into_iter()
+                    .collect();
+                positions.sort_unstable();
+                (color, positions)
+            })
+            .collect();
+        let mut board = GameBoard::from_board_type(&ruleset.board_type);
+        board.set_piece_rules(&ruleset.pieces);
+        let mut out = Self {
+            first_color: *first_color,
+            alternation_type: *alternation_type,
+            piece_limits: piece_limits.clone(),
+            areas,
+            piece_count: ruleset.pieces.len(),
+            owners: ruleset.pieces.iter().map(|piece| piece.owner).collect(),
+            board,
+            to_place: Some(*first_color),
+            placed_this_turn: 0,
+            counts: HashMap::new(),
+            square_points: HashMap::new(),
+            done: HashSet::new(),
+            placements: Vec::new(),
+            clock: None,
+            forfeited: None,
+        };
+        out.advance();
+        Some(out)
+    }
+    /// Limits every decision to `limit_ms`, see [`PlacementPhase::tick`].
+    pub fn with_clock(mut self, limit_ms: u64, policy: TimeoutPolicy) -> Self {
+        self.clock = Some(DecisionClock {
+            limit_ms,
+            policy,
+            elapsed_ms: 0,
+        });
+        self
+    }
+
+    pub fn alternation_type(&self) -> AlternationType {
+        self.alternation_type
+    }
+    pub fn first_color(&self) -> Color {
+        self.first_color
+    }
+    pub fn board(&self) -> &GameBoard {
+        &self.board
+    }
+    pub fn into_board(self) -> GameBoard {
+        self.board
+    }
+    /// The color placing next, `None` once the phase is finished.
+    pub fn to_place(&self) -> Option<Color> {
+        self.to_place
+    }
+    pub fn is_finished(&self) -> bool {
+        self.to_place.is_none()
+    }
+    /// Every placement so far in order.
+    pub fn placements(&self) -> &[Placement] {
+        &self.placements
+    }
+    pub fn clock(&self) -> Option<&DecisionClock> {
+        self.clock.as_ref()
+    }
+    /// The color that lost by running out of time.
+    pub fn forfeited(&self) -> Option<Color> {
+        self.forfeited
+    }
+
+    /// How many more of `piece_index` `color` can place, `None` if no limit applies.
+    pub fn reserve(&self, color: Color, piece_index: usize) -> Option<usize> {
+        if !self.has_piece(color, piece_index) {
+            return Some(0);
+        }
+        let empty = HashMap::new();
+        let counts = self.counts.get(&color).unwrap_or(&empty);
+        PieceLimit::remaining(&self.piece_limits, counts, piece_index)
+    }
+    pub fn piece_count(&self) -> usize {
+        self.piece_count
+    }
+
+    /// All placements the color to place can make, in a stable order.
+    pub fn legal_placements(&self) -> Vec<Placement> {
+        match self.to_place {
+            Some(color) => self.placements_for(color),
+            None => Vec::new(),
+        }
+    }
+    fn placements_for(&self, color: Color) -> Vec<Placement> {
+        let mut out = Vec::new();
+        for piece_index in 0..self.piece_count {
+            if self.check_piece(color, piece_index).is_err() {
+                continue;
+            }
+            for &position in self.areas[&color].iter() {
+                if self.check_square(color, piece_index, position).is_err() {
+                    continue;
+                }
+                if let Ok(None) = self.board.piece(position) {
+                    out.push(Placement {
+                        color,
+                        piece_index,
+                        position,
+                    });
+                }
+            }
+        }
+        out
+    }
+
+    /// Places a piece for the color to place.
+    pub fn place(&mut self, piece_index: usize, position: Coordinate) -> PlacementResult<()> {
+        let color = self.to_place.ok_or(PlacementError::PhaseFinished)?;
+        let piece = self.check_piece(color, piece_index)?;
+        if !self.areas[&color].contains(&position) {
+            return Err(PlacementError::OutsideArea(position));
+        }
+        self.check_square(color, piece_index, position)?;
+        match self.board.piece_mut(position) {
+            Ok(slot @ None) => *slot = Some(piece),
+            _ => return Err(PlacementError::PositionOccupied(position)),
+        }
+
+        *self
+            .counts
+            .entry(color)
+            .or_default()
+            .entry(piece_index)
+            .or_insert(0) += 1;
+        let square_cost = PieceLimit::square_cost(&self.piece_limits, position);
+        *self.square_points.entry(color).or_insert(0) += square_cost;
+        self.placed_this_turn += match self.alternation_type {
+            AlternationType::TurnsPoints { .. } => self.points(piece_index) + square_cost,
+            _ => 1,
+        };
+        self.placements.push(Placement {
+            color,
+            piece_index,
+            position,
+        });
+        self.advance();
+        Ok(())
+    }
+    /// The color to place stops placing for the rest of the phase.
+    pub fn finish_placing(&mut self) -> PlacementResult<()> {
+        let color = self.to_place.ok_or(PlacementError::PhaseFinished)?;
+        self.done.insert(color);
+        self.placed_this_turn = 0;
+        self.advance();
+        Ok(())
+    }
+
+    /// Adds `elapsed_ms` to the current decision, applying the timeout policy when it runs out.
+    pub fn tick(&mut self, elapsed_ms: u64) -> Option<Timeout> {
+        let color = self.to_place?;
+        let clock = self.clock.as_mut()?;
+        clock.elapsed_ms += elapsed_ms;
+        if clock.elapsed_ms < clock.limit_ms {
+            return None;
+        }
+        match clock.policy {
+            TimeoutPolicy::AutoPlace { seed } => {
+                let mut placements = self.legal_placements();
+                let stream = PLACEMENT_STREAM + self.placements.len() as u64;
+                let mut rng = ChaChaRng::with_stream(seed, stream);
+                let placement = placements.swap_remove(rng.below(placements.len()));
+                self.place(placement.piece_index, placement.position)
+                    .expect("Legal placements must place");
+                Some(Timeout::AutoPlaced(placement))
+            }
+            TimeoutPolicy::Forfeit => {
+                self.forfeited = Some(color);
+                self.to_place = None;
+                Some(Timeout::Forfeited(color))
+            }
+        }
+    }
+
+    fn check_piece(&self, color: Color, piece_index: usize) -> PlacementResult<PieceInstance> {
+        if piece_index >= self.piece_count {
+            return Err(PlacementError::PieceIndexNotFound(piece_index));
+        }
+        if !self.has_piece(color, piece_index) {
+            return Err(PlacementError::PieceNotAvailable(piece_index));
+        }
+        let piece = PieceInstance::new(piece_index, color);
+        let empty = HashMap::new();
+        let counts = self.counts.get(&color).unwrap_or(&empty);
+        if !PieceLimit::allows(&self.piece_limits, counts, piece_index) {
+            return Err(PlacementError::PieceLimitReached(piece_index));
+        }
+        if let AlternationType::TurnsPoints {
+            per_turn_points,
+            hard_limit: true,
+        } = self.alternation_type
+        {
+            if self.placed_this_turn + self.points(piece_index) > per_turn_points {
+                return Err(PlacementError::TurnPointsExceeded(piece_index));
+            }
+        }
+        Ok(piece)
+    }
+    fn has_piece(&self, color: Color, piece_index: usize) -> bool {
+        self.owners
+            .get(piece_index)
+            .copied()
+            .flatten()
+            .is_none_or(|owner| owner == color)
+    }
+    /// Checks the point budget with the cost of `position` added.
+    fn check_square(
+        &self,
+        color: Color,
+        piece_index: usize,
+        position: Coordinate,
+    ) -> PlacementResult<()> {
+        let square_cost = PieceLimit::square_cost(&self.piece_limits, position);
+        let empty = HashMap::new();
+        let counts = self.counts.get(&color).unwrap_or(&empty);
+        let square_points = self.square_points.get(&color).copied().unwrap_or(0);
+        if !PieceLimit::allows_at(
+            &self.piece_limits,
+            counts,
+            square_points,
+            piece_index,
+            position,
+        ) {
+            return Err(PlacementError::SquareTooCostly(position));
+        }
+        if let AlternationType::TurnsPoints {
+            per_turn_points,
+            hard_limit: true,
+        } = self.alternation_type
+        {
+            if self.placed_this_turn + self.points(piece_index) + square_cost > per_turn_points {
+                return Err(PlacementError::TurnPointsExceeded(piece_index));
+            }
+        }
+        Ok(())
+    }
+    fn points(&self, piece_index: usize) -> usize {
+        self.piece_limits
+            .iter()
+            .find_map(|piece_limit| match piece_limit {
+                PieceLimit::PointLimit { point_values, .. } => point_values.get(&piece_index),
+                _ => None,
+            })
+            .copied()
+            .unwrap_or(1)
+    }
+    fn total_points(&self, color: Color) -> usize {
+        let square_points = self.square_points.get(&color).copied().unwrap_or(0);
+        square_points
+            + self.counts.get(&color).map_or(0, |counts| {
+                counts
+                    .iter()
+                    .map(|(&piece_index, count)| self.points(piece_index) * count)
+                    .sum()
+            })
+    }
+
+    /// Picks the next color to place and resets the decision clock.
+    fn advance(&mut self) {
+        if let Some(clock) = self.clock.as_mut() {
+            clock.elapsed_ms = 0;
+        }
+        let current = match self.to_place {
+            Some(current) => current,
+            None => return,
+        };
+        for color in Color::into_enum_iter() {
+            if !self.done.contains(&color) && self.placements_for(color).is_empty() {
+                self.done.insert(color);
+            }
+        }
+
+        let turn_over = match self.alternation_type {
+            AlternationType::TurnsCount { per_turn_count } => {
+                self.placed_this_turn >= per_turn_count
+            }
+            AlternationType::TurnsPoints {
+                per_turn_points, ..
+            } => self.placed_this_turn >= per_turn_points,
+            AlternationType::Points => true,
+            AlternationType::WholePlacement | AlternationType::Hidden => false,
+        };
+        let mut next = match self.alternation_type {
+            AlternationType::Points => {
+                let (first, second) = (self.first_color, self.first_color.opponent());
+                if self.total_points(second) < self.total_points(first) {
+                    second
+                } else {
+                    first
+                }
+            }
+            _ if turn_over => current.opponent(),
+            _ => current,
+        };
+        if self.done.contains(&next) {
+            next = next.opponent();
+        }
+        if next != current {
+            self.placed_this_turn = 0;
+        }
+        self.to_place = if self.done.contains(&next) {
+            None
+        } else {
+            Some(next)
+        };
+    }
+}
+
+pub type PlacementResult<T> = Result<T, PlacementError>;
+#[derive(Copy, Clone, Debug)]
+pub enum PlacementError {
+    PhaseFinished,
+    PieceIndexNotFound(usize),
+    PieceLimitReached(usize),
+    /// The color placing does not have this piece.
+    PieceNotAvailable(usize),
+    /// Placing this piece would go over the turn's points with a hard limit.
+    TurnPointsExceeded(usize),
+    OutsideArea(Coordinate),
+    PositionOccupied(Coordinate),
+    /// The square's cost would go over the point limit.
+    SquareTooCostly(Coordinate),
+}
+impl Display for PlacementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for PlacementError {}
+
+#[cfg(test)]
+mod test {
+    use crate::collections::HashSet;
+    use crate::coordinate::Coordinate;
+    use crate::game::placement::{PlacementError, PlacementPhase, Timeout, TimeoutPolicy};
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::game_board::Color;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::ruleset::verified::VerifiedRuleset;
+    use crate::testing::small_ruleset;
+
+    fn ruleset(alternation_type: AlternationType) -> VerifiedRuleset {
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 3 });
+        let mut ruleset = small_ruleset(0);
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Blue,
+            alternation_type,
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        VerifiedRuleset::new(ruleset).unwrap()
+    }
+
+    #[test]
+    fn alternation_test() {
+        let mut phase =
+            PlacementPhase::new(&ruleset(AlternationType::TurnsCount { per_turn_count: 2 }))
+                .unwrap();
+        let mut order = Vec::new();
+        while let Some(color) = phase.to_place() {
+            order.push(color);
+            let placement = phase.legal_placements()[0];
+            phase
+                .place(placement.piece_index, placement.position)
+                .unwrap();
+        }
+        use Color::{Blue, Red};
+        assert_eq!(order, vec![Blue, Blue, Red, Red, Blue, Red]);
+        assert!(matches!(
+            phase.place(0, phase.placements()[0].position),
+            Err(PlacementError::PhaseFinished)
+        ));
+        assert_eq!(phase.board().pieces_of_color(Red).len(), 3);
+    }
+
+    #[test]
+    fn occupied_and_area_test() {
+        let mut phase = PlacementPhase::new(&ruleset(AlternationType::WholePlacement)).unwrap();
+        let placement = phase.legal_placements()[0];
+        phase
+            .place(placement.piece_index, placement.position)
+            .unwrap();
+        assert!(matches!(
+            phase.place(0, placement.position),
+            Err(PlacementError::PositionOccupied(_))
+        ));
+        phase.finish_placing().unwrap();
+        assert_eq!(phase.to_place(), Some(Color::Red));
+        assert!(matches!(
+            phase.place(0, placement.position),
+            Err(PlacementError::OutsideArea(_))
+        ));
+    }
+
+    #[test]
+    fn owner_test() {
+        let mut ruleset = ruleset(AlternationType::WholePlacement).into_inner();
+        ruleset.pieces[0].owner = Some(Color::Red);
+        let mut phase = PlacementPhase::new(&VerifiedRuleset::new(ruleset).unwrap()).unwrap();
+        assert_eq!(phase.reserve(Color::Blue, 0), Some(0));
+        assert!(phase
+            .legal_placements()
+            .iter()
+            .all(|placement| placement.piece_index == 1));
+        let position = phase.legal_placements()[0].position;
+        assert!(matches!(
+            phase.place(0, position),
+            Err(PlacementError::PieceNotAvailable(0))
+        ));
+        phase.place(1, position).unwrap();
+    }
+
+    #[test]
+    fn timeout_test() {
+        let mut phase = PlacementPhase::new(&ruleset(AlternationType::WholePlacement))
+            .unwrap()
+            .with_clock(100, TimeoutPolicy::AutoPlace { seed: 3 });
+        assert_eq!(phase.tick(60), None);
+        assert!(matches!(phase.tick(60), Some(Timeout::AutoPlaced(_))));
+        assert_eq!(phase.clock().unwrap().elapsed_ms, 0);
+        assert_eq!(phase.placements().len(), 1);
+
+        let mut phase = PlacementPhase::new(&ruleset(AlternationType::WholePlacement))
+            .unwrap()
+            .with_clock(100, TimeoutPolicy::Forfeit);
+        assert_eq!(phase.tick(100), Some(Timeout::Forfeited(Color::Blue)));
+        assert!(phase.is_finished());
+    }
+
+    #[test]
+    fn game_placement_test() {
+        let mut game =
+            Game::from_handle(ruleset(AlternationType::TurnsCount { per_turn_count: 1 })).unwrap();
+        assert!(game.legal_actions().is_empty());
+        let placement = game.placement().unwrap().legal_placements()[0];
+        game.place(placement.piece_index, placement.position)
+            .unwrap();
+
+        #[cfg(feature = "formats")]
+        {
+            let saved = serde_json::to_string(game.placement().unwrap()).unwrap();
+            let phase: PlacementPhase = serde_json::from_str(&saved).unwrap();
+            game = Game::resume_placement(game.ruleset().clone(), phase).unwrap();
+        }
+
+        while let Some(phase) = game.placement() {
+            let placement = phase.legal_placements()[0];
+            game.place(placement.piece_index, placement.position)
+                .unwrap();
+        }
+        assert_eq!(game.placements().len(), 6);
+        assert_eq!(game.to_move(), Color::Blue);
+        assert!(!game.legal_actions().is_empty());
+
+        let replayed = game.record().replay().unwrap();
+        assert_eq!(replayed.board().board, game.board().board);
+    }
+
+    #[test]
+    fn square_cost_test() {
+        let center = Coordinate::new(1, 1);
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::PointLimit {
+            point_values: vec![(0, 2), (1, 1)].into_iter().collect(),
+            point_limit: 4,
+            square_costs: vec![(2, vec![center].into_iter().collect())]
+                .into_iter()
+                .collect(),
+        });
+        let mut ruleset = small_ruleset(0);
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::WholePlacement,
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        let mut phase = PlacementPhase::new(&VerifiedRuleset::new(ruleset).unwrap()).unwrap();
+        phase.place(1, Coordinate::new(1, 0)).unwrap();
+        // 1 + 2 + 2 would go over the 4 points
+        assert!(phase
+            .legal_placements()
+            .iter()
+            .all(|placement| placement.piece_index == 1 || placement.position != center));
+        assert!(matches!(
+            phase.place(0, center),
+            Err(PlacementError::SquareTooCostly(_))
+        ));
+        phase.place(1, center).unwrap();
+        // The square used up Red's remaining points
+        assert_eq!(phase.to_place(), Some(Color::Blue));
+        assert_eq!(phase.board().pieces_of_color(Color::Red).len(), 2);
+    }
+}
@@ -0,0 +1,135 @@
+use alloc::vec::Vec;
+
+use crate::action::{Action, ActionOutcome};
+use crate::game::Game;
+use crate::rng::{ChaChaRng, GameRng, ENGINE_STREAM};
+
+/// Chooses which action to play.
+///
+/// Implemented for closures taking the game and its legal actions.
+pub trait Policy {
+    /// Index into `actions` of the action to play, `actions` is never empty.
+    fn choose(&mut self, game: &Game, actions: &[Action]) -> usize;
+}
+impl<F> Policy for F
+where
+    F: FnMut(&Game, &[Action]) -> usize,
+{
+    fn choose(&mut self, game: &Game, actions: &[Action]) -> usize {
+        self(game, actions)
+    }
+}
+
+/// Picks uniformly between legal actions, reproducible from its seed.
+#[derive(Clone, Debug)]
+pub struct RandomPolicy(pub ChaChaRng);
+impl RandomPolicy {
+    pub fn new(seed: u64) -> Self {
+        Self(ChaChaRng::with_stream(seed, ENGINE_STREAM))
+    }
+}
+impl Policy for RandomPolicy {
+    fn choose(&mut self, _game: &Game, actions: &[Action]) -> usize {
+        self.0.below(actions.len())
+    }
+}
+
+/// Lazily plays a game under a [`Policy`], see [`Game::playout`].
+#[derive(Debug)]
+pub struct Playout<P> {
+    game: Game,
+    policy: P,
+}
+impl<P> Playout<P> {
+    /// The game as of the last yielded ply.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+    pub fn into_game(self) -> Game {
+        self.game
+    }
+}
+impl<P: Policy> Iterator for Playout<P> {
+    type Item = (Action, ActionOutcome);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let actions: Vec<Action> = self.game.legal_actions();
+        if actions.is_empty() {
+            return None;
+        }
+        let index = self.policy.choose(&self.game, &actions);
+        let action = actions
+            .into_iter()
+            .nth(index)
+            .expect("Policy chose an index out of range");
+        let outcome = self.game.apply(&action).expect("Legal actions must apply");
+        Some((action, outcome))
+    }
+}
+
+impl Game {
+    /// Plays the game to completion under `policy`, yielding each ply it chooses as it is played.
+    ///
+    /// Games are not guaranteed to end, use [`Iterator::take`] to bound the length.
+    pub fn playout<P: Policy>(self, policy: P) -> Playout<P> {
+        Playout { game: self, policy }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use proptest::prelude::*;
+
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::testing::{arb_ruleset, small_ruleset};
+
+    fn game(seed: u64) -> Game {
+        Game::new(small_ruleset(seed)).unwrap()
+    }
+
+    #[test]
+    fn random_playout_finishes_test() {
+        let mut playout = game(5).playout(RandomPolicy::new(11));
+        let plies = playout.by_ref().take(10_000).count();
+        assert!(plies > 0);
+        assert!(playout.game().is_finished());
+    }
+
+    proptest! {
+        #[test]
+        fn playout_deterministic_test(ruleset in arb_ruleset(), seed in any::<u64>()) {
+            let game = Game::new(ruleset);
+            prop_assert!(game.is_ok(), "{:?}", game.err());
+            let game = game.unwrap();
+            let first: Vec<_> = game.clone().playout(RandomPolicy::new(seed)).take(50).collect();
+            let second: Vec<_> = game.playout(RandomPolicy::new(seed)).take(50).collect();
+            prop_assert_eq!(first, second);
+        }
+
+        #[test]
+        fn playout_ends_with_result_test(ruleset in arb_ruleset(), seed in any::<u64>()) {
+            let game = Game::new(ruleset);
+            prop_assert!(game.is_ok(), "{:?}", game.err());
+            let mut playout = game.unwrap().playout(RandomPolicy::new(seed));
+            let plies = playout.by_ref().take(200).count();
+            // Passes the stalemate rule forces are played without the policy
+            let history = playout.game().history();
+            let passes = history.iter().filter(|(action, _)| action.action_type.is_pass()).count();
+            prop_assert!(plies <= history.len() && history.len() <= plies + passes);
+            if plies < 200 {
+                prop_assert!(playout.game().is_finished() || playout.game().history().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn closure_policy_test() {
+        let mut playout = game(3).playout(|_: &Game, actions: &[_]| actions.len() - 1);
+        let plies: Vec<_> = playout.by_ref().take(10).collect();
+        assert!(!plies.is_empty());
+        assert_eq!(playout.game().history().len(), plies.len());
+    }
+}
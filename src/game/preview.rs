@@ -0,0 +1,166 @@
+//! What an action would leave open to the opponent, see [`Game::preview`].
+
+use alloc::vec::Vec;
+
+use crate::action::{Action, ActionError, ActionOutcome};
+use crate::coordinate::Coordinate;
+use crate::game::{Game, GameOutcome};
+use crate::game_board::board::Board;
+
+/// The position after an action from the mover's side, see [`Game::preview`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PreviewReport {
+    /// What the action itself captures.
+    pub outcome: ActionOutcome,
+    /// The result if the action finishes the game.
+    pub result: Option<GameOutcome>,
+    /// Squares of the mover's pieces the opponent can capture after the action.
+    pub threatened: Vec<Coordinate>,
+    /// The part of `threatened` that could not be captured before the action.
+    pub newly_threatened: Vec<Coordinate>,
+    /// The opponent's replies that capture the mover's pieces.
+    pub recaptures: Vec<Action>,
+    /// Whether every reply is one of `recaptures`, so the opponent must capture.
+    pub forced_recapture: bool,
+}
+impl PreviewReport {
+    /// Whether the action leaves a piece capturable that was safe before.
+    pub fn is_blunder(&self) -> bool {
+        !self.newly_threatened.is_empty()
+    }
+}
+
+impl Game {
+    /// Plays `action` on a copy and reports the threats it leaves against the mover, so apps can
+    /// warn before it is committed.
+    ///
+    /// Only looks one reply ahead, use an [`Engine`](crate::engine::Engine) to judge whether
+    /// the threats matter.
+    pub fn preview(&self, action: &Action) -> Result<PreviewReport, ActionError> {
+        let mover = self.to_move;
+        let before = self.board.attack_map(mover.opponent());
+        let destination = self.board.destination(action);
+        let mut after = self.clone();
+        let outcome = after.apply(action)?;
+        let attacked = after.board.attack_map(mover.opponent());
+
+        let mut threatened: Vec<Coordinate> = after
+            .board
+            .pieces_of_color(mover)
+            .into_iter()
+            .map(|(position, _)| position)
+            .filter(|&position| attacked[position])
+            .collect();
+        threatened.sort_by_key(|position| (position.column, position.row));
+        let newly_threatened = threatened
+            .iter()
+            .copied()
+            .filter(|&position| {
+                // The moved piece was threatened where it started, if at all
+                let from = if Some(position) == destination {
+                    action.start_pos
+                } else {
+                    position
+                };
+                !(self.board.is_valid_position(from) && before[from])
+            })
+            .collect();
+
+        let replies = after.legal_actions();
+        let recaptures: Vec<Action> = replies
+            .iter()
+            .filter(|reply| {
+                after.board.apply_action(reply).is_ok_and(|(_, outcome)| {
+                    outcome
+                        .captures
+                        .iter()
+                        .any(|(_, piece)| piece.color() == mover)
+                })
+            })
+            .cloned()
+            .collect();
+        Ok(PreviewReport {
+            outcome,
+            result: after.result,
+            threatened,
+            newly_threatened,
+            forced_recapture: !recaptures.is_empty() && recaptures.len() == replies.len(),
+            recaptures,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+    use alloc::vec;
+
+    use crate::action::{Action, ActionType};
+    use crate::collections::HashMap;
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game::scenario::Scenario;
+    use crate::game::Game;
+    use crate::game_board::Color;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn preview_test() {
+        let game = Game::new(small_ruleset(1)).unwrap();
+        for action in game.legal_actions() {
+            let report = game.preview(&action).unwrap();
+            let mut after = game.clone();
+            assert_eq!(report.outcome, after.apply(&action).unwrap());
+            assert!(report
+                .newly_threatened
+                .iter()
+                .all(|position| report.threatened.contains(position)));
+            assert!(report
+                .recaptures
+                .iter()
+                .all(|reply| reply.action_type.is_jump()));
+        }
+
+        let mut position = HashMap::new();
+        position.insert(
+            Color::Red,
+            vec![(0, vec![Coordinate::new(3, 2)])].into_iter().collect(),
+        );
+        position.insert(
+            Color::Blue,
+            vec![(0, vec![Coordinate::new(5, 2)])].into_iter().collect(),
+        );
+        let scenario = Scenario {
+            name: "Blunder".into(),
+            description: String::new(),
+            ruleset: small_ruleset(0),
+            position,
+            to_move: Color::Red,
+            restrictions: vec![],
+            victory_conditions: None,
+            move_limit: None,
+        };
+        let game = scenario.start().unwrap().into_game();
+        let forward = Action {
+            start_pos: Coordinate::new(3, 2),
+            action_type: ActionType::Move(Direction::South),
+        };
+        let report = game.preview(&forward).unwrap();
+        assert_eq!(report.threatened, vec![Coordinate::new(4, 2)]);
+        assert!(report.is_blunder());
+        assert!(!report.recaptures.is_empty());
+        assert!(report.recaptures.iter().all(|reply| {
+            reply.start_pos == Coordinate::new(5, 2) && reply.action_type.is_jump()
+        }));
+
+        let sideways = Action {
+            start_pos: Coordinate::new(3, 2),
+            action_type: ActionType::Move(Direction::West),
+        };
+        let report = game.preview(&sideways).unwrap();
+        assert!(!report.is_blunder());
+        assert!(report.recaptures.is_empty());
+        assert!(!report.forced_recapture);
+    }
+}
@@ -0,0 +1,330 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::action::Action;
+use crate::game::huff::{Huff, HuffError};
+use crate::game::placement::{Placement, PlacementError};
+use crate::game::simultaneous::Round;
+use crate::game::{Game, GameError, GameOutcome, GameResult};
+use crate::ruleset::scoring::Scores;
+use crate::ruleset::Ruleset;
+
+/// Everything needed to save and replay a game.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct GameRecord {
+    pub ruleset: Ruleset,
+    /// Pieces placed before play, empty unless the ruleset uses a placement phase.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub placements: Vec<Placement>,
+    /// Actions in the order they were played.
+    pub actions: Vec<Action>,
+    /// The result when the record was made, `None` for games in progress.
+    pub result: Option<GameOutcome>,
+    /// The scores when the record was made.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scores: Scores,
+    /// A comment after each action by ply, may be shorter than `actions`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub comments: Vec<Option<String>>,
+    /// Pieces removed by [`Game::huff`], in the order they were huffed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub huffs: Vec<Huff>,
+    /// Rounds of simultaneous actions, see [`Game::rounds`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rounds: Vec<Round>,
+    /// The face rolled for each turn, empty unless the ruleset has a
+    /// [`ChanceRule`](crate::ruleset::chance_rule::ChanceRule).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rolls: Vec<usize>,
+    /// See [`Game::seed`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seed: u64,
+}
+impl GameRecord {
+    /// Plays every action from the start of the game.
+    pub fn replay(&self) -> GameResult<Game> {
+        replay_onto(
+            Game::new(self.ruleset.clone())?
+                .with_seed(self.seed)
+                .with_rolls(&self.rolls)?,
+            &self.placements,
+            &self.actions,
+            &self.huffs,
+            &self.rounds,
+        )
+    }
+    /// Replays the record under `ruleset` and checks that it ends with the recorded result and
+    /// scores, returning the first place it diverges.
+    pub fn verify(&self, ruleset: &Ruleset) -> ReplayResult<()> {
+        let game = replay_onto(
+            Game::new(ruleset.clone())?
+                .with_seed(self.seed)
+                .with_rolls(&self.rolls)?,
+            &self.placements,
+            &self.actions,
+            &self.huffs,
+            &self.rounds,
+        )?;
+        if game.result() != self.result {
+            return Err(ReplayError::WrongResult {
+                recorded: self.result,
+                replayed: game.result(),
+            });
+        }
+        if game.scores() != self.scores {
+            return Err(ReplayError::WrongScores {
+                recorded: self.scores,
+                replayed: game.scores(),
+            });
+        }
+        Ok(())
+    }
+}
+
+pub type ReplayResult<T> = Result<T, ReplayError>;
+#[derive(Clone, Debug)]
+pub enum ReplayError {
+    /// The game could not be started or a placement or action was illegal.
+    GameError(GameError),
+    /// The replayed game ended differently than recorded.
+    WrongResult {
+        recorded: Option<GameOutcome>,
+        replayed: Option<GameOutcome>,
+    },
+    /// The replayed game finished with different scores than recorded.
+    WrongScores { recorded: Scores, replayed: Scores },
+}
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for ReplayError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::GameError(error) => Some(error),
+            Self::WrongResult { .. } | Self::WrongScores { .. } => None,
+        }
+    }
+}
+impl From<GameError> for ReplayError {
+    fn from(from: GameError) -> Self {
+        Self::GameError(from)
+    }
+}
+
+/// Makes `placements` then plays `actions` in a new `game`, huffing each of `huffs` at its ply
+/// and resolving each of `rounds` from its ply.
+pub(crate) fn replay_onto(
+    mut game: Game,
+    placements: &[Placement],
+    actions: &[Action],
+    huffs: &[Huff],
+    rounds: &[Round],
+) -> GameResult<Game> {
+    for (index, placement) in placements.iter().enumerate() {
+        let color = game.placement().and_then(|phase| phase.to_place());
+        if color != Some(placement.color) {
+            return Err(GameError::IllegalPlacement {
+                index,
+                error: PlacementError::PhaseFinished,
+            });
+        }
+        game.place(placement.piece_index, placement.position)
+            .map_err(|error| GameError::IllegalPlacement { index, error })?;
+    }
+    let mut pending = huffs.iter().enumerate().peekable();
+    let mut rounds = rounds.iter().enumerate().peekable();
+    let mut ply = 0;
+    while ply <= actions.len() {
+        while let Some((index, huff)) = pending.next_if(|(_, huff)| huff.ply == ply) {
+            game.huff(huff.position)
+                .map_err(|error| GameError::IllegalHuff { index, error })?;
+        }
+        if let Some((index, round)) = rounds.next_if(|(_, round)| round.ply == ply) {
+            let played = actions
+                .get(ply..ply + round.played.len())
+                .ok_or(GameError::IllegalRound { index })?;
+            game.replay_round(round, played)?;
+            ply += played.len();
+            continue;
+        }
+        // A pass the stalemate rule forced was played with the action before it
+        if let Some(action) = actions.get(ply).filter(|_| game.history().len() == ply) {
+            game.apply(action)
+                .map_err(|error| GameError::IllegalAction { ply, error })?;
+        }
+        ply += 1;
+    }
+    if let Some((index, huff)) = pending.next() {
+        return Err(GameError::IllegalHuff {
+            index,
+            error: HuffError::NotHuffable(huff.position),
+        });
+    }
+    if let Some((index, _)) = rounds.next() {
+        return Err(GameError::IllegalRound { index });
+    }
+    Ok(game)
+}
+
+impl Game {
+    pub fn record(&self) -> GameRecord {
+        GameRecord {
+            ruleset: self.ruleset().clone(),
+            placements: self.placements.clone(),
+            actions: self
+                .history
+                .iter()
+                .map(|(action, _)| action.clone())
+                .collect(),
+            result: self.result,
+            scores: self.scores,
+            comments: Vec::new(),
+            huffs: self.huffs.clone(),
+            rounds: self.rounds.clone(),
+            rolls: self.rolls.clone(),
+            seed: self.seed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::{Action, ActionError};
+    use crate::collections::{HashMap, HashSet};
+    use crate::coordinate::Coordinate;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::record::ReplayError;
+    use crate::game::{Game, GameError};
+    use crate::game_board::Color;
+    use crate::grid::Grid;
+    use crate::ruleset::board_type::space::Space;
+    use crate::ruleset::board_type::BoardType;
+    use crate::ruleset::piece_definition::{CaptureRequirement, JumpLimit};
+    use crate::ruleset::stalemate_rule::StalemateRule;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn replay_test() {
+        let mut playout = Game::new(small_ruleset(1))
+            .unwrap()
+            .playout(RandomPolicy::new(2));
+        playout.by_ref().take(20).for_each(drop);
+        let game = playout.into_game();
+
+        let record = game.record();
+        let replayed = record.replay().unwrap();
+        assert_eq!(replayed.board().board, game.board().board);
+        assert_eq!(replayed.record(), record);
+
+        let mut broken = record;
+        broken.actions.swap(0, 1);
+        assert!(matches!(
+            broken.replay(),
+            Err(GameError::IllegalAction { ply: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn verify_test() {
+        let ruleset = small_ruleset(3);
+        let mut playout = Game::new(ruleset.clone())
+            .unwrap()
+            .playout(RandomPolicy::new(4));
+        playout.by_ref().for_each(drop);
+        let game = playout.into_game();
+        assert!(game.is_finished());
+
+        let record = game.record();
+        record.verify(&ruleset).unwrap();
+
+        let mut wrong_result = record.clone();
+        wrong_result.result = None;
+        assert!(matches!(
+            wrong_result.verify(&ruleset),
+            Err(ReplayError::WrongResult { replayed, .. }) if replayed == record.result
+        ));
+
+        let mut extra = record.clone();
+        extra.actions.push(Action::pass());
+        let ply = record.actions.len();
+        assert!(matches!(
+            extra.verify(&ruleset),
+            Err(ReplayError::GameError(GameError::IllegalAction {
+                ply: error_ply,
+                error: ActionError::GameFinished,
+            })) if error_ply == ply
+        ));
+
+        let mut unfinished = record;
+        unfinished.actions.pop();
+        assert!(matches!(
+            unfinished.verify(&ruleset),
+            Err(ReplayError::WrongResult { replayed: None, .. })
+        ));
+    }
+
+    #[test]
+    fn stalemate_pass_test() {
+        // Blue's piece is walled into a corner, so it passes every turn
+        let mut ruleset = small_ruleset(1);
+        ruleset.stalemate_rule = StalemateRule::Pass;
+        ruleset.pieces[1] = ruleset.pieces[1]
+            .clone()
+            .with_capture_rules(HashMap::new())
+            .with_capture_requirement(CaptureRequirement::None)
+            .with_jump_limit(JumpLimit::Cannot);
+        let mut spaces = Grid::new((6, 6));
+        spaces[(0, 2)] = Space::Goal(Color::Red);
+        spaces[(5, 2)] = Space::Goal(Color::Blue);
+        for position in [(4, 4), (4, 5), (5, 4)] {
+            spaces[position] = Space::Invalid;
+        }
+        ruleset.board_type = BoardType::Custom(spaces);
+        let mut areas = HashMap::new();
+        areas.insert(
+            Color::Red,
+            vec![Coordinate::new(0, 0)].into_iter().collect(),
+        );
+        areas.insert(
+            Color::Blue,
+            vec![Coordinate::new(5, 5)].into_iter().collect(),
+        );
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 1 });
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::TurnsCount { per_turn_count: 1 },
+            placement_area: PlacementArea::NonMirrored(areas),
+            piece_limits,
+        };
+        let mut game = Game::new(ruleset).unwrap();
+        game.place(0, Coordinate::new(0, 0)).unwrap();
+        game.place(1, Coordinate::new(5, 5)).unwrap();
+        let mut playout = game.playout(RandomPolicy::new(2));
+        playout.by_ref().take(20).for_each(drop);
+        let game = playout.into_game();
+        let passes = game
+            .history()
+            .iter()
+            .filter(|(action, _)| action.action_type.is_pass())
+            .count();
+        assert!(passes >= 10);
+
+        let replayed = game.record().replay().unwrap();
+        assert_eq!(replayed.history(), game.history());
+        assert_eq!(replayed.to_move(), game.to_move());
+    }
+}
@@ -0,0 +1,252 @@
+//! Saving games in progress to resume them later, see [`Game::save`].
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::action::Action;
+use crate::game::event::{EventQueue, Phase};
+use crate::game::huff::Huff;
+use crate::game::placement::{Placement, PlacementPhase};
+use crate::game::record::replay_onto;
+use crate::game::simultaneous::Round;
+use crate::game::{Game, GameError};
+use crate::game_board::Color;
+use crate::ruleset::verified::RulesetHandle;
+
+/// Version of the [`SaveGame`] format, saves of other versions cannot be resumed.
+pub const SAVE_GAME_VERSION: u32 = 1;
+
+/// A game as it was when saved, without its ruleset.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaveGame {
+    pub version: u32,
+    /// [`Ruleset::fingerprint`](crate::ruleset::Ruleset::fingerprint) of the rules played.
+    pub ruleset_fingerprint: [u8; 32],
+    pub placements: Vec<Placement>,
+    /// Actions in the order they were played.
+    pub actions: Vec<Action>,
+    /// See [`Game::huffs`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub huffs: Vec<Huff>,
+    /// See [`Game::rounds`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rounds: Vec<Round>,
+    /// See [`Game::rolls`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rolls: Vec<usize>,
+    /// See [`Game::seed`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seed: u64,
+    /// The placement phase with its decision clock, while placing.
+    pub placement: Option<PlacementPhase>,
+    pub phase: Phase,
+    pub draw_offer: Option<Color>,
+    /// See [`Game::set_clock_interval`].
+    pub clock_interval_ms: Option<u64>,
+    /// Clock time since the last clock event.
+    pub clock_since_ms: u64,
+}
+
+impl Game {
+    pub fn save(&self) -> SaveGame {
+        SaveGame {
+            version: SAVE_GAME_VERSION,
+            ruleset_fingerprint: self.ruleset().fingerprint(),
+            placements: self.placements.clone(),
+            actions: self
+                .history
+                .iter()
+                .map(|(action, _)| action.clone())
+                .collect(),
+            huffs: self.huffs.clone(),
+            rounds: self.rounds.clone(),
+            rolls: self.rolls.clone(),
+            seed: self.seed,
+            placement: self.placement.clone(),
+            phase: self.phase(),
+            draw_offer: self.draw_offer,
+            clock_interval_ms: self.events.clock_interval_ms,
+            clock_since_ms: self.events.clock_since_ms,
+        }
+    }
+    /// Continues a saved game under the ruleset it was played with.
+    ///
+    /// Events start over with the phase the game is in, as for a new game. Results the rules do
+    /// not decide, such as resignations or agreed draws, are taken from the save.
+    pub fn resume(save: SaveGame, ruleset: impl Into<RulesetHandle>) -> SaveResult<Self> {
+        if save.version != SAVE_GAME_VERSION {
+            return Err(SaveError::UnsupportedVersion(save.version));
+        }
+        let ruleset = ruleset.into();
+        if ruleset.fingerprint() != save.ruleset_fingerprint {
+            return Err(SaveError::RulesetMismatch);
+        }
+        let mut game = Game::from_handle(ruleset)?
+            .with_seed(save.seed)
+            .with_rolls(&save.rolls)?;
+        match save.placement {
+            Some(placement) => {
+                game.board = placement.board().clone();
+                game.board.index_pieces();
+                game.placements = placement.placements().to_vec();
+                game.placement = Some(placement);
+                game.finish_placement();
+            }
+            None => {
+                game = replay_onto(
+                    game,
+                    &save.placements,
+                    &save.actions,
+                    &save.huffs,
+                    &save.rounds,
+                )?
+            }
+        }
+        if let (Phase::Finished(outcome), None) = (save.phase, game.result) {
+            game.result = Some(outcome);
+        }
+        if game.phase() != save.phase {
+            return Err(SaveError::PhaseMismatch {
+                saved: save.phase,
+                resumed: game.phase(),
+            });
+        }
+        game.draw_offer = save.draw_offer.filter(|_| !game.is_finished());
+        game.events = EventQueue {
+            events: Vec::new(),
+            clock_interval_ms: save.clock_interval_ms,
+            clock_since_ms: save.clock_since_ms,
+        };
+        game.push_start_events();
+        Ok(game)
+    }
+}
+
+pub type SaveResult<T> = Result<T, SaveError>;
+#[derive(Clone, Debug)]
+pub enum SaveError {
+    UnsupportedVersion(u32),
+    /// The save was made under different rules.
+    RulesetMismatch,
+    GameError(GameError),
+    /// The saved game replays to a different phase, the save is corrupt.
+    PhaseMismatch {
+        saved: Phase,
+        resumed: Phase,
+    },
+}
+impl Display for SaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for SaveError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::GameError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+impl From<GameError> for SaveError {
+    fn from(from: GameError) -> Self {
+        Self::GameError(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collections::HashSet;
+    use crate::engine::Engine;
+    use crate::game::event::{GameEvent, Phase};
+    use crate::game::placement::{PlacementPhase, TimeoutPolicy};
+    use crate::game::playout::RandomPolicy;
+    use crate::game::save::{SaveError, SAVE_GAME_VERSION};
+    use crate::game::{Game, GameOutcome};
+    use crate::game_board::Color;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::ruleset::verified::{RulesetHandle, VerifiedRuleset};
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn save_test() {
+        let handle = RulesetHandle::new(small_ruleset(1)).unwrap();
+        let mut game = Game::from_handle(handle.clone()).unwrap();
+        let mut policy = RandomPolicy::new(2);
+        for _ in 0..6 {
+            let action = policy.choose_action(&game).unwrap();
+            game.apply(&action).unwrap();
+        }
+        game.offer_draw(game.to_move());
+        let mut resumed = Game::resume(game.save(), handle.clone()).unwrap();
+        assert_eq!(resumed.history(), game.history());
+        assert!(resumed.board().diff(game.board()).is_empty());
+        assert_eq!(resumed.draw_offer(), Some(game.to_move()));
+        assert_eq!(
+            resumed.take_events(),
+            vec![GameEvent::PhaseChanged(Phase::Play)]
+        );
+        assert!(resumed.accept_draw());
+        let finished = Game::resume(resumed.save(), handle.clone()).unwrap();
+        assert_eq!(finished.result(), Some(GameOutcome::Draw));
+        assert_eq!(finished.draw_offer(), None);
+
+        let mut save = game.save();
+        save.version = SAVE_GAME_VERSION + 1;
+        assert!(matches!(
+            Game::resume(save, handle.clone()),
+            Err(SaveError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            Game::resume(game.save(), RulesetHandle::new(small_ruleset(2)).unwrap()),
+            Err(SaveError::RulesetMismatch)
+        ));
+    }
+
+    #[test]
+    fn placement_save_test() {
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 2 });
+        let mut ruleset = small_ruleset(0);
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::TurnsCount { per_turn_count: 1 },
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        let phase = PlacementPhase::new(&VerifiedRuleset::new(ruleset.clone()).unwrap())
+            .unwrap()
+            .with_clock(100, TimeoutPolicy::Forfeit);
+        let mut game = Game::resume_placement(ruleset.clone(), phase).unwrap();
+        game.set_clock_interval(Some(30));
+        let placement = game.placement().unwrap().legal_placements()[0];
+        game.place(placement.piece_index, placement.position)
+            .unwrap();
+        game.tick_placement(40);
+
+        let mut resumed = Game::resume(game.save(), RulesetHandle::new(ruleset).unwrap()).unwrap();
+        assert_eq!(resumed.phase(), Phase::Placement);
+        assert_eq!(resumed.placements(), game.placements());
+        assert_eq!(
+            resumed.placement().unwrap().clock(),
+            game.placement().unwrap().clock()
+        );
+        resumed.take_events();
+        resumed.tick_placement(20);
+        assert_eq!(
+            resumed.take_events(),
+            vec![GameEvent::ClockUpdated {
+                color: Color::Blue,
+                remaining_ms: 40,
+            }]
+        );
+    }
+}
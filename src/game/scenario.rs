@@ -0,0 +1,304 @@
+//! Tutorial levels and challenges described entirely by data, see [`Scenario`].
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::action::{Action, ActionError, ActionOutcome};
+use crate::collections::{HashMap, HashSet};
+use crate::coordinate::Coordinate;
+use crate::game::{Game, GameError, GameOutcome};
+use crate::game_board::Color;
+use crate::ruleset::starting_positions::StartingPositions;
+use crate::ruleset::victory_condition::VictoryCondition;
+use crate::ruleset::Ruleset;
+
+/// A fixed position to play from under a ruleset, with optional limits on what may be played.
+///
+/// The color to move plays the scenario, [`ScenarioGame::status`] tells when it is won or lost.
+/// Games started with Blue to move do not replay from [`Game::record`], replay them with
+/// [`Scenario::start`] and [`ScenarioGame::apply`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct Scenario {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub description: String,
+    /// The rules, its starting positions are replaced by `position`.
+    pub ruleset: Ruleset,
+    /// Squares of each color's pieces by piece index.
+    pub position: HashMap<Color, HashMap<usize, Vec<Coordinate>>>,
+    /// The color playing the scenario, moving first.
+    pub to_move: Color,
+    /// Limits on the actions of either color, every one applies.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub restrictions: Vec<MoveRestriction>,
+    /// Replaces the ruleset's victory conditions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub victory_conditions: Option<HashSet<VictoryCondition>>,
+    /// Actions `to_move` may take to win, the scenario is lost after that many.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub move_limit: Option<usize>,
+}
+impl Scenario {
+    /// The ruleset with the scenario's position and victory conditions.
+    pub fn ruleset(&self) -> Ruleset {
+        let mut out = self.ruleset.clone();
+        out.starting_positions = StartingPositions::NotMirrored(self.position.clone());
+        if let Some(victory_conditions) = &self.victory_conditions {
+            out.victory_conditions = victory_conditions.clone();
+        }
+        out
+    }
+    pub fn start(&self) -> ScenarioResult<ScenarioGame> {
+        let mut game = Game::new(self.ruleset())?;
+        game.to_move = self.to_move;
+        game.events.events.clear();
+        game.push_start_events();
+        Ok(ScenarioGame {
+            game,
+            player: self.to_move,
+            restrictions: self.restrictions.clone(),
+            move_limit: self.move_limit,
+        })
+    }
+}
+
+/// A limit on the actions `color` may take.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct MoveRestriction {
+    pub color: Color,
+    pub rule: RestrictionRule,
+}
+impl MoveRestriction {
+    /// Whether `color` may play `action` under this restriction.
+    pub fn allows(&self, color: Color, action: &Action) -> bool {
+        if color != self.color {
+            return true;
+        }
+        match &self.rule {
+            RestrictionRule::JumpsOnly => action.action_type.is_jump(),
+            RestrictionRule::FromSquares(squares) => {
+                !action.action_type.is_pass() && squares.contains(&action.start_pos)
+            }
+            RestrictionRule::ToSquares(squares) => {
+                !action.action_type.is_pass() && squares.contains(&action.end_pos())
+            }
+            RestrictionRule::NoPass => !action.action_type.is_pass(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum RestrictionRule {
+    /// Only jump chains.
+    JumpsOnly,
+    /// Only actions of the pieces on these squares.
+    FromSquares(Vec<Coordinate>),
+    /// Only actions ending on these squares.
+    ToSquares(Vec<Coordinate>),
+    /// Anything but passing.
+    NoPass,
+}
+
+/// How a [`ScenarioGame`] stands for the color playing it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScenarioStatus {
+    InProgress,
+    Won,
+    /// The game was lost or drawn, the move limit ran out, or no allowed action is left.
+    Lost,
+}
+
+/// A game started from a [`Scenario`].
+#[derive(Clone, Debug)]
+pub struct ScenarioGame {
+    game: Game,
+    player: Color,
+    restrictions: Vec<MoveRestriction>,
+    move_limit: Option<usize>,
+}
+impl ScenarioGame {
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+    /// The color playing the scenario.
+    pub fn player(&self) -> Color {
+        self.player
+    }
+    /// Actions the player has taken.
+    pub fn player_moves(&self) -> usize {
+        self.game.history().len().div_ceil(2)
+    }
+    /// [`Game::legal_actions`] the restrictions allow.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        let color = self.game.to_move();
+        self.game
+            .legal_actions()
+            .into_iter()
+            .filter(|action| self.allows(color, action))
+            .collect()
+    }
+    /// Plays `action` if the restrictions allow it, see [`Game::apply`].
+    pub fn apply(&mut self, action: &Action) -> ScenarioResult<ActionOutcome> {
+        if self.status() != ScenarioStatus::InProgress {
+            return Err(ScenarioError::Finished);
+        }
+        if !self.allows(self.game.to_move(), action) {
+            return Err(ScenarioError::Restricted(action.clone()));
+        }
+        Ok(self.game.apply(action)?)
+    }
+    pub fn status(&self) -> ScenarioStatus {
+        match self.game.result() {
+            Some(GameOutcome::Win(color)) if color == self.player => ScenarioStatus::Won,
+            Some(_) => ScenarioStatus::Lost,
+            None if self
+                .move_limit
+                .is_some_and(|limit| self.player_moves() >= limit) =>
+            {
+                ScenarioStatus::Lost
+            }
+            None if self.legal_actions().is_empty() => ScenarioStatus::Lost,
+            None => ScenarioStatus::InProgress,
+        }
+    }
+    pub fn into_game(self) -> Game {
+        self.game
+    }
+
+    fn allows(&self, color: Color, action: &Action) -> bool {
+        self.restrictions
+            .iter()
+            .all(|restriction| restriction.allows(color, action))
+    }
+}
+
+pub type ScenarioResult<T> = Result<T, ScenarioError>;
+#[derive(Clone, Debug)]
+pub enum ScenarioError {
+    GameError(GameError),
+    ActionError(ActionError),
+    /// An action a [`MoveRestriction`] forbids.
+    Restricted(Action),
+    /// The scenario was already won or lost.
+    Finished,
+}
+impl Display for ScenarioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for ScenarioError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::GameError(error) => Some(error),
+            Self::ActionError(_) | Self::Restricted(_) | Self::Finished => None,
+        }
+    }
+}
+impl From<GameError> for ScenarioError {
+    fn from(from: GameError) -> Self {
+        Self::GameError(from)
+    }
+}
+impl From<ActionError> for ScenarioError {
+    fn from(from: ActionError) -> Self {
+        Self::ActionError(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+    use alloc::vec;
+
+    use crate::action::{Action, ActionType};
+    use crate::collections::HashMap;
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game::scenario::{
+        MoveRestriction, RestrictionRule, Scenario, ScenarioError, ScenarioStatus,
+    };
+    use crate::game_board::board::Board;
+    use crate::game_board::{Color, PieceInstance};
+    use crate::ruleset::piece_definition::CaptureRequirement;
+    use crate::testing::small_ruleset;
+
+    fn scenario() -> Scenario {
+        let mut position = HashMap::new();
+        position.insert(
+            Color::Blue,
+            vec![(0, vec![Coordinate::new(4, 2)])].into_iter().collect(),
+        );
+        position.insert(
+            Color::Red,
+            vec![(1, vec![Coordinate::new(5, 2)])].into_iter().collect(),
+        );
+        Scenario {
+            name: "First capture".into(),
+            description: String::new(),
+            ruleset: small_ruleset(0),
+            position,
+            to_move: Color::Blue,
+            restrictions: vec![MoveRestriction {
+                color: Color::Blue,
+                rule: RestrictionRule::JumpsOnly,
+            }],
+            victory_conditions: None,
+            move_limit: Some(1),
+        }
+    }
+
+    #[test]
+    fn scenario_test() {
+        let mut game = scenario().start().unwrap();
+        assert_eq!(game.game().to_move(), Color::Blue);
+        assert_eq!(
+            game.game().board().piece(Coordinate::new(4, 2)).unwrap(),
+            Some(PieceInstance::new(0, Color::Blue))
+        );
+        assert!(game
+            .legal_actions()
+            .iter()
+            .all(|action| action.action_type.is_jump()));
+        let step = Action {
+            start_pos: Coordinate::new(4, 2),
+            action_type: ActionType::Move(Direction::North),
+        };
+        assert!(matches!(
+            game.apply(&step),
+            Err(ScenarioError::Restricted(_))
+        ));
+
+        let mut failed = scenario();
+        failed.restrictions.clear();
+        for piece in failed.ruleset.pieces.iter_mut() {
+            piece.capture_requirement = CaptureRequirement::None;
+        }
+        let mut failed = failed.start().unwrap();
+        failed.apply(&step).unwrap();
+        assert_eq!(failed.status(), ScenarioStatus::Lost);
+
+        let capture = Action {
+            start_pos: Coordinate::new(4, 2),
+            action_type: ActionType::Jump([Direction::South].into()),
+        };
+        game.apply(&capture).unwrap();
+        assert_eq!(game.status(), ScenarioStatus::Won);
+        assert!(matches!(
+            game.apply(&Action::pass()),
+            Err(ScenarioError::Finished)
+        ));
+    }
+}
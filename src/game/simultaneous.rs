@@ -0,0 +1,315 @@
+//! Rounds where both colors act at once, see [`Game::submit`] and [`Game::resolve`].
+//!
+//! Each color submits an action legal in the current position without seeing the other's.
+//! Resolving plays them one after the other in the order of a [`ConflictRule`], and an action
+//! the first one made illegal, such as a step onto the square the other piece just took or a
+//! jump over a piece that was captured, is cancelled. [`Game::rounds`] keeps the order each
+//! round was played in so [`Game::record`] replays it.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::action::{Action, ActionError, ActionOutcome};
+use crate::game::{Game, GameError, GameResult};
+use crate::game_board::board::Board;
+use crate::game_board::Color;
+
+/// Which submitted action of a round is played first.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum ConflictRule {
+    /// The action capturing more pieces, ties go to the color to move.
+    #[default]
+    CapturesFirst,
+    /// The color to move's action.
+    ToMoveFirst,
+}
+
+/// What a round did, see [`Game::resolve`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Resolution {
+    /// Actions played, in the order they were played.
+    pub applied: Vec<(Color, Action, ActionOutcome)>,
+    /// Actions that were no longer legal once the other was played.
+    pub cancelled: Vec<(Color, Action, ActionError)>,
+}
+
+/// A resolved round, see [`Game::rounds`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct Round {
+    /// Actions played before the round.
+    pub ply: usize,
+    /// Colors whose actions were played, in the order they were played.
+    pub played: Vec<Color>,
+}
+
+impl<B: Board + Clone> Game<B> {
+    /// Rounds resolved so far, in the order they were resolved.
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+    /// Submits `color`'s action for this round, it must be legal in the current position.
+    pub fn submit(&mut self, color: Color, action: &Action) -> SimultaneousResult<()> {
+        if self.submissions[color.index()].is_some() {
+            return Err(SimultaneousError::AlreadySubmitted(color));
+        }
+        self.check_turn(color, action)?;
+        self.board.is_valid_action(action)?;
+        self.submissions[color.index()] = Some(action.clone());
+        Ok(())
+    }
+    /// The action `color` submitted this round.
+    pub fn submitted(&self, color: Color) -> Option<&Action> {
+        self.submissions[color.index()].as_ref()
+    }
+    /// Plays both submitted actions in the order `rule` gives, cancelling the second if it is
+    /// no longer legal or the first finished the game.
+    ///
+    /// The color to move then changes as after an action, so ties alternate between rounds.
+    pub fn resolve(&mut self, rule: ConflictRule) -> SimultaneousResult<Resolution> {
+        let to_move = self.to_move;
+        for color in [to_move, to_move.opponent()] {
+            if self.submissions[color.index()].is_none() {
+                return Err(SimultaneousError::Waiting(color));
+            }
+        }
+        let captures = |game: &Self, color: Color| {
+            let mut after = game.board.clone();
+            game.submitted(color)
+                .and_then(|action| after.apply_with_outcome(action).ok())
+                .map_or(0, |outcome| outcome.captures.len())
+        };
+        let first = match rule {
+            ConflictRule::CapturesFirst
+                if captures(self, to_move.opponent()) > captures(self, to_move) =>
+            {
+                to_move.opponent()
+            }
+            ConflictRule::CapturesFirst | ConflictRule::ToMoveFirst => to_move,
+        };
+
+        let ply = self.history.len();
+        let mut out = Resolution {
+            applied: Vec::new(),
+            cancelled: Vec::new(),
+        };
+        for color in [first, first.opponent()] {
+            let action = self.submissions[color.index()]
+                .take()
+                .expect("Checked above");
+            self.to_move = color;
+            match self.apply(&action) {
+                Ok(outcome) => out.applied.push((color, action, outcome)),
+                Err(error) => out.cancelled.push((color, action, error)),
+            }
+        }
+        if !self.is_finished() {
+            self.to_move = to_move.opponent();
+        }
+        self.rounds.push(Round {
+            ply,
+            played: out.applied.iter().map(|(color, _, _)| *color).collect(),
+        });
+        Ok(out)
+    }
+
+    /// Plays the recorded `actions` of `round` in the order [`Game::resolve`] played them.
+    pub(crate) fn replay_round(&mut self, round: &Round, actions: &[Action]) -> GameResult<()> {
+        let to_move = self.to_move;
+        for (ply, (color, action)) in (round.ply..).zip(round.played.iter().zip(actions)) {
+            self.to_move = *color;
+            self.apply(action)
+                .map_err(|error| GameError::IllegalAction { ply, error })?;
+        }
+        if !self.is_finished() {
+            self.to_move = to_move.opponent();
+        }
+        self.rounds.push(round.clone());
+        Ok(())
+    }
+}
+
+pub type SimultaneousResult<T> = Result<T, SimultaneousError>;
+#[derive(Clone, Debug)]
+pub enum SimultaneousError {
+    ActionError(ActionError),
+    /// The color already submitted this round.
+    AlreadySubmitted(Color),
+    /// The color has not submitted yet.
+    Waiting(Color),
+}
+impl Display for SimultaneousError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for SimultaneousError {}
+impl From<ActionError> for SimultaneousError {
+    fn from(from: ActionError) -> Self {
+        Self::ActionError(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+    use alloc::vec;
+
+    use crate::action::{Action, ActionError, ActionType};
+    use crate::collections::HashMap;
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game::scenario::Scenario;
+    use crate::game::simultaneous::{ConflictRule, Round, SimultaneousError};
+    use crate::game::{Game, GameError};
+    use crate::game_board::board::Board;
+    use crate::game_board::{Color, PieceInstance};
+    use crate::ruleset::piece_definition::CaptureRequirement;
+    use crate::testing::small_ruleset;
+
+    fn collision() -> Game {
+        let mut position = HashMap::new();
+        position.insert(
+            Color::Red,
+            vec![(0, vec![Coordinate::new(3, 1)])].into_iter().collect(),
+        );
+        position.insert(
+            Color::Blue,
+            vec![(0, vec![Coordinate::new(3, 3)])].into_iter().collect(),
+        );
+        // Steps stay legal next to a capture
+        let mut ruleset = small_ruleset(0);
+        for piece in ruleset.pieces.iter_mut() {
+            piece.capture_requirement = CaptureRequirement::None;
+        }
+        let scenario = Scenario {
+            name: "Collision".into(),
+            description: String::new(),
+            ruleset,
+            position,
+            to_move: Color::Red,
+            restrictions: vec![],
+            victory_conditions: None,
+            move_limit: None,
+        };
+        scenario.start().unwrap().into_game()
+    }
+
+    #[test]
+    fn resolve_test() {
+        let mut game = collision();
+        let red = Action {
+            start_pos: Coordinate::new(3, 1),
+            action_type: ActionType::Move(Direction::East),
+        };
+        let blue = Action {
+            start_pos: Coordinate::new(3, 3),
+            action_type: ActionType::Move(Direction::West),
+        };
+        assert!(matches!(
+            game.submit(Color::Red, &blue),
+            Err(SimultaneousError::ActionError(ActionError::WrongColor(
+                Color::Blue
+            )))
+        ));
+        game.submit(Color::Red, &red).unwrap();
+        assert!(matches!(
+            game.resolve(ConflictRule::ToMoveFirst),
+            Err(SimultaneousError::Waiting(Color::Blue))
+        ));
+        assert!(matches!(
+            game.submit(Color::Red, &red),
+            Err(SimultaneousError::AlreadySubmitted(Color::Red))
+        ));
+        game.submit(Color::Blue, &blue).unwrap();
+
+        // Both step onto the same square, Red is to move so it gets there first
+        let resolution = game.resolve(ConflictRule::ToMoveFirst).unwrap();
+        assert_eq!(resolution.applied.len(), 1);
+        assert_eq!(resolution.applied[0].0, Color::Red);
+        assert_eq!(resolution.cancelled.len(), 1);
+        assert_eq!(resolution.cancelled[0].0, Color::Blue);
+        assert_eq!(
+            game.board().piece(Coordinate::new(3, 2)).unwrap(),
+            Some(PieceInstance::new(0, Color::Red))
+        );
+        assert_eq!(game.submitted(Color::Red), None);
+        assert_eq!(game.to_move(), Color::Blue);
+
+        // Red can now jump Blue, capturing goes first even though Blue is to move
+        let jump = Action {
+            start_pos: Coordinate::new(3, 2),
+            action_type: ActionType::Jump([Direction::East].into()),
+        };
+        let step = Action {
+            start_pos: Coordinate::new(3, 3),
+            action_type: ActionType::Move(Direction::South),
+        };
+        game.submit(Color::Red, &jump).unwrap();
+        game.submit(Color::Blue, &step).unwrap();
+        let resolution = game.resolve(ConflictRule::CapturesFirst).unwrap();
+        assert_eq!(resolution.applied[0].0, Color::Red);
+        assert_eq!(resolution.applied[0].2.captures.len(), 1);
+        assert_eq!(resolution.cancelled.len(), 1);
+        assert!(game.is_finished());
+    }
+
+    #[test]
+    fn replay_test() {
+        let mut game = collision();
+        let step = |row, column, direction| Action {
+            start_pos: Coordinate::new(row, column),
+            action_type: ActionType::Move(direction),
+        };
+        game.submit(Color::Red, &step(3, 1, Direction::North))
+            .unwrap();
+        game.submit(Color::Blue, &step(3, 3, Direction::South))
+            .unwrap();
+        game.resolve(ConflictRule::ToMoveFirst).unwrap();
+        // Blue moves again after the round, which alternating actions would not replay
+        assert_eq!(game.to_move(), Color::Blue);
+        game.apply(&step(4, 3, Direction::West)).unwrap();
+        game.submit(Color::Red, &step(2, 1, Direction::West))
+            .unwrap();
+        game.submit(Color::Blue, &step(4, 2, Direction::West))
+            .unwrap();
+        game.resolve(ConflictRule::ToMoveFirst).unwrap();
+        assert_eq!(
+            game.rounds(),
+            &[
+                Round {
+                    ply: 0,
+                    played: vec![Color::Red, Color::Blue],
+                },
+                Round {
+                    ply: 3,
+                    played: vec![Color::Red, Color::Blue],
+                },
+            ]
+        );
+
+        let record = game.record();
+        let replayed = record.replay().unwrap();
+        assert_eq!(replayed.board().board, game.board().board);
+        assert_eq!(replayed.to_move(), game.to_move());
+        assert_eq!(replayed.record(), record);
+
+        let mut alternating = record.clone();
+        alternating.rounds.clear();
+        assert!(alternating.replay().is_err());
+        let mut broken = record;
+        broken.rounds[1].played.push(Color::Red);
+        assert!(matches!(
+            broken.replay(),
+            Err(GameError::IllegalRound { index: 1 })
+        ));
+    }
+}
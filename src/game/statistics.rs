@@ -0,0 +1,104 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::game_board::board::Board;
+use crate::game_board::{Color, GameBoard};
+use crate::movegen::legal_actions;
+
+/// A summary of both colors' positions, see [`Game::statistics`](crate::game::Game::statistics).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameStatistics {
+    pub red: ColorStatistics,
+    pub blue: ColorStatistics,
+}
+impl GameStatistics {
+    /// Statistics of `board` for rulesets with `piece_count` piece definitions.
+    pub fn new(board: &GameBoard, piece_count: usize) -> Self {
+        Self {
+            red: ColorStatistics::new(board, Color::Red, piece_count),
+            blue: ColorStatistics::new(board, Color::Blue, piece_count),
+        }
+    }
+
+    pub fn color(&self, color: Color) -> &ColorStatistics {
+        match color {
+            Color::Red => &self.red,
+            Color::Blue => &self.blue,
+        }
+    }
+}
+
+/// One color's part of [`GameStatistics`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorStatistics {
+    /// Pieces on the board, indexed by piece definition.
+    pub pieces: Vec<usize>,
+    /// Legal actions on the board as if this color were to move, not counting passes.
+    pub mobility: usize,
+    /// Pieces in the opponent's goals.
+    pub in_goal: usize,
+    /// Pieces this color can capture next turn, see [`GameBoard::attack_map`].
+    pub pending_captures: usize,
+    /// Points scored so far, only known to [`Game::statistics`](crate::game::Game::statistics).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub score: usize,
+}
+impl ColorStatistics {
+    fn new(board: &GameBoard, color: Color, piece_count: usize) -> Self {
+        let mut pieces = vec![0; piece_count];
+        for (_, piece) in board.pieces_of_color(color) {
+            if let Some(count) = pieces.get_mut(piece.definition_index()) {
+                *count += 1;
+            }
+        }
+        let in_goal = board
+            .goals_of_color(color.opponent())
+            .into_iter()
+            .filter_map(|(_, piece)| piece)
+            .filter(|piece| piece.color() == color)
+            .count();
+        Self {
+            pieces,
+            mobility: legal_actions(board, color).len(),
+            in_goal,
+            pending_captures: board
+                .attack_map(color)
+                .values
+                .iter()
+                .filter(|&&attacked| attacked)
+                .count(),
+            score: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collections::HashSet;
+    use crate::game::Game;
+    use crate::game_board::Color;
+    use crate::movegen::legal_actions;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn statistics_test() {
+        let game = Game::new(small_ruleset(0)).unwrap();
+        let statistics = game.statistics();
+        for color in [Color::Red, Color::Blue] {
+            let stats = statistics.color(color);
+            let actions = legal_actions(game.board(), color);
+            assert_eq!(stats.pieces, vec![2, 3]);
+            assert_eq!(stats.mobility, actions.len());
+            assert_eq!(stats.in_goal, 0);
+
+            let mut captured = HashSet::new();
+            for action in actions.iter() {
+                let (_, outcome) = game.board().apply_action(action).unwrap();
+                captured.extend(outcome.captures.iter().map(|(position, _)| *position));
+            }
+            assert_eq!(stats.pending_captures, captured.len());
+        }
+    }
+}
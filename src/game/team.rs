@@ -0,0 +1,281 @@
+//! Pair play with several players sharing each color, see [`TeamGame`].
+//!
+//! Each color is a [`Team`] whose players take its turns in order, so a 2v2 game alternates
+//! Red's first player, Blue's first player, Red's second player, and so on. A team is exactly one
+//! color, not a group of them: teammates share that color's pieces rather than owning their own,
+//! any of them may move any of the team's pieces on their turn. Victory conditions are met per
+//! color and so per team. Captures of [`CaptureTarget::EnemyOnly`] pieces only take the other
+//! team's pieces, [`CaptureTarget::All`] takes the team's own pieces as well.
+//!
+//! [`CaptureTarget::EnemyOnly`]: crate::ruleset::piece_definition::CaptureTarget::EnemyOnly
+//! [`CaptureTarget::All`]: crate::ruleset::piece_definition::CaptureTarget::All
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::action::{Action, ActionError, ActionOutcome};
+use crate::coordinate::Coordinate;
+use crate::game::huff::HuffError;
+use crate::game::{Game, GameOutcome};
+use crate::game_board::{Color, PieceInstance};
+
+/// The players sharing a color.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct Team {
+    pub name: String,
+    /// Player ids in turn order, what they identify is up to the app.
+    pub players: Vec<usize>,
+}
+
+/// The team playing each color.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct Teams {
+    pub red: Team,
+    pub blue: Team,
+}
+impl Teams {
+    pub fn verify(&self) -> TeamResult<()> {
+        for color in Color::into_enum_iter() {
+            if self.team(color).players.is_empty() {
+                return Err(TeamError::EmptyTeam(color));
+            }
+        }
+        if let Some(&player) = self
+            .red
+            .players
+            .iter()
+            .find(|player| self.blue.players.contains(player))
+        {
+            return Err(TeamError::PlayerOnBothTeams(player));
+        }
+        Ok(())
+    }
+    pub fn team(&self, color: Color) -> &Team {
+        match color {
+            Color::Red => &self.red,
+            Color::Blue => &self.blue,
+        }
+    }
+    /// The color `player` plays, `None` if they are on neither team.
+    pub fn color_of(&self, player: usize) -> Option<Color> {
+        Color::into_enum_iter().find(|&color| self.team(color).players.contains(&player))
+    }
+}
+
+/// A game played by [`Teams`], checking each action is made by the player whose turn it is.
+#[derive(Clone, Debug)]
+pub struct TeamGame {
+    game: Game,
+    teams: Teams,
+    /// Actions each color has made, by [`Color::index`].
+    turns: [usize; 2],
+}
+impl TeamGame {
+    /// Teams play `game` from its current position, each starting with its first player.
+    pub fn new(game: Game, teams: Teams) -> TeamResult<Self> {
+        teams.verify()?;
+        Ok(Self {
+            game,
+            teams,
+            turns: [0; 2],
+        })
+    }
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+    pub fn teams(&self) -> &Teams {
+        &self.teams
+    }
+    /// The player whose turn it is, also the one who may [`huff`](TeamGame::huff).
+    pub fn player_to_move(&self) -> usize {
+        let color = self.game.to_move();
+        let players = &self.teams.team(color).players;
+        players[self.turns[color.index()] % players.len()]
+    }
+    /// Plays `action` for `player`, see [`Game::apply`].
+    pub fn apply(&mut self, player: usize, action: &Action) -> TeamResult<ActionOutcome> {
+        self.check_turn(player)?;
+        let color = self.game.to_move();
+        let outcome = self.game.apply(action)?;
+        self.turns[color.index()] += 1;
+        Ok(outcome)
+    }
+    /// Huffs for `player` without ending their turn, see [`Game::huff`].
+    pub fn huff(&mut self, player: usize, position: Coordinate) -> TeamResult<PieceInstance> {
+        self.check_turn(player)?;
+        Ok(self.game.huff(position)?)
+    }
+    /// The team that won, `None` while playing or for a draw.
+    pub fn winner(&self) -> Option<&Team> {
+        match self.game.result()? {
+            GameOutcome::Win(color) => Some(self.teams.team(color)),
+            GameOutcome::Draw => None,
+        }
+    }
+    pub fn into_game(self) -> Game {
+        self.game
+    }
+
+    fn check_turn(&self, player: usize) -> TeamResult<()> {
+        let to_move = self.player_to_move();
+        if player != to_move {
+            return Err(TeamError::NotToMove { player, to_move });
+        }
+        Ok(())
+    }
+}
+
+pub type TeamResult<T> = Result<T, TeamError>;
+#[derive(Clone, Debug)]
+pub enum TeamError {
+    /// A team without players.
+    EmptyTeam(Color),
+    PlayerOnBothTeams(usize),
+    /// `player` acted on `to_move`'s turn.
+    NotToMove {
+        player: usize,
+        to_move: usize,
+    },
+    ActionError(ActionError),
+    HuffError(HuffError),
+}
+impl Display for TeamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for TeamError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::HuffError(error) => Some(error),
+            Self::EmptyTeam(_)
+            | Self::PlayerOnBothTeams(_)
+            | Self::NotToMove { .. }
+            | Self::ActionError(_) => None,
+        }
+    }
+}
+impl From<ActionError> for TeamError {
+    fn from(from: ActionError) -> Self {
+        Self::ActionError(from)
+    }
+}
+impl From<HuffError> for TeamError {
+    fn from(from: HuffError) -> Self {
+        Self::HuffError(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use crate::action::{Action, ActionType};
+    use crate::collections::HashMap;
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::engine::Engine;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::team::{Team, TeamError, TeamGame, Teams};
+    use crate::game::Game;
+    use crate::game_board::{Color, PieceInstance};
+    use crate::ruleset::piece_definition::{CaptureRule, CaptureTarget};
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::testing::small_ruleset;
+
+    fn teams() -> Teams {
+        Teams {
+            red: Team {
+                name: "North".into(),
+                players: vec![0, 2],
+            },
+            blue: Team {
+                name: "South".into(),
+                players: vec![1, 3],
+            },
+        }
+    }
+
+    #[test]
+    fn team_game_test() {
+        let mut broken = teams();
+        broken.blue.players.push(2);
+        assert!(matches!(
+            broken.verify(),
+            Err(TeamError::PlayerOnBothTeams(2))
+        ));
+        broken.blue.players.clear();
+        assert!(matches!(
+            broken.verify(),
+            Err(TeamError::EmptyTeam(Color::Blue))
+        ));
+        assert_eq!(teams().color_of(3), Some(Color::Blue));
+        assert_eq!(teams().color_of(4), None);
+
+        let mut game = TeamGame::new(Game::new(small_ruleset(1)).unwrap(), teams()).unwrap();
+        let mut policy = RandomPolicy::new(3);
+        let mut order = vec![];
+        while order.len() < 6 && !game.game().is_finished() {
+            let action = policy.choose_action(game.game()).unwrap();
+            let player = game.player_to_move();
+            let other = (player + 1) % 4;
+            assert!(matches!(
+                game.apply(other, &action),
+                Err(TeamError::NotToMove { .. })
+            ));
+            game.apply(player, &action).unwrap();
+            order.push(player);
+        }
+        assert_eq!(order, vec![0, 1, 2, 3, 0, 1]);
+        assert!(game.winner().is_none());
+    }
+
+    #[test]
+    fn teammate_capture_test() {
+        let with_target = |target| {
+            let mut ruleset = small_ruleset(0);
+            let mut capture_rules = HashMap::new();
+            capture_rules.insert(CaptureRule::JumpOver, target);
+            ruleset.pieces[1].capture_rules = capture_rules;
+            let mut position = HashMap::new();
+            position.insert(
+                Color::Red,
+                vec![(1, vec![Coordinate::new(3, 2), Coordinate::new(2, 2)])]
+                    .into_iter()
+                    .collect(),
+            );
+            position.insert(
+                Color::Blue,
+                vec![(1, vec![Coordinate::new(5, 5)])].into_iter().collect(),
+            );
+            ruleset.starting_positions = StartingPositions::NotMirrored(position);
+            TeamGame::new(Game::new(ruleset).unwrap(), teams()).unwrap()
+        };
+        // Player 0 jumps the piece player 2 shares with them
+        let jump = Action {
+            start_pos: Coordinate::new(3, 2),
+            action_type: ActionType::Jump([Direction::North].into()),
+        };
+
+        let mut all = with_target(CaptureTarget::All);
+        let outcome = all.apply(0, &jump).unwrap();
+        assert_eq!(
+            outcome.captures,
+            vec![(Coordinate::new(2, 2), PieceInstance::new(1, Color::Red))]
+        );
+
+        let mut enemy_only = with_target(CaptureTarget::EnemyOnly);
+        assert!(enemy_only.apply(0, &jump).unwrap().captures.is_empty());
+    }
+}
@@ -1,17 +1,49 @@
-use std::ops::{Index, IndexMut};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ops::IndexMut;
 
 use enum_iterator::IntoEnumIterator;
-use matrix::prelude::Conventional;
-use matrix::{Element, Position, Size};
 
-use crate::action::ActionError::PieceOnMove;
-use crate::action::{Action, ActionError, ActionType};
+use crate::action::jump_directions::JumpDirections;
+use crate::action::{Action, ActionError, ActionOutcome, ActionType};
 use crate::coordinate::Coordinate;
 use crate::direction::Direction;
+use crate::direction::Directions;
+use crate::game_board::board::Board;
+use crate::grid::{Grid, Position, Size};
+use crate::movegen::legal_actions;
+use crate::ruleset::board_type::space::Space;
+use crate::ruleset::board_type::BoardType;
+use crate::ruleset::piece_definition::{
+    CaptureRequirement, CaptureRule, CaptureTarget, CaptureTimingRule, ChainContinuationRule,
+    GoalMovementRule, JumpLimit, JumpRule, PieceDefinition,
+};
+
+pub mod board;
+pub mod destinations;
+pub mod display;
+pub mod fixed;
+pub mod reachability;
+pub mod speculative;
+pub mod validated;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameBoard {
-    pub board: Conventional<BoardSpace>,
+    /// Pieces changed here rather than through [`GameBoard::piece_mut`] must be followed by
+    /// [`GameBoard::invalidate_pieces`].
+    pub board: Grid<BoardSpace>,
+    /// The rules of each piece by definition index.
+    ///
+    /// Pieces without any use [`PieceRules::STANDARD`], and the small piece for indices past it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub piece_rules: Vec<PieceRules>,
+    /// Every piece of each color in square order, by [`Color::index`], `None` until indexed.
+    ///
+    /// Kept up to date by [`GameBoard::apply_action`] so reading pieces does not scan the board.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pieces: Option<[Vec<(Coordinate, PieceInstance)>; 2]>,
 }
 impl GameBoard {
     pub fn new<S: Size>(board_size: S, goal_pos: &[usize]) -> Self {
@@ -20,201 +52,492 @@ impl GameBoard {
         let columns = board_size.columns();
         assert!(rows >= 1, "Rows must be >= 1");
         assert!(columns >= 2, "Columns must be >= 2");
-        let mut board = Conventional::new((rows, columns));
+        let mut board = Grid::new((rows, columns));
         for index in 0..columns {
             if !goal_pos.contains(&index) {
                 *board.index_mut((0, index)) = BoardSpace::Invalid;
                 *board.index_mut((rows - 1, index)) = BoardSpace::Invalid;
             }
         }
-        Self { board }
+        Self {
+            board,
+            piece_rules: Vec::new(),
+            pieces: None,
+        }
+    }
+    /// Creates an empty board from a board definition, keeping all of its goal spaces.
+    pub fn from_board_type(board_type: &BoardType) -> Self {
+        let mut board = Grid::new((board_type.rows(), board_type.columns()));
+        for row in 0..board_type.rows() {
+            for column in 0..board_type.columns() {
+                let position = Coordinate::new(row as i16, column as i16);
+                board[position] = match board_type.get_space(position) {
+                    Space::Invalid => BoardSpace::Invalid,
+                    Space::Normal => BoardSpace::Normal(None),
+                    Space::Goal(color) => BoardSpace::Goal {
+                        goal_for: color,
+                        piece: None,
+                    },
+                    Space::Blocked => BoardSpace::Blocked,
+                    Space::Sticky => BoardSpace::Sticky(None),
+                    Space::Portal(pair) => BoardSpace::Portal { pair, piece: None },
+                };
+            }
+        }
+        Self {
+            board,
+            piece_rules: Vec::new(),
+            pieces: None,
+        }
+    }
+    /// Enforces the rules of `pieces` on actions.
+    pub fn set_piece_rules(&mut self, pieces: &[PieceDefinition]) {
+        self.piece_rules = pieces.iter().map(PieceRules::from).collect();
     }
 
-    pub fn is_valid_position(&self, position: impl Position) -> bool {
-        self.board.columns > position.column()
-            && self.board.rows > position.row()
-            && self.board.index(position) != &BoardSpace::Invalid
+    /// Whether the board keeps piece lists, see [`GameBoard::index_pieces`].
+    pub fn is_indexed(&self) -> bool {
+        self.pieces.is_some()
+    }
+    /// Builds the lists of each color's pieces, kept up to date from then on by
+    /// [`GameBoard::apply_action`] until a square is changed by hand.
+    pub fn index_pieces(&mut self) {
+        let mut lists = [Vec::new(), Vec::new()];
+        for (position, piece) in self.pieces() {
+            lists[piece.color().index()].push((position, piece));
+        }
+        self.pieces = Some(lists);
     }
-    fn check_valid_position(&self, position: impl Position) -> GameBoardResult<()> {
-        if self.is_valid_position(position) {
-            Ok(())
-        } else {
-            Err(GameBoardError::InvalidPosition)
+    /// Drops the piece lists after squares are changed through [`GameBoard::board`].
+    pub fn invalidate_pieces(&mut self) {
+        self.pieces = None;
+    }
+    /// Records `piece` now being at `position` in the piece lists, `old` being the piece that was.
+    fn update_pieces(
+        &mut self,
+        position: Coordinate,
+        old: Option<PieceInstance>,
+        piece: Option<PieceInstance>,
+    ) {
+        let lists = match &mut self.pieces {
+            Some(lists) => lists,
+            None => return,
+        };
+        if let Some(old) = old {
+            let list = &mut lists[old.color().index()];
+            if let Ok(index) = list
+                .binary_search_by_key(&square_order(position), |&(position, _)| {
+                    square_order(position)
+                })
+            {
+                list.remove(index);
+            }
+        }
+        if let Some(piece) = piece {
+            let list = &mut lists[piece.color().index()];
+            match list.binary_search_by_key(&square_order(position), |&(position, _)| {
+                square_order(position)
+            }) {
+                Ok(index) => list[index].1 = piece,
+                Err(index) => list.insert(index, (position, piece)),
+            }
         }
     }
 
-    pub fn pieces_of_size(&self, size: PieceSize) -> Vec<(impl Position, Piece)> {
-        let mut out = Vec::new();
-        for (index, space) in self.board.values.iter().enumerate() {
-            match space {
-                BoardSpace::Normal(piece) | BoardSpace::Goal { goal_for: _, piece } => {
-                    if let Some(piece) = piece {
-                        if piece.size() == size {
-                            out.push((index_to_position(&self.board, index), *piece));
-                        }
-                    }
+    /// Marks every square holding a piece that one of `color`'s legal actions captures.
+    pub fn attack_map(&self, color: Color) -> Grid<bool> {
+        let mut out = Grid::new((self.board.rows, self.board.columns));
+        for action in legal_actions(self, color) {
+            let directions = match &action.action_type {
+                ActionType::Jump(directions) => directions,
+                _ => continue,
+            };
+            let piece = match self.piece(action.start_pos) {
+                Ok(Some(piece)) => piece,
+                _ => continue,
+            };
+            if let Ok(chain) = self.jump_chain(piece, action.start_pos, directions) {
+                for step in chain.steps().iter().filter(|step| step.captures) {
+                    out[step.middle] = true;
                 }
-                _ => {}
             }
         }
         out
     }
-    pub fn pieces_of_color(&self, color: Color) -> Vec<(impl Position, Piece)> {
+    /// Whether `by_color` can capture the piece at `position` next turn, see [`GameBoard::attack_map`].
+    pub fn is_threatened(&self, position: Coordinate, by_color: Color) -> bool {
+        self.is_valid_position(position) && self.attack_map(by_color)[position]
+    }
+
+    /// Pieces that left, arrived, or moved between `self` and `other`.
+    ///
+    /// A piece leaving one square and an identical piece arriving on another is a move,
+    /// paired with the closest arrival. Pieces replaced by a different piece disappear and appear.
+    pub fn diff(&self, other: &GameBoard) -> Vec<BoardDelta> {
+        let pieces = |board: &GameBoard| {
+            let mut out = board.pieces_of_color(Color::Red);
+            out.extend(board.pieces_of_color(Color::Blue));
+            out
+        };
+        let mut disappeared: Vec<_> = pieces(self)
+            .into_iter()
+            .filter(|&(position, piece)| other.piece(position).ok().flatten() != Some(piece))
+            .collect();
+        let mut appeared: Vec<_> = pieces(other)
+            .into_iter()
+            .filter(|&(position, piece)| self.piece(position).ok().flatten() != Some(piece))
+            .collect();
+        disappeared.sort_by_key(|&(position, _)| position);
+        appeared.sort_by_key(|&(position, _)| position);
+
         let mut out = Vec::new();
-        for (index, space) in self.board.values.iter().enumerate() {
-            match space {
-                BoardSpace::Normal(piece) | BoardSpace::Goal { goal_for: _, piece } => {
-                    if let Some(piece) = piece {
-                        if piece.color() == color {
-                            out.push((index_to_position(&self.board, index), *piece));
-                        }
-                    }
+        disappeared.retain(|&(from, piece)| {
+            let closest = appeared
+                .iter()
+                .enumerate()
+                .filter(|(_, &(_, arrived))| arrived == piece)
+                .min_by_key(|(_, &(to, _))| {
+                    let offset = to - from;
+                    offset.row.unsigned_abs().max(offset.column.unsigned_abs())
+                })
+                .map(|(index, _)| index);
+            match closest {
+                Some(index) => {
+                    let (to, _) = appeared.remove(index);
+                    out.push(BoardDelta::Moved { from, to, piece });
+                    false
                 }
-                _ => {}
+                None => true,
             }
-        }
+        });
+        out.extend(
+            disappeared
+                .into_iter()
+                .map(|(position, piece)| BoardDelta::Disappeared { position, piece }),
+        );
+        out.extend(
+            appeared
+                .into_iter()
+                .map(|(position, piece)| BoardDelta::Appeared { position, piece }),
+        );
         out
     }
 
-    pub fn piece(&self, position: impl Position + Copy) -> GameBoardResult<Option<Piece>> {
-        self.check_valid_position(position)?;
-        match self.board.index(position) {
-            BoardSpace::Normal(piece) | BoardSpace::Goal { goal_for: _, piece } => Ok(*piece),
-            _ => unreachable!("Should have been checked with check_valid_position"),
+    /// Applies deltas from [`GameBoard::diff`], `None` if one does not match the board.
+    ///
+    /// The board is left partly changed when a delta does not match.
+    pub fn apply_deltas(&mut self, deltas: &[BoardDelta]) -> Option<()> {
+        let take = |board: &mut GameBoard, position: Coordinate, piece: PieceInstance| {
+            let square = board.piece_mut(position).ok()?;
+            if *square != Some(piece) {
+                return None;
+            }
+            *square = None;
+            Some(())
+        };
+        // Remove first so moves onto squares vacated by other deltas succeed
+        for delta in deltas {
+            match *delta {
+                BoardDelta::Disappeared { position, piece } => take(self, position, piece)?,
+                BoardDelta::Moved { from, piece, .. } => take(self, from, piece)?,
+                BoardDelta::Appeared { .. } => {}
+            }
         }
-    }
-    pub fn piece_mut(
-        &mut self,
-        position: impl Position + Copy,
-    ) -> GameBoardResult<&mut Option<Piece>> {
-        self.check_valid_position(position)?;
-        match self.board.index_mut(position) {
-            BoardSpace::Normal(piece) | BoardSpace::Goal { goal_for: _, piece } => Ok(piece),
-            _ => unreachable!("Should have been checked with check_valid_position"),
+        for delta in deltas {
+            if let BoardDelta::Appeared {
+                position: to,
+                piece,
+            }
+            | BoardDelta::Moved { to, piece, .. } = *delta
+            {
+                let square = self.piece_mut(to).ok()?;
+                if square.is_some() {
+                    return None;
+                }
+                *square = Some(piece);
+            }
         }
+        Some(())
     }
 
-    pub fn apply_action(
-        &self,
-        action: &Action,
-        capture_callback: impl Fn(Coordinate, Piece),
-    ) -> Result<GameBoard, ActionError> {
-        self.is_valid_action(action)?;
-        let mut board = self.clone();
-        let piece_start = board.piece_mut(action.start_pos).unwrap();
-        let piece = piece_start.unwrap();
-        *piece_start = None;
-
-        match &action.action_type {
-            ActionType::Move(direction) => {
-                *board
-                    .piece_mut(direction.offset() + action.start_pos)
-                    .unwrap() = Some(piece);
+    /// The pieces packed into as few bits per valid square as the piece kinds allow.
+    ///
+    /// A header byte holds the bits per square, 2 for a single piece kind, 3 for two, 4 for up to
+    /// seven. The layout of the board is not stored, see [`GameBoard::decode`].
+    pub fn encode(&self) -> Vec<u8> {
+        let symbols: Vec<usize> = self
+            .board
+            .values
+            .iter()
+            .filter_map(|space| {
+                space.piece().map(|piece| {
+                    piece.map_or(0, |piece| {
+                        1 + 2 * piece.definition_index() + (piece.color() == Color::Blue) as usize
+                    })
+                })
+            })
+            .collect();
+        let kinds = symbols
+            .iter()
+            .map(|symbol| symbol.div_ceil(2))
+            .chain([self.piece_rules.len(), 1])
+            .max()
+            .unwrap_or(1);
+        let bits = (usize::BITS - (2 * kinds).leading_zeros()) as usize;
+
+        let mut out = vec![0; 1 + (symbols.len() * bits).div_ceil(8)];
+        out[0] = bits as u8;
+        for (index, symbol) in symbols.into_iter().enumerate() {
+            for bit in 0..bits {
+                let position = index * bits + bit;
+                out[1 + position / 8] |= (((symbol >> bit) & 1) as u8) << (position % 8);
             }
-            ActionType::Jump(directions) => {
-                let mut position = action.start_pos;
-                for direction in directions {
-                    let middle_pos = direction.offset() + position;
-                    let middle_piece = board.piece_mut(middle_pos).unwrap();
-                    if middle_piece.unwrap().color() != piece.color() {
-                        capture_callback(middle_pos, middle_piece.unwrap());
-                        *middle_piece = None;
-                    }
-
-                    position = direction.offset() * 2 + position;
+        }
+        out
+    }
+    /// Reads [`GameBoard::encode`]d pieces onto a copy of `layout`, which supplies the squares
+    /// and piece rules.
+    ///
+    /// Pieces without rules in `layout` are refused with [`BoardDecodeError::UnknownPiece`].
+    pub fn decode(layout: &GameBoard, bytes: &[u8]) -> BoardDecodeResult<GameBoard> {
+        let (&bits, body) = bytes.split_first().ok_or(BoardDecodeError::Truncated)?;
+        if !(2..usize::BITS as u8).contains(&bits) {
+            return Err(BoardDecodeError::InvalidBitsPerSquare(bits));
+        }
+        let bits = bits as usize;
+        let kinds = layout.piece_rules.len();
+        let mut board = layout.clone();
+        board.invalidate_pieces();
+        let mut squares: Vec<_> = board
+            .board
+            .values
+            .iter_mut()
+            .filter_map(BoardSpace::piece_mut)
+            .collect();
+        let length = (squares.len() * bits).div_ceil(8);
+        match body.len() {
+            len if len < length => return Err(BoardDecodeError::Truncated),
+            len if len > length => return Err(BoardDecodeError::TrailingBytes(len - length)),
+            _ => {}
+        }
+        for (index, piece) in squares.iter_mut().enumerate() {
+            let symbol = (0..bits).fold(0, |symbol, bit| {
+                let position = index * bits + bit;
+                symbol | ((body[position / 8] >> (position % 8)) as usize & 1) << bit
+            });
+            **piece = match symbol.checked_sub(1) {
+                Some(symbol) if symbol / 2 >= kinds => {
+                    return Err(BoardDecodeError::UnknownPiece(symbol / 2));
                 }
-                *board.piece_mut(position).unwrap() = Some(piece);
-            }
+                Some(symbol) => {
+                    let color = if symbol % 2 == 0 {
+                        Color::Red
+                    } else {
+                        Color::Blue
+                    };
+                    Some(PieceInstance::new(symbol / 2, color))
+                }
+                None => None,
+            };
         }
-
         Ok(board)
     }
-    pub fn is_valid_action(&self, action: &Action) -> Result<(), ActionError> {
-        let piece = match self.piece(action.start_pos) {
-            Ok(piece) => piece,
-            Err(error) => {
-                return match error {
-                    GameBoardError::InvalidPosition => Err(ActionError::InvalidStartPosition),
-                };
-            }
-        };
-        if piece.is_none() {
-            return Err(ActionError::NoPieceAtStart);
-        }
-        let piece = piece.unwrap();
 
-        match &action.action_type {
-            ActionType::Move(direction) => self.is_valid_move(action.start_pos, *direction)?,
-            ActionType::Jump(directions) => {
-                self.is_valid_jump(piece, action.start_pos, directions)?
-            }
-        }
+    /// Drops the piece lists, see [`GameBoard::index_pieces`].
+    pub fn piece_mut(
+        &mut self,
+        position: impl Position + Copy,
+    ) -> GameBoardResult<&mut Option<PieceInstance>> {
+        self.pieces = None;
+        self.board
+            .get_mut(position)
+            .and_then(BoardSpace::piece_mut)
+            .ok_or(GameBoardError::InvalidPosition)
+    }
 
+    /// The board after `action` and what happened, see [`ActionOutcome`].
+    pub fn apply_action(&self, action: &Action) -> Result<(GameBoard, ActionOutcome), ActionError> {
+        let validated = self.validate(action)?;
+        self.apply_validated(validated)
+    }
+}
+impl Board for GameBoard {
+    fn rows(&self) -> usize {
+        self.board.rows
+    }
+    fn columns(&self) -> usize {
+        self.board.columns
+    }
+    fn space(&self, position: impl Position) -> Option<&BoardSpace> {
+        self.board.get(position)
+    }
+    /// Keeps the piece lists up to date.
+    fn set_space(&mut self, position: impl Position, space: BoardSpace) -> GameBoardResult<()> {
+        let position = Coordinate::new(position.row() as i16, position.column() as i16);
+        let square = self
+            .board
+            .get_mut(position)
+            .ok_or(GameBoardError::InvalidPosition)?;
+        let old = core::mem::replace(square, space);
+        self.update_pieces(
+            position,
+            old.piece().copied().flatten(),
+            space.piece().copied().flatten(),
+        );
         Ok(())
     }
-    pub fn is_valid_move(
-        &self,
-        start_pos: Coordinate,
-        direction: Direction,
-    ) -> Result<(), ActionError> {
-        let new_pos = direction.offset() + start_pos;
-        match self.piece(new_pos) {
-            Ok(piece) => {
-                if let Some(piece) = piece {
-                    Err(PieceOnMove(piece))
-                } else {
-                    Ok(())
-                }
-            }
-            Err(error) => match error {
-                GameBoardError::InvalidPosition => Err(ActionError::MoveOffBoard),
-            },
+    /// From the piece lists when indexed.
+    fn pieces_of_color(&self, color: Color) -> Vec<(Coordinate, PieceInstance)> {
+        match &self.pieces {
+            Some(lists) => lists[color.index()].clone(),
+            None => self
+                .pieces()
+                .filter(|(_, piece)| piece.color() == color)
+                .collect(),
         }
     }
-    pub fn is_valid_jump(
-        &self,
-        piece: Piece,
-        start_pos: Coordinate,
-        directions: &[Direction],
-    ) -> Result<(), ActionError> {
-        if directions.is_empty() {
-            return Err(ActionError::EmptyJump);
-        }
-        if piece.size().is_small() && directions.len() > 1 {
-            return Err(ActionError::MultipleJumpsForSmall);
-        }
+    /// From the piece lists when indexed.
+    fn pieces_of_definition(&self, definition_index: usize) -> Vec<(Coordinate, PieceInstance)> {
+        let mut out: Vec<_> = match &self.pieces {
+            Some(lists) => lists.iter().flatten().copied().collect(),
+            None => self.pieces().collect(),
+        };
+        out.retain(|(_, piece)| piece.definition_index() == definition_index);
+        out.sort_by_key(|&(position, _)| square_order(position));
+        out
+    }
+    fn rules(&self, piece: PieceInstance) -> PieceRules {
+        let index = piece.definition_index();
+        self.piece_rules
+            .get(index)
+            .or_else(|| PieceRules::STANDARD.get(index))
+            .copied()
+            .unwrap_or(PieceRules::STANDARD[1])
+    }
+}
 
-        let mut prev_positions = Vec::with_capacity(directions.len());
-        prev_positions.push(start_pos);
-        for direction in directions {
-            let middle_pos = direction.offset() + *prev_positions.last().unwrap();
-            let new_pos = direction.offset() + middle_pos;
-            if let Some(piece) = match self.piece(new_pos) {
-                Ok(piece) => piece,
-                Err(error) => {
-                    return match error {
-                        GameBoardError::InvalidPosition => Err(ActionError::JumpOffBoard),
-                    };
-                }
-            } {
-                return Err(ActionError::PieceOnJump(piece));
-            }
-            if prev_positions.contains(&new_pos) {
-                return Err(ActionError::JumpedBackToPrevPosition);
-            }
-            prev_positions.push(new_pos);
+/// The parts of a [`PieceDefinition`] the board enforces, `Copy` so boards stay cheap to clone.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PieceRules {
+    pub goal_move_rule: GoalMovementRule,
+    pub capture_timing_rule: CaptureTimingRule,
+    pub jump_rule: JumpRule,
+    pub jump_limit: JumpLimit,
+    pub chain_rule: ChainContinuationRule,
+    /// What jumping over a piece captures, `None` if it never captures.
+    pub jump_over_target: Option<CaptureTarget>,
+    /// Whether the piece must capture when it can, see [`legal_actions`].
+    pub capture_requirement: CaptureRequirement,
+}
+impl PieceRules {
+    /// The rules of [`standard_pieces`](crate::ruleset::standard::standard_pieces).
+    pub const STANDARD: [PieceRules; 2] = [
+        PieceRules {
+            goal_move_rule: GoalMovementRule::Free,
+            capture_timing_rule: CaptureTimingRule::AfterTurn,
+            jump_rule: JumpRule::NoSameStart,
+            jump_limit: JumpLimit::Unlimited {
+                directions: Directions::ALL,
+            },
+            chain_rule: ChainContinuationRule::MayStop,
+            jump_over_target: Some(CaptureTarget::EnemyOnly),
+            capture_requirement: CaptureRequirement::Forced(10),
+        },
+        PieceRules {
+            goal_move_rule: GoalMovementRule::Free,
+            capture_timing_rule: CaptureTimingRule::AfterTurn,
+            jump_rule: JumpRule::NoSameStart,
+            jump_limit: JumpLimit::Limited {
+                limit: 1,
+                directions: Directions::ALL,
+            },
+            chain_rule: ChainContinuationRule::MayStop,
+            jump_over_target: Some(CaptureTarget::EnemyOnly),
+            capture_requirement: CaptureRequirement::Forced(10),
+        },
+    ];
+}
+impl From<&PieceDefinition> for PieceRules {
+    fn from(from: &PieceDefinition) -> Self {
+        Self {
+            goal_move_rule: from.goal_move_rule,
+            capture_timing_rule: from.capture_timing_rule,
+            jump_rule: from.jump_rule,
+            jump_limit: from.jump_limit,
+            chain_rule: from.chain_rule,
+            jump_over_target: from.capture_rules.get(&CaptureRule::JumpOver).copied(),
+            capture_requirement: from.capture_requirement,
+        }
+    }
+}
 
-            if self.piece(middle_pos).unwrap().is_none() {
-                return Err(ActionError::NoPieceJumped);
-            }
+/// A jump chain being built one jump at a time, see [`Board::next_jump`].
+#[derive(Clone, Debug)]
+pub struct JumpChain {
+    piece: PieceInstance,
+    start: Coordinate,
+    steps: Vec<JumpStep>,
+}
+impl JumpChain {
+    pub fn new(piece: PieceInstance, start: Coordinate) -> Self {
+        Self {
+            piece,
+            start,
+            steps: Vec::new(),
         }
-        Ok(())
+    }
+    pub fn piece(&self) -> PieceInstance {
+        self.piece
+    }
+    pub fn start(&self) -> Coordinate {
+        self.start
+    }
+    /// Where the jumping piece currently is.
+    pub fn position(&self) -> Coordinate {
+        self.steps.last().map_or(self.start, |step| step.landing)
+    }
+    pub fn steps(&self) -> &[JumpStep] {
+        &self.steps
+    }
+    pub fn directions(&self) -> JumpDirections {
+        self.steps.iter().map(|step| step.direction).collect()
+    }
+    /// Whether the piece at `position` was captured earlier in the chain.
+    pub fn captured(&self, position: Coordinate) -> bool {
+        self.steps
+            .iter()
+            .any(|step| step.captures && step.middle == position)
+    }
+    pub fn push(&mut self, step: JumpStep) {
+        self.steps.push(step);
+    }
+    pub fn pop(&mut self) -> Option<JumpStep> {
+        self.steps.pop()
     }
 }
+/// A single jump of a [`JumpChain`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct JumpStep {
+    pub direction: Direction,
+    /// The position jumped over.
+    pub middle: Coordinate,
+    /// Where the piece ends this jump, the other portal if it landed on one.
+    pub landing: Coordinate,
+    /// Whether the jumped piece is captured by this jump.
+    pub captures: bool,
+}
 
-pub fn index_to_position<T: Element>(matrix: &Conventional<T>, index: usize) -> impl Position {
+pub fn index_to_position<T>(matrix: &Grid<T>, index: usize) -> impl Position {
     (index % matrix.rows, index / matrix.rows)
 }
+pub fn index_to_coordinate<T>(matrix: &Grid<T>, index: usize) -> Coordinate {
+    Coordinate::new((index % matrix.rows) as i16, (index / matrix.rows) as i16)
+}
+/// Sorts squares in the order of [`Grid::values`], column by column.
+fn square_order(position: Coordinate) -> (i16, i16) {
+    (position.column, position.row)
+}
 
 pub type GameBoardResult<T> = Result<T, GameBoardError>;
 #[derive(Copy, Clone, Debug)]
@@ -222,91 +545,602 @@ pub enum GameBoardError {
     InvalidPosition,
 }
 
+pub type BoardDecodeResult<T> = Result<T, BoardDecodeError>;
+#[derive(Copy, Clone, Debug)]
+pub enum BoardDecodeError {
+    /// There were fewer bytes than the layout has squares.
+    Truncated,
+    InvalidBitsPerSquare(u8),
+    /// There were more bytes than the layout has squares.
+    TrailingBytes(usize),
+    /// A square holds this piece definition index, the layout has no rules for it.
+    UnknownPiece(usize),
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BoardSpace {
     Invalid,
-    Normal(Option<Piece>),
+    Normal(Option<PieceInstance>),
     Goal {
         goal_for: Color,
-        piece: Option<Piece>,
+        piece: Option<PieceInstance>,
+    },
+    /// Holds no pieces but can be jumped over, see [`Space::Blocked`].
+    Blocked,
+    /// Ends jump chains landing on it, see [`Space::Sticky`].
+    Sticky(Option<PieceInstance>),
+    /// Moves pieces landing on it to the other portal of `pair`, see [`Space::Portal`].
+    Portal {
+        pair: u8,
+        piece: Option<PieceInstance>,
     },
 }
-impl Element for BoardSpace {
-    fn zero() -> Self {
+impl BoardSpace {
+    /// The piece on this space, `None` if it cannot hold one.
+    pub fn piece(&self) -> Option<&Option<PieceInstance>> {
+        match self {
+            BoardSpace::Normal(piece)
+            | BoardSpace::Goal { piece, .. }
+            | BoardSpace::Sticky(piece)
+            | BoardSpace::Portal { piece, .. } => Some(piece),
+            BoardSpace::Invalid | BoardSpace::Blocked => None,
+        }
+    }
+    pub fn piece_mut(&mut self) -> Option<&mut Option<PieceInstance>> {
+        match self {
+            BoardSpace::Normal(piece)
+            | BoardSpace::Goal { piece, .. }
+            | BoardSpace::Sticky(piece)
+            | BoardSpace::Portal { piece, .. } => Some(piece),
+            BoardSpace::Invalid | BoardSpace::Blocked => None,
+        }
+    }
+}
+impl Default for BoardSpace {
+    fn default() -> Self {
         Self::Normal(None)
     }
 }
+/// A change between two boards, see [`GameBoard::diff`].
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub enum Piece {
-    SmallRed,
-    LargeRed,
-    SmallBlue,
-    LargeBlue,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoardDelta {
+    Appeared {
+        position: Coordinate,
+        piece: PieceInstance,
+    },
+    Disappeared {
+        position: Coordinate,
+        piece: PieceInstance,
+    },
+    Moved {
+        from: Coordinate,
+        to: Coordinate,
+        piece: PieceInstance,
+    },
 }
-impl Piece {
-    pub fn color(&self) -> Color {
-        match self {
-            Piece::SmallRed => Color::Red,
-            Piece::LargeRed => Color::Red,
-            Piece::SmallBlue => Color::Blue,
-            Piece::LargeBlue => Color::Blue,
+
+/// Piece definitions a ruleset may have, as many as a [`PieceInstance`] can index.
+pub const MAX_PIECE_DEFINITIONS: usize = u8::MAX as usize + 1;
+
+/// A piece on the board, one of the ruleset's [`PieceDefinition`]s owned by a color.
+///
+/// Two bytes, so a [`BoardSpace`] holding one stays small.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct PieceInstance {
+    definition_index: u8,
+    color: Color,
+}
+impl PieceInstance {
+    /// Panics if `definition_index` is not below [`MAX_PIECE_DEFINITIONS`], which verified
+    /// rulesets guarantee.
+    pub fn new(definition_index: usize, color: Color) -> Self {
+        Self {
+            definition_index: u8::try_from(definition_index)
+                .expect("Rulesets have at most MAX_PIECE_DEFINITIONS pieces"),
+            color,
         }
     }
 
-    pub fn size(&self) -> PieceSize {
-        match self {
-            Piece::SmallRed => PieceSize::Small,
-            Piece::LargeRed => PieceSize::Large,
-            Piece::SmallBlue => PieceSize::Small,
-            Piece::LargeBlue => PieceSize::Large,
-        }
+    /// Index of this piece's definition in [`Ruleset::pieces`](crate::ruleset::Ruleset::pieces).
+    pub fn definition_index(&self) -> usize {
+        self.definition_index as usize
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
     }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, IntoEnumIterator)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum Color {
     Red,
     Blue,
 }
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub enum PieceSize {
-    Small,
-    Large,
-}
-impl PieceSize {
-    pub fn is_small(&self) -> bool {
-        matches!(self, PieceSize::Small)
+impl Color {
+    pub fn opponent(&self) -> Self {
+        match self {
+            Color::Red => Color::Blue,
+            Color::Blue => Color::Red,
+        }
     }
-
-    pub fn is_large(&self) -> bool {
-        matches!(self, PieceSize::Large)
+    /// 0 for Red and 1 for Blue.
+    pub fn index(&self) -> usize {
+        match self {
+            Color::Red => 0,
+            Color::Blue => 1,
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use std::ops::Index;
-
-    use matrix::format::Conventional;
-    use matrix::matrix;
+    use core::ops::Index;
 
-    use crate::game_board::index_to_position;
+    use crate::action::{Action, ActionError, ActionType};
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::direction::Directions;
+    use crate::engine::Engine;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::game_board::{
+        index_to_position, BoardDecodeError, BoardDelta, BoardSpace, Color, GameBoard,
+        PieceInstance, PieceRules,
+    };
+    use crate::grid::Grid;
+    use crate::movegen::{legal_actions, MoveCache};
+    use crate::ruleset::board_type::space::Space;
+    use crate::ruleset::board_type::BoardType;
+    use crate::ruleset::piece_definition::{
+        CaptureTarget, CaptureTimingRule, GoalMovementRule, JumpLimit, JumpRule,
+    };
+    use crate::ruleset::standard::standard_pieces;
+    use crate::testing::small_ruleset;
 
     #[test]
     fn index_position_test() {
-        let matrix = Conventional::from_vec(
-            (4, 4),
-            matrix![
-                 1,  2,  3,  4;
-                 5,  6,  7,  8;
-                 9, 10, 11, 12;
-                13, 14, 15, 16;
-            ],
-        );
+        let matrix = Grid::from_vec((4, 4), (1..=16).collect());
 
         for (index, val) in matrix.values.iter().enumerate() {
             assert_eq!(val, matrix.index(index_to_position(&matrix, index)));
         }
     }
+
+    fn rules(goal_move_rule: GoalMovementRule) -> PieceRules {
+        PieceRules {
+            goal_move_rule,
+            ..PieceRules::STANDARD[1]
+        }
+    }
+
+    #[test]
+    fn goal_movement_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        board.board[Coordinate::new(0, 2)] = BoardSpace::Goal {
+            goal_for: Color::Red,
+            piece: None,
+        };
+        let goal = Coordinate::new(0, 2);
+        let near_goal = Coordinate::new(1, 2);
+        let enter = Action {
+            start_pos: near_goal,
+            action_type: ActionType::Move(Direction::North),
+        };
+        let leave = Action {
+            start_pos: goal,
+            action_type: ActionType::Move(Direction::South),
+        };
+
+        board.piece_rules = vec![rules(GoalMovementRule::NoOwnGoal); 2];
+        *board.piece_mut(near_goal).unwrap() = Some(PieceInstance::new(1, Color::Red));
+        assert!(matches!(
+            board.is_valid_action(&enter),
+            Err(ActionError::EnteredOwnGoal)
+        ));
+        *board.piece_mut(near_goal).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        let (board_after, outcome) = board.apply_action(&enter).unwrap();
+        assert_eq!(
+            outcome.goal_entries,
+            vec![(
+                board.destination(&enter).unwrap(),
+                PieceInstance::new(1, Color::Blue)
+            )]
+        );
+
+        let mut locked = board_after.clone();
+        locked.piece_rules = vec![rules(GoalMovementRule::Locked); 2];
+        assert!(matches!(
+            locked.is_valid_action(&leave),
+            Err(ActionError::GoalLocked)
+        ));
+        let mut only_to_goal = board_after.clone();
+        only_to_goal.piece_rules = vec![rules(GoalMovementRule::OnlyToGoal); 2];
+        assert!(matches!(
+            only_to_goal.is_valid_action(&leave),
+            Err(ActionError::LeftGoal)
+        ));
+        assert!(board_after.is_valid_action(&leave).is_ok());
+    }
+
+    #[test]
+    fn capture_timing_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        let start = Coordinate::new(2, 1);
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 2)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(3, 3)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        board.piece_rules = vec![
+            PieceRules {
+                capture_timing_rule: CaptureTimingRule::AfterJump,
+                ..PieceRules::STANDARD[0]
+            };
+            2
+        ];
+        // Jumps the piece at (3, 2) twice
+        let action = Action {
+            start_pos: start,
+            action_type: ActionType::Jump(
+                [Direction::SouthEast, Direction::North, Direction::SouthWest].into(),
+            ),
+        };
+        assert!(matches!(
+            board.is_valid_action(&action),
+            Err(ActionError::NoPieceJumped)
+        ));
+
+        board.piece_rules = vec![
+            PieceRules {
+                capture_timing_rule: CaptureTimingRule::AfterTurn,
+                ..PieceRules::STANDARD[0]
+            };
+            2
+        ];
+        let (applied, outcome) = board.apply_action(&action).unwrap();
+        assert_eq!(
+            outcome
+                .captures
+                .iter()
+                .map(|(position, _)| *position)
+                .collect::<Vec<_>>(),
+            vec![Coordinate::new(3, 2), Coordinate::new(3, 3)]
+        );
+        assert_eq!(applied.pieces_of_color(Color::Blue).len(), 0);
+        assert_eq!(
+            applied.piece(Coordinate::new(4, 1)).unwrap(),
+            Some(PieceInstance::new(0, Color::Red))
+        );
+    }
+
+    #[test]
+    fn jump_rules_test() {
+        use Direction::{East, North, South, SouthEast, West};
+
+        for (index, definition) in standard_pieces().iter().enumerate() {
+            assert_eq!(PieceRules::from(definition), PieceRules::STANDARD[index]);
+        }
+
+        let mut board = GameBoard::new((5, 5), &[2]);
+        let start = Coordinate::new(5, 1);
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        for &(row, column) in [(4, 1), (2, 1), (1, 2), (2, 3), (3, 2)].iter() {
+            *board.piece_mut(Coordinate::new(row, column)).unwrap() =
+                Some(PieceInstance::new(1, Color::Red));
+        }
+        let jump = |directions: &[Direction]| Action {
+            start_pos: start,
+            action_type: ActionType::Jump(directions.into()),
+        };
+        let with_rules = |jump_rule, jump_limit| {
+            let mut out = board.clone();
+            out.piece_rules = vec![
+                PieceRules {
+                    jump_rule,
+                    jump_limit,
+                    ..PieceRules::STANDARD[0]
+                };
+                2
+            ];
+            out
+        };
+        let unlimited = JumpLimit::Unlimited {
+            directions: Directions::ALL,
+        };
+        // Lands on (3, 1) twice
+        let revisit = jump(&[North, North, East, South, West]);
+        assert!(with_rules(JumpRule::NoSameStart, unlimited)
+            .is_valid_action(&revisit)
+            .is_ok());
+        assert!(matches!(
+            with_rules(JumpRule::NoBacktracking, unlimited).is_valid_action(&revisit),
+            Err(ActionError::JumpedBackToPrevPosition)
+        ));
+        assert!(matches!(
+            with_rules(JumpRule::Open, unlimited)
+                .is_valid_action(&jump(&[North, North, East, South, West, North, East])),
+            Err(ActionError::RepeatedJump)
+        ));
+
+        let limited = |limit, directions| JumpLimit::Limited { limit, directions };
+        assert!(matches!(
+            with_rules(JumpRule::Open, limited(1, Directions::ALL))
+                .is_valid_action(&jump(&[North, North])),
+            Err(ActionError::JumpLimitExceeded(1))
+        ));
+        assert!(matches!(
+            with_rules(JumpRule::Open, limited(2, Directions::CARDINAL))
+                .is_valid_action(&jump(&[SouthEast])),
+            Err(ActionError::JumpDirectionNotAllowed(SouthEast))
+        ));
+        assert!(matches!(
+            with_rules(JumpRule::Open, JumpLimit::Cannot).is_valid_action(&jump(&[North])),
+            Err(ActionError::CannotJump)
+        ));
+    }
+
+    #[test]
+    fn capture_target_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        let start = Coordinate::new(3, 0);
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Red));
+        *board.piece_mut(Coordinate::new(2, 2)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        let action = Action {
+            start_pos: start,
+            action_type: ActionType::Jump([Direction::East, Direction::North].into()),
+        };
+        let captures = |target| {
+            let mut board = board.clone();
+            board.piece_rules = vec![
+                PieceRules {
+                    jump_over_target: target,
+                    ..PieceRules::STANDARD[0]
+                };
+                2
+            ];
+            let (_, outcome) = board.apply_action(&action).unwrap();
+            outcome
+                .captures
+                .iter()
+                .map(|(position, _)| *position)
+                .collect::<Vec<_>>()
+        };
+        let own = Coordinate::new(3, 1);
+        let enemy = Coordinate::new(2, 2);
+        assert_eq!(captures(Some(CaptureTarget::EnemyOnly)), vec![enemy]);
+        assert_eq!(captures(Some(CaptureTarget::OwnOnly)), vec![own]);
+        assert_eq!(captures(Some(CaptureTarget::All)), vec![own, enemy]);
+        assert_eq!(captures(None), vec![]);
+    }
+
+    #[test]
+    fn attack_map_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        let start = Coordinate::new(3, 0);
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(2, 2)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(5, 4)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+
+        let attacked = board.attack_map(Color::Red);
+        let marked: Vec<_> = (0..7)
+            .flat_map(|row| (0..5).map(move |column| Coordinate::new(row, column)))
+            .filter(|&position| attacked[position])
+            .collect();
+        // The second capture needs the chain through the first
+        assert_eq!(marked, vec![Coordinate::new(2, 2), Coordinate::new(3, 1)]);
+        assert!(board.is_threatened(Coordinate::new(2, 2), Color::Red));
+        assert!(!board.is_threatened(Coordinate::new(5, 4), Color::Red));
+        assert!(!board.is_threatened(start, Color::Blue));
+        assert!(!board.is_threatened(Coordinate::new(-1, 0), Color::Red));
+    }
+
+    #[test]
+    fn diff_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        let start = Coordinate::new(3, 0);
+        let red = PieceInstance::new(0, Color::Red);
+        let blue = PieceInstance::new(1, Color::Blue);
+        *board.piece_mut(start).unwrap() = Some(red);
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(blue);
+        assert_eq!(board.diff(&board), vec![]);
+
+        let action = Action {
+            start_pos: start,
+            action_type: ActionType::Jump([Direction::East].into()),
+        };
+        let (mut after, _) = board.apply_action(&action).unwrap();
+        *after.piece_mut(Coordinate::new(5, 4)).unwrap() = Some(blue);
+        assert_eq!(
+            board.diff(&after),
+            vec![
+                BoardDelta::Moved {
+                    from: start,
+                    to: Coordinate::new(3, 2),
+                    piece: red,
+                },
+                BoardDelta::Moved {
+                    from: Coordinate::new(3, 1),
+                    to: Coordinate::new(5, 4),
+                    piece: blue,
+                },
+            ]
+        );
+
+        *after.piece_mut(Coordinate::new(5, 4)).unwrap() = Some(PieceInstance::new(0, Color::Blue));
+        assert_eq!(
+            board.diff(&after)[1..],
+            [
+                BoardDelta::Disappeared {
+                    position: Coordinate::new(3, 1),
+                    piece: blue,
+                },
+                BoardDelta::Appeared {
+                    position: Coordinate::new(5, 4),
+                    piece: PieceInstance::new(0, Color::Blue),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn encode_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        board.set_piece_rules(&standard_pieces());
+        let layout = board.clone();
+        *board.piece_mut(Coordinate::new(3, 0)).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(Coordinate::new(6, 2)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(0, 2)).unwrap() = Some(PieceInstance::new(1, Color::Red));
+
+        // 27 squares at 3 bits
+        let bytes = board.encode();
+        assert_eq!(bytes.len(), 1 + 11);
+        assert_eq!(bytes[0], 3);
+        let decoded = GameBoard::decode(&layout, &bytes).unwrap();
+        assert_eq!(decoded.board, board.board);
+        assert_eq!(decoded.encode(), bytes);
+
+        assert!(matches!(
+            GameBoard::decode(&layout, &bytes[..bytes.len() - 1]),
+            Err(BoardDecodeError::Truncated)
+        ));
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(matches!(
+            GameBoard::decode(&layout, &extended),
+            Err(BoardDecodeError::TrailingBytes(1))
+        ));
+
+        // Layouts only take the pieces they have rules for
+        let mut unknown = board.clone();
+        unknown.piece_rules.push(unknown.piece_rules[0]);
+        *unknown.piece_mut(Coordinate::new(3, 1)).unwrap() =
+            Some(PieceInstance::new(2, Color::Red));
+        assert!(matches!(
+            GameBoard::decode(&layout, &unknown.encode()),
+            Err(BoardDecodeError::UnknownPiece(2))
+        ));
+
+        // A single piece kind needs 2 bits
+        let mut single = GameBoard::new((5, 5), &[2]);
+        single.set_piece_rules(&standard_pieces()[..1]);
+        let single_layout = single.clone();
+        *single.piece_mut(Coordinate::new(3, 0)).unwrap() =
+            Some(PieceInstance::new(0, Color::Blue));
+        let bytes = single.encode();
+        assert_eq!(bytes.len(), 1 + 7);
+        assert_eq!(
+            GameBoard::decode(&single_layout, &bytes).unwrap().board,
+            single.board
+        );
+    }
+
+    #[test]
+    fn terrain_test() {
+        let mut spaces: Grid<Space> = Grid::new((5, 5));
+        spaces[(2, 2)] = Space::Blocked;
+        spaces[(0, 2)] = Space::Sticky;
+        spaces[(4, 0)] = Space::Portal(7);
+        spaces[(0, 4)] = Space::Portal(7);
+        let mut board = GameBoard::from_board_type(&BoardType::Custom(spaces));
+        let jump = |start: Coordinate, directions: &[Direction]| Action {
+            start_pos: start,
+            action_type: ActionType::Jump(directions.into()),
+        };
+        let step = |start: Coordinate, direction: Direction| Action {
+            start_pos: start,
+            action_type: ActionType::Move(direction),
+        };
+        let red = Coordinate::new(2, 1);
+        *board.piece_mut(red).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        assert!(!board.is_valid_position(Coordinate::new(2, 2)));
+
+        // Blocked spaces can be jumped over but not entered
+        assert!(matches!(
+            board.is_valid_action(&step(red, Direction::East)),
+            Err(ActionError::Blocked)
+        ));
+        let (over, outcome) = board.apply_action(&jump(red, &[Direction::East])).unwrap();
+        assert!(outcome.captures.is_empty(), "Nothing to capture");
+        assert_eq!(
+            over.piece(Coordinate::new(2, 3)).unwrap(),
+            Some(PieceInstance::new(0, Color::Red))
+        );
+        *board.piece_mut(Coordinate::new(2, 0)).unwrap() = Some(PieceInstance::new(0, Color::Blue));
+        assert!(matches!(
+            board.is_valid_action(&jump(Coordinate::new(2, 0), &[Direction::East])),
+            Err(ActionError::Blocked)
+        ));
+        *board.piece_mut(Coordinate::new(2, 0)).unwrap() = None;
+
+        // Sticky spaces end the chain
+        *board.piece_mut(Coordinate::new(1, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(1, 3)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        let sticky = jump(Coordinate::new(2, 0), &[Direction::NorthEast]);
+        *board.piece_mut(red).unwrap() = None;
+        *board.piece_mut(Coordinate::new(2, 0)).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        assert!(board.is_valid_action(&sticky).is_ok());
+        assert!(matches!(
+            board.is_valid_action(&jump(
+                Coordinate::new(2, 0),
+                &[Direction::NorthEast, Direction::SouthEast]
+            )),
+            Err(ActionError::StuckOnSticky)
+        ));
+
+        // Portals move landing pieces to their pair
+        let portal = step(Coordinate::new(3, 1), Direction::SouthWest);
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Red));
+        assert_eq!(board.destination(&portal), Some(Coordinate::new(0, 4)));
+        let mut cache = MoveCache::new(&board);
+        let (after, _) = board.apply_action(&portal).unwrap();
+        assert_eq!(after.piece(Coordinate::new(4, 0)).unwrap(), None);
+        assert_eq!(
+            after.piece(Coordinate::new(0, 4)).unwrap(),
+            Some(PieceInstance::new(1, Color::Red))
+        );
+        cache.update(&after, &[Coordinate::new(3, 1), Coordinate::new(0, 4)]);
+        assert_eq!(
+            cache.legal_actions(&after, Color::Red),
+            legal_actions(&after, Color::Red)
+        );
+        // A portal whose pair is occupied cannot be entered
+        *board.piece_mut(Coordinate::new(0, 4)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        assert!(matches!(
+            board.is_valid_action(&portal),
+            Err(ActionError::PieceOnMove(_))
+        ));
+    }
+
+    #[test]
+    fn piece_lists_test() {
+        let mut game = Game::new(small_ruleset(5)).unwrap();
+        let mut policy = RandomPolicy::new(6);
+        while game.history().len() < 40 && !game.is_finished() {
+            let action = policy.choose_action(&game).unwrap();
+            game.apply(&action).unwrap();
+            let board = game.board();
+            assert!(board.is_indexed());
+            let mut scanned = board.clone();
+            scanned.invalidate_pieces();
+            for color in [Color::Red, Color::Blue] {
+                assert_eq!(board.pieces_of_color(color), scanned.pieces_of_color(color));
+            }
+            for definition in 0..2 {
+                assert_eq!(
+                    board.pieces_of_definition(definition),
+                    scanned.pieces_of_definition(definition)
+                );
+            }
+        }
+        let mut board = game.board().clone();
+        *board.piece_mut(Coordinate::new(1, 0)).unwrap() = None;
+        assert!(!board.is_indexed());
+    }
 }
@@ -0,0 +1,446 @@
+//! The rules of movement shared by every board representation, see [`Board`].
+
+use alloc::vec::Vec;
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::action::{Action, ActionError, ActionOutcome, ActionType};
+use crate::coordinate::Coordinate;
+use crate::direction::{Direction, Directions};
+use crate::game_board::destinations::DestinationSet;
+use crate::game_board::{
+    BoardSpace, Color, GameBoardError, GameBoardResult, JumpChain, JumpStep, PieceInstance,
+    PieceRules,
+};
+use crate::grid::Position;
+use crate::movegen::{actions_from, JumpChains};
+use crate::ruleset::board_type::nearest_goal_distance;
+use crate::ruleset::piece_definition::{
+    CaptureTarget, CaptureTimingRule, ChainContinuationRule, GoalMovementRule, JumpLimit, JumpRule,
+};
+
+/// Storage for the spaces of a board, the rules are provided on top of it.
+///
+/// Implemented by the heap backed [`GameBoard`](crate::game_board::GameBoard) and the inline
+/// [`FixedGameBoard`](crate::game_board::fixed::FixedGameBoard), move generation in
+/// [`movegen`](crate::movegen) works on either.
+pub trait Board {
+    /// Rows, goal rows included.
+    fn rows(&self) -> usize;
+    fn columns(&self) -> usize;
+    /// The space at `position`, `None` off the grid.
+    fn space(&self, position: impl Position) -> Option<&BoardSpace>;
+    /// Replaces the space at `position`, an error off the grid.
+    fn set_space(&mut self, position: impl Position, space: BoardSpace) -> GameBoardResult<()>;
+    /// The rules enforced on `piece`'s actions.
+    fn rules(&self, piece: PieceInstance) -> PieceRules;
+
+    /// Whether `position` can hold a piece.
+    fn is_valid_position(&self, position: impl Position) -> bool {
+        self.space(position)
+            .is_some_and(|space| space.piece().is_some())
+    }
+    /// Whether `position` is a [`BoardSpace::Blocked`] space.
+    fn is_blocked(&self, position: impl Position) -> bool {
+        self.space(position) == Some(&BoardSpace::Blocked)
+    }
+    /// Whether `position` is a [`BoardSpace::Sticky`] space.
+    fn is_sticky(&self, position: impl Position) -> bool {
+        matches!(self.space(position), Some(BoardSpace::Sticky(_)))
+    }
+    /// Where a piece landing on `position` ends up, the other portal of the pair on portals and
+    /// `position` itself everywhere else.
+    fn portal_exit(&self, position: Coordinate) -> Coordinate {
+        if !self.is_valid_position(position) {
+            return position;
+        }
+        let pair = match self.space(position) {
+            Some(BoardSpace::Portal { pair, .. }) => *pair,
+            _ => return position,
+        };
+        self.positions()
+            .find(|&other| {
+                other != position
+                    && matches!(self.space(other), Some(BoardSpace::Portal { pair: other_pair, .. }) if *other_pair == pair)
+            })
+            .unwrap_or(position)
+    }
+    /// Every square of the grid, column by column like [`Grid::values`](crate::grid::Grid::values).
+    fn positions(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        let (rows, columns) = (self.rows() as i16, self.columns() as i16);
+        (0..columns).flat_map(move |column| (0..rows).map(move |row| Coordinate::new(row, column)))
+    }
+    /// Every piece with its square, in the order of [`Board::positions`].
+    fn pieces(&self) -> impl Iterator<Item = (Coordinate, PieceInstance)> + '_ {
+        self.positions()
+            .filter_map(move |position| Some((position, self.piece(position).ok()??)))
+    }
+    fn pieces_of_color(&self, color: Color) -> Vec<(Coordinate, PieceInstance)> {
+        self.pieces()
+            .filter(|(_, piece)| piece.color() == color)
+            .collect()
+    }
+    fn pieces_of_definition(&self, definition_index: usize) -> Vec<(Coordinate, PieceInstance)> {
+        self.pieces()
+            .filter(|(_, piece)| piece.definition_index() == definition_index)
+            .collect()
+    }
+    /// All goal spaces belonging to `color` with the piece in each.
+    fn goals_of_color(&self, color: Color) -> Vec<(Coordinate, Option<PieceInstance>)> {
+        self.positions()
+            .filter_map(|position| match self.space(position) {
+                Some(BoardSpace::Goal { goal_for, piece }) if *goal_for == color => {
+                    Some((position, *piece))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+    /// Fewest single steps from `position` to a goal belonging to `color`.
+    /// `None` if `color` has no goal.
+    fn goal_distance(&self, position: Coordinate, color: Color) -> Option<usize> {
+        nearest_goal_distance(
+            position,
+            self.goals_of_color(color).into_iter().map(|(goal, _)| goal),
+        )
+    }
+
+    /// Every legal jump chain of the piece at `position`, lazily, empty without a piece.
+    fn jump_chains_from(&self, position: Coordinate) -> JumpChains<'_, Self>
+    where
+        Self: Sized,
+    {
+        JumpChains::new(self, position)
+    }
+    /// Every square the piece at `position` can end its turn on, after a move or any jump chain.
+    ///
+    /// Empty without a piece.
+    fn legal_destinations(&self, position: Coordinate) -> DestinationSet
+    where
+        Self: Sized,
+    {
+        let mut out = DestinationSet::new(self.rows(), self.columns());
+        for action in actions_from(self, position) {
+            if let Some(destination) = self.destination(&action) {
+                out.insert(destination);
+            }
+        }
+        out
+    }
+    /// The square the piece moved by `action` ends on, `None` for passes and invalid actions.
+    ///
+    /// Differs from [`Action::end_pos`] when the piece lands on a portal.
+    fn destination(&self, action: &Action) -> Option<Coordinate> {
+        match &action.action_type {
+            ActionType::Move(direction) => {
+                self.is_valid_move(action.start_pos, *direction).ok()?;
+                Some(self.portal_exit(direction.offset() + action.start_pos))
+            }
+            ActionType::Jump(directions) => {
+                let piece = self.piece(action.start_pos).ok()??;
+                let chain = self.jump_chain(piece, action.start_pos, directions).ok()?;
+                Some(chain.position())
+            }
+            ActionType::Pass => None,
+        }
+    }
+    /// The color whose goal `position` is, `None` if it is not a goal.
+    fn goal_for(&self, position: impl Position + Copy) -> Option<Color> {
+        if !self.is_valid_position(position) {
+            return None;
+        }
+        match self.space(position) {
+            Some(BoardSpace::Goal { goal_for, .. }) => Some(*goal_for),
+            _ => None,
+        }
+    }
+    fn piece(&self, position: impl Position + Copy) -> GameBoardResult<Option<PieceInstance>> {
+        self.space(position)
+            .and_then(BoardSpace::piece)
+            .copied()
+            .ok_or(GameBoardError::InvalidPosition)
+    }
+    /// Puts `piece` on `position`, an error if it cannot hold one.
+    fn set_piece(
+        &mut self,
+        position: impl Position + Copy,
+        piece: Option<PieceInstance>,
+    ) -> GameBoardResult<()> {
+        let mut space = *self
+            .space(position)
+            .ok_or(GameBoardError::InvalidPosition)?;
+        *space.piece_mut().ok_or(GameBoardError::InvalidPosition)? = piece;
+        self.set_space(position, space)
+    }
+    /// Passes are always valid on the board, the ruleset decides when they may be played.
+    fn is_valid_action(&self, action: &Action) -> Result<(), ActionError> {
+        let result = check_action(self, action);
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &result {
+            tracing::trace!(?action, ?error, "invalid action");
+        }
+        result
+    }
+    fn is_valid_move(
+        &self,
+        start_pos: Coordinate,
+        direction: Direction,
+    ) -> Result<(), ActionError> {
+        let new_pos = direction.offset() + start_pos;
+        if self.is_blocked(new_pos) {
+            return Err(ActionError::Blocked);
+        }
+        let new_pos = self.portal_exit(new_pos);
+        match self.piece(new_pos) {
+            Ok(piece) => {
+                if let Some(piece) = piece {
+                    Err(ActionError::PieceOnMove(piece))
+                } else {
+                    Ok(())
+                }
+            }
+            Err(error) => match error {
+                GameBoardError::InvalidPosition => Err(ActionError::MoveOffBoard),
+            },
+        }?;
+        match self.piece(start_pos) {
+            Ok(Some(piece)) => self.is_valid_goal_step(piece, start_pos, new_pos),
+            _ => Ok(()),
+        }
+    }
+    fn is_valid_jump(
+        &self,
+        piece: PieceInstance,
+        start_pos: Coordinate,
+        directions: &[Direction],
+    ) -> Result<(), ActionError> {
+        let chain = self.jump_chain(piece, start_pos, directions)?;
+        if !self.may_end_chain(&chain) {
+            return Err(ActionError::ChainUnfinished);
+        }
+        Ok(())
+    }
+    /// Whether `chain` may end where it is under the piece's [`ChainContinuationRule`].
+    fn may_end_chain(&self, chain: &JumpChain) -> bool {
+        let captures_only = match self.rules(chain.piece).chain_rule {
+            ChainContinuationRule::MayStop => return true,
+            ChainContinuationRule::MustCapture => true,
+            ChainContinuationRule::MustContinue => false,
+        };
+        !Direction::into_enum_iter().any(|direction| {
+            self.next_jump(chain, direction)
+                .is_ok_and(|step| step.captures || !captures_only)
+        })
+    }
+    /// Every step of jumping `directions` from `start_pos`, checked like [`Board::is_valid_jump`].
+    fn jump_chain(
+        &self,
+        piece: PieceInstance,
+        start_pos: Coordinate,
+        directions: &[Direction],
+    ) -> Result<JumpChain, ActionError> {
+        if directions.is_empty() {
+            return Err(ActionError::EmptyJump);
+        }
+
+        let mut chain = JumpChain::new(piece, start_pos);
+        for &direction in directions {
+            let step = self.next_jump(&chain, direction)?;
+            chain.push(step);
+        }
+        Ok(chain)
+    }
+    /// Checks jumping `direction` from the end of `chain`.
+    ///
+    /// With [`CaptureTimingRule::AfterJump`] pieces captured earlier in the chain are already removed,
+    /// with [`CaptureTimingRule::AfterTurn`] they stay until the chain ends and can be jumped again
+    /// without being captured twice.
+    /// No [`JumpRule`] allows jumping over the same piece along the same line twice.
+    fn next_jump(&self, chain: &JumpChain, direction: Direction) -> Result<JumpStep, ActionError> {
+        let piece = chain.piece;
+        let rules = self.rules(piece);
+        let (limit, allowed) = match rules.jump_limit {
+            JumpLimit::Unlimited { directions } => (None, directions),
+            JumpLimit::Limited { limit, directions } => (Some(limit), directions),
+            JumpLimit::Cannot => return Err(ActionError::CannotJump),
+        };
+        if let Some(limit) = limit {
+            if chain.steps.len() >= limit {
+                return Err(ActionError::JumpLimitExceeded(limit));
+            }
+        }
+        if !allowed.contains(Directions::from(direction)) {
+            return Err(ActionError::JumpDirectionNotAllowed(direction));
+        }
+
+        let removes = rules.capture_timing_rule == CaptureTimingRule::AfterJump;
+        let from = chain.position();
+        if !chain.steps.is_empty() && self.is_sticky(from) {
+            return Err(ActionError::StuckOnSticky);
+        }
+        let middle = direction.offset() + from;
+        let landing = direction.offset() + middle;
+        if self.is_blocked(landing) {
+            return Err(ActionError::Blocked);
+        }
+        let landing = self.portal_exit(landing);
+        match self.piece(landing) {
+            Ok(Some(piece)) => return Err(ActionError::PieceOnJump(piece)),
+            Ok(None) => {}
+            Err(GameBoardError::InvalidPosition) => return Err(ActionError::JumpOffBoard),
+        }
+        let revisits = match rules.jump_rule {
+            JumpRule::NoBacktracking => {
+                landing == chain.start || chain.steps.iter().any(|step| step.landing == landing)
+            }
+            JumpRule::NoSameStart => landing == chain.start,
+            JumpRule::Open => false,
+        };
+        if revisits {
+            return Err(ActionError::JumpedBackToPrevPosition);
+        }
+        // Steps over the same middle on the same line either repeat or reverse this one
+        if chain
+            .steps
+            .iter()
+            .any(|step| step.middle == middle && (step.landing == landing || step.landing == from))
+        {
+            return Err(ActionError::RepeatedJump);
+        }
+        let captures = match self.piece(middle) {
+            Ok(Some(_)) if removes && chain.captured(middle) => {
+                return Err(ActionError::NoPieceJumped)
+            }
+            Ok(Some(middle_piece)) => {
+                let own = middle_piece.color() == piece.color();
+                let target = match rules.jump_over_target {
+                    Some(CaptureTarget::EnemyOnly) => !own,
+                    Some(CaptureTarget::OwnOnly) => own,
+                    Some(CaptureTarget::All) => true,
+                    None => false,
+                };
+                target && !chain.captured(middle)
+            }
+            _ if self.is_blocked(middle) => false,
+            _ => return Err(ActionError::NoPieceJumped),
+        };
+        self.is_valid_goal_step(piece, from, landing)?;
+        Ok(JumpStep {
+            direction,
+            middle,
+            landing,
+            captures,
+        })
+    }
+    /// Checks `piece`'s goal rule for a single move or jump from `from` to `to`.
+    fn is_valid_goal_step(
+        &self,
+        piece: PieceInstance,
+        from: Coordinate,
+        to: Coordinate,
+    ) -> Result<(), ActionError> {
+        let rule = self.rules(piece).goal_move_rule;
+        let from_goal = self.goal_for(from).is_some();
+        match rule {
+            GoalMovementRule::Locked if from_goal => Err(ActionError::GoalLocked),
+            GoalMovementRule::OnlyToGoal if from_goal && self.goal_for(to).is_none() => {
+                Err(ActionError::LeftGoal)
+            }
+            GoalMovementRule::NoOwnGoal if self.goal_for(to) == Some(piece.color()) => {
+                Err(ActionError::EnteredOwnGoal)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Applies `action` to this board and reports what happened, see [`ActionOutcome`].
+    ///
+    /// The board is unchanged when the action is invalid.
+    fn apply_with_outcome(&mut self, action: &Action) -> Result<ActionOutcome, ActionError> {
+        let goal = self
+            .destination(action)
+            .filter(|&destination| destination != action.start_pos)
+            .filter(|&destination| self.goal_for(destination).is_some());
+        let mut outcome = ActionOutcome::default();
+        self.apply_in_place(action, |position, piece| {
+            outcome.captures.push((position, piece))
+        })?;
+        if let Some(goal) = goal {
+            if let Ok(Some(piece)) = self.piece(goal) {
+                outcome.goal_entries.push((goal, piece));
+            }
+        }
+        Ok(outcome)
+    }
+    /// Applies `action` to this board, calling `capture_callback` with every piece captured.
+    ///
+    /// The board is unchanged when the action is invalid.
+    fn apply_in_place(
+        &mut self,
+        action: &Action,
+        mut capture_callback: impl FnMut(Coordinate, PieceInstance),
+    ) -> Result<(), ActionError> {
+        self.is_valid_action(action)?;
+        let piece = match &action.action_type {
+            ActionType::Pass => return Ok(()),
+            _ => self
+                .piece(action.start_pos)
+                .expect("Checked by is_valid_action")
+                .expect("Checked by is_valid_action"),
+        };
+        let end = match &action.action_type {
+            ActionType::Move(direction) => {
+                self.set_piece(action.start_pos, None)
+                    .expect("Checked by is_valid_action");
+                self.portal_exit(direction.offset() + action.start_pos)
+            }
+            ActionType::Jump(directions) => {
+                let chain = self.jump_chain(piece, action.start_pos, directions)?;
+                self.set_piece(action.start_pos, None)
+                    .expect("Checked by is_valid_action");
+                // The chain only captures each piece once, with either timing
+                for step in chain.steps().iter().filter(|step| step.captures) {
+                    let captured = self
+                        .piece(step.middle)
+                        .expect("Jumped squares are on the board")
+                        .expect("Captured squares hold a piece");
+                    capture_callback(step.middle, captured);
+                    self.set_piece(step.middle, None)
+                        .expect("Jumped squares are on the board");
+                }
+                chain.position()
+            }
+            ActionType::Pass => unreachable!("Passes return early"),
+        };
+        self.set_piece(end, Some(piece))
+            .expect("Checked by is_valid_action");
+        Ok(())
+    }
+}
+
+/// [`Board::is_valid_action`] without its tracing.
+fn check_action(board: &(impl Board + ?Sized), action: &Action) -> Result<(), ActionError> {
+    if action.action_type.is_pass() {
+        return Ok(());
+    }
+    let piece = match board.piece(action.start_pos) {
+        Ok(piece) => piece,
+        Err(error) => {
+            return match error {
+                GameBoardError::InvalidPosition => Err(ActionError::InvalidStartPosition),
+            };
+        }
+    };
+    if piece.is_none() {
+        return Err(ActionError::NoPieceAtStart);
+    }
+    let piece = piece.unwrap();
+
+    match &action.action_type {
+        ActionType::Move(direction) => board.is_valid_move(action.start_pos, *direction)?,
+        ActionType::Jump(directions) => board.is_valid_jump(piece, action.start_pos, directions)?,
+        ActionType::Pass => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! Sets of squares packed into bits, see
+//! [`Board::legal_destinations`](crate::game_board::board::Board::legal_destinations).
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::coordinate::Coordinate;
+
+/// A set of squares of a board, one bit per square.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DestinationSet {
+    rows: usize,
+    columns: usize,
+    /// Squares column by column like [`Grid::values`](crate::grid::Grid::values).
+    bits: Vec<u64>,
+}
+impl DestinationSet {
+    /// An empty set for a board of `rows` by `columns`.
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Self {
+            rows,
+            columns,
+            bits: vec![0; (rows * columns).div_ceil(64)],
+        }
+    }
+    /// Adds `position`, `false` if it was already in the set or is off the board.
+    pub fn insert(&mut self, position: Coordinate) -> bool {
+        let index = match self.index(position) {
+            Some(index) => index,
+            None => return false,
+        };
+        let word = &mut self.bits[index / 64];
+        let bit = 1 << (index % 64);
+        let inserted = *word & bit == 0;
+        *word |= bit;
+        inserted
+    }
+    /// Removes `position`, `false` if it was not in the set.
+    pub fn remove(&mut self, position: Coordinate) -> bool {
+        let index = match self.index(position) {
+            Some(index) => index,
+            None => return false,
+        };
+        let word = &mut self.bits[index / 64];
+        let bit = 1 << (index % 64);
+        let removed = *word & bit != 0;
+        *word &= !bit;
+        removed
+    }
+    pub fn contains(&self, position: Coordinate) -> bool {
+        self.index(position)
+            .is_some_and(|index| self.bits[index / 64] & 1 << (index % 64) != 0)
+    }
+    pub fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&word| word == 0)
+    }
+    /// The squares in the set, column by column.
+    pub fn iter(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        self.bits
+            .iter()
+            .enumerate()
+            .flat_map(move |(word_index, &word)| {
+                (0..64)
+                    .filter(move |bit| word & 1 << bit != 0)
+                    .map(move |bit| {
+                        let index = word_index * 64 + bit;
+                        Coordinate::new((index % self.rows) as i16, (index / self.rows) as i16)
+                    })
+            })
+    }
+    /// The words of the set, bit `i` of word `w` is the `64 * w + i`th square column by column.
+    pub fn as_words(&self) -> &[u64] {
+        &self.bits
+    }
+
+    fn index(&self, position: Coordinate) -> Option<usize> {
+        let (row, column) = (position.row as usize, position.column as usize);
+        if position.row < 0 || position.column < 0 || row >= self.rows || column >= self.columns {
+            return None;
+        }
+        Some(column * self.rows + row)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use crate::coordinate::Coordinate;
+    use crate::engine::Engine;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::game_board::destinations::DestinationSet;
+    use crate::movegen::actions_from;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn destination_set_test() {
+        let mut set = DestinationSet::new(9, 8);
+        assert!(set.insert(Coordinate::new(8, 7)));
+        assert!(!set.insert(Coordinate::new(8, 7)));
+        assert!(set.insert(Coordinate::new(2, 0)));
+        assert!(!set.insert(Coordinate::new(-1, 0)));
+        assert!(!set.insert(Coordinate::new(0, 8)));
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![Coordinate::new(2, 0), Coordinate::new(8, 7)]
+        );
+        assert!(set.remove(Coordinate::new(8, 7)));
+        assert!(!set.contains(Coordinate::new(8, 7)));
+        assert!(!set.is_empty());
+    }
+
+    #[test]
+    fn legal_destinations_test() {
+        let mut game = Game::new(small_ruleset(2)).unwrap();
+        let mut policy = RandomPolicy::new(3);
+        while game.history().len() < 20 && !game.is_finished() {
+            let board = game.board();
+            for (position, _) in board.pieces_of_color(game.to_move()) {
+                let destinations = board.legal_destinations(position);
+                let mut expected: Vec<_> = actions_from(board, position)
+                    .iter()
+                    .filter_map(|action| board.destination(action))
+                    .collect();
+                expected.sort_by_key(|position| (position.column, position.row));
+                expected.dedup();
+                assert_eq!(destinations.iter().collect::<Vec<_>>(), expected);
+            }
+            let action = policy.choose_action(&game).unwrap();
+            game.apply(&action).unwrap();
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Text diagrams of boards for terminals and logs.
+//!
+//! Every square is three characters wide with row 0 on top. Pieces are letters by definition
+//! index, `A` for Red's first definition and `a` for Blue's. Empty squares are `.`, goals `_`,
+//! blocked squares `#`, sticky squares `~`, portals the last digit of their pair, and squares
+//! off the board blank.
+
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+use crate::action::{Action, ActionOutcome, ActionType};
+use crate::coordinate::Coordinate;
+use crate::game_board::{BoardSpace, Color, GameBoard, PieceInstance};
+
+/// How [`BoardDisplay`] marks the squares of the last action.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Highlight {
+    /// `(X)` for the start, `:X:` for squares landed on mid chain, `[X]` for the end, and `{X}`
+    /// for captured squares.
+    Brackets,
+    /// The same squares with yellow, cyan, green, and red backgrounds.
+    Ansi,
+}
+
+/// What a square was in the last action.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Mark {
+    Start,
+    Path,
+    End,
+    Captured,
+}
+
+/// A [`GameBoard`] as text, see [`GameBoard::display`].
+#[derive(Copy, Clone, Debug)]
+pub struct BoardDisplay<'a> {
+    board: &'a GameBoard,
+    last: Option<(&'a Action, &'a ActionOutcome)>,
+    highlight: Highlight,
+}
+impl<'a> BoardDisplay<'a> {
+    /// Marks `action`, the action that led to the board, and the captures in its `outcome`.
+    pub fn last(mut self, action: &'a Action, outcome: &'a ActionOutcome) -> Self {
+        self.last = Some((action, outcome));
+        self
+    }
+    pub fn highlight(mut self, highlight: Highlight) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    fn mark(&self, position: Coordinate) -> Option<Mark> {
+        let (action, outcome) = self.last?;
+        if outcome
+            .captures
+            .iter()
+            .any(|(captured, _)| *captured == position)
+        {
+            return Some(Mark::Captured);
+        }
+        if action.action_type.is_pass() {
+            return None;
+        }
+        if position == action.end_pos() {
+            return Some(Mark::End);
+        }
+        if position == action.start_pos {
+            return Some(Mark::Start);
+        }
+        if let ActionType::Jump(directions) = &action.action_type {
+            let mut landing = action.start_pos;
+            for direction in directions {
+                landing = direction.offset() * 2 + landing;
+                if landing == position {
+                    return Some(Mark::Path);
+                }
+            }
+        }
+        None
+    }
+}
+impl Display for BoardDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let grid = &self.board.board;
+        for row in 0..grid.rows {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for column in 0..grid.columns {
+                let position = Coordinate::new(row as i16, column as i16);
+                let symbol = symbol(grid[position]);
+                match (self.mark(position), self.highlight) {
+                    (None, _) => write!(f, " {} ", symbol)?,
+                    (Some(mark), Highlight::Brackets) => {
+                        let (open, close) = match mark {
+                            Mark::Start => ('(', ')'),
+                            Mark::Path => (':', ':'),
+                            Mark::End => ('[', ']'),
+                            Mark::Captured => ('{', '}'),
+                        };
+                        write!(f, "{}{}{}", open, symbol, close)?
+                    }
+                    (Some(mark), Highlight::Ansi) => {
+                        let background = match mark {
+                            Mark::Start => 43,
+                            Mark::Path => 46,
+                            Mark::End => 42,
+                            Mark::Captured => 41,
+                        };
+                        write!(f, "\x1b[{}m {} \x1b[0m", background, symbol)?
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GameBoard {
+    /// This board as text, marking nothing until given [`BoardDisplay::last`].
+    pub fn display(&self) -> BoardDisplay<'_> {
+        BoardDisplay {
+            board: self,
+            last: None,
+            highlight: Highlight::Brackets,
+        }
+    }
+}
+impl Display for GameBoard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+fn symbol(space: BoardSpace) -> char {
+    let piece = |piece: PieceInstance| {
+        let first = match piece.color() {
+            Color::Red => b'A',
+            Color::Blue => b'a',
+        };
+        (first + (piece.definition_index() % 26) as u8) as char
+    };
+    match space {
+        BoardSpace::Invalid => ' ',
+        BoardSpace::Blocked => '#',
+        BoardSpace::Normal(Some(occupant))
+        | BoardSpace::Goal {
+            piece: Some(occupant),
+            ..
+        }
+        | BoardSpace::Sticky(Some(occupant))
+        | BoardSpace::Portal {
+            piece: Some(occupant),
+            ..
+        } => piece(occupant),
+        BoardSpace::Normal(None) => '.',
+        BoardSpace::Goal { .. } => '_',
+        BoardSpace::Sticky(None) => '~',
+        BoardSpace::Portal { pair, .. } => (b'0' + pair % 10) as char,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    use crate::action::{Action, ActionOutcome, ActionType};
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game_board::display::Highlight;
+    use crate::game_board::{BoardSpace, Color, GameBoard, PieceInstance};
+
+    #[test]
+    fn display_test() {
+        let mut board = GameBoard::new((3, 3), &[1]);
+        let red = PieceInstance::new(0, Color::Red);
+        let blue = PieceInstance::new(1, Color::Blue);
+        board.board[Coordinate::new(3, 0)] = BoardSpace::Normal(Some(red));
+        board.board[Coordinate::new(2, 1)] = BoardSpace::Normal(Some(blue));
+        assert_eq!(
+            board.to_string(),
+            "    .    \n .  .  . \n .  b  . \n A  .  . \n    .    "
+        );
+
+        let action = Action {
+            start_pos: Coordinate::new(3, 0),
+            action_type: ActionType::Jump([Direction::NorthEast, Direction::West].into()),
+        };
+        let outcome = ActionOutcome {
+            captures: vec![(Coordinate::new(2, 1), blue)],
+            ..ActionOutcome::default()
+        };
+        board.board[Coordinate::new(3, 0)] = BoardSpace::Normal(None);
+        board.board[Coordinate::new(2, 1)] = BoardSpace::Normal(None);
+        board.board[Coordinate::new(1, 0)] = BoardSpace::Normal(Some(red));
+        let text = board.display().last(&action, &outcome).to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], "[A] . :.:");
+        assert_eq!(lines[2], " . {.} . ");
+        assert_eq!(lines[3], "(.) .  . ");
+
+        let ansi = board
+            .display()
+            .last(&action, &outcome)
+            .highlight(Highlight::Ansi)
+            .to_string();
+        assert!(ansi.contains("\x1b[42m A \x1b[0m"));
+        assert!(ansi.contains("\x1b[41m . \x1b[0m"));
+    }
+}
@@ -0,0 +1,190 @@
+//! Boards with their size fixed at compile time, see [`FixedGameBoard`].
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::action::{Action, ActionError, ActionOutcome};
+use crate::game_board::board::Board;
+use crate::game_board::{
+    BoardSpace, GameBoard, GameBoardError, GameBoardResult, PieceInstance, PieceRules,
+};
+use crate::grid::{Grid, Position};
+
+/// Piece rules a [`FixedGameBoard`] holds, as many piece kinds as [`GameBoard::encode`] packs.
+pub const FIXED_PIECE_KINDS: usize = 7;
+
+/// The standard 10 by 10 board with its goal rows.
+pub type StandardBoard = FixedGameBoard<12, 10>;
+
+/// A board of `R` rows, goal rows included, and `C` columns stored inline.
+///
+/// Copying one never allocates, so engines can keep a board per search node. Converts to and
+/// from a [`GameBoard`] of the same size.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FixedGameBoard<const R: usize, const C: usize> {
+    /// Columns of spaces, in the order of [`Grid::values`].
+    spaces: [[BoardSpace; R]; C],
+    /// The rules of each piece by definition index, see [`GameBoard::piece_rules`].
+    piece_rules: [Option<PieceRules>; FIXED_PIECE_KINDS],
+}
+impl<const R: usize, const C: usize> FixedGameBoard<R, C> {
+    /// The board after `action` and what happened, see [`GameBoard::apply_action`].
+    pub fn apply_action(&self, action: &Action) -> Result<(Self, ActionOutcome), ActionError> {
+        let mut board = *self;
+        let outcome = board.apply_with_outcome(action)?;
+        Ok((board, outcome))
+    }
+}
+impl<const R: usize, const C: usize> Board for FixedGameBoard<R, C> {
+    fn rows(&self) -> usize {
+        R
+    }
+    fn columns(&self) -> usize {
+        C
+    }
+    fn space(&self, position: impl Position) -> Option<&BoardSpace> {
+        let (row, column) = position.coordinates();
+        self.spaces.get(column)?.get(row)
+    }
+    fn set_space(&mut self, position: impl Position, space: BoardSpace) -> GameBoardResult<()> {
+        let (row, column) = position.coordinates();
+        let square = self
+            .spaces
+            .get_mut(column)
+            .and_then(|column| column.get_mut(row))
+            .ok_or(GameBoardError::InvalidPosition)?;
+        *square = space;
+        Ok(())
+    }
+    fn rules(&self, piece: PieceInstance) -> PieceRules {
+        let index = piece.definition_index();
+        self.piece_rules
+            .get(index)
+            .copied()
+            .flatten()
+            .or_else(|| PieceRules::STANDARD.get(index).copied())
+            .unwrap_or(PieceRules::STANDARD[1])
+    }
+}
+impl<const R: usize, const C: usize> TryFrom<&GameBoard> for FixedGameBoard<R, C> {
+    type Error = FixedBoardError;
+
+    fn try_from(from: &GameBoard) -> Result<Self, Self::Error> {
+        if (from.rows(), from.columns()) != (R, C) {
+            return Err(FixedBoardError::WrongSize {
+                rows: from.rows(),
+                columns: from.columns(),
+            });
+        }
+        if from.piece_rules.len() > FIXED_PIECE_KINDS {
+            return Err(FixedBoardError::TooManyPieceKinds(from.piece_rules.len()));
+        }
+        let mut out = Self {
+            spaces: [[BoardSpace::Invalid; R]; C],
+            piece_rules: [None; FIXED_PIECE_KINDS],
+        };
+        for (column, spaces) in out.spaces.iter_mut().enumerate() {
+            for (row, space) in spaces.iter_mut().enumerate() {
+                *space = from.board[(row, column)];
+            }
+        }
+        for (rules, from) in out.piece_rules.iter_mut().zip(from.piece_rules.iter()) {
+            *rules = Some(*from);
+        }
+        Ok(out)
+    }
+}
+impl<const R: usize, const C: usize> From<&FixedGameBoard<R, C>> for GameBoard {
+    fn from(from: &FixedGameBoard<R, C>) -> Self {
+        GameBoard {
+            board: Grid::from_vec((R, C), from.spaces.iter().flatten().copied().collect()),
+            piece_rules: from.piece_rules.iter().map_while(|rules| *rules).collect(),
+            pieces: None,
+        }
+    }
+}
+
+pub type FixedBoardResult<T> = Result<T, FixedBoardError>;
+#[derive(Copy, Clone, Debug)]
+pub enum FixedBoardError {
+    /// The board is not `R` by `C`.
+    WrongSize { rows: usize, columns: usize },
+    /// More piece rules than [`FIXED_PIECE_KINDS`].
+    TooManyPieceKinds(usize),
+}
+impl Display for FixedBoardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for FixedBoardError {}
+
+#[cfg(test)]
+mod test {
+    use core::convert::TryFrom;
+
+    use crate::engine::Engine;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::game_board::fixed::{FixedBoardError, FixedGameBoard, StandardBoard};
+    use crate::game_board::GameBoard;
+    use crate::movegen::legal_actions;
+    use crate::ruleset::presets::Preset;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn fixed_test() {
+        let mut game = Game::new(Preset::CaptureAll.ruleset().unwrap()).unwrap();
+        let mut policy = RandomPolicy::new(0);
+        let mut fixed = StandardBoard::try_from(game.board()).unwrap();
+        while game.history().len() < 40 && !game.is_finished() {
+            assert_eq!(
+                legal_actions(&fixed, game.to_move()),
+                legal_actions(game.board(), game.to_move())
+            );
+            let action = policy.choose_action(&game).unwrap();
+            fixed = fixed.apply_action(&action).unwrap().0;
+            game.apply(&action).unwrap();
+            assert!(GameBoard::from(&fixed).diff(game.board()).is_empty());
+            assert_eq!(fixed, StandardBoard::try_from(game.board()).unwrap());
+        }
+
+        let small = Game::new(small_ruleset(0)).unwrap();
+        assert!(matches!(
+            FixedGameBoard::<12, 10>::try_from(small.board()),
+            Err(FixedBoardError::WrongSize { .. })
+        ));
+        let small_fixed = FixedGameBoard::<8, 6>::try_from(small.board()).unwrap();
+        assert_eq!((small_fixed.rows(), small_fixed.columns()), (8, 6));
+    }
+
+    #[test]
+    fn game_test() {
+        let mut game = Game::new(Preset::CaptureAll.ruleset().unwrap()).unwrap();
+        let mut fixed: Game<StandardBoard> = game.with_board().unwrap();
+        let mut policy = RandomPolicy::new(1);
+        while let Some(action) = policy.choose_action(&game) {
+            assert_eq!(fixed.legal_actions(), game.legal_actions());
+            let outcome = game.apply(&action).unwrap();
+            assert_eq!(
+                format!("{:?}", fixed.apply(&action).unwrap()),
+                format!("{:?}", outcome)
+            );
+            assert_eq!(fixed.to_move(), game.to_move());
+        }
+        assert_eq!(fixed.result(), game.result());
+        assert_eq!(fixed.scores(), game.scores());
+        assert_eq!(
+            fixed.board(),
+            &StandardBoard::try_from(game.board()).unwrap()
+        );
+
+        let small = Game::new(small_ruleset(0)).unwrap();
+        assert!(small.with_board::<StandardBoard>().is_none());
+    }
+}
@@ -0,0 +1,277 @@
+//! Checks that a board could come up in a game, see [`GameBoard::is_reachable`].
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::collections::HashMap;
+use crate::coordinate::Coordinate;
+use crate::game_board::board::Board;
+use crate::game_board::{BoardSpace, Color, GameBoard};
+use crate::ruleset::piece_definition::GoalMovementRule;
+use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+use crate::ruleset::starting_positions::{StartingPositions, StartingPositionsError};
+use crate::ruleset::verified::VerifiedRuleset;
+
+impl GameBoard {
+    /// Whether a game under `ruleset` could reach this board, see
+    /// [`GameBoard::unreachable_reasons`].
+    pub fn is_reachable(&self, ruleset: &VerifiedRuleset) -> bool {
+        self.unreachable_reasons(ruleset).is_empty()
+    }
+    /// Everything that keeps a game under `ruleset` from reaching this board, for positions made
+    /// in an editor.
+    ///
+    /// Pieces are never added during play, so each color may have at most as many of each
+    /// piece as it starts with or could place. An empty list does not mean the board is
+    /// reachable, only that none of these heuristics rule it out.
+    pub fn unreachable_reasons(&self, ruleset: &VerifiedRuleset) -> Vec<ReachabilityError> {
+        let start = match ruleset.starting_positions.build_board(ruleset) {
+            Ok(start) => start,
+            Err(error) => return vec![error.into()],
+        };
+        if (self.rows(), self.columns()) != (start.rows(), start.columns()) {
+            return vec![ReachabilityError::WrongSize {
+                rows: self.rows(),
+                columns: self.columns(),
+            }];
+        }
+
+        let mut out = Vec::new();
+        for position in self.positions() {
+            let mut space = *self.space(position).expect("Positions are on the board");
+            let expected = start.space(position).expect("Boards are the same size");
+            let piece = space.piece_mut().and_then(Option::take);
+            if space != without_piece(*expected) {
+                out.push(ReachabilityError::SpaceChanged(position));
+                continue;
+            }
+            let piece = match piece {
+                Some(piece) => piece,
+                None => continue,
+            };
+            let definition = match ruleset.get_piece(piece.definition_index()) {
+                Some(definition) => definition,
+                None => {
+                    out.push(ReachabilityError::UnknownPiece {
+                        position,
+                        definition_index: piece.definition_index(),
+                    });
+                    continue;
+                }
+            };
+            let started_here =
+                matches!(expected.piece(), Some(Some(start_piece)) if *start_piece == piece);
+            if definition.goal_move_rule == GoalMovementRule::NoOwnGoal
+                && self.goal_for(position) == Some(piece.color())
+                && !started_here
+            {
+                out.push(ReachabilityError::InOwnGoal(position));
+            }
+        }
+
+        for color in Color::into_enum_iter() {
+            let mut counts = HashMap::new();
+            for (_, piece) in self.pieces_of_color(color) {
+                *counts.entry(piece.definition_index()).or_insert(0usize) += 1;
+            }
+            let mut start_counts = HashMap::new();
+            for (_, piece) in start.pieces_of_color(color) {
+                *start_counts
+                    .entry(piece.definition_index())
+                    .or_insert(0usize) += 1;
+            }
+            for (&definition_index, &count) in counts.iter() {
+                if definition_index >= ruleset.pieces.len() {
+                    continue;
+                }
+                let allowed = match &ruleset.starting_positions {
+                    StartingPositions::Placement { piece_limits, .. } => {
+                        // The last of them must still have been placeable
+                        let mut before = counts.clone();
+                        before.insert(definition_index, count - 1);
+                        PieceLimit::remaining(piece_limits, &before, definition_index)
+                            .is_none_or(|remaining| remaining > 0)
+                    }
+                    _ => count <= start_counts.get(&definition_index).copied().unwrap_or(0),
+                };
+                if !allowed {
+                    out.push(ReachabilityError::TooManyPieces {
+                        color,
+                        definition_index,
+                        count,
+                    });
+                }
+            }
+        }
+        out
+    }
+}
+
+fn without_piece(mut space: BoardSpace) -> BoardSpace {
+    if let Some(piece) = space.piece_mut() {
+        *piece = None;
+    }
+    space
+}
+
+#[derive(Clone, Debug)]
+pub enum ReachabilityError {
+    /// The ruleset's starting board could not be built.
+    StartingPositionsError(StartingPositionsError),
+    /// The board is not the size of the ruleset's.
+    WrongSize { rows: usize, columns: usize },
+    /// A space of a different kind than on the ruleset's board, or a piece where none can be.
+    SpaceChanged(Coordinate),
+    /// A piece the ruleset does not define.
+    UnknownPiece {
+        position: Coordinate,
+        definition_index: usize,
+    },
+    /// More of a piece than the color starts with or could place.
+    TooManyPieces {
+        color: Color,
+        definition_index: usize,
+        count: usize,
+    },
+    /// A piece in its own goal that may not enter it, see [`GoalMovementRule::NoOwnGoal`].
+    InOwnGoal(Coordinate),
+}
+impl Display for ReachabilityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for ReachabilityError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::StartingPositionsError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+impl From<StartingPositionsError> for ReachabilityError {
+    fn from(from: StartingPositionsError) -> Self {
+        Self::StartingPositionsError(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use crate::coordinate::Coordinate;
+    use crate::engine::Engine;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::game_board::reachability::ReachabilityError;
+    use crate::game_board::{BoardSpace, Color, PieceInstance};
+    use crate::ruleset::piece_definition::GoalMovementRule;
+    use crate::ruleset::presets::Preset;
+    use crate::ruleset::verified::VerifiedRuleset;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn reachable_test() {
+        let ruleset = VerifiedRuleset::new(small_ruleset(4)).unwrap();
+        let mut game = Game::new(small_ruleset(4)).unwrap();
+        let mut policy = RandomPolicy::new(5);
+        while game.history().len() < 20 && !game.is_finished() {
+            assert!(game.board().is_reachable(&ruleset));
+            let action = policy.choose_action(&game).unwrap();
+            game.apply(&action).unwrap();
+        }
+
+        let start = Game::new(small_ruleset(4)).unwrap().board().clone();
+        let empty = start
+            .positions()
+            .find(|&position| start.piece(position).ok() == Some(None))
+            .unwrap();
+        let mut extra = start.clone();
+        extra
+            .set_piece(empty, Some(PieceInstance::new(1, Color::Red)))
+            .unwrap();
+        assert!(matches!(
+            extra.unreachable_reasons(&ruleset)[..],
+            [ReachabilityError::TooManyPieces {
+                color: Color::Red,
+                definition_index: 1,
+                count: 4,
+            }]
+        ));
+
+        let mut unknown = start.clone();
+        unknown
+            .set_piece(empty, Some(PieceInstance::new(5, Color::Blue)))
+            .unwrap();
+        assert!(matches!(
+            unknown.unreachable_reasons(&ruleset)[..],
+            [ReachabilityError::UnknownPiece {
+                definition_index: 5,
+                ..
+            }]
+        ));
+
+        let invalid = Coordinate::new(0, 0);
+        assert_eq!(start.space(invalid), Some(&BoardSpace::Invalid));
+        let mut changed = start.clone();
+        changed.board[invalid] = BoardSpace::Normal(Some(PieceInstance::new(0, Color::Red)));
+        changed.invalidate_pieces();
+        assert!(!changed.is_reachable(&ruleset));
+        assert!(matches!(
+            changed.unreachable_reasons(&ruleset)[0],
+            ReachabilityError::SpaceChanged(position) if position == invalid
+        ));
+    }
+
+    #[test]
+    fn reachable_rules_test() {
+        let mut rules = small_ruleset(4);
+        for piece in rules.pieces.iter_mut() {
+            piece.goal_move_rule = GoalMovementRule::NoOwnGoal;
+        }
+        let ruleset = VerifiedRuleset::new(rules.clone()).unwrap();
+        let mut board = Game::new(rules).unwrap().board().clone();
+        let goal = board
+            .positions()
+            .find(|&position| board.goal_for(position) == Some(Color::Red))
+            .unwrap();
+        let (from, piece) = board.pieces_of_color(Color::Red)[0];
+        board.set_piece(from, None).unwrap();
+        board.set_piece(goal, Some(piece)).unwrap();
+        assert!(matches!(
+            board.unreachable_reasons(&ruleset)[..],
+            [ReachabilityError::InOwnGoal(position)] if position == goal
+        ));
+
+        let ruleset = VerifiedRuleset::new(Preset::PointsPlacement.ruleset().unwrap()).unwrap();
+        let mut board = Game::new(ruleset.clone().into_inner())
+            .unwrap()
+            .board()
+            .clone();
+        let mut squares = board
+            .positions()
+            .filter(|&position| matches!(board.space(position), Some(BoardSpace::Normal(None))))
+            .collect::<Vec<_>>()
+            .into_iter();
+        for _ in 0..12 {
+            let square = squares.next().unwrap();
+            board
+                .set_piece(square, Some(PieceInstance::new(1, Color::Blue)))
+                .unwrap();
+        }
+        assert!(board.is_reachable(&ruleset));
+        let square = squares.next().unwrap();
+        board
+            .set_piece(square, Some(PieceInstance::new(1, Color::Blue)))
+            .unwrap();
+        assert!(!board.is_reachable(&ruleset));
+    }
+}
@@ -0,0 +1,142 @@
+//! Actions applied over a borrowed board, see [`GameBoard::apply_speculative`].
+
+use alloc::vec::Vec;
+
+use crate::action::{Action, ActionError, ActionOutcome};
+use crate::coordinate::Coordinate;
+use crate::game_board::board::Board;
+use crate::game_board::{
+    BoardSpace, GameBoard, GameBoardError, GameBoardResult, PieceInstance, PieceRules,
+};
+use crate::grid::Position;
+
+/// A board after an action, storing only the squares the action touched over its parent.
+///
+/// Cheap to make for every candidate action, [`SpeculativeBoard::commit`] turns it into a
+/// [`GameBoard`] when the action is kept.
+#[derive(Clone, Debug)]
+pub struct SpeculativeBoard<'a> {
+    parent: &'a GameBoard,
+    /// Touched squares with what they hold now, each square once.
+    changes: Vec<(Coordinate, BoardSpace)>,
+    outcome: ActionOutcome,
+}
+impl<'a> SpeculativeBoard<'a> {
+    fn new(parent: &'a GameBoard) -> Self {
+        Self {
+            parent,
+            changes: Vec::new(),
+            outcome: ActionOutcome::default(),
+        }
+    }
+    /// The board before the action.
+    pub fn parent(&self) -> &'a GameBoard {
+        self.parent
+    }
+    /// Squares whose contents the action changed.
+    pub fn touched(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        self.changes.iter().map(|(position, _)| *position)
+    }
+    /// Pieces captured by the action, in the order they were jumped.
+    pub fn captures(&self) -> &[(Coordinate, PieceInstance)] {
+        &self.outcome.captures
+    }
+    /// What the action did.
+    pub fn outcome(&self) -> &ActionOutcome {
+        &self.outcome
+    }
+    /// The board after the action, with piece lists, see [`GameBoard::index_pieces`].
+    pub fn commit(self) -> GameBoard {
+        let mut board = self.parent.clone();
+        if !board.is_indexed() {
+            board.index_pieces();
+        }
+        for (position, space) in self.changes {
+            board
+                .set_space(position, space)
+                .expect("Touched squares are on the board");
+        }
+        board
+    }
+}
+impl Board for SpeculativeBoard<'_> {
+    fn rows(&self) -> usize {
+        self.parent.rows()
+    }
+    fn columns(&self) -> usize {
+        self.parent.columns()
+    }
+    fn space(&self, position: impl Position) -> Option<&BoardSpace> {
+        let position = position.coordinates();
+        match self
+            .changes
+            .iter()
+            .find(|(changed, _)| changed.coordinates() == position)
+        {
+            Some((_, space)) => Some(space),
+            None => self.parent.space(position),
+        }
+    }
+    fn set_space(&mut self, position: impl Position, space: BoardSpace) -> GameBoardResult<()> {
+        let position = Coordinate::new(position.row() as i16, position.column() as i16);
+        if self.parent.space(position).is_none() {
+            return Err(GameBoardError::InvalidPosition);
+        }
+        match self
+            .changes
+            .iter_mut()
+            .find(|(changed, _)| *changed == position)
+        {
+            Some((_, square)) => *square = space,
+            None => self.changes.push((position, space)),
+        }
+        Ok(())
+    }
+    fn rules(&self, piece: PieceInstance) -> PieceRules {
+        self.parent.rules(piece)
+    }
+}
+
+impl GameBoard {
+    /// Applies `action` without copying the board, see [`SpeculativeBoard`].
+    pub fn apply_speculative(&self, action: &Action) -> Result<SpeculativeBoard<'_>, ActionError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("apply_speculative", ?action).entered();
+        let mut out = SpeculativeBoard::new(self);
+        out.outcome = out.apply_with_outcome(action)?;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::Engine;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::movegen::legal_actions;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn speculative_test() {
+        let mut game = Game::new(small_ruleset(3)).unwrap();
+        let mut policy = RandomPolicy::new(4);
+        while game.history().len() < 20 && !game.is_finished() {
+            let board = game.board();
+            for action in legal_actions(board, game.to_move()) {
+                let speculative = board.apply_speculative(&action).unwrap();
+                let (applied, outcome) = board.apply_action(&action).unwrap();
+                assert_eq!(speculative.captures(), outcome.captures.as_slice());
+                for position in speculative.touched() {
+                    assert_eq!(
+                        speculative.piece(position).ok(),
+                        applied.piece(position).ok()
+                    );
+                }
+                assert!(speculative.commit().diff(&applied).is_empty());
+            }
+            let action = policy.choose_action(&game).unwrap();
+            game.apply(&action).unwrap();
+        }
+    }
+}
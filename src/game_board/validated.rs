@@ -0,0 +1,207 @@
+//! Actions checked once and applied later, see [`GameBoard::validate`].
+
+use alloc::vec::Vec;
+
+use crate::action::{Action, ActionError, ActionOutcome};
+use crate::coordinate::Coordinate;
+use crate::game_board::board::Board;
+use crate::game_board::{GameBoard, PieceInstance};
+
+/// An action [`GameBoard::validate`] accepted, with the squares it changes.
+///
+/// Only `validate` makes one, so [`GameBoard::apply_validated`] never applies an unchecked
+/// action. It borrows the board it was made from so that board cannot change before it is
+/// applied, and other boards refuse it unless they hold the same position.
+#[derive(Clone, Debug)]
+pub struct ValidatedAction<'a> {
+    board: &'a GameBoard,
+    action: Action,
+    /// Touched squares with what they hold after the action.
+    changes: Vec<(Coordinate, Option<PieceInstance>)>,
+    outcome: ActionOutcome,
+}
+impl ValidatedAction<'_> {
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+    /// What applying the action will do.
+    pub fn outcome(&self) -> &ActionOutcome {
+        &self.outcome
+    }
+    pub fn into_action(self) -> Action {
+        self.action
+    }
+}
+
+impl GameBoard {
+    /// Checks `action` against this board, for [`GameBoard::apply_validated`].
+    pub fn validate(&self, action: &Action) -> Result<ValidatedAction<'_>, ActionError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("validate", ?action).entered();
+        let speculative = self.apply_speculative(action);
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &speculative {
+            tracing::debug!(?error, "validation failed");
+        }
+        let speculative = speculative?;
+        let changes = speculative
+            .touched()
+            .map(|position| {
+                let piece = speculative
+                    .piece(position)
+                    .expect("Touched squares are on the board");
+                (position, piece)
+            })
+            .collect();
+        Ok(ValidatedAction {
+            board: self,
+            action: action.clone(),
+            changes,
+            outcome: speculative.outcome().clone(),
+        })
+    }
+    /// The board after an action checked by [`GameBoard::validate`], without checking it again.
+    ///
+    /// Fails with [`ActionError::StaleValidation`] unless this is the board the action was
+    /// validated on or holds the same position.
+    pub fn apply_validated(
+        &self,
+        validated: ValidatedAction<'_>,
+    ) -> Result<(GameBoard, ActionOutcome), ActionError> {
+        let same = core::ptr::eq(self, validated.board)
+            || (self.board == validated.board.board
+                && self.piece_rules == validated.board.piece_rules);
+        if !same {
+            return Err(ActionError::StaleValidation);
+        }
+        let mut board = self.clone();
+        if !board.is_indexed() {
+            board.index_pieces();
+        }
+        for (position, piece) in validated.changes {
+            board
+                .set_piece(position, piece)
+                .expect("Validated squares are on the board");
+        }
+        Ok((board, validated.outcome))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::{Action, ActionError, ActionType};
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::engine::Engine;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::movegen::legal_actions;
+    use crate::testing::{assert_board_consistent, small_ruleset};
+
+    #[test]
+    fn validate_test() {
+        let mut game = Game::new(small_ruleset(2)).unwrap();
+        let mut policy = RandomPolicy::new(6);
+        while game.history().len() < 20 && !game.is_finished() {
+            let board = game.board();
+            for action in legal_actions(board, game.to_move()) {
+                let validated = board.validate(&action).unwrap();
+                assert_eq!(validated.action(), &action);
+                let (applied, outcome) = board.apply_validated(validated).unwrap();
+                assert_board_consistent(&applied);
+                let speculative = board.apply_speculative(&action).unwrap();
+                assert_eq!(outcome.captures, speculative.captures());
+                assert_eq!(applied.board, speculative.commit().board);
+            }
+            let action = policy.choose_action(&game).unwrap();
+            game.apply(&action).unwrap();
+        }
+
+        let off_board = Action {
+            start_pos: Coordinate::new(-1, 0),
+            action_type: ActionType::Move(Direction::North),
+        };
+        assert!(matches!(
+            game.board().validate(&off_board),
+            Err(ActionError::InvalidStartPosition)
+        ));
+    }
+
+    #[test]
+    fn stale_test() {
+        let game = Game::new(small_ruleset(1)).unwrap();
+        let board = game.board();
+        let actions = legal_actions(board, game.to_move());
+        let first = &actions[0];
+        let other = actions.iter().find(|action| *action != first).unwrap();
+        let validated = board.validate(first).unwrap();
+        let (moved, _) = board.apply_action(other).unwrap();
+        assert!(matches!(
+            moved.apply_validated(validated.clone()),
+            Err(ActionError::StaleValidation)
+        ));
+        assert_eq!(
+            board.clone().apply_validated(validated).unwrap().0.board,
+            board.apply_action(first).unwrap().0.board
+        );
+    }
+
+    #[cfg(all(feature = "std", feature = "tracing"))]
+    #[test]
+    fn tracing_test() {
+        use std::fmt::Debug;
+        use std::string::{String, ToString};
+        use std::sync::{Arc, Mutex};
+        use std::vec::Vec;
+
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Records the names of spans and the messages of events.
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+        impl Visit for Recorder {
+            fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+                if field.name() == "message" {
+                    self.0.lock().unwrap().push(format!("{:?}", value));
+                }
+            }
+        }
+        impl Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut lines = self.0.lock().unwrap();
+                lines.push(span.metadata().name().to_string());
+                Id::from_u64(lines.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                event.record(&mut Recorder(self.0.clone()));
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let game = Game::new(small_ruleset(2)).unwrap();
+        let off_board = Action {
+            start_pos: Coordinate::new(-1, 0),
+            action_type: ActionType::Move(Direction::North),
+        };
+        tracing::subscriber::with_default(Recorder(lines.clone()), || {
+            assert!(game.board().validate(&off_board).is_err());
+        });
+        assert_eq!(
+            *lines.lock().unwrap(),
+            [
+                "validate",
+                "apply_speculative",
+                "invalid action",
+                "validation failed"
+            ]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Index, IndexMut};
+
+/// A row/column position within a [`Grid`].
+pub trait Position {
+    fn row(&self) -> usize;
+    fn column(&self) -> usize;
+
+    fn coordinates(&self) -> (usize, usize) {
+        (self.row(), self.column())
+    }
+}
+impl Position for (usize, usize) {
+    fn row(&self) -> usize {
+        self.0
+    }
+
+    fn column(&self) -> usize {
+        self.1
+    }
+}
+
+/// The dimensions of a [`Grid`].
+pub trait Size {
+    fn rows(&self) -> usize;
+    fn columns(&self) -> usize;
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows(), self.columns())
+    }
+}
+impl Size for (usize, usize) {
+    fn rows(&self) -> usize {
+        self.0
+    }
+
+    fn columns(&self) -> usize {
+        self.1
+    }
+}
+
+/// A dense matrix stored in column-major order.
+///
+/// Replaces the `matrix` crate's `Conventional` so boards work without `std`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct Grid<T> {
+    pub rows: usize,
+    pub columns: usize,
+    pub values: Vec<T>,
+}
+impl<T: Clone + Default> Grid<T> {
+    /// Creates a grid filled with `T::default()`.
+    pub fn new(size: impl Size) -> Self {
+        let (rows, columns) = size.dimensions();
+        Self {
+            rows,
+            columns,
+            values: vec![T::default(); rows * columns],
+        }
+    }
+}
+impl<T> Grid<T> {
+    /// Creates a grid from column-major values.
+    pub fn from_vec(size: impl Size, values: Vec<T>) -> Self {
+        let (rows, columns) = size.dimensions();
+        assert_eq!(rows * columns, values.len(), "Values must fill the grid");
+        Self {
+            rows,
+            columns,
+            values,
+        }
+    }
+
+    pub fn get(&self, position: impl Position) -> Option<&T> {
+        let (row, column) = position.coordinates();
+        if row < self.rows && column < self.columns {
+            self.values.get(column * self.rows + row)
+        } else {
+            None
+        }
+    }
+    pub fn get_mut(&mut self, position: impl Position) -> Option<&mut T> {
+        let (row, column) = position.coordinates();
+        if row < self.rows && column < self.columns {
+            self.values.get_mut(column * self.rows + row)
+        } else {
+            None
+        }
+    }
+}
+impl<T> Size for Grid<T> {
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn columns(&self) -> usize {
+        self.columns
+    }
+}
+impl<T, P: Position> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: P) -> &Self::Output {
+        self.get(index).expect("Position out of bounds")
+    }
+}
+impl<T, P: Position> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, index: P) -> &mut Self::Output {
+        self.get_mut(index).expect("Position out of bounds")
+    }
+}
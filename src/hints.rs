@@ -0,0 +1,211 @@
+//! Suggested actions with short reasons for beginners, see [`suggest`].
+//!
+//! Actions are scored by a shallow search with [`ParamEvaluator`], and each is given the single
+//! most telling reason from what it does on the board. Reasons are [`Message`]s so apps show
+//! them in their [`Locale`](crate::locale::Locale).
+//!
+//! [`Message`]: crate::locale::Message
+
+use alloc::vec::Vec;
+
+use crate::action::Action;
+use crate::eval::{Evaluator, ParamEvaluator, WIN_SCORE};
+use crate::game::{Game, GameOutcome};
+use crate::game_board::board::Board;
+use crate::game_board::Color;
+
+/// How far [`suggest`] looks ahead.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HintLevel {
+    /// Only the position right after the action.
+    #[default]
+    Beginner,
+    /// The opponent's best reply as well.
+    Intermediate,
+    /// The mover's next action as well.
+    Advanced,
+}
+impl HintLevel {
+    /// Actions searched, counting both colors.
+    pub fn depth(self) -> usize {
+        match self {
+            HintLevel::Beginner => 1,
+            HintLevel::Intermediate => 2,
+            HintLevel::Advanced => 3,
+        }
+    }
+}
+
+/// An action suggested by [`suggest`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hint {
+    pub action: Action,
+    /// Search score for the color to move, see [`Evaluator::evaluate`].
+    pub score: i32,
+    pub reason: HintReason,
+}
+
+/// Why an action is worth playing, in the order [`suggest`] prefers them.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HintReason {
+    /// The action wins the game.
+    Wins,
+    /// The action captures this many pieces.
+    Captures(usize),
+    /// The moved piece could be captured before and cannot after.
+    EscapesThreat,
+    /// The moved piece ends closer to the goal it scores in.
+    ApproachesGoal,
+    /// Nothing stands out, the search prefers the resulting position.
+    StrongPosition,
+}
+
+/// Every legal action of `game` with a reason, best first.
+///
+/// Beginner UIs usually show the first few. Empty once the game is over.
+pub fn suggest(game: &Game, level: HintLevel) -> Vec<Hint> {
+    let evaluator = ParamEvaluator::default();
+    let mover = game.to_move();
+    let board = game.board();
+    let mut out: Vec<Hint> = game
+        .legal_actions()
+        .into_iter()
+        .filter_map(|action| {
+            let mut after = game.clone();
+            let outcome = after.apply(&action).ok()?;
+            let score = minimax(
+                &evaluator,
+                &after,
+                level.depth() - 1,
+                mover,
+                (-WIN_SCORE - 1, WIN_SCORE + 1),
+            );
+            let destination = board.destination(&action);
+            let reason = if after.result() == Some(GameOutcome::Win(mover)) {
+                HintReason::Wins
+            } else if !outcome.captures.is_empty() {
+                HintReason::Captures(outcome.captures.len())
+            } else if destination.is_some_and(|destination| {
+                board.is_threatened(action.start_pos, mover.opponent())
+                    && !after.board().is_threatened(destination, mover.opponent())
+            }) {
+                HintReason::EscapesThreat
+            } else if destination.is_some_and(|destination| {
+                let before = board.goal_distance(action.start_pos, mover.opponent());
+                let after = after.board().goal_distance(destination, mover.opponent());
+                matches!((before, after), (Some(before), Some(after)) if after < before)
+            }) {
+                HintReason::ApproachesGoal
+            } else {
+                HintReason::StrongPosition
+            };
+            Some(Hint {
+                action,
+                score,
+                reason,
+            })
+        })
+        .collect();
+    // Stable, so equal scores keep the legal action order
+    out.sort_by_key(|hint| -hint.score);
+    out
+}
+
+/// The score of `game` for `perspective` searching `depth` more actions within `window`.
+fn minimax(
+    evaluator: &ParamEvaluator,
+    game: &Game,
+    depth: usize,
+    perspective: Color,
+    (mut alpha, mut beta): (i32, i32),
+) -> i32 {
+    let actions = game.legal_actions();
+    if depth == 0 || actions.is_empty() {
+        return evaluator.evaluate(game, perspective);
+    }
+    let maximizing = game.to_move() == perspective;
+    let mut best = if maximizing { alpha } else { beta };
+    for action in actions {
+        let mut after = game.clone();
+        if after.apply(&action).is_err() {
+            continue;
+        }
+        let score = minimax(evaluator, &after, depth - 1, perspective, (alpha, beta));
+        if maximizing {
+            best = best.max(score);
+            alpha = alpha.max(score);
+        } else {
+            best = best.min(score);
+            beta = beta.min(score);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+    use alloc::vec;
+
+    use crate::action::{Action, ActionType};
+    use crate::collections::HashMap;
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game::scenario::Scenario;
+    use crate::game::Game;
+    use crate::game_board::Color;
+    use crate::hints::{suggest, HintLevel, HintReason};
+    use crate::locale::{English, Locale};
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn suggest_test() {
+        let game = Game::new(small_ruleset(3)).unwrap();
+        for level in [HintLevel::Beginner, HintLevel::Intermediate].iter() {
+            let hints = suggest(&game, *level);
+            assert_eq!(hints.len(), game.legal_actions().len());
+            assert!(hints.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        }
+
+        let mut position = HashMap::new();
+        position.insert(
+            Color::Red,
+            vec![(0, vec![Coordinate::new(4, 2)])].into_iter().collect(),
+        );
+        position.insert(
+            Color::Blue,
+            vec![(1, vec![Coordinate::new(5, 2)])].into_iter().collect(),
+        );
+        let scenario = Scenario {
+            name: "Capture".into(),
+            description: String::new(),
+            ruleset: small_ruleset(0),
+            position,
+            to_move: Color::Red,
+            restrictions: vec![],
+            victory_conditions: None,
+            move_limit: None,
+        };
+        let game = scenario.start().unwrap().into_game();
+        let hints = suggest(&game, HintLevel::Advanced);
+        assert_eq!(
+            hints[0].action,
+            Action {
+                start_pos: Coordinate::new(4, 2),
+                action_type: ActionType::Jump([Direction::South].into()),
+            }
+        );
+        assert_eq!(hints[0].reason, HintReason::Wins);
+        assert_eq!(English.localize(&hints[0].reason), "Wins the game.");
+        assert_eq!(
+            English.localize(&HintReason::Captures(2)),
+            "Captures 2 pieces."
+        );
+    }
+}
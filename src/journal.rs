@@ -0,0 +1,453 @@
+//! Crash-safe logs of live games.
+//!
+//! A [`Journal`] appends each [`JournalEntry`] to a file as one line and syncs it to disk before
+//! returning, so [`Journal::recover`] can rebuild the game after a crash. A last line torn by
+//! the crash is dropped. Lines are
+//!
+//! ```text
+//! kapto-journal 1 <ruleset fingerprint in hex>
+//! <stamp_ms> seed <seed>
+//! <stamp_ms> place <color> <piece index> <square>
+//! <stamp_ms> action <action>
+//! ```
+//!
+//! with actions and squares in [`notation`](crate::notation), see [`JournalEntry`] for the rest.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter, Write as _};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::action::Action;
+use crate::coordinate::Coordinate;
+use crate::game::{Game, GameError};
+use crate::game_board::Color;
+use crate::notation::{format_action, parse_action, CoordinateScheme};
+use crate::ruleset::verified::RulesetHandle;
+
+/// Version of the journal format, written in the header.
+pub const JOURNAL_VERSION: u32 = 1;
+
+const MAGIC: &str = "kapto-journal";
+const SCHEME: CoordinateScheme = CoordinateScheme::Algebraic;
+
+/// Something done to a game, entries replayed in order rebuild it.
+///
+/// Journal what was done rather than the [`GameEvent`](crate::game::event::GameEvent)s it
+/// caused, placements made by a timeout are made again by replaying the tick.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JournalEntry {
+    /// [`Game::set_seed`], written `seed <seed>`.
+    ///
+    /// Journal it before the first action of a game with a
+    /// [`ChanceRule`](crate::ruleset::chance_rule::ChanceRule), recovery rolls the wrong faces
+    /// without it.
+    Seed(u64),
+    /// [`Game::place`] by `color`, written `place <color> <piece index> <square>`.
+    Place {
+        color: Color,
+        piece_index: usize,
+        position: Coordinate,
+    },
+    /// [`Game::finish_placing`], written `finish`.
+    FinishPlacing,
+    /// [`Game::tick_placement`], written `tick <elapsed ms>`.
+    Tick(u64),
+    /// [`Game::apply`], written `action <action>`.
+    Action(Action),
+    /// [`Game::huff`], written `huff <square>`.
+    Huff(Coordinate),
+    /// [`Game::offer_draw`], written `offer <color>`.
+    OfferDraw(Color),
+    /// [`Game::accept_draw`], written `accept`.
+    AcceptDraw,
+    /// [`Game::decline_draw`], written `decline`.
+    DeclineDraw,
+}
+impl JournalEntry {
+    /// Does the entry to `game`, `index` is its position in the journal for errors.
+    pub fn apply(&self, game: &mut Game, index: usize) -> JournalResult<()> {
+        let placement_error =
+            |error| JournalError::GameError(GameError::IllegalPlacement { index, error });
+        match self {
+            Self::Seed(seed) => game.set_seed(*seed),
+            Self::Place {
+                color,
+                piece_index,
+                position,
+            } => {
+                let to_place = game.placement().and_then(|phase| phase.to_place());
+                if to_place != Some(*color) {
+                    return Err(JournalError::WrongColor { index });
+                }
+                game.place(*piece_index, *position)
+                    .map_err(placement_error)?;
+            }
+            Self::FinishPlacing => game.finish_placing().map_err(placement_error)?,
+            Self::Tick(elapsed_ms) => {
+                game.tick_placement(*elapsed_ms);
+            }
+            Self::Action(action) => {
+                game.apply(action).map_err(|error| {
+                    JournalError::GameError(GameError::IllegalAction {
+                        ply: game.history().len(),
+                        error,
+                    })
+                })?;
+            }
+            Self::Huff(position) => {
+                game.huff(*position).map_err(|error| {
+                    JournalError::GameError(GameError::IllegalHuff {
+                        index: game.huffs().len(),
+                        error,
+                    })
+                })?;
+            }
+            Self::OfferDraw(color) => game.offer_draw(*color),
+            Self::AcceptDraw => {
+                game.accept_draw();
+            }
+            Self::DeclineDraw => game.decline_draw(),
+        }
+        Ok(())
+    }
+
+    fn write(&self, out: &mut String) {
+        let color = |color: &Color| match color {
+            Color::Red => "red",
+            Color::Blue => "blue",
+        };
+        let _ = match self {
+            Self::Seed(seed) => write!(out, "seed {}", seed),
+            Self::Place {
+                color: placer,
+                piece_index,
+                position,
+            } => write!(
+                out,
+                "place {} {} {}",
+                color(placer),
+                piece_index,
+                SCHEME.format_square(*position)
+            ),
+            Self::FinishPlacing => write!(out, "finish"),
+            Self::Tick(elapsed_ms) => write!(out, "tick {}", elapsed_ms),
+            Self::Action(action) => write!(out, "action {}", format_action(action, SCHEME)),
+            Self::Huff(position) => write!(out, "huff {}", SCHEME.format_square(*position)),
+            Self::OfferDraw(offerer) => write!(out, "offer {}", color(offerer)),
+            Self::AcceptDraw => write!(out, "accept"),
+            Self::DeclineDraw => write!(out, "decline"),
+        };
+    }
+    /// `None` if the text is not an entry.
+    fn parse(text: &str) -> Option<Self> {
+        let color = |text: &str| match text {
+            "red" => Some(Color::Red),
+            "blue" => Some(Color::Blue),
+            _ => None,
+        };
+        let mut words = text.split(' ');
+        let out = match words.next()? {
+            "seed" => Self::Seed(words.next()?.parse().ok()?),
+            "place" => Self::Place {
+                color: color(words.next()?)?,
+                piece_index: words.next()?.parse().ok()?,
+                position: SCHEME.parse_square(words.next()?).ok()?,
+            },
+            "finish" => Self::FinishPlacing,
+            "tick" => Self::Tick(words.next()?.parse().ok()?),
+            "action" => Self::Action(parse_action(words.next()?, SCHEME).ok()?),
+            "huff" => Self::Huff(SCHEME.parse_square(words.next()?).ok()?),
+            "offer" => Self::OfferDraw(color(words.next()?)?),
+            "accept" => Self::AcceptDraw,
+            "decline" => Self::DeclineDraw,
+            _ => return None,
+        };
+        match words.next() {
+            Some(_) => None,
+            None => Some(out),
+        }
+    }
+}
+
+/// What [`Journal::recover`] read back.
+#[derive(Clone, Debug)]
+pub struct Recovery {
+    /// The game after every entry.
+    pub game: Game,
+    /// Every entry with its stamp, in order.
+    pub entries: Vec<(u64, JournalEntry)>,
+    /// Whether a torn last line was dropped.
+    pub torn: bool,
+}
+
+/// An append-only log of one game, see the [module docs](self).
+#[derive(Debug)]
+pub struct Journal {
+    file: File,
+    entries: usize,
+}
+impl Journal {
+    /// Creates a journal at `path` for a game under `ruleset`, replacing any file there.
+    pub fn create(path: impl AsRef<Path>, ruleset: &RulesetHandle) -> io::Result<Self> {
+        let mut file = File::create(path)?;
+        let mut header = String::new();
+        let _ = writeln!(
+            header,
+            "{} {} {}",
+            MAGIC,
+            JOURNAL_VERSION,
+            fingerprint_hex(ruleset)
+        );
+        file.write_all(header.as_bytes())?;
+        file.sync_all()?;
+        Ok(Self { file, entries: 0 })
+    }
+    /// Entries in the journal.
+    pub fn len(&self) -> usize {
+        self.entries
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries == 0
+    }
+    /// Writes `entry` at `stamp_ms` and syncs it to disk.
+    ///
+    /// Append once the game accepted the entry and before answering the players, the stamp is
+    /// any clock the operator keeps such as milliseconds since the game started.
+    pub fn append(&mut self, stamp_ms: u64, entry: &JournalEntry) -> io::Result<()> {
+        let mut line = String::new();
+        let _ = write!(line, "{} ", stamp_ms);
+        entry.write(&mut line);
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        self.file.sync_data()?;
+        self.entries += 1;
+        Ok(())
+    }
+
+    /// Rebuilds the game journaled at `path` and reopens the journal to continue it.
+    ///
+    /// A torn last line is truncated from the file.
+    pub fn recover(
+        path: impl AsRef<Path>,
+        ruleset: impl Into<RulesetHandle>,
+    ) -> JournalResult<(Self, Recovery)> {
+        let path = path.as_ref();
+        let mut bytes = Vec::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_end(&mut bytes))
+            .map_err(JournalError::Io)?;
+        let text = String::from_utf8_lossy(&bytes);
+        // Complete lines end with a newline, anything after the last one was torn
+        let complete = text.rfind('\n').map_or(0, |end| end + 1);
+        let torn = complete < text.len();
+        let mut lines = text[..complete].lines();
+
+        let ruleset = ruleset.into();
+        let header = lines.next().ok_or(JournalError::MissingHeader)?;
+        let mut words = header.split(' ');
+        if words.next() != Some(MAGIC) {
+            return Err(JournalError::MissingHeader);
+        }
+        let version = words
+            .next()
+            .and_then(|version| version.parse().ok())
+            .ok_or(JournalError::MissingHeader)?;
+        if version != JOURNAL_VERSION {
+            return Err(JournalError::UnsupportedVersion(version));
+        }
+        if words.next() != Some(fingerprint_hex(&ruleset).as_str()) {
+            return Err(JournalError::RulesetMismatch);
+        }
+
+        let mut game = Game::from_handle(ruleset)?;
+        let mut entries = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let (stamp, entry) = line
+                .split_once(' ')
+                .and_then(|(stamp, entry)| Some((stamp.parse().ok()?, JournalEntry::parse(entry)?)))
+                .ok_or(JournalError::MalformedEntry { index })?;
+            entry.apply(&mut game, index)?;
+            entries.push((stamp, entry));
+        }
+
+        let file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .and_then(|file| {
+                if torn {
+                    file.set_len(complete as u64)?;
+                    file.sync_all()?;
+                }
+                Ok(file)
+            })
+            .map_err(JournalError::Io)?;
+        let journal = Self {
+            file,
+            entries: entries.len(),
+        };
+        Ok((
+            journal,
+            Recovery {
+                game,
+                entries,
+                torn,
+            },
+        ))
+    }
+}
+
+fn fingerprint_hex(ruleset: &RulesetHandle) -> String {
+    let mut out = String::new();
+    for byte in ruleset.fingerprint() {
+        let _ = write!(out, "{:02x}", byte);
+    }
+    out
+}
+
+pub type JournalResult<T> = Result<T, JournalError>;
+#[derive(Debug)]
+pub enum JournalError {
+    Io(io::Error),
+    /// The file does not start with a journal header.
+    MissingHeader,
+    UnsupportedVersion(u32),
+    /// The journal was written for a different ruleset.
+    RulesetMismatch,
+    /// Entry `index` is not a valid entry, only the last line may be torn.
+    MalformedEntry {
+        index: usize,
+    },
+    /// Entry `index` places for the color not placing.
+    WrongColor {
+        index: usize,
+    },
+    GameError(GameError),
+}
+impl Display for JournalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+impl Error for JournalError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::GameError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+impl From<GameError> for JournalError {
+    fn from(from: GameError) -> Self {
+        Self::GameError(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::io::Write;
+
+    use std::vec;
+
+    use crate::direction::Directions;
+    use crate::engine::Engine;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::journal::{Journal, JournalEntry, JournalError};
+    use crate::ruleset::chance_rule::{ChanceFace, ChanceRule};
+    use crate::ruleset::verified::RulesetHandle;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn journal_test() {
+        let path = std::env::temp_dir().join(format!("kapto-journal-{}", std::process::id()));
+        let handle = RulesetHandle::new(small_ruleset(3)).unwrap();
+        let mut journal = Journal::create(&path, &handle).unwrap();
+        let mut game = Game::from_handle(handle.clone()).unwrap();
+        let mut policy = RandomPolicy::new(4);
+        for stamp in 0..8 {
+            let action = policy.choose_action(&game).unwrap();
+            game.apply(&action).unwrap();
+            journal
+                .append(stamp * 100, &JournalEntry::Action(action))
+                .unwrap();
+        }
+        journal
+            .append(800, &JournalEntry::OfferDraw(game.to_move()))
+            .unwrap();
+        game.offer_draw(game.to_move());
+        drop(journal);
+
+        // A crash in the middle of the next write
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"900 acti")
+            .unwrap();
+        let (mut journal, recovery) = Journal::recover(&path, handle.clone()).unwrap();
+        assert!(recovery.torn);
+        assert_eq!(recovery.entries.len(), 9);
+        assert_eq!(recovery.entries[8].0, 800);
+        assert_eq!(recovery.game.history(), game.history());
+        assert_eq!(recovery.game.draw_offer(), game.draw_offer());
+
+        journal.append(900, &JournalEntry::AcceptDraw).unwrap();
+        drop(journal);
+        let (_, recovery) = Journal::recover(&path, handle).unwrap();
+        assert!(!recovery.torn);
+        assert!(recovery.game.is_finished());
+
+        assert!(matches!(
+            Journal::recover(&path, RulesetHandle::new(small_ruleset(4)).unwrap()),
+            Err(JournalError::RulesetMismatch)
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn seed_test() {
+        let path = std::env::temp_dir().join(format!("kapto-journal-seed-{}", std::process::id()));
+        let mut ruleset = small_ruleset(3);
+        ruleset.chance_rule = ChanceRule {
+            faces: vec![
+                ChanceFace {
+                    pieces: Some(vec![0]),
+                    directions: None,
+                },
+                ChanceFace {
+                    pieces: None,
+                    directions: Some(Directions::CARDINAL),
+                },
+            ],
+        };
+        let handle = RulesetHandle::new(ruleset).unwrap();
+        let mut journal = Journal::create(&path, &handle).unwrap();
+        let mut game = Game::from_handle(handle.clone()).unwrap();
+        game.set_seed(11);
+        journal.append(0, &JournalEntry::Seed(11)).unwrap();
+        let mut policy = RandomPolicy::new(4);
+        for stamp in 1..12 {
+            let action = policy.choose_action(&game).unwrap();
+            game.apply(&action).unwrap();
+            journal
+                .append(stamp * 100, &JournalEntry::Action(action))
+                .unwrap();
+        }
+        drop(journal);
+
+        let (_, recovery) = Journal::recover(&path, handle).unwrap();
+        assert_eq!(recovery.entries[0], (0, JournalEntry::Seed(11)));
+        assert_eq!(recovery.game.seed(), 11);
+        assert_eq!(recovery.game.history(), game.history());
+        assert_eq!(recovery.game.rolls(), game.rolls());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,10 +1,64 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_debug_implementations)]
+// Verification errors carry the offending definitions by value.
+#![allow(clippy::result_large_err)]
+
+extern crate alloc;
 
 pub mod action;
+pub mod annotation;
+pub mod broadcast;
 pub mod coordinate;
+pub mod dataset;
 pub mod direction;
+pub mod engine;
+pub mod eval;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod fixtures;
+#[cfg(feature = "formats")]
+pub mod formats;
+pub mod game;
 pub mod game_board;
+pub mod grid;
+pub mod hints;
+#[cfg(feature = "std")]
+pub mod journal;
+pub mod locale;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod movegen;
+#[cfg(feature = "net")]
+pub mod net;
+pub mod notation;
+pub mod pdn;
+pub mod puzzles;
+#[cfg(feature = "std")]
+pub mod rating;
+pub mod rng;
 pub mod ruleset;
+pub mod runner;
+#[cfg(feature = "std")]
+pub mod search;
+#[cfg(feature = "std")]
+pub mod selfplay;
+pub mod series;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod tournament;
+#[cfg(feature = "std")]
+pub mod tuner;
+
+/// Hash collections used throughout the crate.
+///
+/// These are the `std` collections when the `std` feature is enabled and `hashbrown` otherwise.
+pub mod collections {
+    #[cfg(not(feature = "std"))]
+    pub use hashbrown::{hash_map, hash_set, HashMap, HashSet};
+    #[cfg(feature = "std")]
+    pub use std::collections::{hash_map, hash_set, HashMap, HashSet};
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,495 @@
+//! Stable keys and default English text for strings shown to players.
+//!
+//! Apps translate by implementing [`Locale`] or filling a [`Catalog`], keys missing from a
+//! locale fall back to [`English`]. Templates name their arguments in braces, such as
+//! `"{color} cannot move now."`.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+use crate::action::ActionError;
+use crate::collections::HashMap;
+use crate::game::huff::HuffError;
+use crate::game::placement::PlacementError;
+use crate::game::GameError;
+use crate::game_board::Color;
+use crate::hints::HintReason;
+use crate::ruleset::piece_definition::PieceDefinitionError;
+use crate::ruleset::RulesetError;
+
+/// A string shown to players.
+pub trait Message {
+    /// Stable key of the template, such as `action.wrong_color`.
+    fn key(&self) -> &'static str;
+    /// Values for the template's arguments, messages among them localized in `locale`.
+    fn args(&self, _locale: &dyn Locale) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+/// A message catalog.
+pub trait Locale {
+    /// The template for `key`, `None` to fall back to [`English`].
+    fn template(&self, key: &str) -> Option<&str>;
+
+    /// `message` in this locale.
+    fn localize(&self, message: &dyn Message) -> String
+    where
+        Self: Sized,
+    {
+        localize(self, message)
+    }
+    /// The piece definition named `name`, looked up as `piece.<name>` and left as is when
+    /// missing. Piece names come from rulesets so English has none.
+    fn piece_name(&self, name: &str) -> String {
+        self.template(&format!("piece.{}", name))
+            .unwrap_or(name)
+            .to_string()
+    }
+}
+
+/// The template for `key` in `locale`, falling back to [`English`] then to the key itself.
+pub fn text<'a>(locale: &'a dyn Locale, key: &'a str) -> &'a str {
+    locale
+        .template(key)
+        .or_else(|| English.template(key))
+        .unwrap_or(key)
+}
+
+/// `message` in `locale`, see [`text`].
+pub fn localize(locale: &dyn Locale, message: &dyn Message) -> String {
+    let mut out = text(locale, message.key()).to_string();
+    for (name, value) in message.args(locale) {
+        out = out.replace(&format!("{{{}}}", name), &value);
+    }
+    out
+}
+
+/// The default English strings.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct English;
+impl Locale for English {
+    fn template(&self, key: &str) -> Option<&str> {
+        ENGLISH
+            .iter()
+            .find(|(english_key, _)| *english_key == key)
+            .map(|(_, template)| *template)
+    }
+}
+
+/// Templates by key, such as loaded from a translation file.
+#[derive(Clone, Debug, Default)]
+pub struct Catalog {
+    templates: HashMap<String, String>,
+}
+impl Catalog {
+    pub fn insert(&mut self, key: impl Into<String>, template: impl Into<String>) {
+        self.templates.insert(key.into(), template.into());
+    }
+    /// Every key with an English default, for translators.
+    pub fn keys() -> impl Iterator<Item = &'static str> {
+        ENGLISH.iter().map(|(key, _)| *key)
+    }
+}
+impl Locale for Catalog {
+    fn template(&self, key: &str) -> Option<&str> {
+        self.templates.get(key).map(String::as_str)
+    }
+}
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Catalog {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut out = Self::default();
+        for (key, template) in iter {
+            out.insert(key, template);
+        }
+        out
+    }
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("color.red", "Red"),
+    ("color.blue", "Blue"),
+    (
+        "action.invalid_start_position",
+        "That square is not on the board.",
+    ),
+    (
+        "action.no_piece_at_start",
+        "There is no piece to move there.",
+    ),
+    ("action.piece_on_move", "A piece is in the way."),
+    ("action.move_off_board", "That move leaves the board."),
+    ("action.empty_jump", "A jump needs at least one step."),
+    ("action.piece_on_jump", "A piece is on the landing square."),
+    ("action.no_piece_jumped", "There is no piece to jump over."),
+    ("action.jump_off_board", "That jump leaves the board."),
+    (
+        "action.jumped_back",
+        "A jump cannot go back to a square already visited.",
+    ),
+    ("action.cannot_jump", "This piece cannot jump."),
+    (
+        "action.jump_limit_exceeded",
+        "This piece can only chain {limit} jumps.",
+    ),
+    (
+        "action.jump_direction_not_allowed",
+        "This piece cannot jump that way.",
+    ),
+    (
+        "action.repeated_jump",
+        "A piece cannot be jumped twice along the same line.",
+    ),
+    ("action.chain_unfinished", "This piece must keep jumping."),
+    ("action.goal_locked", "This piece cannot leave the goal."),
+    (
+        "action.left_goal",
+        "This piece can only move to another goal square.",
+    ),
+    (
+        "action.entered_own_goal",
+        "This piece cannot enter its own goal.",
+    ),
+    ("action.wrong_color", "That piece belongs to {color}."),
+    ("action.game_finished", "The game is over."),
+    (
+        "action.placement_in_progress",
+        "Pieces are still being placed.",
+    ),
+    ("action.pass_not_allowed", "Passing is not allowed now."),
+    ("action.blocked", "That square is blocked."),
+    ("action.stuck_on_sticky", "A jump ends on a sticky square."),
+    ("action.move_after_step", "The action has already moved."),
+    ("action.not_rolled", "The roll does not allow this action."),
+    (
+        "action.capture_forced",
+        "A piece that must capture can capture.",
+    ),
+    (
+        "action.stale_validation",
+        "The action was checked on a different board.",
+    ),
+    ("placement.phase_finished", "Placing has finished."),
+    ("placement.piece_not_found", "There is no such piece."),
+    (
+        "placement.piece_limit_reached",
+        "No more of that piece can be placed.",
+    ),
+    (
+        "placement.piece_not_available",
+        "That piece belongs to the other color.",
+    ),
+    (
+        "placement.turn_points_exceeded",
+        "That piece costs more points than are left this turn.",
+    ),
+    (
+        "placement.outside_area",
+        "Pieces cannot be placed on {square}.",
+    ),
+    (
+        "placement.position_occupied",
+        "{square} already has a piece.",
+    ),
+    (
+        "placement.square_too_costly",
+        "{square} costs more points than are left.",
+    ),
+    ("huff.game_finished", "The game is over."),
+    (
+        "huff.not_huffable",
+        "The piece on {square} cannot be huffed.",
+    ),
+    ("game.invalid_ruleset", "The rules are invalid."),
+    (
+        "game.invalid_starting_positions",
+        "The starting positions are invalid.",
+    ),
+    (
+        "game.illegal_action",
+        "Action {ply} of the record is illegal: {error}",
+    ),
+    (
+        "game.illegal_placement",
+        "Placement {index} of the record is illegal: {error}",
+    ),
+    (
+        "game.illegal_huff",
+        "Huff {index} of the record is illegal: {error}",
+    ),
+    (
+        "game.illegal_roll",
+        "Roll {ply} of the record is face {face}, which the die does not have.",
+    ),
+    (
+        "game.illegal_round",
+        "Round {index} of the record plays actions the record does not have.",
+    ),
+    (
+        "ruleset.too_many_pieces",
+        "The ruleset has too many pieces.",
+    ),
+    ("ruleset.piece_duplicated", "Two pieces have the same name."),
+    ("ruleset.piece_definition", "A piece definition is invalid."),
+    (
+        "ruleset.capture_without_jumps",
+        "A piece captures by jumping but cannot jump.",
+    ),
+    (
+        "ruleset.capture_without_moves",
+        "A piece captures by moving but cannot move.",
+    ),
+    (
+        "ruleset.forced_without_captures",
+        "A piece must capture but has no way to capture.",
+    ),
+    (
+        "ruleset.goal_rule_without_goals",
+        "A piece has goal rules but the board has no goals.",
+    ),
+    ("ruleset.board_type", "The board is invalid."),
+    (
+        "ruleset.starting_positions",
+        "The starting positions are invalid.",
+    ),
+    (
+        "ruleset.victory_condition",
+        "A victory condition is invalid.",
+    ),
+    (
+        "ruleset.scoring",
+        "The scoring gives points to a piece that does not exist.",
+    ),
+    (
+        "ruleset.chance_rule",
+        "A face of the die allows nothing or a piece that does not exist.",
+    ),
+    ("hint.wins", "Wins the game."),
+    ("hint.capture", "Captures a piece."),
+    ("hint.captures", "Captures {count} pieces."),
+    ("hint.escapes_threat", "Moves a threatened piece to safety."),
+    ("hint.approaches_goal", "Brings a piece closer to the goal."),
+    ("hint.strong_position", "Leads to the strongest position."),
+    ("summary.board", "Board"),
+    ("summary.goals", "Goals"),
+    ("summary.setup", "Setup"),
+    ("summary.to_win", "To win"),
+];
+
+impl Message for Color {
+    fn key(&self) -> &'static str {
+        match self {
+            Color::Red => "color.red",
+            Color::Blue => "color.blue",
+        }
+    }
+}
+
+impl Message for ActionError {
+    fn key(&self) -> &'static str {
+        match self {
+            ActionError::InvalidStartPosition => "action.invalid_start_position",
+            ActionError::NoPieceAtStart => "action.no_piece_at_start",
+            ActionError::PieceOnMove(_) => "action.piece_on_move",
+            ActionError::MoveOffBoard => "action.move_off_board",
+            ActionError::EmptyJump => "action.empty_jump",
+            ActionError::PieceOnJump(_) => "action.piece_on_jump",
+            ActionError::NoPieceJumped => "action.no_piece_jumped",
+            ActionError::JumpOffBoard => "action.jump_off_board",
+            ActionError::JumpedBackToPrevPosition => "action.jumped_back",
+            ActionError::CannotJump => "action.cannot_jump",
+            ActionError::JumpLimitExceeded(_) => "action.jump_limit_exceeded",
+            ActionError::JumpDirectionNotAllowed(_) => "action.jump_direction_not_allowed",
+            ActionError::RepeatedJump => "action.repeated_jump",
+            ActionError::ChainUnfinished => "action.chain_unfinished",
+            ActionError::GoalLocked => "action.goal_locked",
+            ActionError::LeftGoal => "action.left_goal",
+            ActionError::EnteredOwnGoal => "action.entered_own_goal",
+            ActionError::WrongColor(_) => "action.wrong_color",
+            ActionError::GameFinished => "action.game_finished",
+            ActionError::PlacementInProgress => "action.placement_in_progress",
+            ActionError::PassNotAllowed => "action.pass_not_allowed",
+            ActionError::Blocked => "action.blocked",
+            ActionError::StuckOnSticky => "action.stuck_on_sticky",
+            ActionError::MoveAfterStep => "action.move_after_step",
+            ActionError::NotRolled => "action.not_rolled",
+            ActionError::CaptureForced => "action.capture_forced",
+            ActionError::StaleValidation => "action.stale_validation",
+        }
+    }
+    fn args(&self, locale: &dyn Locale) -> Vec<(&'static str, String)> {
+        match self {
+            ActionError::JumpLimitExceeded(limit) => vec![("limit", limit.to_string())],
+            ActionError::WrongColor(color) => vec![("color", localize(locale, color))],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Message for HintReason {
+    fn key(&self) -> &'static str {
+        match self {
+            HintReason::Wins => "hint.wins",
+            HintReason::Captures(1) => "hint.capture",
+            HintReason::Captures(_) => "hint.captures",
+            HintReason::EscapesThreat => "hint.escapes_threat",
+            HintReason::ApproachesGoal => "hint.approaches_goal",
+            HintReason::StrongPosition => "hint.strong_position",
+        }
+    }
+    fn args(&self, _locale: &dyn Locale) -> Vec<(&'static str, String)> {
+        match self {
+            HintReason::Captures(count) => vec![("count", count.to_string())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Message for PlacementError {
+    fn key(&self) -> &'static str {
+        match self {
+            PlacementError::PhaseFinished => "placement.phase_finished",
+            PlacementError::PieceIndexNotFound(_) => "placement.piece_not_found",
+            PlacementError::PieceLimitReached(_) => "placement.piece_limit_reached",
+            PlacementError::PieceNotAvailable(_) => "placement.piece_not_available",
+            PlacementError::TurnPointsExceeded(_) => "placement.turn_points_exceeded",
+            PlacementError::OutsideArea(_) => "placement.outside_area",
+            PlacementError::PositionOccupied(_) => "placement.position_occupied",
+            PlacementError::SquareTooCostly(_) => "placement.square_too_costly",
+        }
+    }
+    fn args(&self, _locale: &dyn Locale) -> Vec<(&'static str, String)> {
+        match self {
+            PlacementError::OutsideArea(square)
+            | PlacementError::PositionOccupied(square)
+            | PlacementError::SquareTooCostly(square) => vec![("square", square.to_string())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Message for HuffError {
+    fn key(&self) -> &'static str {
+        match self {
+            HuffError::GameFinished => "huff.game_finished",
+            HuffError::NotHuffable(_) => "huff.not_huffable",
+        }
+    }
+    fn args(&self, _locale: &dyn Locale) -> Vec<(&'static str, String)> {
+        match self {
+            HuffError::NotHuffable(square) => vec![("square", square.to_string())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Message for RulesetError {
+    fn key(&self) -> &'static str {
+        match self {
+            RulesetError::TooManyPieces(_) => "ruleset.too_many_pieces",
+            RulesetError::PieceDuplicated(_) => "ruleset.piece_duplicated",
+            RulesetError::PieceDefinitionError(error) => match error {
+                PieceDefinitionError::CaptureWithoutJumps(_) => "ruleset.capture_without_jumps",
+                PieceDefinitionError::CaptureWithoutMoves => "ruleset.capture_without_moves",
+                PieceDefinitionError::ForcedWithoutCaptures => "ruleset.forced_without_captures",
+                PieceDefinitionError::GoalRuleWithoutGoals(_) => "ruleset.goal_rule_without_goals",
+                _ => "ruleset.piece_definition",
+            },
+            RulesetError::BoardTypeVerifyError(_) => "ruleset.board_type",
+            RulesetError::StartingPositionsError(_) => "ruleset.starting_positions",
+            RulesetError::VictoryConditionError(_) => "ruleset.victory_condition",
+            RulesetError::ScoringError(_) => "ruleset.scoring",
+            RulesetError::ChanceRuleError(_) => "ruleset.chance_rule",
+        }
+    }
+}
+
+impl Message for GameError {
+    fn key(&self) -> &'static str {
+        match self {
+            GameError::RulesetError(_) => "game.invalid_ruleset",
+            GameError::StartingPositionsError(_) => "game.invalid_starting_positions",
+            GameError::IllegalAction { .. } => "game.illegal_action",
+            GameError::IllegalPlacement { .. } => "game.illegal_placement",
+            GameError::IllegalHuff { .. } => "game.illegal_huff",
+            GameError::IllegalRoll { .. } => "game.illegal_roll",
+            GameError::IllegalRound { .. } => "game.illegal_round",
+        }
+    }
+    fn args(&self, locale: &dyn Locale) -> Vec<(&'static str, String)> {
+        match self {
+            GameError::IllegalAction { ply, error } => vec![
+                ("ply", (ply + 1).to_string()),
+                ("error", localize(locale, error)),
+            ],
+            GameError::IllegalPlacement { index, error } => vec![
+                ("index", (index + 1).to_string()),
+                ("error", localize(locale, error)),
+            ],
+            GameError::IllegalHuff { index, error } => vec![
+                ("index", (index + 1).to_string()),
+                ("error", localize(locale, error)),
+            ],
+            GameError::IllegalRoll { ply, face } => vec![
+                ("ply", (ply + 1).to_string()),
+                ("face", (face + 1).to_string()),
+            ],
+            GameError::IllegalRound { index } => vec![("index", (index + 1).to_string())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::ActionError;
+    use crate::collections::HashSet;
+    use crate::game::GameError;
+    use crate::game_board::Color;
+    use crate::locale::{localize, text, Catalog, English, Locale};
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn locale_test() {
+        let error = GameError::IllegalAction {
+            ply: 2,
+            error: ActionError::WrongColor(Color::Blue),
+        };
+        assert_eq!(
+            English.localize(&error),
+            "Action 3 of the record is illegal: That piece belongs to Blue."
+        );
+
+        let french: Catalog = vec![
+            ("color.blue", "Bleu"),
+            ("action.wrong_color", "Cette pièce appartient à {color}."),
+            ("piece.Big", "Grand"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            french.localize(&error),
+            "Action 3 of the record is illegal: Cette pièce appartient à Bleu."
+        );
+        let ruleset = small_ruleset(0);
+        assert_eq!(french.piece_name(&ruleset.pieces[0].name), "Grand");
+        assert_eq!(french.piece_name(&ruleset.pieces[1].name), "Little");
+        let sheet = ruleset.summary().describe_in(&french);
+        assert!(sheet.contains("Grand:\n"), "{}", sheet);
+        assert_eq!(sheet.replace("Grand:", "Big:"), ruleset.describe(),);
+
+        let keys: HashSet<_> = Catalog::keys().collect();
+        assert_eq!(keys.len(), Catalog::keys().count());
+        for key in keys {
+            assert!(English.template(key).is_some());
+        }
+        assert_eq!(
+            localize(&Catalog::default(), &ActionError::CannotJump),
+            "This piece cannot jump."
+        );
+        assert_eq!(text(&English, "no.such.key"), "no.such.key");
+    }
+}
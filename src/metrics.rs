@@ -0,0 +1,225 @@
+//! Telemetry for servers embedding kapto.
+//!
+//! Enabled by the `metrics` feature. The crate reports to the recorder installed with
+//! [`set_recorder`], [`OpenMetricsRecorder`] keeps totals and renders them for a Prometheus scrape.
+
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::error::Error;
+use std::string::String;
+use std::sync::OnceLock;
+
+use enum_iterator::IntoEnumIterator;
+
+/// A monotonically increasing metric.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Counter {
+    /// Actions applied to a [`Game`](crate::game::Game).
+    Moves,
+    /// Positions visited by a search.
+    SearchNodes,
+    /// Errors returned to the caller.
+    Errors(ErrorKind),
+}
+/// A metric that goes up and down.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Gauge {
+    /// Open [`Session`]s.
+    ActiveSessions,
+}
+/// What produced an error counted by [`Counter::Errors`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, IntoEnumIterator)]
+pub enum ErrorKind {
+    /// An illegal action was rejected.
+    Action,
+    /// A ruleset failed to start a game.
+    Ruleset,
+    /// A remote peer misbehaved or the transport failed.
+    Net,
+}
+impl ErrorKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ErrorKind::Action => "action",
+            ErrorKind::Ruleset => "ruleset",
+            ErrorKind::Net => "net",
+        }
+    }
+}
+
+/// Receives metric updates, implement this to forward them to a metrics system.
+pub trait Recorder: Sync {
+    fn increment_counter(&self, counter: Counter, value: u64);
+    fn add_gauge(&self, gauge: Gauge, delta: i64);
+}
+
+static RECORDER: OnceLock<&'static dyn Recorder> = OnceLock::new();
+
+/// Installs the global recorder, can only be done once.
+pub fn set_recorder(recorder: &'static dyn Recorder) -> Result<(), SetRecorderError> {
+    RECORDER.set(recorder).map_err(|_| SetRecorderError)
+}
+/// The global recorder, `None` until [`set_recorder`] is called.
+pub fn recorder() -> Option<&'static dyn Recorder> {
+    RECORDER.get().copied()
+}
+
+/// Increments `counter` on the global recorder if one is installed.
+pub fn increment_counter(counter: Counter, value: u64) {
+    if let Some(recorder) = recorder() {
+        recorder.increment_counter(counter, value);
+    }
+}
+/// Adds `delta` to `gauge` on the global recorder if one is installed.
+pub fn add_gauge(gauge: Gauge, delta: i64) {
+    if let Some(recorder) = recorder() {
+        recorder.add_gauge(gauge, delta);
+    }
+}
+
+/// Counts towards [`Gauge::ActiveSessions`] until dropped.
+#[derive(Debug)]
+pub struct Session {
+    _private: (),
+}
+impl Session {
+    pub fn start() -> Self {
+        add_gauge(Gauge::ActiveSessions, 1);
+        Self { _private: () }
+    }
+}
+impl Drop for Session {
+    fn drop(&mut self) {
+        add_gauge(Gauge::ActiveSessions, -1);
+    }
+}
+
+/// Keeps running totals and renders them in the OpenMetrics text format.
+///
+/// Rates such as moves per second are derived by the scraper from the totals.
+#[derive(Debug, Default)]
+pub struct OpenMetricsRecorder {
+    moves: AtomicU64,
+    search_nodes: AtomicU64,
+    action_errors: AtomicU64,
+    ruleset_errors: AtomicU64,
+    net_errors: AtomicU64,
+    active_sessions: AtomicI64,
+}
+impl OpenMetricsRecorder {
+    pub const fn new() -> Self {
+        Self {
+            moves: AtomicU64::new(0),
+            search_nodes: AtomicU64::new(0),
+            action_errors: AtomicU64::new(0),
+            ruleset_errors: AtomicU64::new(0),
+            net_errors: AtomicU64::new(0),
+            active_sessions: AtomicI64::new(0),
+        }
+    }
+
+    fn counter(&self, counter: Counter) -> &AtomicU64 {
+        match counter {
+            Counter::Moves => &self.moves,
+            Counter::SearchNodes => &self.search_nodes,
+            Counter::Errors(ErrorKind::Action) => &self.action_errors,
+            Counter::Errors(ErrorKind::Ruleset) => &self.ruleset_errors,
+            Counter::Errors(ErrorKind::Net) => &self.net_errors,
+        }
+    }
+    fn gauge(&self, gauge: Gauge) -> &AtomicI64 {
+        match gauge {
+            Gauge::ActiveSessions => &self.active_sessions,
+        }
+    }
+
+    pub fn counter_value(&self, counter: Counter) -> u64 {
+        self.counter(counter).load(Ordering::Relaxed)
+    }
+    pub fn gauge_value(&self, gauge: Gauge) -> i64 {
+        self.gauge(gauge).load(Ordering::Relaxed)
+    }
+
+    /// The current values as an OpenMetrics text exposition, ending with `# EOF`.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.write_to(&mut out)
+            .expect("Writing to a string cannot fail");
+        out
+    }
+    fn write_to(&self, out: &mut String) -> fmt::Result {
+        writeln!(out, "# TYPE kapto_active_sessions gauge")?;
+        writeln!(out, "# HELP kapto_active_sessions Open sessions.")?;
+        writeln!(
+            out,
+            "kapto_active_sessions {}",
+            self.gauge_value(Gauge::ActiveSessions)
+        )?;
+
+        for (name, help, counter) in [
+            ("kapto_moves", "Actions applied.", Counter::Moves),
+            (
+                "kapto_search_nodes",
+                "Positions visited by searches.",
+                Counter::SearchNodes,
+            ),
+        ] {
+            writeln!(out, "# TYPE {} counter", name)?;
+            writeln!(out, "# HELP {} {}", name, help)?;
+            writeln!(out, "{}_total {}", name, self.counter_value(counter))?;
+        }
+
+        writeln!(out, "# TYPE kapto_errors counter")?;
+        writeln!(out, "# HELP kapto_errors Errors returned to callers.")?;
+        for kind in ErrorKind::into_enum_iter() {
+            writeln!(
+                out,
+                "kapto_errors_total{{kind=\"{}\"}} {}",
+                kind.label(),
+                self.counter_value(Counter::Errors(kind))
+            )?;
+        }
+        writeln!(out, "# EOF")
+    }
+}
+impl Recorder for OpenMetricsRecorder {
+    fn increment_counter(&self, counter: Counter, value: u64) {
+        self.counter(counter).fetch_add(value, Ordering::Relaxed);
+    }
+
+    fn add_gauge(&self, gauge: Gauge, delta: i64) {
+        self.gauge(gauge).fetch_add(delta, Ordering::Relaxed);
+    }
+}
+
+/// [`set_recorder`] was already called.
+#[derive(Copy, Clone, Debug)]
+pub struct SetRecorderError;
+impl Display for SetRecorderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+impl Error for SetRecorderError {}
+
+#[cfg(test)]
+mod test {
+    use crate::metrics::{Counter, ErrorKind, Gauge, OpenMetricsRecorder, Recorder};
+
+    #[test]
+    fn render_test() {
+        let recorder = OpenMetricsRecorder::new();
+        recorder.increment_counter(Counter::Moves, 3);
+        recorder.increment_counter(Counter::Errors(ErrorKind::Action), 1);
+        recorder.add_gauge(Gauge::ActiveSessions, 2);
+        recorder.add_gauge(Gauge::ActiveSessions, -1);
+
+        let rendered = recorder.render();
+        assert!(rendered.contains("\nkapto_moves_total 3\n"));
+        assert!(rendered.contains("\nkapto_search_nodes_total 0\n"));
+        assert!(rendered.contains("\nkapto_errors_total{kind=\"action\"} 1\n"));
+        assert!(rendered.contains("\nkapto_active_sessions 1\n"));
+        assert!(rendered.ends_with("# EOF\n"));
+    }
+}
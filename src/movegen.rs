@@ -0,0 +1,724 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::action::jump_directions::JumpDirections;
+use crate::action::{Action, ActionType};
+use crate::collections::HashMap;
+use crate::coordinate::Coordinate;
+use crate::direction::Direction;
+use crate::game::Game;
+use crate::game_board::board::Board;
+use crate::game_board::{Color, GameBoard, JumpChain};
+use crate::ruleset::capabilities::RequiredFeatures;
+use crate::ruleset::piece_definition::CaptureRequirement;
+use crate::ruleset::Ruleset;
+
+/// A move generator reduced to rulesets needing at most `FEATURES`.
+///
+/// Branches for features outside of `FEATURES` are skipped at compile time,
+/// check [`MoveGenProfile::supports`] before routing a ruleset to a reduced profile.
+pub trait MoveGenProfile {
+    const FEATURES: RequiredFeatures;
+
+    fn supports(ruleset: &Ruleset) -> bool {
+        Self::FEATURES.contains(ruleset.capabilities())
+    }
+}
+/// Supports every ruleset.
+#[derive(Copy, Clone, Debug)]
+pub struct General;
+impl MoveGenProfile for General {
+    const FEATURES: RequiredFeatures = RequiredFeatures::all();
+}
+/// Rulesets where no piece chains jumps.
+#[derive(Copy, Clone, Debug)]
+pub struct SingleJumps;
+impl MoveGenProfile for SingleJumps {
+    const FEATURES: RequiredFeatures = RequiredFeatures::from_bits_truncate(
+        RequiredFeatures::all().bits() & !RequiredFeatures::MULTI_JUMPS.bits(),
+    );
+}
+/// Rulesets where no piece jumps.
+#[derive(Copy, Clone, Debug)]
+pub struct MovesOnly;
+impl MoveGenProfile for MovesOnly {
+    const FEATURES: RequiredFeatures = RequiredFeatures::from_bits_truncate(
+        RequiredFeatures::all().bits()
+            & !(RequiredFeatures::JUMPS.bits() | RequiredFeatures::MULTI_JUMPS.bits()),
+    );
+}
+
+/// All legal actions for `color` on `board`.
+///
+/// Every returned action passes [`Board::is_valid_action`]. When a piece with
+/// [`CaptureRequirement::Forced`] can capture, only the captures of the pieces with the highest
+/// priority among those are legal.
+pub fn legal_actions(board: &impl Board, color: Color) -> Vec<Action> {
+    legal_actions_for::<General>(board, color)
+}
+/// [`legal_actions`] using a reduced profile.
+pub fn legal_actions_for<P: MoveGenProfile>(board: &impl Board, color: Color) -> Vec<Action> {
+    let mut out = Vec::new();
+    for (position, _) in board.pieces_of_color(color) {
+        push_actions_from::<P>(board, position, &mut out);
+    }
+    if P::FEATURES.contains(RequiredFeatures::FORCED_CAPTURE) {
+        retain_forced(board, &mut out);
+    }
+    #[cfg(feature = "tracing")]
+    tracing::trace!(?color, actions = out.len(), "generated actions");
+    out
+}
+
+/// Whether [`CaptureRequirement::Forced`] lets `color` play the valid `action`.
+pub fn is_forced_allowed(board: &impl Board, color: Color, action: &Action) -> bool {
+    let forced = board.pieces_of_color(color).into_iter().any(|(_, piece)| {
+        matches!(
+            board.rules(piece).capture_requirement,
+            CaptureRequirement::Forced(_)
+        )
+    });
+    if !forced || action.action_type.is_pass() {
+        return true;
+    }
+    let mut actions = Vec::new();
+    for (position, _) in board.pieces_of_color(color) {
+        push_actions_from::<General>(board, position, &mut actions);
+    }
+    match actions
+        .iter()
+        .filter_map(|action| forced_priority(board, action))
+        .max()
+    {
+        Some(top) => forced_priority(board, action) == Some(top),
+        None => true,
+    }
+}
+/// The [`CaptureRequirement::Forced`] priority of the moved piece if `action` captures.
+fn forced_priority(board: &impl Board, action: &Action) -> Option<isize> {
+    let piece = board.piece(action.start_pos).ok()??;
+    match board.rules(piece).capture_requirement {
+        CaptureRequirement::Forced(priority) => captures(board, action).then_some(priority),
+        CaptureRequirement::None | CaptureRequirement::Huff => None,
+    }
+}
+/// Whether `action` is a jump chain capturing a piece.
+pub(crate) fn captures(board: &impl Board, action: &Action) -> bool {
+    let directions = match &action.action_type {
+        ActionType::Jump(directions) => directions,
+        ActionType::Move(_) | ActionType::Pass => return false,
+    };
+    match board.piece(action.start_pos) {
+        Ok(Some(piece)) => board
+            .jump_chain(piece, action.start_pos, directions)
+            .is_ok_and(|chain| chain.steps().iter().any(|step| step.captures)),
+        _ => false,
+    }
+}
+/// Keeps only the captures of the highest priority forced pieces if any of them can capture.
+fn retain_forced(board: &impl Board, actions: &mut Vec<Action>) {
+    let priorities: Vec<_> = actions
+        .iter()
+        .map(|action| forced_priority(board, action))
+        .collect();
+    if let Some(top) = priorities.iter().flatten().max().copied() {
+        let mut priorities = priorities.into_iter();
+        actions.retain(|_| priorities.next().flatten() == Some(top));
+    }
+}
+
+/// All legal actions for the piece at `position`, empty if there is no piece.
+///
+/// Ignores [`CaptureRequirement::Forced`], which depends on the other pieces.
+pub fn actions_from(board: &impl Board, position: Coordinate) -> Vec<Action> {
+    let mut out = Vec::new();
+    push_actions_from::<General>(board, position, &mut out);
+    out
+}
+
+/// Lines of play `depth` actions long from `game`, counting lines that end the game early once.
+///
+/// Counts of known positions check move generation, and timing them measures it.
+pub fn perft(game: &Game, depth: usize) -> u64 {
+    if depth == 0 || game.is_finished() {
+        return 1;
+    }
+    let actions = game.legal_actions();
+    if depth == 1 {
+        return actions.len().max(1) as u64;
+    }
+    actions
+        .iter()
+        .map(|action| {
+            let mut after = game.clone();
+            after.apply(action).expect("Legal actions must apply");
+            perft(&after, depth - 1)
+        })
+        .sum::<u64>()
+        .max(1)
+}
+
+fn push_actions_from<P: MoveGenProfile>(
+    board: &impl Board,
+    position: Coordinate,
+    out: &mut Vec<Action>,
+) {
+    let piece = match board.piece(position) {
+        Ok(Some(piece)) => piece,
+        _ => return,
+    };
+    for direction in Direction::into_enum_iter() {
+        if board.is_valid_move(position, direction).is_ok() {
+            out.push(Action {
+                start_pos: position,
+                action_type: ActionType::Move(direction),
+            });
+        }
+    }
+
+    if !P::FEATURES.contains(RequiredFeatures::JUMPS) {
+        return;
+    }
+    let mut chain = JumpChain::new(piece, position);
+    push_jump_chains::<P>(board, &mut chain, out);
+}
+
+/// Legal actions per piece, regenerated only for pieces near the squares an action changed.
+///
+/// A piece's actions only read the squares one and two steps from its start and from
+/// every landing of its jump chains, and the portals those lead to, so a change anywhere else
+/// leaves them as they were.
+#[derive(Clone, Debug, Default)]
+pub struct MoveCache {
+    entries: HashMap<Coordinate, CacheEntry>,
+}
+#[derive(Clone, Debug)]
+struct CacheEntry {
+    actions: Vec<Action>,
+    /// Squares read while generating `actions`.
+    reach: Vec<Coordinate>,
+}
+impl MoveCache {
+    pub fn new(board: &impl Board) -> Self {
+        let mut out = Self::default();
+        out.update(board, &[]);
+        out
+    }
+
+    /// Regenerates the pieces on or in reach of `changed`, `board` is the board after the change.
+    pub fn update(&mut self, board: &impl Board, changed: &[Coordinate]) {
+        self.entries.retain(|position, entry| {
+            !changed.contains(position) && !entry.reach.iter().any(|reach| changed.contains(reach))
+        });
+        #[cfg(feature = "tracing")]
+        let kept = self.entries.len();
+        for color in Color::into_enum_iter() {
+            for (position, _) in board.pieces_of_color(color) {
+                self.entries
+                    .entry(position)
+                    .or_insert_with(|| CacheEntry::new(board, position));
+            }
+        }
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            kept,
+            regenerated = self.entries.len() - kept,
+            "updated move cache"
+        );
+    }
+
+    /// The same actions as [`legal_actions`] in the same order, `board` must be the last updated board.
+    pub fn legal_actions(&self, board: &impl Board, color: Color) -> Vec<Action> {
+        let mut out = Vec::new();
+        #[cfg(feature = "tracing")]
+        let mut misses = 0;
+        for (position, _) in board.pieces_of_color(color) {
+            match self.entries.get(&position) {
+                Some(entry) => out.extend(entry.actions.iter().cloned()),
+                None => {
+                    #[cfg(feature = "tracing")]
+                    {
+                        misses += 1;
+                    }
+                    push_actions_from::<General>(board, position, &mut out)
+                }
+            }
+        }
+        retain_forced(board, &mut out);
+        #[cfg(feature = "tracing")]
+        tracing::trace!(?color, actions = out.len(), misses, "cached actions");
+        out
+    }
+}
+impl CacheEntry {
+    fn new(board: &impl Board, position: Coordinate) -> Self {
+        let actions = actions_from(board, position);
+        let mut reach = Vec::new();
+        let piece = board.piece(position).ok().flatten();
+        // Chains that must continue are only generated whole, so every landing counts
+        let landings: Vec<Coordinate> = actions
+            .iter()
+            .filter_map(|action| match (&action.action_type, piece) {
+                (ActionType::Jump(directions), Some(piece)) => {
+                    board.jump_chain(piece, position, directions).ok()
+                }
+                _ => None,
+            })
+            .flat_map(|chain| {
+                chain
+                    .steps()
+                    .iter()
+                    .map(|step| step.landing)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let nodes = core::iter::once(position).chain(landings);
+        for node in nodes {
+            for direction in Direction::into_enum_iter() {
+                for square in [direction.offset() + node, direction.offset() * 2 + node] {
+                    reach.push(square);
+                    reach.push(board.portal_exit(square));
+                }
+            }
+        }
+        reach.sort();
+        reach.dedup();
+        Self { actions, reach }
+    }
+}
+
+/// Depth first search over jump chains, every prefix of a chain the piece may end on is its own
+/// action.
+fn push_jump_chains<P: MoveGenProfile>(
+    board: &impl Board,
+    chain: &mut JumpChain,
+    out: &mut Vec<Action>,
+) {
+    if !P::FEATURES.contains(RequiredFeatures::MULTI_JUMPS) && !chain.steps().is_empty() {
+        return;
+    }
+    for direction in Direction::into_enum_iter() {
+        let step = match board.next_jump(chain, direction) {
+            Ok(step) => step,
+            Err(_) => continue,
+        };
+        chain.push(step);
+        if board.may_end_chain(chain) {
+            out.push(Action {
+                start_pos: chain.start(),
+                action_type: ActionType::Jump(chain.directions()),
+            });
+        }
+        push_jump_chains::<P>(board, chain, out);
+        chain.pop();
+    }
+}
+
+/// Every legal jump chain from a square, generated one at a time, see
+/// [`Board::jump_chains_from`].
+///
+/// Chains come in the same order as the jumps of [`actions_from`], every chain before the chains
+/// continuing it.
+#[derive(Clone, Debug)]
+pub struct JumpChains<'a, B = GameBoard> {
+    board: &'a B,
+    chain: Option<JumpChain>,
+    /// The next direction to try after each landing of `chain`, and after its start.
+    next: Vec<usize>,
+}
+impl<'a, B: Board> JumpChains<'a, B> {
+    pub fn new(board: &'a B, position: Coordinate) -> Self {
+        let chain = match board.piece(position) {
+            Ok(Some(piece)) => Some(JumpChain::new(piece, position)),
+            _ => None,
+        };
+        Self {
+            board,
+            next: if chain.is_some() { vec![0] } else { Vec::new() },
+            chain,
+        }
+    }
+}
+impl<B: Board> Iterator for JumpChains<'_, B> {
+    type Item = JumpDirections;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chain = self.chain.as_mut()?;
+        loop {
+            let index = self.next.last_mut()?;
+            let direction = match Direction::into_enum_iter().nth(*index) {
+                Some(direction) => direction,
+                None => {
+                    self.next.pop();
+                    if self.next.is_empty() {
+                        return None;
+                    }
+                    chain.pop();
+                    continue;
+                }
+            };
+            *index += 1;
+            if let Ok(step) = self.board.next_jump(chain, direction) {
+                chain.push(step);
+                self.next.push(0);
+                if self.board.may_end_chain(chain) {
+                    return Some(chain.directions());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use proptest::prelude::*;
+
+    use crate::action::{Action, ActionError, ActionType};
+    use crate::collections::HashMap;
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game::scenario::Scenario;
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::game_board::{Color, GameBoard, PieceInstance, PieceRules};
+    use crate::movegen::{
+        actions_from, is_forced_allowed, legal_actions, legal_actions_for, perft, MoveCache,
+        MoveGenProfile, MovesOnly, SingleJumps,
+    };
+    use crate::rng::{ChaChaRng, GameRng};
+    use crate::ruleset::piece_definition::{CaptureRequirement, ChainContinuationRule, JumpLimit};
+    use crate::testing::arb_game_board;
+    use crate::testing::{arb_ruleset, small_ruleset};
+
+    #[test]
+    fn small_and_large_jumps_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        *board.piece_mut(Coordinate::new(3, 0)).unwrap() = Some(PieceInstance::new(1, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(4, 3)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+
+        let small = actions_from(&board, Coordinate::new(3, 0));
+        assert!(small.iter().all(|action| match &action.action_type {
+            ActionType::Jump(directions) => directions.len() == 1,
+            ActionType::Move(_) => true,
+            ActionType::Pass => false,
+        }));
+
+        *board.piece_mut(Coordinate::new(3, 0)).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        let large = actions_from(&board, Coordinate::new(3, 0));
+        assert!(large.iter().any(|action| match &action.action_type {
+            ActionType::Jump(directions) => directions.len() == 2,
+            ActionType::Move(_) | ActionType::Pass => false,
+        }));
+    }
+
+    #[test]
+    fn three_definitions_test() {
+        assert_eq!(core::mem::size_of::<PieceInstance>(), 2);
+        let mut board = GameBoard::new((5, 5), &[2]);
+        board.piece_rules = vec![
+            PieceRules::STANDARD[1],
+            PieceRules::STANDARD[1],
+            PieceRules::STANDARD[0],
+        ];
+        let start = Coordinate::new(3, 0);
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(2, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(4, 3)).unwrap() = Some(PieceInstance::new(0, Color::Blue));
+
+        // Only the third definition jumps more than once
+        let actions = legal_actions(&board, Color::Red);
+        assert!(actions.iter().any(|action| match &action.action_type {
+            ActionType::Jump(directions) => directions.len() == 2,
+            ActionType::Move(_) | ActionType::Pass => false,
+        }));
+        for action in actions {
+            let (after, outcome) = board.apply_action(&action).unwrap();
+            let moved = after.pieces_of_definition(2);
+            assert_eq!(moved.len(), 1);
+            assert_eq!(Some(moved[0].0), board.destination(&action));
+            assert_eq!(moved[0].1, PieceInstance::new(2, Color::Red));
+            for (position, captured) in outcome.captures {
+                assert_eq!(board.piece(position).unwrap(), Some(captured));
+                assert_eq!(after.piece(position).unwrap(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn chain_rule_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        let start = Coordinate::new(3, 0);
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(2, 2)).unwrap() = Some(PieceInstance::new(1, Color::Red));
+        let first = Action {
+            start_pos: start,
+            action_type: ActionType::Jump([Direction::East].into()),
+        };
+        let with_rule = |chain_rule| {
+            let mut out = board.clone();
+            out.piece_rules = vec![
+                PieceRules {
+                    chain_rule,
+                    ..PieceRules::STANDARD[0]
+                };
+                2
+            ];
+            out
+        };
+
+        let may_stop = with_rule(ChainContinuationRule::MayStop);
+        assert!(actions_from(&may_stop, start).contains(&first));
+        // Only a jump over its own piece follows, which does not capture
+        let mut must_capture = with_rule(ChainContinuationRule::MustCapture);
+        must_capture.is_valid_action(&first).unwrap();
+        *must_capture.piece_mut(Coordinate::new(4, 3)).unwrap() =
+            Some(PieceInstance::new(1, Color::Blue));
+        assert!(matches!(
+            must_capture.is_valid_action(&first),
+            Err(ActionError::ChainUnfinished)
+        ));
+        assert!(!actions_from(&must_capture, start).contains(&first));
+
+        let must_continue = with_rule(ChainContinuationRule::MustContinue);
+        assert!(matches!(
+            must_continue.is_valid_action(&first),
+            Err(ActionError::ChainUnfinished)
+        ));
+        let jumps: Vec<_> = actions_from(&must_continue, start)
+            .into_iter()
+            .filter(|action| action.action_type.is_jump())
+            .collect();
+        assert!(!jumps.is_empty());
+        assert!(!jumps.contains(&first));
+        for action in jumps.iter() {
+            must_continue.is_valid_action(action).unwrap();
+        }
+        assert_eq!(must_continue.jump_chains_from(start).count(), jumps.len());
+    }
+
+    #[test]
+    fn forced_priority_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        let big = Coordinate::new(3, 0);
+        let little = Coordinate::new(1, 0);
+        *board.piece_mut(big).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(little).unwrap() = Some(PieceInstance::new(1, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(1, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        let with_requirements = |big, little| {
+            let mut out = board.clone();
+            out.piece_rules = vec![
+                PieceRules {
+                    capture_requirement: big,
+                    ..PieceRules::STANDARD[0]
+                },
+                PieceRules {
+                    capture_requirement: little,
+                    ..PieceRules::STANDARD[1]
+                },
+            ];
+            out
+        };
+        let starts = |board: &GameBoard| {
+            let actions = legal_actions(board, Color::Red);
+            for action in actions.iter() {
+                let (_, outcome) = board.apply_action(action).unwrap();
+                assert!(!outcome.captures.is_empty(), "{:?}", action);
+                assert!(is_forced_allowed(board, Color::Red, action));
+            }
+            let mut out: Vec<_> = actions.iter().map(|action| action.start_pos).collect();
+            out.dedup();
+            out
+        };
+
+        assert_eq!(
+            starts(&with_requirements(
+                CaptureRequirement::Forced(10),
+                CaptureRequirement::Forced(0)
+            )),
+            vec![big]
+        );
+        assert_eq!(
+            starts(&with_requirements(
+                CaptureRequirement::Forced(0),
+                CaptureRequirement::Forced(10)
+            )),
+            vec![little]
+        );
+        // Pieces without a requirement neither force nor are forced
+        let unforced = with_requirements(CaptureRequirement::None, CaptureRequirement::Forced(0));
+        assert_eq!(starts(&unforced), vec![little]);
+        let step = Action {
+            start_pos: big,
+            action_type: ActionType::Move(Direction::South),
+        };
+        unforced.is_valid_action(&step).unwrap();
+        assert!(!is_forced_allowed(&unforced, Color::Red, &step));
+        let free = with_requirements(CaptureRequirement::None, CaptureRequirement::None);
+        assert!(is_forced_allowed(&free, Color::Red, &step));
+        assert!(legal_actions(&free, Color::Red).contains(&step));
+    }
+
+    #[test]
+    fn forced_chain_rule_test() {
+        let mut board = GameBoard::new((5, 5), &[2]);
+        let start = Coordinate::new(3, 0);
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(4, 3)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        let first = Action {
+            start_pos: start,
+            action_type: ActionType::Jump([Direction::East].into()),
+        };
+        for chain_rule in [
+            ChainContinuationRule::MustCapture,
+            ChainContinuationRule::MustContinue,
+        ] {
+            let mut rules = board.clone();
+            rules.piece_rules = vec![
+                PieceRules {
+                    chain_rule,
+                    capture_requirement: CaptureRequirement::Forced(0),
+                    ..PieceRules::STANDARD[0]
+                };
+                2
+            ];
+            // The forced capture is the whole chain, never its first jump
+            let actions = legal_actions(&rules, Color::Red);
+            assert!(!actions.is_empty());
+            assert!(!actions.contains(&first));
+            for action in actions.iter() {
+                assert!(action.action_type.is_jump());
+                let (_, outcome) = rules.apply_action(action).unwrap();
+                assert!(!outcome.captures.is_empty());
+            }
+            let cache = MoveCache::new(&rules);
+            assert_eq!(cache.legal_actions(&rules, Color::Red), actions);
+        }
+    }
+
+    #[test]
+    fn forced_apply_test() {
+        let mut position = HashMap::new();
+        position.insert(
+            Color::Red,
+            vec![(1, vec![Coordinate::new(3, 2)])].into_iter().collect(),
+        );
+        position.insert(
+            Color::Blue,
+            vec![(1, vec![Coordinate::new(4, 2)])].into_iter().collect(),
+        );
+        let scenario = Scenario {
+            name: "Forced".into(),
+            description: String::new(),
+            ruleset: small_ruleset(0),
+            position,
+            to_move: Color::Red,
+            restrictions: vec![],
+            victory_conditions: None,
+            move_limit: None,
+        };
+        let mut game = scenario.start().unwrap().into_game();
+        let ignore = Action {
+            start_pos: Coordinate::new(3, 2),
+            action_type: ActionType::Move(Direction::North),
+        };
+        game.board().is_valid_action(&ignore).unwrap();
+        assert!(!game.legal_actions().contains(&ignore));
+        assert!(matches!(
+            game.apply(&ignore),
+            Err(ActionError::CaptureForced)
+        ));
+        let capture = game.legal_actions()[0].clone();
+        game.apply(&capture).unwrap();
+    }
+
+    #[test]
+    fn profile_test() {
+        let mut ruleset = small_ruleset(0);
+        assert!(!SingleJumps::supports(&ruleset));
+        for piece in ruleset.pieces.iter_mut() {
+            piece.jump_limit = JumpLimit::Cannot;
+        }
+        assert!(MovesOnly::supports(&ruleset));
+
+        let mut board = GameBoard::new((5, 5), &[2]);
+        *board.piece_mut(Coordinate::new(3, 0)).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(Coordinate::new(3, 1)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        *board.piece_mut(Coordinate::new(4, 3)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        let single = legal_actions_for::<SingleJumps>(&board, Color::Red);
+        assert!(single.iter().any(|action| action.action_type.is_jump()));
+        assert!(single.iter().all(|action| match &action.action_type {
+            ActionType::Jump(directions) => directions.len() == 1,
+            ActionType::Move(_) => true,
+            ActionType::Pass => false,
+        }));
+        let moves = legal_actions_for::<MovesOnly>(&board, Color::Red);
+        assert!(moves.iter().all(|action| !action.action_type.is_jump()));
+    }
+
+    proptest! {
+        #[test]
+        fn jump_chains_test(board in arb_game_board(6, 6)) {
+            for (position, _) in board.pieces_of_color(Color::Red) {
+                let jumps: Vec<_> = actions_from(&board, position)
+                    .into_iter()
+                    .filter_map(|action| match action.action_type {
+                        ActionType::Jump(directions) => Some(directions),
+                        _ => None,
+                    })
+                    .collect();
+                prop_assert_eq!(board.jump_chains_from(position).collect::<Vec<_>>(), jumps);
+            }
+            prop_assert_eq!(board.jump_chains_from(Coordinate::new(-1, 0)).count(), 0);
+        }
+
+        #[test]
+        fn generated_actions_valid_test(board in arb_game_board(6, 6)) {
+            for color in [Color::Red, Color::Blue] {
+                for action in legal_actions(&board, color) {
+                    prop_assert!(board.is_valid_action(&action).is_ok(), "{:?}", action);
+                }
+            }
+        }
+
+        #[test]
+        fn move_cache_test(ruleset in arb_ruleset(), seed in any::<u64>()) {
+            let mut game = Game::new(ruleset).unwrap();
+            game.set_move_cache(true);
+            let mut rng = ChaChaRng::new(seed);
+            for _ in 0..200 {
+                let actions = game.legal_actions();
+                prop_assert_eq!(game.legal_actions_cached(), actions.clone());
+                if actions.is_empty() {
+                    break;
+                }
+                game.apply(&actions[rng.below(actions.len())]).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn perft_test() {
+        let game = Game::new(small_ruleset(0)).unwrap();
+        assert_eq!(perft(&game, 0), 1);
+        let actions = game.legal_actions();
+        assert_eq!(perft(&game, 1), actions.len() as u64);
+        let replies: u64 = actions
+            .iter()
+            .map(|action| {
+                let mut after = game.clone();
+                after.apply(action).unwrap();
+                after.legal_actions().len().max(1) as u64
+            })
+            .sum();
+        assert_eq!(perft(&game, 2), replies);
+    }
+}
@@ -0,0 +1,346 @@
+//! Message protocol for remote games.
+//!
+//! Enabled by the `net` feature. Messages are serde types so any format can be used on the wire,
+//! the transport itself is supplied by implementing [`Transport`].
+//!
+//! [`sync`] has a compact binary alternative for lossy connections.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+use core::future::Future;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::game_board::Color;
+#[cfg(feature = "metrics")]
+use crate::metrics;
+#[cfg(feature = "metrics")]
+use crate::metrics::{Counter, ErrorKind};
+
+pub mod sync;
+
+/// Version of the message protocol, peers with different versions cannot play.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Identifies a game across reconnects.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct GameId(pub u64);
+
+/// First message sent by both peers.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Hello {
+    pub protocol_version: u32,
+    /// [`Ruleset::fingerprint`](crate::ruleset::Ruleset::fingerprint) of the rules this peer plays.
+    pub ruleset_fingerprint: [u8; 32],
+    pub game_id: GameId,
+    /// The color this peer wants to play, `None` for no preference.
+    pub color: Option<Color>,
+}
+
+/// Remaining time on each clock in milliseconds.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ClockState {
+    pub red_remaining_ms: u64,
+    pub blue_remaining_ms: u64,
+}
+
+/// A message between peers.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Message {
+    /// Handshake, see [`handshake`].
+    Hello(Hello),
+    /// The handshake failed and the connection will close.
+    Reject(RejectReason),
+    /// An action played by the sender.
+    /// Sequence numbers start at 0 and count every action in the game.
+    Action {
+        sequence: u64,
+        action: Action,
+    },
+    /// The receiver applied every action up to `sequence`.
+    ActionAck {
+        sequence: u64,
+    },
+    /// The sender's view of the clocks at `sent_at_ms` on its own monotonic clock.
+    ClockSync {
+        clocks: ClockState,
+        sent_at_ms: u64,
+    },
+    /// The sender resigns the game.
+    Resign,
+    /// The sender offers a draw.
+    OfferDraw,
+    /// The sender accepts the pending draw offer.
+    AcceptDraw,
+    /// The sender declines the pending draw offer.
+    DeclineDraw,
+    /// Sent after reconnecting, asks for every action after `last_sequence`.
+    /// `None` asks for the whole game.
+    Resume {
+        game_id: GameId,
+        last_sequence: Option<u64>,
+    },
+    /// Answer to [`Message::Resume`].
+    ResumeActions {
+        first_sequence: u64,
+        actions: Vec<Action>,
+    },
+    Ping(u64),
+    Pong(u64),
+}
+
+/// Why a handshake was rejected.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum RejectReason {
+    ProtocolVersion { ours: u32, theirs: u32 },
+    RulesetMismatch,
+    GameIdMismatch,
+    ColorConflict(Color),
+}
+
+/// A bidirectional, ordered message channel such as a TCP or WebSocket connection.
+pub trait Transport {
+    type Error;
+
+    fn send(&mut self, message: Message) -> impl Future<Output = Result<(), Self::Error>>;
+    fn receive(&mut self) -> impl Future<Output = Result<Message, Self::Error>>;
+}
+
+/// Exchanges [`Hello`]s and checks the peer plays the same game.
+///
+/// On a mismatch the peer is sent a [`Message::Reject`] before returning the error.
+pub async fn handshake<T: Transport>(transport: &mut T, ours: Hello) -> NetResult<Hello, T::Error> {
+    let out = handshake_unrecorded(transport, ours).await;
+    #[cfg(feature = "metrics")]
+    if out.is_err() {
+        metrics::increment_counter(Counter::Errors(ErrorKind::Net), 1);
+    }
+    out
+}
+async fn handshake_unrecorded<T: Transport>(
+    transport: &mut T,
+    ours: Hello,
+) -> NetResult<Hello, T::Error> {
+    transport
+        .send(Message::Hello(ours.clone()))
+        .await
+        .map_err(NetError::Transport)?;
+    let theirs = match transport.receive().await.map_err(NetError::Transport)? {
+        Message::Hello(theirs) => theirs,
+        Message::Reject(reason) => return Err(NetError::Rejected(reason)),
+        message => return Err(NetError::UnexpectedMessage(message)),
+    };
+
+    let reason = if theirs.protocol_version != ours.protocol_version {
+        Some(RejectReason::ProtocolVersion {
+            ours: ours.protocol_version,
+            theirs: theirs.protocol_version,
+        })
+    } else if theirs.ruleset_fingerprint != ours.ruleset_fingerprint {
+        Some(RejectReason::RulesetMismatch)
+    } else if theirs.game_id != ours.game_id {
+        Some(RejectReason::GameIdMismatch)
+    } else {
+        match (ours.color, theirs.color) {
+            (Some(ours), Some(theirs)) if ours == theirs => Some(RejectReason::ColorConflict(ours)),
+            _ => None,
+        }
+    };
+    match reason {
+        None => Ok(theirs),
+        Some(reason) => {
+            transport
+                .send(Message::Reject(reason))
+                .await
+                .map_err(NetError::Transport)?;
+            Err(NetError::Rejected(reason))
+        }
+    }
+}
+
+/// Every action of a game by sequence number, used to answer [`Message::Resume`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ActionLog {
+    actions: Vec<Action>,
+}
+impl ActionLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the next action and returns the message announcing it.
+    pub fn push(&mut self, action: Action) -> Message {
+        let sequence = self.actions.len() as u64;
+        self.actions.push(action.clone());
+        Message::Action { sequence, action }
+    }
+    /// Records an action received from the peer, which must be the next in sequence.
+    pub fn receive(&mut self, sequence: u64, action: Action) -> NetResult<(), ()> {
+        let expected = self.actions.len() as u64;
+        if sequence != expected {
+            #[cfg(feature = "metrics")]
+            metrics::increment_counter(Counter::Errors(ErrorKind::Net), 1);
+            return Err(NetError::OutOfSequence {
+                expected,
+                received: sequence,
+            });
+        }
+        self.actions.push(action);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// The answer to a [`Message::Resume`] asking for actions after `last_sequence`.
+    pub fn resume_from(&self, last_sequence: Option<u64>) -> Message {
+        let first_sequence = last_sequence.map_or(0, |sequence| sequence + 1);
+        let start = (first_sequence as usize).min(self.actions.len());
+        Message::ResumeActions {
+            first_sequence,
+            actions: self.actions[start..].to_vec(),
+        }
+    }
+}
+
+pub type NetResult<T, E> = Result<T, NetError<E>>;
+#[derive(Clone, Debug)]
+pub enum NetError<E> {
+    /// The transport failed.
+    Transport(E),
+    /// The peer rejected us, or we rejected the peer.
+    Rejected(RejectReason),
+    /// The peer sent a message that is not valid at this point.
+    UnexpectedMessage(Message),
+    /// An action arrived with the wrong sequence number.
+    OutOfSequence { expected: u64, received: u64 },
+}
+impl<E: Debug> Display for NetError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl<E: Debug> Error for NetError<E> {}
+
+#[cfg(test)]
+mod test {
+    use alloc::collections::VecDeque;
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+
+    use crate::action::{Action, ActionType};
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game_board::Color;
+    use crate::net::{
+        handshake, ActionLog, GameId, Hello, Message, NetError, RejectReason, Transport,
+        PROTOCOL_VERSION,
+    };
+
+    /// Replays scripted incoming messages and records outgoing ones.
+    struct ScriptedTransport {
+        incoming: VecDeque<Message>,
+        sent: Vec<Message>,
+    }
+    impl Transport for ScriptedTransport {
+        type Error = ();
+
+        fn send(&mut self, message: Message) -> impl Future<Output = Result<(), ()>> {
+            self.sent.push(message);
+            core::future::ready(Ok(()))
+        }
+
+        fn receive(&mut self) -> impl Future<Output = Result<Message, ()>> {
+            core::future::ready(self.incoming.pop_front().ok_or(()))
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(out) = future.as_mut().poll(&mut context) {
+                return out;
+            }
+        }
+    }
+
+    fn hello(fingerprint: u8, color: Option<Color>) -> Hello {
+        Hello {
+            protocol_version: PROTOCOL_VERSION,
+            ruleset_fingerprint: [fingerprint; 32],
+            game_id: GameId(1),
+            color,
+        }
+    }
+
+    #[test]
+    fn handshake_test() {
+        let mut transport = ScriptedTransport {
+            incoming: vec![Message::Hello(hello(3, Some(Color::Blue)))].into(),
+            sent: Vec::new(),
+        };
+        let theirs = block_on(handshake(&mut transport, hello(3, Some(Color::Red)))).unwrap();
+        assert_eq!(theirs.color, Some(Color::Blue));
+
+        let mut transport = ScriptedTransport {
+            incoming: vec![Message::Hello(hello(4, None))].into(),
+            sent: Vec::new(),
+        };
+        assert!(matches!(
+            block_on(handshake(&mut transport, hello(3, None))),
+            Err(NetError::Rejected(RejectReason::RulesetMismatch))
+        ));
+        assert_eq!(
+            transport.sent.last(),
+            Some(&Message::Reject(RejectReason::RulesetMismatch))
+        );
+    }
+
+    #[test]
+    fn action_log_test() {
+        let action = Action {
+            start_pos: Coordinate::new(3, 3),
+            action_type: ActionType::Move(Direction::North),
+        };
+        let mut log = ActionLog::new();
+        assert_eq!(
+            log.push(action.clone()),
+            Message::Action {
+                sequence: 0,
+                action: action.clone(),
+            }
+        );
+        assert!(log.receive(1, action.clone()).is_ok());
+        assert!(matches!(
+            log.receive(5, action.clone()),
+            Err(NetError::OutOfSequence {
+                expected: 2,
+                received: 5
+            })
+        ));
+        match log.resume_from(Some(0)) {
+            Message::ResumeActions {
+                first_sequence,
+                actions,
+            } => {
+                assert_eq!(first_sequence, 1);
+                assert_eq!(actions.len(), 1);
+            }
+            _ => panic!("Expected resume actions"),
+        }
+    }
+}
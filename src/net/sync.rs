@@ -0,0 +1,586 @@
+//! Compact binary board synchronization for lossy connections.
+//!
+//! The host sends one [`SyncFrame::Delta`] per action with the [`BoardDelta`]s it caused and a
+//! hash of the resulting board. A receiver that misses a frame, or ends up on a board with a
+//! different hash, asks for a [`SyncFrame::Resync`] from its last good position and is sent
+//! either the missing deltas or a full [`SyncFrame::Snapshot`].
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use enum_iterator::IntoEnumIterator;
+use sha2::{Digest, Sha256};
+
+use crate::action::{Action, ActionType};
+use crate::coordinate::Coordinate;
+use crate::direction::Direction;
+use crate::game_board::board::Board;
+use crate::game_board::{BoardDelta, Color, GameBoard, PieceInstance};
+use crate::net::ActionLog;
+
+/// A message of the sync protocol, see [`SyncFrame::encode`] for the wire format.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SyncFrame {
+    /// The action with `sequence` and what it changed, `board_hash` is the board after it.
+    Delta {
+        sequence: u64,
+        action: Action,
+        deltas: Vec<BoardDelta>,
+        board_hash: u64,
+    },
+    /// Every piece on the board before the action with `next_sequence`.
+    Snapshot {
+        next_sequence: u64,
+        pieces: Vec<(Coordinate, PieceInstance)>,
+        board_hash: u64,
+    },
+    /// The receiver's board hashes to `board_hash` and it expects `next_sequence` next.
+    Resync { next_sequence: u64, board_hash: u64 },
+}
+impl SyncFrame {
+    /// Encodes the frame with variable length integers, most deltas take 3 to 5 bytes.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            SyncFrame::Delta {
+                sequence,
+                action,
+                deltas,
+                board_hash,
+            } => {
+                out.push(0);
+                write_varint(&mut out, *sequence);
+                write_action(&mut out, action);
+                write_varint(&mut out, deltas.len() as u64);
+                for delta in deltas {
+                    write_delta(&mut out, delta);
+                }
+                out.extend_from_slice(&board_hash.to_le_bytes());
+            }
+            SyncFrame::Snapshot {
+                next_sequence,
+                pieces,
+                board_hash,
+            } => {
+                out.push(1);
+                write_varint(&mut out, *next_sequence);
+                write_varint(&mut out, pieces.len() as u64);
+                for (position, piece) in pieces {
+                    write_coordinate(&mut out, *position);
+                    write_piece(&mut out, *piece);
+                }
+                out.extend_from_slice(&board_hash.to_le_bytes());
+            }
+            SyncFrame::Resync {
+                next_sequence,
+                board_hash,
+            } => {
+                out.push(2);
+                write_varint(&mut out, *next_sequence);
+                out.extend_from_slice(&board_hash.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    pub fn decode(bytes: &[u8]) -> SyncResult<Self> {
+        let mut reader = Reader { bytes };
+        let out = match reader.byte()? {
+            0 => {
+                let sequence = reader.varint()?;
+                let action = reader.action()?;
+                let deltas = (0..reader.varint()?)
+                    .map(|_| reader.delta())
+                    .collect::<SyncResult<_>>()?;
+                SyncFrame::Delta {
+                    sequence,
+                    action,
+                    deltas,
+                    board_hash: reader.hash()?,
+                }
+            }
+            1 => {
+                let next_sequence = reader.varint()?;
+                let pieces = (0..reader.varint()?)
+                    .map(|_| Ok((reader.coordinate()?, reader.piece()?)))
+                    .collect::<SyncResult<_>>()?;
+                SyncFrame::Snapshot {
+                    next_sequence,
+                    pieces,
+                    board_hash: reader.hash()?,
+                }
+            }
+            2 => SyncFrame::Resync {
+                next_sequence: reader.varint()?,
+                board_hash: reader.hash()?,
+            },
+            tag => return Err(SyncError::InvalidTag(tag)),
+        };
+        if !reader.bytes.is_empty() {
+            return Err(SyncError::TrailingBytes(reader.bytes.len()));
+        }
+        Ok(out)
+    }
+}
+
+/// A hash of every piece on `board` and its dimensions.
+pub fn board_hash(board: &GameBoard) -> u64 {
+    let mut bytes = Vec::new();
+    write_varint(&mut bytes, board.board.rows as u64);
+    write_varint(&mut bytes, board.board.columns as u64);
+    for (index, space) in board.board.values.iter().enumerate() {
+        if let Some(Some(piece)) = space.piece() {
+            write_varint(&mut bytes, index as u64);
+            write_piece(&mut bytes, *piece);
+        }
+    }
+    let digest = Sha256::digest(&bytes);
+    let mut out = [0; 8];
+    out.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(out)
+}
+
+/// The host side, keeps every action's deltas to answer resyncs.
+#[derive(Clone, Debug)]
+pub struct SyncSender {
+    log: ActionLog,
+    board: GameBoard,
+    deltas: Vec<Vec<BoardDelta>>,
+    /// The hash before every action and after the last.
+    hashes: Vec<u64>,
+}
+impl SyncSender {
+    pub fn new(board: GameBoard) -> Self {
+        Self {
+            log: ActionLog::new(),
+            hashes: vec![board_hash(&board)],
+            board,
+            deltas: Vec::new(),
+        }
+    }
+
+    /// Records `action`, which led to `board`, and returns the frame announcing it.
+    pub fn push(&mut self, action: Action, board: &GameBoard) -> SyncFrame {
+        let deltas = self.board.diff(board);
+        self.board = board.clone();
+        self.log.push(action);
+        self.deltas.push(deltas);
+        self.hashes.push(board_hash(board));
+        self.delta_frame(self.deltas.len() - 1)
+    }
+
+    /// The frames bringing a receiver at `next_sequence` with a board hashing to `board_hash`
+    /// up to date, empty if it already is.
+    pub fn resync(&self, next_sequence: u64, board_hash: u64) -> Vec<SyncFrame> {
+        let known = usize::try_from(next_sequence)
+            .ok()
+            .and_then(|next| Some((next, *self.hashes.get(next)?)));
+        match known {
+            Some((next, hash)) if hash == board_hash => (next..self.deltas.len())
+                .map(|sequence| self.delta_frame(sequence))
+                .collect(),
+            _ => vec![self.snapshot()],
+        }
+    }
+
+    /// The whole current board.
+    pub fn snapshot(&self) -> SyncFrame {
+        let mut pieces = self.board.pieces_of_color(Color::Red);
+        pieces.extend(self.board.pieces_of_color(Color::Blue));
+        SyncFrame::Snapshot {
+            next_sequence: self.deltas.len() as u64,
+            pieces,
+            board_hash: self.hashes[self.deltas.len()],
+        }
+    }
+
+    pub fn board(&self) -> &GameBoard {
+        &self.board
+    }
+    pub fn log(&self) -> &ActionLog {
+        &self.log
+    }
+
+    fn delta_frame(&self, sequence: usize) -> SyncFrame {
+        SyncFrame::Delta {
+            sequence: sequence as u64,
+            action: self.log.actions()[sequence].clone(),
+            deltas: self.deltas[sequence].clone(),
+            board_hash: self.hashes[sequence + 1],
+        }
+    }
+}
+
+/// The remote side, follows the host's board from frames that may be lost, repeated, or reordered.
+#[derive(Clone, Debug)]
+pub struct SyncReceiver {
+    board: GameBoard,
+    next_sequence: u64,
+}
+impl SyncReceiver {
+    /// Starts from the same board the host's [`SyncSender`] started from.
+    pub fn new(board: GameBoard) -> Self {
+        Self {
+            board,
+            next_sequence: 0,
+        }
+    }
+
+    /// Applies `frame` if it follows the current board.
+    ///
+    /// Returns a [`SyncFrame::Resync`] to send to the host when frames are missing or the
+    /// board no longer matches. Frames already applied are ignored.
+    pub fn receive(&mut self, frame: &SyncFrame) -> Option<SyncFrame> {
+        match frame {
+            SyncFrame::Delta {
+                sequence,
+                deltas,
+                board_hash: hash,
+                ..
+            } => {
+                if *sequence < self.next_sequence {
+                    return None;
+                }
+                if *sequence > self.next_sequence {
+                    return Some(self.status());
+                }
+                let mut board = self.board.clone();
+                if board.apply_deltas(deltas).is_none() || board_hash(&board) != *hash {
+                    return Some(self.status());
+                }
+                self.board = board;
+                self.next_sequence += 1;
+                None
+            }
+            SyncFrame::Snapshot {
+                next_sequence,
+                pieces,
+                board_hash: hash,
+            } => {
+                if *next_sequence < self.next_sequence {
+                    return None;
+                }
+                let mut board = self.board.clone();
+                board.invalidate_pieces();
+                for space in board.board.values.iter_mut() {
+                    if let Some(piece) = space.piece_mut() {
+                        *piece = None;
+                    }
+                }
+                for (position, piece) in pieces {
+                    match board.piece_mut(*position) {
+                        Ok(square) => *square = Some(*piece),
+                        Err(_) => return Some(self.status()),
+                    }
+                }
+                if board_hash(&board) != *hash {
+                    return Some(self.status());
+                }
+                self.board = board;
+                self.next_sequence = *next_sequence;
+                None
+            }
+            SyncFrame::Resync { .. } => None,
+        }
+    }
+
+    /// The current position, sent periodically so the host notices lost trailing frames.
+    pub fn status(&self) -> SyncFrame {
+        SyncFrame::Resync {
+            next_sequence: self.next_sequence,
+            board_hash: board_hash(&self.board),
+        }
+    }
+
+    pub fn board(&self) -> &GameBoard {
+        &self.board
+    }
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+fn write_coordinate(out: &mut Vec<u8>, coordinate: Coordinate) {
+    for value in [coordinate.row, coordinate.column] {
+        // Zigzag so small negative offsets stay one byte
+        write_varint(out, ((value << 1) ^ (value >> 15)) as u16 as u64);
+    }
+}
+fn write_piece(out: &mut Vec<u8>, piece: PieceInstance) {
+    let color = match piece.color() {
+        Color::Red => 0,
+        Color::Blue => 1,
+    };
+    write_varint(out, (piece.definition_index() as u64) << 1 | color);
+}
+fn write_direction(out: &mut Vec<u8>, direction: Direction) {
+    out.push(
+        Direction::into_enum_iter()
+            .position(|other| other == direction)
+            .expect("Every direction is iterated") as u8,
+    );
+}
+/// Passes ignore their start, so it is not sent.
+fn write_action(out: &mut Vec<u8>, action: &Action) {
+    match &action.action_type {
+        ActionType::Move(direction) => {
+            out.push(0);
+            write_coordinate(out, action.start_pos);
+            write_direction(out, *direction);
+        }
+        ActionType::Jump(directions) => {
+            out.push(1);
+            write_coordinate(out, action.start_pos);
+            write_varint(out, directions.len() as u64);
+            for direction in directions {
+                write_direction(out, *direction);
+            }
+        }
+        ActionType::Pass => out.push(2),
+    }
+}
+fn write_delta(out: &mut Vec<u8>, delta: &BoardDelta) {
+    match *delta {
+        BoardDelta::Appeared { position, piece } => {
+            out.push(0);
+            write_coordinate(out, position);
+            write_piece(out, piece);
+        }
+        BoardDelta::Disappeared { position, piece } => {
+            out.push(1);
+            write_coordinate(out, position);
+            write_piece(out, piece);
+        }
+        BoardDelta::Moved { from, to, piece } => {
+            out.push(2);
+            write_coordinate(out, from);
+            write_coordinate(out, to);
+            write_piece(out, piece);
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+impl Reader<'_> {
+    fn byte(&mut self) -> SyncResult<u8> {
+        let (&first, rest) = self.bytes.split_first().ok_or(SyncError::Truncated)?;
+        self.bytes = rest;
+        Ok(first)
+    }
+    fn varint(&mut self) -> SyncResult<u64> {
+        let mut out = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            out |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(out);
+            }
+        }
+        Err(SyncError::VarintOverflow)
+    }
+    fn hash(&mut self) -> SyncResult<u64> {
+        let mut out = [0; 8];
+        for byte in out.iter_mut() {
+            *byte = self.byte()?;
+        }
+        Ok(u64::from_le_bytes(out))
+    }
+    fn coordinate(&mut self) -> SyncResult<Coordinate> {
+        let mut value = || -> SyncResult<i16> {
+            let zigzag = u16::try_from(self.varint()?).map_err(|_| SyncError::VarintOverflow)?;
+            Ok((zigzag >> 1) as i16 ^ -((zigzag & 1) as i16))
+        };
+        Ok(Coordinate::new(value()?, value()?))
+    }
+    fn piece(&mut self) -> SyncResult<PieceInstance> {
+        let value = self.varint()?;
+        let color = if value & 1 == 0 {
+            Color::Red
+        } else {
+            Color::Blue
+        };
+        Ok(PieceInstance::new((value >> 1) as usize, color))
+    }
+    fn direction(&mut self) -> SyncResult<Direction> {
+        let byte = self.byte()?;
+        Direction::into_enum_iter()
+            .nth(byte as usize)
+            .ok_or(SyncError::InvalidTag(byte))
+    }
+    fn action(&mut self) -> SyncResult<Action> {
+        match self.byte()? {
+            0 => Ok(Action {
+                start_pos: self.coordinate()?,
+                action_type: ActionType::Move(self.direction()?),
+            }),
+            1 => {
+                let start_pos = self.coordinate()?;
+                let directions = (0..self.varint()?)
+                    .map(|_| self.direction())
+                    .collect::<SyncResult<_>>()?;
+                Ok(Action {
+                    start_pos,
+                    action_type: ActionType::Jump(directions),
+                })
+            }
+            2 => Ok(Action::pass()),
+            tag => Err(SyncError::InvalidTag(tag)),
+        }
+    }
+    fn delta(&mut self) -> SyncResult<BoardDelta> {
+        match self.byte()? {
+            0 => Ok(BoardDelta::Appeared {
+                position: self.coordinate()?,
+                piece: self.piece()?,
+            }),
+            1 => Ok(BoardDelta::Disappeared {
+                position: self.coordinate()?,
+                piece: self.piece()?,
+            }),
+            2 => Ok(BoardDelta::Moved {
+                from: self.coordinate()?,
+                to: self.coordinate()?,
+                piece: self.piece()?,
+            }),
+            tag => Err(SyncError::InvalidTag(tag)),
+        }
+    }
+}
+
+pub type SyncResult<T> = Result<T, SyncError>;
+#[derive(Clone, Debug)]
+pub enum SyncError {
+    /// The frame ended early.
+    Truncated,
+    /// An unknown frame, action, delta, or direction tag.
+    InvalidTag(u8),
+    /// A variable length integer did not fit its type.
+    VarintOverflow,
+    /// Bytes were left over after the frame.
+    TrailingBytes(usize),
+}
+impl Display for SyncError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for SyncError {}
+
+#[cfg(test)]
+mod test {
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::net::sync::{board_hash, SyncError, SyncFrame, SyncReceiver, SyncSender};
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn encode_test() {
+        let mut playout = Game::new(small_ruleset(2))
+            .unwrap()
+            .playout(RandomPolicy::new(2));
+        let mut sender = SyncSender::new(playout.game().board().clone());
+        let mut frames = vec![sender.snapshot()];
+        for _ in 0..40 {
+            let Some((action, _)) = playout.next() else {
+                break;
+            };
+            frames.push(sender.push(action, playout.game().board()));
+        }
+        frames.push(SyncFrame::Resync {
+            next_sequence: u64::MAX,
+            board_hash: 7,
+        });
+        for frame in frames {
+            let bytes = frame.encode();
+            assert_eq!(SyncFrame::decode(&bytes).unwrap(), frame);
+            assert!(matches!(
+                SyncFrame::decode(&bytes[..bytes.len() - 1]),
+                Err(SyncError::Truncated)
+            ));
+        }
+        assert!(matches!(
+            SyncFrame::decode(&[9]),
+            Err(SyncError::InvalidTag(9))
+        ));
+    }
+
+    #[test]
+    fn lossy_sync_test() {
+        let mut playout = Game::new(small_ruleset(3))
+            .unwrap()
+            .playout(RandomPolicy::new(3));
+        let start = playout.game().board().clone();
+        let mut sender = SyncSender::new(start.clone());
+        let mut receiver = SyncReceiver::new(start);
+
+        let mut resyncs = 0;
+        for index in 0..60 {
+            let Some((action, _)) = playout.next() else {
+                break;
+            };
+            let frame = sender.push(action, playout.game().board());
+            // Every third frame is lost
+            if index % 3 == 1 {
+                continue;
+            }
+            if let Some(SyncFrame::Resync {
+                next_sequence,
+                board_hash,
+            }) = receiver.receive(&frame)
+            {
+                resyncs += 1;
+                for frame in sender.resync(next_sequence, board_hash) {
+                    assert_eq!(receiver.receive(&frame), None);
+                }
+            }
+            // Repeats are ignored
+            assert_eq!(receiver.receive(&frame), None);
+            assert_eq!(receiver.board().board, sender.board().board);
+        }
+        assert!(resyncs > 0);
+        // The last frame may have been lost, the periodic status catches it up
+        if let SyncFrame::Resync {
+            next_sequence,
+            board_hash,
+        } = receiver.status()
+        {
+            for frame in sender.resync(next_sequence, board_hash) {
+                assert_eq!(receiver.receive(&frame), None);
+            }
+        }
+        assert_eq!(receiver.next_sequence() as usize, sender.log().len());
+
+        // A receiver on a diverged board is sent a snapshot
+        let mut diverged = SyncReceiver::new(playout.game().board().clone());
+        let SyncFrame::Resync {
+            next_sequence,
+            board_hash: hash,
+        } = diverged.status()
+        else {
+            panic!("Expected a resync");
+        };
+        assert_eq!(hash, board_hash(playout.game().board()));
+        let frames = sender.resync(next_sequence, hash);
+        assert!(matches!(frames[..], [SyncFrame::Snapshot { .. }]));
+        assert_eq!(diverged.receive(&frames[0]), None);
+        assert_eq!(diverged.board().board, sender.board().board);
+        assert!(sender
+            .resync(diverged.next_sequence(), board_hash(diverged.board()))
+            .is_empty());
+    }
+}
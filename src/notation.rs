@@ -0,0 +1,474 @@
+//! Human readable text for actions and move lists.
+//!
+//! Squares are written in a [`CoordinateScheme`], [`CoordinateScheme::Algebraic`] by default.
+//! Moves are written as `b2-b3`, jump chains as every landing `b2:d4:f2`, and
+//! passes as `pass`. Every action names its squares so no disambiguation is needed.
+//! A [huff](crate::game::Game::huff) follows the action it punishes as `huff:c3`.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::action::jump_directions::JumpDirections;
+use crate::action::{Action, ActionType};
+use crate::coordinate::Coordinate;
+use crate::direction::Direction;
+use crate::game::huff::Huff;
+use crate::game::record::GameRecord;
+use crate::game::GameOutcome;
+use crate::game_board::Color;
+
+/// The actions, huffs, and result read by [`parse_move_list`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MoveList {
+    pub actions: Vec<Action>,
+    pub huffs: Vec<Huff>,
+    pub result: Option<GameOutcome>,
+}
+
+/// How squares are labeled.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CoordinateScheme {
+    /// A column letter and a row number counted from the top, `a1` is row 0 column 0.
+    /// Columns after `z` continue with `aa`.
+    #[default]
+    Algebraic,
+    /// Row and column indices from 0, `2,3` is row 2 column 3.
+    /// Parsing also accepts them in parentheses, `(2,3)`.
+    RowColumn,
+    /// Squares numbered from 1 left to right then top to bottom, like draughts.
+    Numeric {
+        /// Columns of the board.
+        columns: usize,
+    },
+}
+impl CoordinateScheme {
+    /// `position` must be on the board.
+    pub fn format_square(self, position: Coordinate) -> String {
+        match self {
+            Self::Algebraic => format_algebraic(position),
+            Self::RowColumn => format!("{},{}", position.row, position.column),
+            Self::Numeric { columns } => {
+                (position.row as usize * columns + position.column as usize + 1).to_string()
+            }
+        }
+    }
+
+    pub fn parse_square(self, text: &str) -> NotationResult<Coordinate> {
+        let invalid = || NotationError::InvalidSquare(text.to_string());
+        match self {
+            Self::Algebraic => parse_algebraic(text).ok_or_else(invalid),
+            Self::RowColumn => {
+                let pair = text
+                    .strip_prefix('(')
+                    .and_then(|pair| pair.strip_suffix(')'))
+                    .unwrap_or(text);
+                let (row, column) = pair.split_once(',').ok_or_else(invalid)?;
+                let (row, column) = (row.trim(), column.trim());
+                let row: u16 = parse_digits(row).ok_or_else(invalid)?;
+                let column: u16 = parse_digits(column).ok_or_else(invalid)?;
+                if row > i16::MAX as u16 || column > i16::MAX as u16 {
+                    return Err(invalid());
+                }
+                Ok(Coordinate::new(row as i16, column as i16))
+            }
+            Self::Numeric { columns } => {
+                let number: usize = parse_digits(text).ok_or_else(invalid)?;
+                if number == 0 || columns == 0 || (number - 1) / columns > i16::MAX as usize {
+                    return Err(invalid());
+                }
+                Ok(Coordinate::new(
+                    ((number - 1) / columns) as i16,
+                    ((number - 1) % columns) as i16,
+                ))
+            }
+        }
+    }
+}
+
+/// Numbered move text for `record`, two actions per line with the second column aligned.
+///
+/// Jumps that captured are followed by the capture count in braces, `b2:d4 {1}`, as long as
+/// the record replays. Finished games end with a result line, `1-0` when Red won,
+/// `0-1` when Blue won, and `1/2-1/2` for draws.
+pub fn format_move_list(record: &GameRecord, scheme: CoordinateScheme) -> String {
+    let mut game = GameRecord {
+        actions: Vec::new(),
+        huffs: Vec::new(),
+        ..record.clone()
+    }
+    .replay()
+    .ok();
+    let mut entries: Vec<String> = record
+        .actions
+        .iter()
+        .enumerate()
+        .map(|(ply, action)| {
+            for huff in record.huffs.iter().filter(|huff| huff.ply == ply) {
+                if game
+                    .as_mut()
+                    .is_some_and(|game| game.huff(huff.position).is_err())
+                {
+                    game = None;
+                }
+            }
+            let captures = game
+                .as_mut()
+                .and_then(|game| game.apply(action).ok())
+                .map(|outcome| outcome.captures.len());
+            if captures.is_none() {
+                game = None;
+            }
+            match captures {
+                Some(captures) if captures > 0 => {
+                    format!("{} {{{}}}", format_action(action, scheme), captures)
+                }
+                _ => format_action(action, scheme),
+            }
+        })
+        .collect();
+    for huff in record.huffs.iter() {
+        if let Some(entry) = huff.ply.checked_sub(1).and_then(|ply| entries.get_mut(ply)) {
+            let _ = write!(entry, " huff:{}", scheme.format_square(huff.position));
+        }
+    }
+
+    let number_width = entries.len().div_ceil(2).to_string().len();
+    let first_width = entries
+        .iter()
+        .step_by(2)
+        .map(String::len)
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for (index, pair) in entries.chunks(2).enumerate() {
+        let _ = write!(out, "{:>width$}. ", index + 1, width = number_width);
+        match pair {
+            [first, second] => {
+                let _ = writeln!(out, "{:<width$}  {}", first, second, width = first_width);
+            }
+            [first] => {
+                let _ = writeln!(out, "{}", first);
+            }
+            _ => unreachable!("Chunks have one or two entries"),
+        }
+    }
+    if let Some(result) = record.result {
+        let _ = writeln!(out, "{}", format_result(result));
+    }
+    out
+}
+
+/// Reads text written by [`format_move_list`].
+///
+/// Move numbers and capture counts are skipped, a result may only be the last token. Huffs are
+/// recorded before the next action.
+pub fn parse_move_list(text: &str, scheme: CoordinateScheme) -> NotationResult<MoveList> {
+    let mut out = MoveList::default();
+    let mut tokens = text.split_whitespace().peekable();
+    while let Some(token) = tokens.next() {
+        if let Some(result) = parse_result(token) {
+            if tokens.peek().is_some() {
+                return Err(NotationError::ResultNotLast(token.to_string()));
+            }
+            out.result = Some(result);
+        } else if is_move_number(token) || is_capture_count(token) {
+            continue;
+        } else if let Some(square) = token.strip_prefix("huff:") {
+            out.huffs.push(Huff {
+                ply: out.actions.len(),
+                position: scheme.parse_square(square)?,
+            });
+        } else {
+            out.actions.push(parse_action(token, scheme)?);
+        }
+    }
+    Ok(out)
+}
+
+pub fn format_action(action: &Action, scheme: CoordinateScheme) -> String {
+    match &action.action_type {
+        ActionType::Move(_) => format!(
+            "{}-{}",
+            scheme.format_square(action.start_pos),
+            scheme.format_square(action.end_pos())
+        ),
+        ActionType::Jump(directions) => {
+            let mut out = scheme.format_square(action.start_pos);
+            let mut position = action.start_pos;
+            for direction in directions {
+                position = direction.offset() * 2 + position;
+                out.push(':');
+                out.push_str(&scheme.format_square(position));
+            }
+            out
+        }
+        ActionType::Pass => "pass".to_string(),
+    }
+}
+
+pub fn parse_action(text: &str, scheme: CoordinateScheme) -> NotationResult<Action> {
+    if text == "pass" {
+        return Ok(Action::pass());
+    }
+    if let Some((start, end)) = text.split_once('-') {
+        let start_pos = scheme.parse_square(start)?;
+        let direction = step_direction(start_pos, scheme.parse_square(end)?, 1)?;
+        return Ok(Action {
+            start_pos,
+            action_type: ActionType::Move(direction),
+        });
+    }
+    let mut squares = text.split(':');
+    let start_pos = scheme.parse_square(squares.next().unwrap_or_default())?;
+    let mut position = start_pos;
+    let mut directions = JumpDirections::new();
+    for square in squares {
+        let landing = scheme.parse_square(square)?;
+        directions.push(step_direction(position, landing, 2)?);
+        position = landing;
+    }
+    if directions.is_empty() {
+        return Err(NotationError::InvalidAction(text.to_string()));
+    }
+    Ok(Action {
+        start_pos,
+        action_type: ActionType::Jump(directions),
+    })
+}
+
+fn format_algebraic(position: Coordinate) -> String {
+    let mut letters = Vec::new();
+    let mut column = position.column as i32 + 1;
+    while column > 0 {
+        column -= 1;
+        letters.push((b'a' + (column % 26) as u8) as char);
+        column /= 26;
+    }
+    letters.iter().rev().collect::<String>() + &(position.row as i32 + 1).to_string()
+}
+
+fn parse_algebraic(text: &str) -> Option<Coordinate> {
+    let split = text.find(|character: char| !character.is_ascii_lowercase())?;
+    let (letters, digits) = text.split_at(split);
+    if letters.is_empty() {
+        return None;
+    }
+    let column = letters.bytes().try_fold(0i32, |column, byte| {
+        column
+            .checked_mul(26)
+            .and_then(|column| column.checked_add((byte - b'a') as i32 + 1))
+    })?;
+    let row: i32 = parse_digits(digits)?;
+    if row < 1 || row > i16::MAX as i32 || column > i16::MAX as i32 {
+        return None;
+    }
+    Some(Coordinate::new(row as i16 - 1, column as i16 - 1))
+}
+
+/// Parses only ASCII digits, rejecting signs.
+fn parse_digits<T: core::str::FromStr>(text: &str) -> Option<T> {
+    if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+fn format_result(result: GameOutcome) -> &'static str {
+    match result {
+        GameOutcome::Win(Color::Red) => "1-0",
+        GameOutcome::Win(Color::Blue) => "0-1",
+        GameOutcome::Draw => "1/2-1/2",
+    }
+}
+
+fn parse_result(token: &str) -> Option<GameOutcome> {
+    match token {
+        "1-0" => Some(GameOutcome::Win(Color::Red)),
+        "0-1" => Some(GameOutcome::Win(Color::Blue)),
+        "1/2-1/2" => Some(GameOutcome::Draw),
+        _ => None,
+    }
+}
+
+fn is_move_number(token: &str) -> bool {
+    token.strip_suffix('.').is_some_and(|number| {
+        !number.is_empty() && number.bytes().all(|byte| byte.is_ascii_digit())
+    })
+}
+
+fn is_capture_count(token: &str) -> bool {
+    token
+        .strip_prefix('{')
+        .and_then(|token| token.strip_suffix('}'))
+        .is_some_and(|count| count.parse::<usize>().is_ok())
+}
+
+/// The direction `distance` steps of which lead from `from` to `to`.
+fn step_direction(from: Coordinate, to: Coordinate, distance: i16) -> NotationResult<Direction> {
+    Direction::into_enum_iter()
+        .find(|direction| direction.offset() * distance + from == to)
+        .ok_or(NotationError::InvalidStep { from, to })
+}
+
+pub type NotationResult<T> = Result<T, NotationError>;
+#[derive(Clone, Debug)]
+pub enum NotationError {
+    InvalidSquare(String),
+    /// Text that is not a move, jump chain, or pass.
+    InvalidAction(String),
+    /// Squares that are not one step apart for moves or two for jumps.
+    InvalidStep {
+        from: Coordinate,
+        to: Coordinate,
+    },
+    /// A result followed by more tokens.
+    ResultNotLast(String),
+}
+impl Display for NotationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for NotationError {}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::action::{Action, ActionType};
+    use crate::coordinate::Coordinate;
+    use crate::direction::Direction;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::notation::{
+        format_action, format_move_list, parse_action, parse_move_list, CoordinateScheme,
+        NotationError,
+    };
+    use crate::testing::{arb_action, small_ruleset};
+
+    #[test]
+    fn action_text_test() {
+        let scheme = CoordinateScheme::Algebraic;
+        assert_eq!(scheme.format_square(Coordinate::new(0, 0)), "a1");
+        assert_eq!(scheme.format_square(Coordinate::new(9, 27)), "ab10");
+        assert_eq!(scheme.parse_square("ab10").unwrap(), Coordinate::new(9, 27));
+        assert!(matches!(
+            scheme.parse_square("a0"),
+            Err(NotationError::InvalidSquare(_))
+        ));
+
+        let jump = Action {
+            start_pos: Coordinate::new(1, 1),
+            action_type: ActionType::Jump([Direction::SouthEast, Direction::North].into()),
+        };
+        assert_eq!(format_action(&jump, scheme), "b2:d4:d2");
+        assert_eq!(parse_action("b2:d4:d2", scheme).unwrap(), jump);
+        assert!(matches!(
+            parse_action("b2-b4", scheme),
+            Err(NotationError::InvalidStep { .. })
+        ));
+        assert!(matches!(
+            parse_action("b2", scheme),
+            Err(NotationError::InvalidAction(_))
+        ));
+    }
+
+    #[test]
+    fn coordinate_scheme_test() {
+        let position = Coordinate::new(2, 3);
+        let schemes = [
+            (CoordinateScheme::Algebraic, "d3"),
+            (CoordinateScheme::RowColumn, "2,3"),
+            (CoordinateScheme::Numeric { columns: 6 }, "16"),
+        ];
+        for (scheme, text) in schemes.iter() {
+            assert_eq!(scheme.format_square(position), *text);
+            assert_eq!(scheme.parse_square(text).unwrap(), position);
+        }
+        assert!(CoordinateScheme::RowColumn.parse_square("-1,2").is_err());
+        assert!(CoordinateScheme::Numeric { columns: 6 }
+            .parse_square("0")
+            .is_err());
+
+        let action = Action {
+            start_pos: position,
+            action_type: ActionType::Move(Direction::SouthWest),
+        };
+        let scheme = CoordinateScheme::Numeric { columns: 6 };
+        assert_eq!(format_action(&action, scheme), "16-21");
+        assert_eq!(parse_action("16-21", scheme).unwrap(), action);
+    }
+
+    #[test]
+    fn move_list_test() {
+        let mut playout = Game::new(small_ruleset(1))
+            .unwrap()
+            .playout(RandomPolicy::new(1));
+        playout.by_ref().take(400).for_each(drop);
+        let record = playout.game().record();
+
+        let scheme = CoordinateScheme::Numeric { columns: 6 };
+        let text = format_move_list(&record, scheme);
+        assert!(text.trim_start().starts_with("1. "));
+        assert!(text.contains(" {1}"));
+        let pairs: Vec<_> = text
+            .lines()
+            .filter_map(|line| {
+                let actions: Vec<_> = line
+                    .split_whitespace()
+                    .filter(|token| !token.ends_with('.') && !token.starts_with('{'))
+                    .collect();
+                match actions[..] {
+                    [_, second] => Some(line.rfind(second).unwrap()),
+                    _ => None,
+                }
+            })
+            .collect();
+        assert!(pairs.len() > 1);
+        assert!(pairs.iter().all(|&column| column == pairs[0]));
+
+        let parsed = parse_move_list(&text, scheme).unwrap();
+        assert_eq!(parsed.actions, record.actions);
+        assert_eq!(parsed.result, record.result);
+    }
+
+    proptest! {
+        #[test]
+        fn action_round_trip_test(action in arb_action(30, 30, 4)) {
+            let action = match action.action_type {
+                ActionType::Pass => Action::pass(),
+                _ => action,
+            };
+            let mut position = action.start_pos;
+            let mut squares = vec![position];
+            if let ActionType::Jump(directions) = &action.action_type {
+                for direction in directions {
+                    position = direction.offset() * 2 + position;
+                    squares.push(position);
+                }
+            }
+            squares.push(action.end_pos());
+            prop_assume!(squares.iter().all(|square| square.row >= 0 && square.column >= 0));
+            for scheme in [
+                CoordinateScheme::Algebraic,
+                CoordinateScheme::RowColumn,
+                // Wide enough that squares off the right edge do not wrap
+                CoordinateScheme::Numeric { columns: 64 },
+            ] {
+                prop_assert_eq!(
+                    parse_action(&format_action(&action, scheme), scheme).unwrap(),
+                    action.clone()
+                );
+            }
+        }
+    }
+}
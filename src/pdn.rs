@@ -0,0 +1,502 @@
+//! Portable Draughts Notation, so draughts databases and tools work with kapto games.
+//!
+//! Only the squares where row plus column is odd are numbered, from 1 at the top left, left to
+//! right then top to bottom, like a draughts board with the goal rows included. That covers
+//! boards with an even number of columns where every piece starts on a numbered square and
+//! only moves and jumps diagonally. The color to move first plays White. Moves are written as
+//! `32-28` and jump chains as every landing `28x19x10`.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::action::{Action, ActionType};
+use crate::coordinate::Coordinate;
+use crate::direction::Directions;
+use crate::game::record::GameRecord;
+use crate::game::{Game, GameError, GameOutcome};
+use crate::game_board::board::Board;
+use crate::game_board::Color;
+use crate::ruleset::Ruleset;
+
+/// Movetext lines are wrapped before this many characters.
+const LINE_WIDTH: usize = 80;
+
+/// PDN text for `record`, with a `GameType` tag sized to the board and a `Result` tag.
+///
+/// Draws are `1-1` and wins `2-0` or `0-2` as in international draughts, games in progress `*`.
+pub fn format_pdn(record: &GameRecord) -> PdnResult<String> {
+    let game = GameRecord {
+        actions: Vec::new(),
+        ..record.clone()
+    }
+    .replay()?;
+    let (rows, columns) = (game.board().rows(), game.board().columns());
+    if columns % 2 != 0 {
+        return Err(PdnError::OddColumns(columns));
+    }
+    if let Some((position, _)) = game
+        .board()
+        .pieces()
+        .find(|(position, _)| pdn_square(*position, columns).is_none())
+    {
+        return Err(PdnError::LightSquare(position));
+    }
+    let white = game.to_move();
+
+    let mut tokens = Vec::with_capacity(record.actions.len() * 3 / 2 + 1);
+    for (ply, action) in record.actions.iter().enumerate() {
+        if ply % 2 == 0 {
+            tokens.push(format!("{}.", ply / 2 + 1));
+        }
+        if action.action_type.is_pass() {
+            return Err(PdnError::Pass { ply });
+        }
+        if !is_diagonal(action) {
+            return Err(PdnError::NotDiagonal { ply });
+        }
+        let squares = action_squares(action);
+        let numbers = squares
+            .iter()
+            .map(|position| {
+                pdn_square(*position, columns)
+                    .map(|number| number.to_string())
+                    .ok_or(PdnError::LightSquare(*position))
+            })
+            .collect::<PdnResult<Vec<_>>>()?;
+        let separator = match action.action_type {
+            ActionType::Move(_) => "-",
+            _ => "x",
+        };
+        tokens.push(numbers.join(separator));
+    }
+    let result = format_result(record.result, white);
+    tokens.push(result.to_string());
+
+    let mut out = format!(
+        "[GameType \"20,W,{},{},N2,0\"]\n[Result \"{}\"]\n\n",
+        columns, rows, result
+    );
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length > 0 && line_length + 1 + token.len() > LINE_WIDTH {
+            out.push('\n');
+            line_length = 0;
+        } else if line_length > 0 {
+            out.push(' ');
+            line_length += 1;
+        }
+        line_length += token.len();
+        out.push_str(&token);
+    }
+    out.push('\n');
+    Ok(out)
+}
+
+/// Reads every game in the PDN text `text`, playing them under `ruleset`.
+///
+/// Jump chains may leave out landings as long as a single legal jump matches. Comments,
+/// variations, move numbers and annotations are skipped. A game's result is the one the text
+/// claims, [`GameRecord::verify`] checks it against the rules.
+pub fn parse_pdn(text: &str, ruleset: &Ruleset) -> PdnResult<Vec<GameRecord>> {
+    let mut out = Vec::new();
+    let mut pending: Option<PendingGame> = None;
+    for token in tokens(text)? {
+        match token {
+            Token::Tag { name, value } => {
+                if pending.as_ref().is_some_and(|game| game.has_moves) {
+                    out.push(pending.take().expect("Checked above").finish());
+                }
+                let game = match &mut pending {
+                    Some(game) => game,
+                    None => pending.insert(PendingGame::new(ruleset)?),
+                };
+                match name {
+                    "Result" => game.result = Some(parse_result(value, game.white)),
+                    "FEN" => return Err(PdnError::UnsupportedTag(name.to_string())),
+                    _ => {}
+                }
+            }
+            Token::Move(text) => {
+                let game = match &mut pending {
+                    Some(game) => game,
+                    None => pending.insert(PendingGame::new(ruleset)?),
+                };
+                if is_result(text) {
+                    game.result = Some(parse_result(text, game.white));
+                    out.push(pending.take().expect("Set above").finish());
+                } else {
+                    game.play(text)?;
+                }
+            }
+        }
+    }
+    out.extend(pending.map(PendingGame::finish));
+    Ok(out)
+}
+
+/// The PDN number of `position` on a board of `columns`, `None` for unnumbered squares.
+fn pdn_square(position: Coordinate, columns: usize) -> Option<usize> {
+    if position.row < 0 || position.column < 0 || (position.row + position.column) % 2 == 0 {
+        return None;
+    }
+    let (row, column) = (position.row as usize, position.column as usize);
+    (column < columns).then(|| row * (columns / 2) + column / 2 + 1)
+}
+
+/// The square numbered `number` on a board of `columns`.
+fn pdn_position(number: usize, columns: usize) -> Option<Coordinate> {
+    let half = columns / 2;
+    if number == 0 || half == 0 || (number - 1) / half > i16::MAX as usize {
+        return None;
+    }
+    let row = (number - 1) / half;
+    let column = 2 * ((number - 1) % half) + row.is_multiple_of(2) as usize;
+    Some(Coordinate::new(row as i16, column as i16))
+}
+
+/// Whether every step of `action` is diagonal, `false` for passes.
+fn is_diagonal(action: &Action) -> bool {
+    match &action.action_type {
+        ActionType::Move(direction) => Directions::DIAGONAL.contains((*direction).into()),
+        ActionType::Jump(directions) => directions
+            .iter()
+            .all(|direction| Directions::DIAGONAL.contains((*direction).into())),
+        ActionType::Pass => false,
+    }
+}
+
+/// The start and every landing of `action`.
+fn action_squares(action: &Action) -> Vec<Coordinate> {
+    let mut out = vec![action.start_pos];
+    match &action.action_type {
+        ActionType::Move(direction) => out.push(direction.offset() + action.start_pos),
+        ActionType::Jump(directions) => {
+            for direction in directions {
+                let landing = direction.offset() * 2 + out[out.len() - 1];
+                out.push(landing);
+            }
+        }
+        ActionType::Pass => {}
+    }
+    out
+}
+
+fn format_result(result: Option<GameOutcome>, white: Color) -> &'static str {
+    match result {
+        Some(GameOutcome::Win(color)) if color == white => "2-0",
+        Some(GameOutcome::Win(_)) => "0-2",
+        Some(GameOutcome::Draw) => "1-1",
+        None => "*",
+    }
+}
+
+fn is_result(token: &str) -> bool {
+    matches!(
+        token,
+        "2-0" | "0-2" | "1-1" | "1-0" | "0-1" | "1/2-1/2" | "0-0" | "*"
+    )
+}
+
+/// `None` for games in progress and unknown results.
+fn parse_result(token: &str, white: Color) -> Option<GameOutcome> {
+    match token {
+        "2-0" | "1-0" => Some(GameOutcome::Win(white)),
+        "0-2" | "0-1" => Some(GameOutcome::Win(white.opponent())),
+        "1-1" | "1/2-1/2" => Some(GameOutcome::Draw),
+        _ => None,
+    }
+}
+
+/// A game being read by [`parse_pdn`].
+struct PendingGame {
+    game: Game,
+    white: Color,
+    /// The claimed result, `Some(None)` for games in progress.
+    result: Option<Option<GameOutcome>>,
+    has_moves: bool,
+}
+impl PendingGame {
+    fn new(ruleset: &Ruleset) -> PdnResult<Self> {
+        let game = Game::new(ruleset.clone())?;
+        Ok(Self {
+            white: game.to_move(),
+            game,
+            result: None,
+            has_moves: false,
+        })
+    }
+
+    fn play(&mut self, text: &str) -> PdnResult<()> {
+        let ply = self.game.history().len();
+        let columns = self.game.board().columns();
+        let jump = text.contains(&['x', 'X'][..]);
+        let squares = text
+            .split(&['-', 'x', 'X'][..])
+            .map(|square| {
+                square
+                    .parse()
+                    .ok()
+                    .filter(|_| square.bytes().all(|byte| byte.is_ascii_digit()))
+                    .and_then(|number| pdn_position(number, columns))
+                    .ok_or_else(|| PdnError::InvalidSquare(square.to_string()))
+            })
+            .collect::<PdnResult<Vec<_>>>()?;
+        if squares.len() < 2 {
+            return Err(PdnError::InvalidMove(text.to_string()));
+        }
+        let mut matching = self.game.legal_actions().into_iter().filter(|action| {
+            let path = action_squares(action);
+            let kind = match action.action_type {
+                ActionType::Move(_) => !jump && squares.len() == 2,
+                ActionType::Jump(_) => jump,
+                ActionType::Pass => false,
+            };
+            kind && path[0] == squares[0]
+                && path.last() == squares.last()
+                && is_subsequence(&squares, &path)
+        });
+        let action = match (matching.next(), matching.next()) {
+            (Some(action), None) => action,
+            (None, _) => {
+                return Err(PdnError::NoMatchingAction {
+                    ply,
+                    text: text.to_string(),
+                })
+            }
+            (Some(_), Some(_)) => {
+                return Err(PdnError::AmbiguousAction {
+                    ply,
+                    text: text.to_string(),
+                })
+            }
+        };
+        self.game
+            .apply(&action)
+            .map_err(|error| GameError::IllegalAction { ply, error })?;
+        self.has_moves = true;
+        Ok(())
+    }
+
+    fn finish(self) -> GameRecord {
+        let mut out = self.game.record();
+        if let Some(result) = self.result {
+            out.result = result;
+        }
+        out
+    }
+}
+
+fn is_subsequence(needle: &[Coordinate], haystack: &[Coordinate]) -> bool {
+    let mut haystack = haystack.iter();
+    needle
+        .iter()
+        .all(|square| haystack.any(|candidate| candidate == square))
+}
+
+enum Token<'a> {
+    Tag { name: &'a str, value: &'a str },
+    Move(&'a str),
+}
+
+/// Tags and moves in `text`, without comments, variations, move numbers and annotations.
+fn tokens(text: &str) -> PdnResult<Vec<Token<'_>>> {
+    let mut out = Vec::new();
+    let mut rest = text;
+    loop {
+        rest = rest.trim_start();
+        let first = match rest.chars().next() {
+            Some(first) => first,
+            None => return Ok(out),
+        };
+        match first {
+            '[' => {
+                let end = rest.find(']').ok_or(PdnError::Unclosed('['))?;
+                let tag = rest[1..end].trim();
+                let (name, value) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .unwrap_or(value);
+                out.push(Token::Tag { name, value });
+                rest = &rest[end + 1..];
+            }
+            '{' => {
+                let end = rest.find('}').ok_or(PdnError::Unclosed('{'))?;
+                rest = &rest[end + 1..];
+            }
+            '(' => {
+                let mut depth = 0usize;
+                let end = rest
+                    .char_indices()
+                    .find(|&(_, character)| {
+                        match character {
+                            '(' => depth += 1,
+                            ')' => depth -= 1,
+                            _ => {}
+                        }
+                        depth == 0
+                    })
+                    .ok_or(PdnError::Unclosed('('))?
+                    .0;
+                rest = &rest[end + 1..];
+            }
+            ';' => rest = rest.split_once('\n').map_or("", |(_, rest)| rest),
+            _ => {
+                let end = rest
+                    .find(|character: char| character.is_whitespace() || "[{(;".contains(character))
+                    .unwrap_or(rest.len());
+                let token = &rest[..end];
+                rest = &rest[end..];
+                if token.starts_with('$') {
+                    continue;
+                }
+                // Move numbers may be joined to the move, `12.32-28`
+                let token = match token.rfind('.') {
+                    Some(dot) if !is_result(token) => &token[dot + 1..],
+                    _ => token,
+                };
+                let token = token.trim_end_matches(['!', '?']);
+                if !token.is_empty() {
+                    out.push(Token::Move(token));
+                }
+            }
+        }
+    }
+}
+
+pub type PdnResult<T> = Result<T, PdnError>;
+#[derive(Clone, Debug)]
+pub enum PdnError {
+    /// The game could not be started or replayed.
+    GameError(GameError),
+    /// Boards with an odd number of columns have no draughts numbering.
+    OddColumns(usize),
+    /// A piece on or moving to an unnumbered square.
+    LightSquare(Coordinate),
+    /// Passes have no PDN notation.
+    Pass { ply: usize },
+    /// A move or jump that is not diagonal.
+    NotDiagonal { ply: usize },
+    /// A square number that is not on the board.
+    InvalidSquare(String),
+    /// Text that is not a move, jump chain or result.
+    InvalidMove(String),
+    /// No legal action has the move's squares.
+    NoMatchingAction { ply: usize, text: String },
+    /// More than one legal action has the move's shortened squares.
+    AmbiguousAction { ply: usize, text: String },
+    /// A tag that needs rules kapto does not read from PDN, like a setup position.
+    UnsupportedTag(String),
+    /// A tag, comment or variation that is never closed.
+    Unclosed(char),
+}
+impl Display for PdnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for PdnError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match self {
+            Self::GameError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+impl From<GameError> for PdnError {
+    fn from(from: GameError) -> Self {
+        Self::GameError(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use crate::coordinate::Coordinate;
+    use crate::game::Game;
+    use crate::pdn::{format_pdn, is_diagonal, parse_pdn, pdn_position, pdn_square, PdnError};
+    use crate::ruleset::piece_definition::PieceDefinition;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::ruleset::Ruleset;
+    use crate::testing::small_ruleset;
+
+    fn checkers_ruleset() -> Ruleset {
+        let mut ruleset = small_ruleset(0);
+        ruleset.pieces = vec![PieceDefinition::checker()];
+        let positions = [(1, 0), (1, 2), (1, 4), (2, 1), (2, 3), (2, 5)]
+            .iter()
+            .map(|&(row, column)| Coordinate::new(row, column))
+            .collect();
+        ruleset.starting_positions =
+            StartingPositions::MirroredRotated(vec![(0, positions)].into_iter().collect());
+        ruleset
+    }
+
+    #[test]
+    fn square_numbers_test() {
+        let squares = [(1, (0, 1)), (5, (0, 9)), (6, (1, 0)), (50, (9, 8))];
+        for &(number, (row, column)) in squares.iter() {
+            let position = Coordinate::new(row, column);
+            assert_eq!(pdn_square(position, 10), Some(number));
+            assert_eq!(pdn_position(number, 10), Some(position));
+        }
+        assert_eq!(pdn_square(Coordinate::new(0, 0), 10), None);
+        assert_eq!(pdn_position(0, 10), None);
+    }
+
+    #[test]
+    fn pdn_test() {
+        let ruleset = checkers_ruleset();
+        let mut game = Game::new(ruleset.clone()).unwrap();
+        while game.history().len() < 60 && !game.is_finished() {
+            let diagonal: Vec<_> = game
+                .legal_actions()
+                .into_iter()
+                .filter(is_diagonal)
+                .collect();
+            if diagonal.is_empty() {
+                break;
+            }
+            let action = &diagonal[game.history().len() * 7 % diagonal.len()];
+            game.apply(action).unwrap();
+        }
+        let record = game.record();
+        assert!(record.actions.len() > 4);
+
+        let text = format_pdn(&record).unwrap();
+        assert!(text.starts_with("[GameType \"20,W,6,8,N2,0\"]\n"));
+        assert!(text.lines().all(|line| line.len() <= 80));
+        assert!(text.contains(" 2. "));
+        let parsed = parse_pdn(&text, &ruleset).unwrap();
+        assert_eq!(parsed, vec![record.clone()]);
+
+        let annotated = text.replacen("1. ", "1. {opening} ", 1).replacen(
+            " 2. ",
+            " (2. 1-5 {variation}) 2. ",
+            1,
+        ) + "\n"
+            + &text;
+        let parsed = parse_pdn(&annotated, &ruleset).unwrap();
+        assert_eq!(parsed, vec![record.clone(), record]);
+
+        assert!(matches!(
+            parse_pdn("1. 1-2", &ruleset),
+            Err(PdnError::NoMatchingAction { ply: 0, .. })
+        ));
+        assert!(matches!(
+            parse_pdn("{unclosed", &ruleset),
+            Err(PdnError::Unclosed('{'))
+        ));
+        let standard = Game::new(small_ruleset(0)).unwrap().record();
+        assert!(format_pdn(&standard).is_err());
+    }
+}
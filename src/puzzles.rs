@@ -0,0 +1,170 @@
+//! Tactics puzzles, positions where exactly one jump chain forces a win.
+//!
+//! Positions are searched a few moves deep, the solver's first action must be the only one
+//! forcing a win at the shallowest depth any action does.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::action::Action;
+use crate::game::record::GameRecord;
+use crate::game::{Game, GameError, GameOutcome, GameResult};
+use crate::game_board::Color;
+
+/// A position with a unique winning jump chain.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Puzzle {
+    /// The game up to the puzzle, [`GameRecord::replay`] gives the position to solve.
+    pub position: GameRecord,
+    /// The main line, alternating between the solver and the replies that hold out longest.
+    pub solution: Vec<Action>,
+    /// Actions the solver needs to win, a win with the first action is `1`.
+    pub difficulty: usize,
+}
+impl Puzzle {
+    /// The color solving the puzzle.
+    pub fn solver(&self) -> GameResult<Color> {
+        Ok(self.position.replay()?.to_move())
+    }
+}
+
+/// The puzzle at the current position of `game`, searching up to `max_depth` solver actions.
+pub fn find(game: &Game, max_depth: usize) -> Option<Puzzle> {
+    if game.is_finished() {
+        return None;
+    }
+    let solver = game.to_move();
+    for depth in 1..=max_depth {
+        let mut wins = game.legal_actions().into_iter().filter_map(|action| {
+            let mut after = game.clone();
+            after.apply(&action).ok()?;
+            let mut line = forced_win(&after, solver, depth - 1)?;
+            line.insert(0, action);
+            Some(line)
+        });
+        let solution = match (wins.next(), wins.next()) {
+            (None, _) => continue,
+            (Some(solution), None) => solution,
+            (Some(_), Some(_)) => return None,
+        };
+        if !solution[0].action_type.is_jump() {
+            return None;
+        }
+        return Some(Puzzle {
+            position: game.record(),
+            solution,
+            difficulty: depth,
+        });
+    }
+    None
+}
+
+/// Every puzzle in the positions before each action of `record`.
+pub fn scan(record: &GameRecord, max_depth: usize) -> GameResult<Vec<Puzzle>> {
+    let mut game = GameRecord {
+        actions: Vec::new(),
+        ..record.clone()
+    }
+    .replay()?;
+    let mut out = Vec::new();
+    for (ply, action) in record.actions.iter().enumerate() {
+        out.extend(find(&game, max_depth));
+        game.apply(action)
+            .map_err(|error| GameError::IllegalAction { ply, error })?;
+    }
+    Ok(out)
+}
+
+/// The line forcing a win for `solver` with at most `depth` more solver actions.
+fn forced_win(game: &Game, solver: Color, depth: usize) -> Option<Vec<Action>> {
+    match game.result() {
+        Some(GameOutcome::Win(color)) if color == solver => return Some(Vec::new()),
+        Some(_) => return None,
+        None if depth == 0 => return None,
+        None => {}
+    }
+    let actions = game.legal_actions();
+    if game.to_move() == solver {
+        return actions.into_iter().find_map(|action| {
+            let mut after = game.clone();
+            after.apply(&action).ok()?;
+            let mut line = forced_win(&after, solver, depth - 1)?;
+            line.insert(0, action);
+            Some(line)
+        });
+    }
+    // Every reply must lose, the reply with the longest line is the main line
+    let mut longest: Option<Vec<Action>> = None;
+    for action in actions {
+        let mut after = game.clone();
+        after.apply(&action).ok()?;
+        let line = forced_win(&after, solver, depth)?;
+        if longest
+            .as_ref()
+            .is_none_or(|longest| line.len() >= longest.len())
+        {
+            longest = Some(vec![action].into_iter().chain(line).collect());
+        }
+    }
+    longest
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collections::HashSet;
+    use crate::coordinate::Coordinate;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::{Game, GameOutcome};
+    use crate::game_board::Color;
+    use crate::puzzles::{find, scan};
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn find_test() {
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 1 });
+        let mut ruleset = small_ruleset(0);
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::TurnsCount { per_turn_count: 1 },
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        let mut game = Game::new(ruleset).unwrap();
+        let half = game.board().board.rows as i16 / 2;
+        game.place(0, Coordinate::new(half - 1, 2)).unwrap();
+        game.place(1, Coordinate::new(half, 2)).unwrap();
+
+        let puzzle = find(&game, 2).unwrap();
+        assert_eq!(puzzle.difficulty, 1);
+        assert_eq!(puzzle.solution.len(), 1);
+        assert_eq!(puzzle.solver().unwrap(), Color::Red);
+        let mut solved = puzzle.position.replay().unwrap();
+        solved.apply(&puzzle.solution[0]).unwrap();
+        assert_eq!(solved.result(), Some(GameOutcome::Win(Color::Red)));
+    }
+
+    #[test]
+    fn scan_test() {
+        let mut playout = Game::new(small_ruleset(0))
+            .unwrap()
+            .playout(RandomPolicy::new(4));
+        playout.by_ref().take(200).for_each(drop);
+        let record = playout.game().record();
+        let puzzles = scan(&record, 1).unwrap();
+        assert!(!puzzles.is_empty());
+        for puzzle in puzzles {
+            let solver = puzzle.solver().unwrap();
+            let mut game = puzzle.position.replay().unwrap();
+            for action in puzzle.solution.iter() {
+                game.apply(action).unwrap();
+            }
+            assert_eq!(game.result(), Some(GameOutcome::Win(solver)));
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Player ratings from game results.
+//!
+//! [`EloRatings`] updates after every game, [`Glicko2Ratings`] collects games into rating
+//! periods and also tracks how certain each rating is. Both are keyed by any player id and fed
+//! [`RatedGame`]s, so they score draws and colors exactly like [`GameOutcome`] does.
+
+use std::f64::consts::PI;
+use std::hash::Hash;
+use std::vec::Vec;
+
+use crate::collections::HashMap;
+use crate::game::GameOutcome;
+use crate::game_board::Color;
+
+/// Glicko-2 ratings are stored in this scale around 1500, like Elo.
+const GLICKO_SCALE: f64 = 173.7178;
+
+/// `color`'s points from `outcome`, `1.0` for a win, `0.5` for a draw, and `0.0` for a loss.
+pub fn score(outcome: GameOutcome, color: Color) -> f64 {
+    match outcome {
+        GameOutcome::Win(winner) if winner == color => 1.0,
+        GameOutcome::Win(_) => 0.0,
+        GameOutcome::Draw => 0.5,
+    }
+}
+
+/// A finished game between two players.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatedGame<P> {
+    pub red: P,
+    pub blue: P,
+    pub outcome: GameOutcome,
+}
+
+/// The expected score of a player rated `rating` against one rated `opponent`.
+pub fn elo_expected(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// Elo ratings updated after every game.
+#[derive(Clone, Debug)]
+pub struct EloRatings<P> {
+    /// Most points a rating moves in one game.
+    pub k: f64,
+    /// The rating of players without games.
+    pub initial: f64,
+    ratings: HashMap<P, f64>,
+}
+impl<P: Clone + Eq + Hash> EloRatings<P> {
+    pub fn new(k: f64) -> Self {
+        Self {
+            k,
+            initial: 1500.0,
+            ratings: HashMap::new(),
+        }
+    }
+
+    pub fn rating(&self, player: &P) -> f64 {
+        self.ratings.get(player).copied().unwrap_or(self.initial)
+    }
+    pub fn ratings(&self) -> &HashMap<P, f64> {
+        &self.ratings
+    }
+
+    /// Updates both players of `game` at once from their ratings before it.
+    pub fn record(&mut self, game: &RatedGame<P>) {
+        let (red, blue) = (self.rating(&game.red), self.rating(&game.blue));
+        let change = self.k * (score(game.outcome, Color::Red) - elo_expected(red, blue));
+        self.ratings.insert(game.red.clone(), red + change);
+        self.ratings.insert(game.blue.clone(), blue - change);
+    }
+    /// Records `games` in order.
+    pub fn record_all<'a>(&mut self, games: impl IntoIterator<Item = &'a RatedGame<P>>)
+    where
+        P: 'a,
+    {
+        for game in games {
+            self.record(game);
+        }
+    }
+}
+impl<P: Clone + Eq + Hash> Default for EloRatings<P> {
+    fn default() -> Self {
+        Self::new(32.0)
+    }
+}
+
+/// A Glicko-2 rating.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Glicko2 {
+    pub rating: f64,
+    /// How uncertain `rating` is, about 95% of the time play is within twice this of it.
+    pub deviation: f64,
+    /// How erratic the player's results are.
+    pub volatility: f64,
+}
+impl Default for Glicko2 {
+    fn default() -> Self {
+        Self {
+            rating: 1500.0,
+            deviation: 350.0,
+            volatility: 0.06,
+        }
+    }
+}
+impl Glicko2 {
+    /// The rating after a period with `results`, each an opponent's rating and this player's
+    /// score against them.
+    ///
+    /// `tau` limits how fast volatility changes, usually between `0.3` and `1.2`.
+    pub fn update(&self, results: &[(Glicko2, f64)], tau: f64) -> Glicko2 {
+        let mu = (self.rating - 1500.0) / GLICKO_SCALE;
+        let phi = self.deviation / GLICKO_SCALE;
+        if results.is_empty() {
+            return Glicko2 {
+                deviation: (phi * phi + self.volatility * self.volatility).sqrt() * GLICKO_SCALE,
+                ..*self
+            };
+        }
+
+        let g = |phi: f64| 1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt();
+        let mut inverse_variance = 0.0;
+        let mut improvement = 0.0;
+        for (opponent, score) in results {
+            let opponent_mu = (opponent.rating - 1500.0) / GLICKO_SCALE;
+            let opponent_g = g(opponent.deviation / GLICKO_SCALE);
+            let expected = 1.0 / (1.0 + (-opponent_g * (mu - opponent_mu)).exp());
+            inverse_variance += opponent_g * opponent_g * expected * (1.0 - expected);
+            improvement += opponent_g * (score - expected);
+        }
+        let variance = 1.0 / inverse_variance;
+        let delta = variance * improvement;
+
+        // Finds the new volatility with the Illinois algorithm
+        let a = (self.volatility * self.volatility).ln();
+        let f = |x: f64| {
+            let ex = x.exp();
+            ex * (delta * delta - phi * phi - variance - ex)
+                / (2.0 * (phi * phi + variance + ex).powi(2))
+                - (x - a) / (tau * tau)
+        };
+        let mut low = a;
+        let mut high = if delta * delta > phi * phi + variance {
+            (delta * delta - phi * phi - variance).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * tau) < 0.0 {
+                k += 1.0;
+            }
+            a - k * tau
+        };
+        let (mut f_low, mut f_high) = (f(low), f(high));
+        while (high - low).abs() > 1e-6 {
+            let middle = low + (low - high) * f_low / (f_high - f_low);
+            let f_middle = f(middle);
+            if f_middle * f_high <= 0.0 {
+                low = high;
+                f_low = f_high;
+            } else {
+                f_low /= 2.0;
+            }
+            high = middle;
+            f_high = f_middle;
+        }
+        let volatility = (low / 2.0).exp();
+
+        let pre_period = (phi * phi + volatility * volatility).sqrt();
+        let new_phi = 1.0 / (1.0 / (pre_period * pre_period) + 1.0 / variance).sqrt();
+        let new_mu = mu + new_phi * new_phi * improvement;
+        Glicko2 {
+            rating: new_mu * GLICKO_SCALE + 1500.0,
+            deviation: new_phi * GLICKO_SCALE,
+            volatility,
+        }
+    }
+}
+
+/// Glicko-2 ratings updated once per rating period.
+///
+/// Games recorded during a period count against the opponents' ratings at its start.
+#[derive(Clone, Debug)]
+pub struct Glicko2Ratings<P> {
+    /// How fast volatility changes, see [`Glicko2::update`].
+    pub tau: f64,
+    /// The rating of players without games.
+    pub initial: Glicko2,
+    ratings: HashMap<P, Glicko2>,
+    period: HashMap<P, Vec<(P, f64)>>,
+}
+impl<P: Clone + Eq + Hash> Glicko2Ratings<P> {
+    pub fn new(tau: f64) -> Self {
+        Self {
+            tau,
+            initial: Glicko2::default(),
+            ratings: HashMap::new(),
+            period: HashMap::new(),
+        }
+    }
+
+    pub fn rating(&self, player: &P) -> Glicko2 {
+        self.ratings.get(player).copied().unwrap_or(self.initial)
+    }
+    pub fn ratings(&self) -> &HashMap<P, Glicko2> {
+        &self.ratings
+    }
+
+    /// Adds `game` to the current period.
+    pub fn record(&mut self, game: &RatedGame<P>) {
+        let red = score(game.outcome, Color::Red);
+        self.period
+            .entry(game.red.clone())
+            .or_default()
+            .push((game.blue.clone(), red));
+        self.period
+            .entry(game.blue.clone())
+            .or_default()
+            .push((game.red.clone(), 1.0 - red));
+    }
+    /// Adds `games` to the current period.
+    pub fn record_all<'a>(&mut self, games: impl IntoIterator<Item = &'a RatedGame<P>>)
+    where
+        P: 'a,
+    {
+        for game in games {
+            self.record(game);
+        }
+    }
+
+    /// Updates every rating from the current period's games and starts a new period.
+    ///
+    /// Rated players without games in the period become less certain.
+    pub fn end_period(&mut self) {
+        let period = core::mem::take(&mut self.period);
+        for player in period.keys() {
+            self.ratings.entry(player.clone()).or_insert(self.initial);
+        }
+        let updated: Vec<(P, Glicko2)> = self
+            .ratings
+            .iter()
+            .map(|(player, rating)| {
+                let results: Vec<(Glicko2, f64)> = period
+                    .get(player)
+                    .map(|games| {
+                        games
+                            .iter()
+                            .map(|(opponent, score)| (self.rating(opponent), *score))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (player.clone(), rating.update(&results, self.tau))
+            })
+            .collect();
+        self.ratings.extend(updated);
+    }
+}
+impl<P: Clone + Eq + Hash> Default for Glicko2Ratings<P> {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::GameOutcome;
+    use crate::game_board::Color;
+    use crate::rating::{EloRatings, Glicko2, Glicko2Ratings, RatedGame};
+
+    #[test]
+    fn elo_test() {
+        let mut ratings = EloRatings::default();
+        ratings.record(&RatedGame {
+            red: "a",
+            blue: "b",
+            outcome: GameOutcome::Win(Color::Red),
+        });
+        assert_eq!(ratings.rating(&"a"), 1516.0);
+        assert_eq!(ratings.rating(&"b"), 1484.0);
+        ratings.record(&RatedGame {
+            red: "b",
+            blue: "a",
+            outcome: GameOutcome::Draw,
+        });
+        assert!(ratings.rating(&"a") < 1516.0);
+        assert!((ratings.rating(&"a") + ratings.rating(&"b") - 3000.0).abs() < 1e-9);
+        assert_eq!(ratings.rating(&"c"), 1500.0);
+    }
+
+    #[test]
+    fn glicko2_test() {
+        // The example from Glickman's description of the system
+        let player = Glicko2 {
+            rating: 1500.0,
+            deviation: 200.0,
+            volatility: 0.06,
+        };
+        let opponent = |rating, deviation| Glicko2 {
+            rating,
+            deviation,
+            volatility: 0.06,
+        };
+        let updated = player.update(
+            &[
+                (opponent(1400.0, 30.0), 1.0),
+                (opponent(1550.0, 100.0), 0.0),
+                (opponent(1700.0, 300.0), 0.0),
+            ],
+            0.5,
+        );
+        assert!((updated.rating - 1464.06).abs() < 0.01);
+        assert!((updated.deviation - 151.52).abs() < 0.01);
+        assert!((updated.volatility - 0.05999).abs() < 0.00001);
+
+        let mut ratings = Glicko2Ratings::default();
+        ratings.record_all(&[RatedGame {
+            red: 0,
+            blue: 1,
+            outcome: GameOutcome::Win(Color::Blue),
+        }]);
+        ratings.end_period();
+        assert!(ratings.rating(&1).rating > 1500.0);
+        assert!(ratings.rating(&0).rating < 1500.0);
+        let deviation = ratings.rating(&0).deviation;
+        ratings.end_period();
+        assert!(ratings.rating(&0).deviation > deviation);
+    }
+}
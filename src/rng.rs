@@ -0,0 +1,107 @@
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// A deterministic source of randomness, output only depends on how it was seeded so anything
+/// generated from it can be reproduced.
+///
+/// Everything random in the crate uses [`ChaChaRng`], see
+/// [`Game::set_seed`](crate::game::Game::set_seed).
+pub trait GameRng {
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniform value in `0..bound`, `bound` must be > 0.
+    fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Bound must be > 0");
+        let bound = bound as u64;
+        // Reject the biased tail so every value is equally likely
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return (value % bound) as usize;
+            }
+        }
+    }
+
+    /// Uniform value in `0.0..1.0`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Fisher-Yates shuffle.
+    fn shuffle<T>(&mut self, values: &mut [T])
+    where
+        Self: Sized,
+    {
+        for index in (1..values.len()).rev() {
+            values.swap(index, self.below(index + 1));
+        }
+    }
+}
+
+/// ChaCha with 8 rounds, the default [`GameRng`].
+///
+/// Each seed has 2^64 independent streams, so one game seed can drive several random rules
+/// without them sharing values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChaChaRng(ChaCha8Rng);
+impl ChaChaRng {
+    pub fn new(seed: u64) -> Self {
+        Self(ChaCha8Rng::seed_from_u64(seed))
+    }
+    /// The generator for `stream` of `seed`.
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut out = Self::new(seed);
+        out.0.set_stream(stream);
+        out
+    }
+}
+impl GameRng for ChaChaRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+/// Stream of random starting layouts.
+///
+/// [`ChanceRule::roll`](crate::ruleset::chance_rule::ChanceRule::roll) uses the streams below
+/// this one, one per ply.
+pub(crate) const LAYOUT_STREAM: u64 = 1 << 62;
+/// Stream of the piece counts drawn for random starting positions.
+pub(crate) const PIECE_COUNT_STREAM: u64 = LAYOUT_STREAM + 1;
+/// Stream of [`RandomPolicy`](crate::game::playout::RandomPolicy) and the other seeded engines.
+pub(crate) const ENGINE_STREAM: u64 = LAYOUT_STREAM + 2;
+/// First stream of automatic placements, each placement made so far moves to the next one.
+pub(crate) const PLACEMENT_STREAM: u64 = 1 << 63;
+
+#[cfg(test)]
+mod test {
+    use crate::rng::{ChaChaRng, GameRng};
+
+    #[test]
+    fn deterministic_test() {
+        let mut first = ChaChaRng::new(7);
+        let mut second = ChaChaRng::new(7);
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+    }
+
+    #[test]
+    fn below_test() {
+        let mut rng = ChaChaRng::new(0);
+        for bound in 1..50 {
+            assert!(rng.below(bound) < bound);
+        }
+    }
+
+    #[test]
+    fn chacha_test() {
+        let mut first = ChaChaRng::new(7);
+        let mut second = ChaChaRng::new(7);
+        let mut other_stream = ChaChaRng::with_stream(7, 1);
+        let values: [u64; 8] = core::array::from_fn(|_| first.next_u64());
+        assert!(values.iter().all(|&value| value == second.next_u64()));
+        assert!(values.iter().all(|&value| value != other_stream.next_u64()));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::collections::HashSet;
+use crate::ruleset::board_type::{BoardTypeVerifyError, BoardTypeVerifyResult};
+
+/// The goal columns along one edge of a rectangular board.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum GoalLocations {
+    /// Goals in exactly these columns.
+    /// All must be < columns.
+    Columns(HashSet<u8>),
+    /// A strip of `width` goal columns centered on the edge.
+    /// Rounds towards the west when it cannot be exactly centered.
+    /// Must be >= 1 and <= columns.
+    Centered { width: u8 },
+    /// A strip of `width` goal columns starting at column `start`.
+    /// `width` must be >= 1 and `start + width` must be <= columns.
+    Strip { start: u8, width: u8 },
+}
+impl GoalLocations {
+    pub fn verify(&self, columns: u8) -> BoardTypeVerifyResult<()> {
+        match self {
+            GoalLocations::Columns(locations) => {
+                for &location in locations {
+                    if location >= columns {
+                        return Err(BoardTypeVerifyError::InvalidGoalLocation(location as usize));
+                    }
+                }
+            }
+            GoalLocations::Centered { width } => {
+                if *width == 0 || *width > columns {
+                    return Err(BoardTypeVerifyError::InvalidGoalWidth(*width as usize));
+                }
+            }
+            GoalLocations::Strip { start, width } => {
+                if *width == 0 || *start as usize + *width as usize > columns as usize {
+                    return Err(BoardTypeVerifyError::InvalidGoalStrip {
+                        start: *start as usize,
+                        width: *width as usize,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `column` is a goal on a board with `columns` columns.
+    pub fn contains(&self, columns: u8, column: u8) -> bool {
+        match self {
+            GoalLocations::Columns(locations) => locations.contains(&column),
+            GoalLocations::Centered { width } => {
+                let start = columns.saturating_sub(*width) / 2;
+                column >= start && column - start < *width
+            }
+            GoalLocations::Strip { start, width } => column >= *start && column - start < *width,
+        }
+    }
+
+    /// All goal columns on a board with `columns` columns.
+    pub fn columns(&self, columns: u8) -> HashSet<u8> {
+        (0..columns)
+            .filter(|&column| self.contains(columns, column))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            GoalLocations::Columns(locations) => locations.is_empty(),
+            GoalLocations::Centered { width } | GoalLocations::Strip { width, .. } => *width == 0,
+        }
+    }
+}
+impl From<HashSet<u8>> for GoalLocations {
+    fn from(from: HashSet<u8>) -> Self {
+        Self::Columns(from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ruleset::board_type::goal_locations::GoalLocations;
+    use crate::ruleset::board_type::BoardTypeVerifyError;
+
+    #[test]
+    fn columns_test() {
+        assert_eq!(
+            GoalLocations::Centered { width: 2 }.columns(10),
+            [4, 5].iter().cloned().collect()
+        );
+        assert_eq!(
+            GoalLocations::Centered { width: 3 }.columns(10),
+            [3, 4, 5].iter().cloned().collect()
+        );
+        assert_eq!(
+            GoalLocations::Strip { start: 0, width: 2 }.columns(10),
+            [0, 1].iter().cloned().collect()
+        );
+    }
+
+    #[test]
+    fn verify_test() {
+        assert_eq!(
+            GoalLocations::Columns([10].iter().cloned().collect()).verify(10),
+            Err(BoardTypeVerifyError::InvalidGoalLocation(10))
+        );
+        assert_eq!(
+            GoalLocations::Centered { width: 11 }.verify(10),
+            Err(BoardTypeVerifyError::InvalidGoalWidth(11))
+        );
+        assert_eq!(
+            GoalLocations::Strip { start: 9, width: 2 }.verify(10),
+            Err(BoardTypeVerifyError::InvalidGoalStrip { start: 9, width: 2 })
+        );
+        assert_eq!(
+            GoalLocations::Strip { start: 8, width: 2 }.verify(10),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use enum_iterator::IntoEnumIterator;
+
+use crate::collections::{HashMap, HashSet};
+use crate::coordinate::{Coordinate, Orientation};
+use crate::game_board::Color;
+use crate::ruleset::board_type::{BoardTypeVerifyError, BoardTypeVerifyResult};
+
+/// Goals on any squares of a board, see [`BoardType::Squares`](super::BoardType::Squares).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum GoalSquares {
+    /// Red's goals, Blue's are these mirrored about the board's horizontal center.
+    /// Will error if overlapping.
+    MirroredFlipped(HashSet<Coordinate>),
+    /// Red's goals, Blue's are these rotated 180 degrees about the board's center.
+    /// Will error if overlapping.
+    MirroredRotated(HashSet<Coordinate>),
+    /// Goals for each color, colors that are missing have no goals.
+    /// Will error if overlapping.
+    NonMirrored(HashMap<Color, HashSet<Coordinate>>),
+}
+impl GoalSquares {
+    pub fn verify(&self, rows: u8, columns: u8) -> BoardTypeVerifyResult<()> {
+        let mut found = HashSet::new();
+        for color in Color::into_enum_iter() {
+            for position in self.squares(rows, columns, color) {
+                if position.row < 0
+                    || position.column < 0
+                    || position.row >= rows as i16
+                    || position.column >= columns as i16
+                {
+                    return Err(BoardTypeVerifyError::InvalidGoalSquare(position));
+                }
+                if !found.insert(position) {
+                    return Err(BoardTypeVerifyError::GoalSquareCollision(position));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The color whose goal is at `position` on a board of `rows` by `columns`.
+    pub fn goal_for(&self, rows: u8, columns: u8, position: Coordinate) -> Option<Color> {
+        let mirrored = |orientation: Orientation, positions: &HashSet<Coordinate>| {
+            if positions.contains(&position) {
+                Some(Color::Red)
+            } else if positions.contains(&orientation.from_canonical(
+                rows as usize,
+                columns as usize,
+                position,
+            )) {
+                Some(Color::Blue)
+            } else {
+                None
+            }
+        };
+        match self {
+            GoalSquares::MirroredFlipped(positions) => mirrored(Orientation::Flipped, positions),
+            GoalSquares::MirroredRotated(positions) => mirrored(Orientation::Rotated, positions),
+            GoalSquares::NonMirrored(color_map) => color_map
+                .iter()
+                .find(|(_, positions)| positions.contains(&position))
+                .map(|(&color, _)| color),
+        }
+    }
+
+    /// All goal squares of `color` on a board of `rows` by `columns`.
+    pub fn squares(&self, rows: u8, columns: u8, color: Color) -> HashSet<Coordinate> {
+        let mirrored = |orientation: Orientation, positions: &HashSet<Coordinate>| match color {
+            Color::Red => positions.clone(),
+            Color::Blue => positions
+                .iter()
+                .map(|&position| {
+                    orientation.from_canonical(rows as usize, columns as usize, position)
+                })
+                .collect(),
+        };
+        match self {
+            GoalSquares::MirroredFlipped(positions) => mirrored(Orientation::Flipped, positions),
+            GoalSquares::MirroredRotated(positions) => mirrored(Orientation::Rotated, positions),
+            GoalSquares::NonMirrored(color_map) => {
+                color_map.get(&color).cloned().unwrap_or_default()
+            }
+        }
+    }
+
+    /// Whether there are no goals.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            GoalSquares::MirroredFlipped(positions) | GoalSquares::MirroredRotated(positions) => {
+                positions.is_empty()
+            }
+            GoalSquares::NonMirrored(color_map) => color_map.values().all(HashSet::is_empty),
+        }
+    }
+}
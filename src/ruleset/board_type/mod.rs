@@ -2,24 +2,31 @@ use core::fmt;
 use core::fmt::{Debug, Display, Formatter};
 use core::result::Result;
 use core::result::Result::{Err, Ok};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::ops::Index;
 
-use matrix::format::conventional::Conventional;
+use alloc::vec::Vec;
 
+use crate::collections::HashMap;
 use crate::coordinate::Coordinate;
 use crate::game_board::Color;
+use crate::grid::Grid;
+use crate::ruleset::board_type::goal_locations::GoalLocations;
+use crate::ruleset::board_type::goal_squares::GoalSquares;
 use crate::ruleset::board_type::space::Space;
-use std::collections::HashSet;
 
+pub mod goal_locations;
+pub mod goal_squares;
 pub mod space;
 
 /// A board definition
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum BoardType {
-    /// Rectangular board of size (rows, columns) with goals in columns defined by goal_locations.
-    /// All goal locations must be < columns.
-    /// Red on top, Blue on bottom
+    /// Rectangular board of size (rows, columns) with goals in columns defined for each color.
+    /// Each side has `goal_depth` goal rows outside of the playing rows.
+    /// Red on top, Blue on bottom, in the canonical [`Orientation`](crate::coordinate::Orientation).
     /// `
     ///             Red Goal
     /// (0,0)-------------------------(0,y)
@@ -31,37 +38,64 @@ pub enum BoardType {
     ///             Blue Goal
     /// `
     Rectangular {
-        /// Must be >= 1 and <= `u8::max_value() - 2`.
+        /// Must be >= 1 and `rows + 2 * goal_depth` must be <= `u8::max_value()`.
         rows: u8,
         /// Must be >= 2.
         columns: u8,
-        /// All must be < columns.
-        goal_locations: HashSet<u8>,
+        /// Goal rows on each side, must be >= 1.
+        goal_depth: u8,
+        /// Goals in Red's row (the top).
+        red_goals: GoalLocations,
+        /// Goals in Blue's row (the bottom).
+        blue_goals: GoalLocations,
+    },
+    /// Rectangular board of size (rows, columns) without goal rows, any squares can be goals.
+    /// Goals may be interior squares or along the sides, and Blue's can mirror Red's.
+    /// Red on top, Blue on bottom, in the canonical [`Orientation`](crate::coordinate::Orientation).
+    Squares {
+        /// Must be >= 1.
+        rows: u8,
+        /// Must be >= 1.
+        columns: u8,
+        /// Which squares are goals, all must be on the board.
+        goals: GoalSquares,
     },
     /// Custom board definition.
-    Custom(Conventional<Space>),
+    Custom(Grid<Space>),
 }
 impl BoardType {
-    fn verify(&self) -> BoardTypeVerifyResult<()> {
+    pub fn verify(&self) -> BoardTypeVerifyResult<()> {
         match self {
             BoardType::Rectangular {
                 rows,
                 columns,
-                goal_locations,
+                goal_depth,
+                red_goals,
+                blue_goals,
             } => {
-                if *rows < 1 || *rows > u8::MAX - 2 {
+                if *goal_depth < 1 {
+                    Err(BoardTypeVerifyError::InvalidGoalDepth(*goal_depth as usize))
+                } else if *rows < 1 || *rows as usize + 2 * *goal_depth as usize > u8::MAX as usize
+                {
                     Err(BoardTypeVerifyError::InvalidRows(*rows as usize))
                 } else if *columns < 2 {
                     Err(BoardTypeVerifyError::InvalidColumns(*columns as usize))
                 } else {
-                    for &location in goal_locations {
-                        if location >= *columns {
-                            return Err(BoardTypeVerifyError::InvalidGoalLocation(
-                                location as usize,
-                            ));
-                        }
-                    }
-                    Ok(())
+                    red_goals.verify(*columns)?;
+                    blue_goals.verify(*columns)
+                }
+            }
+            BoardType::Squares {
+                rows,
+                columns,
+                goals,
+            } => {
+                if *rows < 1 {
+                    Err(BoardTypeVerifyError::InvalidRows(*rows as usize))
+                } else if *columns < 1 {
+                    Err(BoardTypeVerifyError::InvalidColumns(*columns as usize))
+                } else {
+                    goals.verify(*rows, *columns)
                 }
             }
             BoardType::Custom(board) => {
@@ -71,36 +105,140 @@ impl BoardType {
                 if board.columns > u8::MAX as usize {
                     return Err(BoardTypeVerifyError::InvalidColumns(board.columns));
                 }
-                Ok(())
+                let mut portals = HashMap::new();
+                for space in board.values.iter() {
+                    if let Space::Portal(pair) = space {
+                        *portals.entry(*pair).or_insert(0usize) += 1;
+                    }
+                }
+                match portals.into_iter().find(|&(_, count)| count != 2) {
+                    Some((pair, _)) => Err(BoardTypeVerifyError::UnpairedPortal(pair)),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Rows of the full board, including goal rows.
+    pub fn rows(&self) -> usize {
+        match self {
+            BoardType::Rectangular {
+                rows, goal_depth, ..
+            } => *rows as usize + 2 * *goal_depth as usize,
+            BoardType::Squares { rows, .. } => *rows as usize,
+            BoardType::Custom(board) => board.rows,
+        }
+    }
+    /// Columns of the full board.
+    pub fn columns(&self) -> usize {
+        match self {
+            BoardType::Rectangular { columns, .. } | BoardType::Squares { columns, .. } => {
+                *columns as usize
             }
+            BoardType::Custom(board) => board.columns,
         }
     }
 
-    pub fn into_matrix(self) -> Result<Conventional<Space>, (Self, BoardTypeVerifyError)> {
+    /// Gets the space at a position, positions off the board are [`Space::Invalid`].
+    pub fn get_space(&self, position: Coordinate) -> Space {
+        if position.row < 0
+            || position.column < 0
+            || position.row as usize >= self.rows()
+            || position.column as usize >= self.columns()
+        {
+            return Space::Invalid;
+        }
+        match self {
+            BoardType::Rectangular {
+                rows,
+                columns,
+                goal_depth,
+                red_goals,
+                blue_goals,
+            } => {
+                let row = position.row as usize;
+                let column = position.column as u8;
+                if row < *goal_depth as usize {
+                    if red_goals.contains(*columns, column) {
+                        Space::Goal(Color::Red)
+                    } else {
+                        Space::Invalid
+                    }
+                } else if row >= *rows as usize + *goal_depth as usize {
+                    if blue_goals.contains(*columns, column) {
+                        Space::Goal(Color::Blue)
+                    } else {
+                        Space::Invalid
+                    }
+                } else {
+                    Space::Normal
+                }
+            }
+            BoardType::Squares {
+                rows,
+                columns,
+                goals,
+            } => goals
+                .goal_for(*rows, *columns, position)
+                .map_or(Space::Normal, Space::Goal),
+            BoardType::Custom(board) => board[position],
+        }
+    }
+
+    /// All goal spaces belonging to `color`.
+    pub fn goal_spaces(&self, color: Color) -> Vec<Coordinate> {
+        let mut out = Vec::new();
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                let position = Coordinate::new(row as i16, column as i16);
+                if self.get_space(position) == Space::Goal(color) {
+                    out.push(position);
+                }
+            }
+        }
+        out
+    }
+    /// Fewest single steps in any direction from `position` to a goal belonging to `color`.
+    /// `None` if `color` has no goal.
+    pub fn goal_distance(&self, position: Coordinate, color: Color) -> Option<usize> {
+        nearest_goal_distance(position, self.goal_spaces(color))
+    }
+
+    /// Whether the board has any blocked, sticky, or portal spaces.
+    pub fn has_terrain(&self) -> bool {
+        match self {
+            BoardType::Rectangular { .. } | BoardType::Squares { .. } => false,
+            BoardType::Custom(board) => board.values.iter().any(|space| space.is_terrain()),
+        }
+    }
+
+    /// Whether the board has any goal spaces.
+    pub fn has_goal(&self) -> bool {
+        match self {
+            BoardType::Rectangular {
+                red_goals,
+                blue_goals,
+                ..
+            } => !red_goals.is_empty() || !blue_goals.is_empty(),
+            BoardType::Squares { goals, .. } => !goals.is_empty(),
+            BoardType::Custom(board) => board
+                .values
+                .iter()
+                .any(|space| matches!(space, Space::Goal(_))),
+        }
+    }
+
+    pub fn into_matrix(self) -> Result<Grid<Space>, (Self, BoardTypeVerifyError)> {
         match self.verify() {
             Ok(_) => match self {
-                BoardType::Rectangular {
-                    rows,
-                    columns,
-                    goal_locations,
-                } => {
-                    let mut out: Conventional<Space> =
-                        Conventional::new((rows as usize + 2, columns as usize));
-
-                    for x in 0..columns {
-                        let is_goal = goal_locations.contains(&x);
-                        out[(0, x as usize)] = if is_goal {
-                            Space::Goal(Color::Red)
-                        } else {
-                            Space::Invalid
-                        };
-                        out[(rows as usize + 1, x as usize)] = if is_goal {
-                            Space::Goal(Color::Blue)
-                        } else {
-                            Space::Invalid
-                        };
+                BoardType::Rectangular { .. } | BoardType::Squares { .. } => {
+                    let mut out: Grid<Space> = Grid::new((self.rows(), self.columns()));
+                    for row in 0..self.rows() {
+                        for column in 0..self.columns() {
+                            out[(row, column)] =
+                                self.get_space(Coordinate::new(row as i16, column as i16));
+                        }
                     }
-
                     Ok(out)
                 }
                 BoardType::Custom(out) => Ok(out),
@@ -110,31 +248,67 @@ impl BoardType {
     }
 }
 
+/// Fewest single steps in any direction from `position` to any of `goals`, `None` without goals.
+///
+/// Shared by [`BoardType::goal_distance`] and
+/// [`Board::goal_distance`](crate::game_board::board::Board::goal_distance).
+pub(crate) fn nearest_goal_distance(
+    position: Coordinate,
+    goals: impl IntoIterator<Item = Coordinate>,
+) -> Option<usize> {
+    goals
+        .into_iter()
+        .map(|goal| {
+            let offset = goal - position;
+            offset.row.unsigned_abs().max(offset.column.unsigned_abs()) as usize
+        })
+        .min()
+}
+
 pub type BoardTypeVerifyResult<T> = Result<T, BoardTypeVerifyError>;
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum BoardTypeVerifyError {
     InvalidRows(usize),
     InvalidColumns(usize),
     InvalidGoalLocation(usize),
+    InvalidGoalWidth(usize),
+    InvalidGoalStrip {
+        start: usize,
+        width: usize,
+    },
+    InvalidGoalDepth(usize),
+    InvalidGoalSquare(Coordinate),
+    GoalSquareCollision(Coordinate),
+    /// A portal pair id not used by exactly two portals.
+    UnpairedPortal(u8),
 }
 impl Display for BoardTypeVerifyError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for BoardTypeVerifyError {}
 
 #[cfg(test)]
 mod test {
+    use crate::collections::HashSet;
+    use crate::coordinate::Coordinate;
+    use crate::game_board::Color;
+    use crate::grid::Grid;
+    use crate::ruleset::board_type::goal_locations::GoalLocations;
+    use crate::ruleset::board_type::goal_squares::GoalSquares;
+    use crate::ruleset::board_type::space::Space;
     use crate::ruleset::board_type::{BoardType, BoardTypeVerifyError};
-    use std::collections::HashSet;
     #[test]
     fn verify_test() {
         assert_eq!(
             BoardType::Rectangular {
                 rows: 0,
                 columns: 2,
-                goal_locations: HashSet::new(),
+                goal_depth: 1,
+                red_goals: HashSet::new().into(),
+                blue_goals: HashSet::new().into(),
             }
             .verify(),
             Err(BoardTypeVerifyError::InvalidRows(0))
@@ -143,7 +317,9 @@ mod test {
             BoardType::Rectangular {
                 rows: 1,
                 columns: 0,
-                goal_locations: HashSet::new(),
+                goal_depth: 1,
+                red_goals: HashSet::new().into(),
+                blue_goals: HashSet::new().into(),
             }
             .verify(),
             Err(BoardTypeVerifyError::InvalidColumns(0))
@@ -152,10 +328,172 @@ mod test {
             BoardType::Rectangular {
                 rows: 1,
                 columns: 2,
-                goal_locations: HashSet::new(),
+                goal_depth: 1,
+                red_goals: HashSet::new().into(),
+                blue_goals: HashSet::new().into(),
             }
             .verify(),
             Ok(())
         )
     }
+
+    #[test]
+    fn asymmetric_goals_test() {
+        let board = BoardType::Rectangular {
+            rows: 4,
+            columns: 6,
+            goal_depth: 1,
+            red_goals: GoalLocations::Centered { width: 2 },
+            blue_goals: GoalLocations::Strip { start: 0, width: 1 },
+        };
+        assert_eq!(board.verify(), Ok(()));
+        assert_eq!(
+            board.get_space(Coordinate::new(0, 2)),
+            Space::Goal(Color::Red)
+        );
+        assert_eq!(board.get_space(Coordinate::new(0, 0)), Space::Invalid);
+        assert_eq!(
+            board.get_space(Coordinate::new(5, 0)),
+            Space::Goal(Color::Blue)
+        );
+        assert_eq!(board.get_space(Coordinate::new(5, 2)), Space::Invalid);
+        assert_eq!(board.get_space(Coordinate::new(3, 0)), Space::Normal);
+
+        let matrix = board.clone().into_matrix().unwrap();
+        for row in 0..board.rows() {
+            for column in 0..board.columns() {
+                let position = Coordinate::new(row as i16, column as i16);
+                assert_eq!(matrix[position], board.get_space(position));
+            }
+        }
+    }
+
+    #[test]
+    fn goal_depth_test() {
+        let board = BoardType::Rectangular {
+            rows: 4,
+            columns: 4,
+            goal_depth: 2,
+            red_goals: GoalLocations::Centered { width: 2 },
+            blue_goals: GoalLocations::Centered { width: 2 },
+        };
+        assert_eq!(board.verify(), Ok(()));
+        assert_eq!(board.rows(), 8);
+        assert_eq!(
+            board.get_space(Coordinate::new(1, 1)),
+            Space::Goal(Color::Red)
+        );
+        assert_eq!(board.get_space(Coordinate::new(2, 1)), Space::Normal);
+        assert_eq!(
+            board.get_space(Coordinate::new(6, 2)),
+            Space::Goal(Color::Blue)
+        );
+        assert_eq!(board.get_space(Coordinate::new(6, 0)), Space::Invalid);
+        assert_eq!(board.goal_spaces(Color::Blue).len(), 4);
+        assert_eq!(
+            board.goal_distance(Coordinate::new(2, 0), Color::Red),
+            Some(1)
+        );
+        assert_eq!(
+            board.goal_distance(Coordinate::new(2, 0), Color::Blue),
+            Some(4)
+        );
+
+        assert_eq!(
+            BoardType::Rectangular {
+                rows: 4,
+                columns: 4,
+                goal_depth: 0,
+                red_goals: HashSet::new().into(),
+                blue_goals: HashSet::new().into(),
+            }
+            .verify(),
+            Err(BoardTypeVerifyError::InvalidGoalDepth(0))
+        );
+    }
+
+    #[test]
+    fn goal_squares_test() {
+        let board = BoardType::Squares {
+            rows: 5,
+            columns: 5,
+            goals: GoalSquares::MirroredRotated(
+                [Coordinate::new(1, 1), Coordinate::new(2, 0)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+        };
+        assert_eq!(board.verify(), Ok(()));
+        assert!(board.has_goal());
+        assert_eq!(board.rows(), 5);
+        assert_eq!(
+            board.get_space(Coordinate::new(1, 1)),
+            Space::Goal(Color::Red)
+        );
+        assert_eq!(
+            board.get_space(Coordinate::new(3, 3)),
+            Space::Goal(Color::Blue)
+        );
+        assert_eq!(
+            board.get_space(Coordinate::new(2, 4)),
+            Space::Goal(Color::Blue)
+        );
+        assert_eq!(board.get_space(Coordinate::new(0, 0)), Space::Normal);
+        assert_eq!(board.get_space(Coordinate::new(5, 0)), Space::Invalid);
+        assert_eq!(board.goal_spaces(Color::Blue).len(), 2);
+
+        let matrix = board.clone().into_matrix().unwrap();
+        for row in 0..board.rows() {
+            for column in 0..board.columns() {
+                let position = Coordinate::new(row as i16, column as i16);
+                assert_eq!(matrix[position], board.get_space(position));
+            }
+        }
+
+        // The center square rotates onto itself
+        assert_eq!(
+            BoardType::Squares {
+                rows: 5,
+                columns: 5,
+                goals: GoalSquares::MirroredRotated(
+                    [Coordinate::new(2, 2)].iter().cloned().collect()
+                ),
+            }
+            .verify(),
+            Err(BoardTypeVerifyError::GoalSquareCollision(Coordinate::new(
+                2, 2
+            )))
+        );
+        assert_eq!(
+            BoardType::Squares {
+                rows: 5,
+                columns: 5,
+                goals: GoalSquares::MirroredFlipped(
+                    [Coordinate::new(0, 5)].iter().cloned().collect()
+                ),
+            }
+            .verify(),
+            Err(BoardTypeVerifyError::InvalidGoalSquare(Coordinate::new(
+                0, 5
+            )))
+        );
+    }
+
+    #[test]
+    fn portal_verify_test() {
+        let mut spaces: Grid<Space> = Grid::new((3, 3));
+        spaces[(0, 0)] = Space::Portal(1);
+        spaces[(1, 1)] = Space::Sticky;
+        let board = BoardType::Custom(spaces.clone());
+        assert!(board.has_terrain());
+        assert_eq!(board.verify(), Err(BoardTypeVerifyError::UnpairedPortal(1)));
+        spaces[(2, 2)] = Space::Portal(1);
+        assert_eq!(BoardType::Custom(spaces.clone()).verify(), Ok(()));
+        spaces[(2, 0)] = Space::Portal(1);
+        assert_eq!(
+            BoardType::Custom(spaces).verify(),
+            Err(BoardTypeVerifyError::UnpairedPortal(1))
+        );
+    }
 }
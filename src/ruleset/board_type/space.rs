@@ -1,19 +1,28 @@
-use matrix::Element;
-
 use crate::game_board::Color;
 
 /// A space for the board.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum Space {
     /// Not a valid space
     Invalid,
     /// A normal space
+    #[default]
     Normal,
     /// A goal space for a color
     Goal(Color),
+    /// Holds no pieces, but can be jumped over without capturing anything
+    Blocked,
+    /// A normal space that ends any jump chain landing on it
+    Sticky,
+    /// Pieces landing on it move to the other portal with the same pair id.
+    /// Every pair id must be used by exactly two portals.
+    Portal(u8),
 }
-impl Element for Space {
-    fn zero() -> Self {
-        Self::Normal
+impl Space {
+    /// Whether this is special terrain rather than an invalid, normal, or goal space.
+    pub fn is_terrain(self) -> bool {
+        matches!(self, Space::Blocked | Space::Sticky | Space::Portal(_))
     }
 }
@@ -0,0 +1,190 @@
+use bitflags::bitflags;
+
+use crate::ruleset::board_type::BoardType;
+use crate::ruleset::piece_definition::{
+    CaptureRequirement, CaptureRule, CaptureTarget, CaptureTimingRule, GoalMovementRule, JumpLimit,
+    MoveRule, PieceDefinition,
+};
+use crate::ruleset::starting_positions::StartingPositions;
+use crate::ruleset::victory_condition::VictoryCondition;
+use crate::ruleset::Ruleset;
+
+bitflags! {
+    /// The rule features a ruleset relies on, see [`Ruleset::capabilities`].
+    pub struct RequiredFeatures: u32 {
+        /// Pieces move without jumping.
+        const MOVES                 = 1 << 0;
+        /// Pieces move more than one space at a time.
+        const LONG_MOVES            = 1 << 1;
+        /// Pieces jump.
+        const JUMPS                 = 1 << 2;
+        /// Pieces chain more than one jump in an action.
+        const MULTI_JUMPS           = 1 << 3;
+        const CAPTURE_JUMP_OVER     = 1 << 4;
+        const CAPTURE_JUMP_ON       = 1 << 5;
+        const CAPTURE_MOVE          = 1 << 6;
+        /// Pieces capture their own color.
+        const CAPTURE_OWN           = 1 << 7;
+        /// Captures are removed at the end of the turn rather than per jump.
+        const CAPTURE_AFTER_TURN    = 1 << 8;
+        /// Captures are forced when available.
+        const FORCED_CAPTURE        = 1 << 9;
+        /// Pieces are restricted once inside a goal.
+        const GOAL_MOVEMENT         = 1 << 10;
+        /// The board is a custom grid.
+        const CUSTOM_BOARD          = 1 << 11;
+        /// Goals are more than one row deep.
+        const DEEP_GOALS            = 1 << 12;
+        /// Players place their pieces before play.
+        const PLACEMENT             = 1 << 13;
+        const GOAL_COUNT_VICTORY    = 1 << 14;
+        const ALL_CAPTURED_VICTORY  = 1 << 15;
+        const POINT_VICTORY         = 1 << 16;
+        /// The board has blocked, sticky, or portal spaces.
+        const TERRAIN               = 1 << 17;
+        const CAPTURE_COUNT_VICTORY = 1 << 18;
+        const SCORE_VICTORY         = 1 << 19;
+        /// Games end after a number of actions.
+        const TURN_LIMIT            = 1 << 20;
+        /// Some pieces belong to one color only.
+        const ASYMMETRIC_PIECES     = 1 << 21;
+        /// A die limits what may act each turn.
+        const CHANCE                = 1 << 22;
+        const NONE                  = 0;
+    }
+}
+impl RequiredFeatures {
+    fn of_piece(piece: &PieceDefinition) -> Self {
+        let mut out = Self::NONE;
+        match piece.move_rule {
+            MoveRule::SameDirection { limit, .. } | MoveRule::AnyDirection { limit, .. } => {
+                out |= Self::MOVES;
+                if limit > 1 {
+                    out |= Self::LONG_MOVES;
+                }
+            }
+            MoveRule::None => {}
+        }
+        match piece.jump_limit {
+            JumpLimit::Unlimited { .. } => out |= Self::JUMPS | Self::MULTI_JUMPS,
+            JumpLimit::Limited { limit, .. } => {
+                out |= Self::JUMPS;
+                if limit > 1 {
+                    out |= Self::MULTI_JUMPS;
+                }
+            }
+            JumpLimit::Cannot => {}
+        }
+        for (rule, target) in piece.capture_rules.iter() {
+            out |= match rule {
+                CaptureRule::JumpOver => Self::CAPTURE_JUMP_OVER,
+                CaptureRule::JumpOn => Self::CAPTURE_JUMP_ON,
+                CaptureRule::Move => Self::CAPTURE_MOVE,
+            };
+            if *target != CaptureTarget::EnemyOnly {
+                out |= Self::CAPTURE_OWN;
+            }
+        }
+        if !piece.capture_rules.is_empty() {
+            if piece.capture_timing_rule == CaptureTimingRule::AfterTurn {
+                out |= Self::CAPTURE_AFTER_TURN;
+            }
+            if let CaptureRequirement::Forced(_) = piece.capture_requirement {
+                out |= Self::FORCED_CAPTURE;
+            }
+        }
+        if piece.goal_move_rule != GoalMovementRule::Free {
+            out |= Self::GOAL_MOVEMENT;
+        }
+        if piece.owner.is_some() {
+            out |= Self::ASYMMETRIC_PIECES;
+        }
+        out
+    }
+}
+
+impl Ruleset {
+    /// The rule features this ruleset uses.
+    ///
+    /// A reduced engine supporting these features can play the ruleset,
+    /// see [`MoveGenProfile`](crate::movegen::MoveGenProfile).
+    pub fn capabilities(&self) -> RequiredFeatures {
+        let mut out = self
+            .pieces
+            .iter()
+            .fold(RequiredFeatures::NONE, |out, piece| {
+                out | RequiredFeatures::of_piece(piece)
+            });
+        match &self.board_type {
+            BoardType::Rectangular { goal_depth, .. } => {
+                if *goal_depth > 1 {
+                    out |= RequiredFeatures::DEEP_GOALS;
+                }
+            }
+            BoardType::Squares { .. } | BoardType::Custom(_) => {
+                out |= RequiredFeatures::CUSTOM_BOARD
+            }
+        }
+        if self.board_type.has_terrain() {
+            out |= RequiredFeatures::TERRAIN;
+        }
+        if let StartingPositions::Placement { .. } = self.starting_positions {
+            out |= RequiredFeatures::PLACEMENT;
+        }
+        if self.game_length_rule.max_turns.is_some() {
+            out |= RequiredFeatures::TURN_LIMIT;
+        }
+        if self.chance_rule.is_enabled() {
+            out |= RequiredFeatures::CHANCE;
+        }
+        let leaves = self
+            .victory_conditions
+            .iter()
+            .flat_map(VictoryCondition::leaves);
+        for victory_condition in leaves {
+            out |= match victory_condition {
+                VictoryCondition::GoalCount { .. } | VictoryCondition::PiecesInGoal { .. } => {
+                    RequiredFeatures::GOAL_COUNT_VICTORY
+                }
+                VictoryCondition::AllCaptured => RequiredFeatures::ALL_CAPTURED_VICTORY,
+                VictoryCondition::PointDifference(_) => RequiredFeatures::POINT_VICTORY,
+                VictoryCondition::CaptureCount { .. } => RequiredFeatures::CAPTURE_COUNT_VICTORY,
+                VictoryCondition::ScoreAtLeast(_) => RequiredFeatures::SCORE_VICTORY,
+                VictoryCondition::All(_) | VictoryCondition::Any(_) => RequiredFeatures::NONE,
+            };
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ruleset::capabilities::RequiredFeatures;
+    use crate::ruleset::piece_definition::JumpLimit;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn capabilities_test() {
+        let mut ruleset = small_ruleset(0);
+        let features = ruleset.capabilities();
+        assert!(features.contains(
+            RequiredFeatures::MOVES
+                | RequiredFeatures::MULTI_JUMPS
+                | RequiredFeatures::CAPTURE_JUMP_OVER
+                | RequiredFeatures::ALL_CAPTURED_VICTORY
+        ));
+        assert!(!features.intersects(
+            RequiredFeatures::LONG_MOVES
+                | RequiredFeatures::CAPTURE_OWN
+                | RequiredFeatures::CUSTOM_BOARD
+                | RequiredFeatures::PLACEMENT
+        ));
+
+        for piece in ruleset.pieces.iter_mut() {
+            piece.jump_limit = JumpLimit::Cannot;
+        }
+        assert!(!ruleset
+            .capabilities()
+            .intersects(RequiredFeatures::JUMPS | RequiredFeatures::MULTI_JUMPS));
+    }
+}
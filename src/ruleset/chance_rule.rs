@@ -0,0 +1,191 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use alloc::vec::Vec;
+
+use crate::action::{Action, ActionType};
+use crate::direction::Directions;
+use crate::game_board::board::Board;
+use crate::rng::{ChaChaRng, GameRng};
+use crate::ruleset::Ruleset;
+
+/// A die rolled at the start of every turn, the face rolled limits what the color to move may do.
+///
+/// Rulesets without faces have no die. A roll leaving the color to move no legal action is
+/// ignored for that turn, so a bad roll never decides the game on its own.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct ChanceRule {
+    /// Faces of the die, each equally likely
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub faces: Vec<ChanceFace>,
+}
+impl ChanceRule {
+    pub fn verify(&self, ruleset: &Ruleset) -> ChanceRuleResult<()> {
+        for (index, face) in self.faces.iter().enumerate() {
+            let empty = face.pieces.as_ref().is_some_and(Vec::is_empty)
+                || face.directions == Some(Directions::NONE);
+            if empty {
+                return Err(ChanceRuleError::EmptyFace(index));
+            }
+            if let Some(&piece) = face
+                .pieces
+                .iter()
+                .flatten()
+                .find(|&&piece| piece >= ruleset.pieces.len())
+            {
+                return Err(ChanceRuleError::PieceNotFound(piece));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a die is rolled at all.
+    pub fn is_enabled(&self) -> bool {
+        !self.faces.is_empty()
+    }
+    /// The face rolled for the turn after `ply` actions of a game seeded with `seed`, `None`
+    /// without a die.
+    ///
+    /// Only depends on its arguments, so the same seed rolls the same game. Each turn rolls on its
+    /// own [`ChaChaRng`] stream.
+    pub fn roll(&self, seed: u64, ply: usize) -> Option<usize> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let mut rng = ChaChaRng::with_stream(seed, ply as u64);
+        Some(rng.below(self.faces.len()))
+    }
+}
+
+/// What may act when a face of a [`ChanceRule`] is rolled.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct ChanceFace {
+    /// Piece definition indices that may act, `None` for every piece
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pieces: Option<Vec<usize>>,
+    /// Directions pieces may move in or start a jump in, `None` for every direction
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub directions: Option<Directions>,
+}
+impl ChanceFace {
+    /// Whether `action` may be played on `board` with this face rolled, passes always may.
+    pub fn allows(&self, board: &impl Board, action: &Action) -> bool {
+        let direction = match &action.action_type {
+            ActionType::Move(direction) => *direction,
+            ActionType::Jump(directions) => match directions.first() {
+                Some(direction) => *direction,
+                None => return false,
+            },
+            ActionType::Pass => return true,
+        };
+        let piece_allowed = match (&self.pieces, board.piece(action.start_pos)) {
+            (None, _) => true,
+            (Some(pieces), Ok(Some(piece))) => pieces.contains(&piece.definition_index()),
+            (Some(_), _) => false,
+        };
+        piece_allowed
+            && self
+                .directions
+                .is_none_or(|directions| directions.contains(direction.into()))
+    }
+}
+
+pub type ChanceRuleResult<T> = Result<T, ChanceRuleError>;
+#[derive(Copy, Clone, Debug)]
+pub enum ChanceRuleError {
+    /// The face at this index allows no piece or no direction.
+    EmptyFace(usize),
+    /// A face allows a piece definition index the ruleset does not have.
+    PieceNotFound(usize),
+}
+impl Display for ChanceRuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for ChanceRuleError {}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::action::ActionError;
+    use crate::direction::Directions;
+    use crate::engine::Engine;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::{Game, GameError};
+    use crate::movegen::legal_actions;
+    use crate::ruleset::capabilities::RequiredFeatures;
+    use crate::ruleset::chance_rule::{ChanceFace, ChanceRule, ChanceRuleError};
+    use crate::ruleset::RulesetError;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn chance_rule_test() {
+        let mut ruleset = small_ruleset(1);
+        ruleset.chance_rule = ChanceRule {
+            faces: vec![
+                ChanceFace {
+                    pieces: Some(vec![0]),
+                    directions: None,
+                },
+                ChanceFace {
+                    pieces: None,
+                    directions: Some(Directions::CARDINAL),
+                },
+            ],
+        };
+        assert!(ruleset.capabilities().contains(RequiredFeatures::CHANCE));
+        let mut broken = ruleset.clone();
+        broken.chance_rule.faces[0].pieces = Some(vec![2]);
+        assert!(matches!(
+            Game::new(broken),
+            Err(GameError::RulesetError(RulesetError::ChanceRuleError(
+                ChanceRuleError::PieceNotFound(2)
+            )))
+        ));
+
+        let mut game = Game::new(ruleset).unwrap();
+        game.set_seed(5);
+        let mut policy = RandomPolicy::new(2);
+        while game.history().len() < 30 && !game.is_finished() {
+            let face = game.roll().unwrap().clone();
+            let legal = game.legal_actions();
+            let unrestricted = legal_actions(game.board(), game.to_move());
+            let allowed: Vec<_> = unrestricted
+                .iter()
+                .filter(|action| face.allows(game.board(), action))
+                .cloned()
+                .collect();
+            if allowed.is_empty() {
+                assert_eq!(legal, unrestricted);
+            } else {
+                assert_eq!(legal, allowed);
+                if let Some(action) = unrestricted.iter().find(|action| !allowed.contains(action)) {
+                    assert!(matches!(
+                        game.clone().apply(action),
+                        Err(ActionError::NotRolled)
+                    ));
+                }
+            }
+            let action = policy.choose_action(&game).unwrap();
+            game.apply(&action).unwrap();
+        }
+        assert_eq!(game.rolls().len(), game.history().len() + 1);
+
+        let record = game.record();
+        assert_eq!(record.rolls, game.rolls());
+        assert_eq!(record.seed, 5);
+        let replayed = record.replay().unwrap();
+        assert_eq!(replayed.board().board, game.board().board);
+        assert_eq!(replayed.rolls(), game.rolls());
+    }
+}
@@ -0,0 +1,352 @@
+//! How two rulesets differ, see [`Ruleset::diff`].
+
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+use alloc::vec::Vec;
+
+use crate::coordinate::Coordinate;
+use crate::ruleset::board_type::space::Space;
+use crate::ruleset::chance_rule::ChanceRule;
+use crate::ruleset::fingerprint::Fingerprint;
+use crate::ruleset::game_length_rule::GameLengthRule;
+use crate::ruleset::pass_rule::PassRule;
+use crate::ruleset::piece_definition::PieceDefinition;
+use crate::ruleset::scoring::Scoring;
+use crate::ruleset::stalemate_rule::StalemateRule;
+use crate::ruleset::victory_condition::VictoryCondition;
+use crate::ruleset::{BoardType, Ruleset};
+
+/// Every change from one ruleset to another, empty when they are equal.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RulesetDiff {
+    pub pieces: Vec<PieceDiff>,
+    pub board: Option<BoardDiff>,
+    /// Whether the starting positions differ.
+    pub starting_positions: bool,
+    pub victory_conditions: Vec<VictoryConditionDiff>,
+    /// The old and new rule for each rule that changed.
+    pub stalemate_rule: Option<(StalemateRule, StalemateRule)>,
+    pub pass_rule: Option<(PassRule, PassRule)>,
+    pub scoring: Option<(Scoring, Scoring)>,
+    pub game_length_rule: Option<(GameLengthRule, GameLengthRule)>,
+    pub chance_rule: Option<(ChanceRule, ChanceRule)>,
+}
+impl RulesetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+            && self.board.is_none()
+            && !self.starting_positions
+            && self.victory_conditions.is_empty()
+            && self.stalemate_rule.is_none()
+            && self.pass_rule.is_none()
+            && self.scoring.is_none()
+            && self.game_length_rule.is_none()
+            && self.chance_rule.is_none()
+    }
+}
+
+/// A change to the piece definition at `index`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PieceDiff {
+    Added(usize, PieceDefinition),
+    Removed(usize, PieceDefinition),
+    /// Both rulesets define the piece with different rules.
+    Changed {
+        index: usize,
+        fields: Vec<PieceField>,
+    },
+}
+
+/// A rule of a [`PieceDefinition`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PieceField {
+    Name,
+    CaptureRules,
+    JumpRule,
+    CaptureTimingRule,
+    CaptureRequirement,
+    JumpLimit,
+    MoveRule,
+    GoalMoveRule,
+    ChainRule,
+    Owner,
+}
+
+/// A change to the board, comparing the full boards square by square.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoardDiff {
+    /// The old and new (rows, columns).
+    Size((usize, usize), (usize, usize)),
+    /// Same size boards with different squares, as the old and new space.
+    Spaces(Vec<(Coordinate, Space, Space)>),
+}
+
+/// A change to the victory conditions.
+///
+/// Conditions are matched by kind, as the ruleset only holds one of each.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VictoryConditionDiff {
+    Added(VictoryCondition),
+    Removed(VictoryCondition),
+    /// The old and new parameters.
+    Changed(VictoryCondition, VictoryCondition),
+}
+
+impl Ruleset {
+    /// Every change from this ruleset to `other`, compared by their fingerprint encodings.
+    pub fn diff(&self, other: &Ruleset) -> RulesetDiff {
+        let mut pieces = Vec::new();
+        for index in 0..self.pieces.len().max(other.pieces.len()) {
+            match (self.pieces.get(index), other.pieces.get(index)) {
+                (Some(old), Some(new)) => {
+                    let fields = piece_fields(old, new);
+                    if !fields.is_empty() {
+                        pieces.push(PieceDiff::Changed { index, fields });
+                    }
+                }
+                (Some(old), None) => pieces.push(PieceDiff::Removed(index, old.clone())),
+                (None, Some(new)) => pieces.push(PieceDiff::Added(index, new.clone())),
+                (None, None) => unreachable!(),
+            }
+        }
+
+        let mut victory_conditions = Vec::new();
+        for old in self.victory_conditions.iter() {
+            match other.victory_conditions.get(old) {
+                Some(new) if !same(old, new) => {
+                    victory_conditions.push(VictoryConditionDiff::Changed(old.clone(), new.clone()))
+                }
+                Some(_) => {}
+                None => victory_conditions.push(VictoryConditionDiff::Removed(old.clone())),
+            }
+        }
+        for new in other.victory_conditions.iter() {
+            if !self.victory_conditions.contains(new) {
+                victory_conditions.push(VictoryConditionDiff::Added(new.clone()));
+            }
+        }
+
+        RulesetDiff {
+            pieces,
+            board: board_diff(&self.board_type, &other.board_type),
+            starting_positions: !same(&self.starting_positions, &other.starting_positions),
+            victory_conditions,
+            stalemate_rule: changed(self.stalemate_rule, other.stalemate_rule),
+            pass_rule: changed(self.pass_rule, other.pass_rule),
+            scoring: changed(self.scoring.clone(), other.scoring.clone()),
+            game_length_rule: changed(self.game_length_rule, other.game_length_rule),
+            chance_rule: changed(self.chance_rule.clone(), other.chance_rule.clone()),
+        }
+    }
+}
+
+fn changed<T: PartialEq>(old: T, new: T) -> Option<(T, T)> {
+    if old != new {
+        Some((old, new))
+    } else {
+        None
+    }
+}
+fn same<T: Fingerprint + ?Sized>(old: &T, new: &T) -> bool {
+    let encode = |value: &T| {
+        let mut out = Vec::new();
+        value.write_fingerprint(&mut out);
+        out
+    };
+    encode(old) == encode(new)
+}
+
+fn piece_fields(old: &PieceDefinition, new: &PieceDefinition) -> Vec<PieceField> {
+    let mut out = Vec::new();
+    if old.name != new.name {
+        out.push(PieceField::Name);
+    }
+    if !same(&old.capture_rules, &new.capture_rules) {
+        out.push(PieceField::CaptureRules);
+    }
+    if old.jump_rule != new.jump_rule {
+        out.push(PieceField::JumpRule);
+    }
+    if old.capture_timing_rule != new.capture_timing_rule {
+        out.push(PieceField::CaptureTimingRule);
+    }
+    if old.capture_requirement != new.capture_requirement {
+        out.push(PieceField::CaptureRequirement);
+    }
+    if !same(&old.jump_limit, &new.jump_limit) {
+        out.push(PieceField::JumpLimit);
+    }
+    if !same(&old.move_rule, &new.move_rule) {
+        out.push(PieceField::MoveRule);
+    }
+    if old.goal_move_rule != new.goal_move_rule {
+        out.push(PieceField::GoalMoveRule);
+    }
+    if old.chain_rule != new.chain_rule {
+        out.push(PieceField::ChainRule);
+    }
+    if old.owner != new.owner {
+        out.push(PieceField::Owner);
+    }
+    out
+}
+
+fn board_diff(old: &BoardType, new: &BoardType) -> Option<BoardDiff> {
+    let old_size = (old.rows(), old.columns());
+    let new_size = (new.rows(), new.columns());
+    if old_size != new_size {
+        return Some(BoardDiff::Size(old_size, new_size));
+    }
+    let mut spaces = Vec::new();
+    for row in 0..old_size.0 {
+        for column in 0..old_size.1 {
+            let position = Coordinate::new(row as i16, column as i16);
+            let (old_space, new_space) = (old.get_space(position), new.get_space(position));
+            if old_space != new_space {
+                spaces.push((position, old_space, new_space));
+            }
+        }
+    }
+    if spaces.is_empty() {
+        None
+    } else {
+        Some(BoardDiff::Spaces(spaces))
+    }
+}
+
+/// One line per change.
+impl Display for RulesetDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for piece in self.pieces.iter() {
+            match piece {
+                PieceDiff::Added(index, piece) => {
+                    writeln!(f, "piece {} added: {}", index, piece.name)?
+                }
+                PieceDiff::Removed(index, piece) => {
+                    writeln!(f, "piece {} removed: {}", index, piece.name)?
+                }
+                PieceDiff::Changed { index, fields } => {
+                    writeln!(f, "piece {} changed: {:?}", index, fields)?
+                }
+            }
+        }
+        match &self.board {
+            Some(BoardDiff::Size(old, new)) => {
+                writeln!(f, "board size: {}x{} -> {}x{}", old.0, old.1, new.0, new.1)?
+            }
+            Some(BoardDiff::Spaces(spaces)) => {
+                for (position, old, new) in spaces {
+                    writeln!(f, "board {}: {:?} -> {:?}", position, old, new)?;
+                }
+            }
+            None => {}
+        }
+        if self.starting_positions {
+            writeln!(f, "starting positions changed")?;
+        }
+        for condition in self.victory_conditions.iter() {
+            match condition {
+                VictoryConditionDiff::Added(condition) => {
+                    writeln!(f, "victory condition added: {:?}", condition)?
+                }
+                VictoryConditionDiff::Removed(condition) => {
+                    writeln!(f, "victory condition removed: {:?}", condition)?
+                }
+                VictoryConditionDiff::Changed(old, new) => {
+                    writeln!(f, "victory condition: {:?} -> {:?}", old, new)?
+                }
+            }
+        }
+        if let Some((old, new)) = &self.stalemate_rule {
+            writeln!(f, "stalemate rule: {:?} -> {:?}", old, new)?;
+        }
+        if let Some((old, new)) = &self.pass_rule {
+            writeln!(f, "pass rule: {:?} -> {:?}", old, new)?;
+        }
+        if let Some((old, new)) = &self.scoring {
+            writeln!(f, "scoring: {:?} -> {:?}", old, new)?;
+        }
+        if let Some((old, new)) = &self.game_length_rule {
+            writeln!(f, "game length rule: {:?} -> {:?}", old, new)?;
+        }
+        if let Some((old, new)) = &self.chance_rule {
+            writeln!(f, "chance rule: {:?} -> {:?}", old, new)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use crate::coordinate::Coordinate;
+    use crate::grid::Grid;
+    use crate::ruleset::board_type::space::Space;
+    use crate::ruleset::board_type::BoardType;
+    use crate::ruleset::diff::{BoardDiff, PieceDiff, PieceField, VictoryConditionDiff};
+    use crate::ruleset::pass_rule::PassRule;
+    use crate::ruleset::piece_definition::JumpLimit;
+    use crate::ruleset::presets::Preset;
+    use crate::ruleset::victory_condition::VictoryCondition;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn diff_test() {
+        let ruleset = small_ruleset(0);
+        assert!(ruleset.diff(&ruleset.clone()).is_empty());
+        assert!(ruleset.diff(&small_ruleset(1)).starting_positions);
+
+        let mut custom = ruleset.clone();
+        custom.pieces[1].jump_limit = JumpLimit::Cannot;
+        custom.pieces.push(custom.pieces[0].clone());
+        custom.pass_rule = PassRule::Always;
+        custom
+            .victory_conditions
+            .insert(VictoryCondition::PointDifference(2));
+        let diff = ruleset.diff(&custom);
+        assert!(matches!(
+            diff.pieces.as_slice(),
+            [PieceDiff::Changed { index: 1, fields }, PieceDiff::Added(2, _)]
+                if fields == &[PieceField::JumpLimit]
+        ));
+        assert!(diff.board.is_none());
+        assert!(!diff.starting_positions);
+        assert_eq!(diff.pass_rule, Some((PassRule::Never, PassRule::Always)));
+        assert!(matches!(
+            diff.victory_conditions.as_slice(),
+            [VictoryConditionDiff::Added(
+                VictoryCondition::PointDifference(2)
+            )]
+        ));
+        assert_eq!(diff.to_string().lines().count(), 4);
+        assert!(matches!(
+            custom.diff(&ruleset).pieces.as_slice(),
+            [PieceDiff::Changed { .. }, PieceDiff::Removed(2, _)]
+        ));
+
+        let mini = Preset::Mini.ruleset().unwrap();
+        let mut board = mini.clone();
+        if let BoardType::Rectangular { goal_depth, .. } = &mut board.board_type {
+            *goal_depth = 2;
+        }
+        assert!(matches!(mini.diff(&board).board, Some(BoardDiff::Size(..))));
+        assert!(matches!(
+            mini.diff(&Preset::CaptureAll.ruleset().unwrap()).board,
+            Some(BoardDiff::Size((8, 6), (12, 10)))
+        ));
+        let mut goals = mini.clone();
+        goals.board_type = BoardType::Custom(Grid::new((8, 6)));
+        let spaces = match mini.diff(&goals).board {
+            Some(BoardDiff::Spaces(spaces)) => spaces,
+            board => panic!("{:?}", board),
+        };
+        assert!(spaces.contains(&(Coordinate::new(0, 0), Space::Invalid, Space::Normal)));
+    }
+}
@@ -0,0 +1,751 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+
+use crate::collections::{HashMap, HashSet};
+use crate::coordinate::Coordinate;
+use crate::direction::Directions;
+use crate::game_board::Color;
+use crate::grid::Grid;
+use crate::ruleset::board_type::goal_locations::GoalLocations;
+use crate::ruleset::board_type::goal_squares::GoalSquares;
+use crate::ruleset::board_type::space::Space;
+use crate::ruleset::board_type::BoardType;
+use crate::ruleset::chance_rule::{ChanceFace, ChanceRule};
+use crate::ruleset::game_length_rule::{GameLengthRule, Tiebreak};
+use crate::ruleset::pass_rule::PassRule;
+use crate::ruleset::piece_definition::{
+    CaptureRequirement, CaptureRule, CaptureTarget, CaptureTimingRule, ChainContinuationRule,
+    GoalMovementRule, JumpLimit, JumpRule, MoveRule, PieceDefinition,
+};
+use crate::ruleset::scoring::Scoring;
+use crate::ruleset::stalemate_rule::StalemateRule;
+use crate::ruleset::starting_positions::alteration_type::AlternationType;
+use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+use crate::ruleset::starting_positions::placement_area::PlacementArea;
+use crate::ruleset::starting_positions::StartingPositions;
+use crate::ruleset::victory_condition::{PieceFilter, VictoryCondition};
+use crate::ruleset::Ruleset;
+
+/// A canonical byte encoding used to compare rules.
+///
+/// Unordered collections are encoded in sorted order so the encoding does not depend on
+/// `HashMap`/`HashSet` iteration order.
+pub trait Fingerprint {
+    fn write_fingerprint(&self, out: &mut Vec<u8>);
+
+    /// SHA-256 of the canonical encoding.
+    fn fingerprint(&self) -> [u8; 32] {
+        let mut bytes = Vec::new();
+        self.write_fingerprint(&mut bytes);
+        Sha256::digest(&bytes).into()
+    }
+}
+
+fn write_tag(out: &mut Vec<u8>, tag: u8) {
+    out.push(tag);
+}
+fn write_unordered<T: Fingerprint>(out: &mut Vec<u8>, items: impl Iterator<Item = T>) {
+    let mut encoded: Vec<Vec<u8>> = items
+        .map(|item| {
+            let mut bytes = Vec::new();
+            item.write_fingerprint(&mut bytes);
+            bytes
+        })
+        .collect();
+    encoded.sort_unstable();
+    encoded.len().write_fingerprint(out);
+    for bytes in encoded {
+        bytes.len().write_fingerprint(out);
+        out.extend_from_slice(&bytes);
+    }
+}
+
+impl<T: Fingerprint + ?Sized> Fingerprint for &T {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        (**self).write_fingerprint(out)
+    }
+}
+impl<A: Fingerprint, B: Fingerprint> Fingerprint for (A, B) {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.0.write_fingerprint(out);
+        self.1.write_fingerprint(out);
+    }
+}
+impl<T: Fingerprint> Fingerprint for Option<T> {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            None => write_tag(out, 0),
+            Some(value) => {
+                write_tag(out, 1);
+                value.write_fingerprint(out);
+            }
+        }
+    }
+}
+impl Fingerprint for bool {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+impl Fingerprint for u8 {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        out.push(*self);
+    }
+}
+impl Fingerprint for i16 {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+impl Fingerprint for u64 {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+impl Fingerprint for usize {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        (*self as u64).write_fingerprint(out);
+    }
+}
+impl Fingerprint for isize {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(*self as i64).to_le_bytes());
+    }
+}
+impl Fingerprint for str {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.len().write_fingerprint(out);
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+impl Fingerprint for String {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.as_str().write_fingerprint(out);
+    }
+}
+impl<T: Fingerprint> Fingerprint for [T] {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.len().write_fingerprint(out);
+        for item in self {
+            item.write_fingerprint(out);
+        }
+    }
+}
+impl<T: Fingerprint> Fingerprint for Vec<T> {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.as_slice().write_fingerprint(out);
+    }
+}
+impl<T: Fingerprint> Fingerprint for HashSet<T> {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_unordered(out, self.iter());
+    }
+}
+impl<K: Fingerprint, V: Fingerprint> Fingerprint for HashMap<K, V> {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_unordered(out, self.iter());
+    }
+}
+
+impl Fingerprint for Color {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_tag(
+            out,
+            match self {
+                Color::Red => 0,
+                Color::Blue => 1,
+            },
+        );
+    }
+}
+impl Fingerprint for Coordinate {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.row.write_fingerprint(out);
+        self.column.write_fingerprint(out);
+    }
+}
+impl Fingerprint for Directions {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.bits().write_fingerprint(out);
+    }
+}
+impl<T: Fingerprint> Fingerprint for Grid<T> {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.rows.write_fingerprint(out);
+        self.columns.write_fingerprint(out);
+        self.values.write_fingerprint(out);
+    }
+}
+
+impl Fingerprint for Ruleset {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.pieces.write_fingerprint(out);
+        self.board_type.write_fingerprint(out);
+        self.starting_positions.write_fingerprint(out);
+        self.victory_conditions.write_fingerprint(out);
+        self.stalemate_rule.write_fingerprint(out);
+        self.pass_rule.write_fingerprint(out);
+        self.scoring.write_fingerprint(out);
+        self.game_length_rule.write_fingerprint(out);
+        self.chance_rule.write_fingerprint(out);
+    }
+}
+
+impl Fingerprint for PieceDefinition {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.name.write_fingerprint(out);
+        self.capture_rules.write_fingerprint(out);
+        self.jump_rule.write_fingerprint(out);
+        self.capture_timing_rule.write_fingerprint(out);
+        self.capture_requirement.write_fingerprint(out);
+        self.jump_limit.write_fingerprint(out);
+        self.chain_rule.write_fingerprint(out);
+        self.move_rule.write_fingerprint(out);
+        self.goal_move_rule.write_fingerprint(out);
+        self.owner.write_fingerprint(out);
+    }
+}
+impl Fingerprint for JumpRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_tag(
+            out,
+            match self {
+                JumpRule::NoBacktracking => 0,
+                JumpRule::NoSameStart => 1,
+                JumpRule::Open => 2,
+            },
+        );
+    }
+}
+impl Fingerprint for ChainContinuationRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_tag(
+            out,
+            match self {
+                ChainContinuationRule::MayStop => 0,
+                ChainContinuationRule::MustCapture => 1,
+                ChainContinuationRule::MustContinue => 2,
+            },
+        );
+    }
+}
+impl Fingerprint for CaptureRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_tag(
+            out,
+            match self {
+                CaptureRule::JumpOver => 0,
+                CaptureRule::JumpOn => 1,
+                CaptureRule::Move => 2,
+            },
+        );
+    }
+}
+impl Fingerprint for CaptureTimingRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_tag(
+            out,
+            match self {
+                CaptureTimingRule::AfterJump => 0,
+                CaptureTimingRule::AfterTurn => 1,
+            },
+        );
+    }
+}
+impl Fingerprint for CaptureTarget {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_tag(
+            out,
+            match self {
+                CaptureTarget::EnemyOnly => 0,
+                CaptureTarget::OwnOnly => 1,
+                CaptureTarget::All => 2,
+            },
+        );
+    }
+}
+impl Fingerprint for CaptureRequirement {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            CaptureRequirement::Forced(priority) => {
+                write_tag(out, 0);
+                priority.write_fingerprint(out);
+            }
+            CaptureRequirement::None => write_tag(out, 1),
+            CaptureRequirement::Huff => write_tag(out, 2),
+        }
+    }
+}
+impl Fingerprint for JumpLimit {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            JumpLimit::Unlimited { directions } => {
+                write_tag(out, 0);
+                directions.write_fingerprint(out);
+            }
+            JumpLimit::Limited { limit, directions } => {
+                write_tag(out, 1);
+                limit.write_fingerprint(out);
+                directions.write_fingerprint(out);
+            }
+            JumpLimit::Cannot => write_tag(out, 2),
+        }
+    }
+}
+impl Fingerprint for MoveRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            MoveRule::SameDirection { limit, directions } => {
+                write_tag(out, 0);
+                limit.write_fingerprint(out);
+                directions.write_fingerprint(out);
+            }
+            MoveRule::AnyDirection { limit, directions } => {
+                write_tag(out, 1);
+                limit.write_fingerprint(out);
+                directions.write_fingerprint(out);
+            }
+            MoveRule::None => write_tag(out, 2),
+        }
+    }
+}
+impl Fingerprint for GoalMovementRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_tag(
+            out,
+            match self {
+                GoalMovementRule::Locked => 0,
+                GoalMovementRule::OnlyToGoal => 1,
+                GoalMovementRule::Free => 2,
+                GoalMovementRule::NoOwnGoal => 3,
+            },
+        );
+    }
+}
+
+impl Fingerprint for BoardType {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            BoardType::Rectangular {
+                rows,
+                columns,
+                goal_depth,
+                red_goals,
+                blue_goals,
+            } => {
+                write_tag(out, 0);
+                rows.write_fingerprint(out);
+                columns.write_fingerprint(out);
+                goal_depth.write_fingerprint(out);
+                red_goals.write_fingerprint(out);
+                blue_goals.write_fingerprint(out);
+            }
+            BoardType::Custom(board) => {
+                write_tag(out, 1);
+                board.write_fingerprint(out);
+            }
+            BoardType::Squares {
+                rows,
+                columns,
+                goals,
+            } => {
+                write_tag(out, 2);
+                rows.write_fingerprint(out);
+                columns.write_fingerprint(out);
+                goals.write_fingerprint(out);
+            }
+        }
+    }
+}
+impl Fingerprint for GoalSquares {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            GoalSquares::MirroredFlipped(positions) => {
+                write_tag(out, 0);
+                positions.write_fingerprint(out);
+            }
+            GoalSquares::MirroredRotated(positions) => {
+                write_tag(out, 1);
+                positions.write_fingerprint(out);
+            }
+            GoalSquares::NonMirrored(color_positions) => {
+                write_tag(out, 2);
+                color_positions.write_fingerprint(out);
+            }
+        }
+    }
+}
+impl Fingerprint for GoalLocations {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            GoalLocations::Columns(columns) => {
+                write_tag(out, 0);
+                columns.write_fingerprint(out);
+            }
+            GoalLocations::Centered { width } => {
+                write_tag(out, 1);
+                width.write_fingerprint(out);
+            }
+            GoalLocations::Strip { start, width } => {
+                write_tag(out, 2);
+                start.write_fingerprint(out);
+                width.write_fingerprint(out);
+            }
+        }
+    }
+}
+impl Fingerprint for Space {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            Space::Invalid => write_tag(out, 0),
+            Space::Normal => write_tag(out, 1),
+            Space::Goal(color) => {
+                write_tag(out, 2);
+                color.write_fingerprint(out);
+            }
+            Space::Blocked => write_tag(out, 3),
+            Space::Sticky => write_tag(out, 4),
+            Space::Portal(pair) => {
+                write_tag(out, 5);
+                pair.write_fingerprint(out);
+            }
+        }
+    }
+}
+
+impl Fingerprint for StartingPositions {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            StartingPositions::MirroredFlipped(piece_positions) => {
+                write_tag(out, 0);
+                piece_positions.write_fingerprint(out);
+            }
+            StartingPositions::MirroredRotated(piece_positions) => {
+                write_tag(out, 1);
+                piece_positions.write_fingerprint(out);
+            }
+            StartingPositions::NotMirrored(color_piece_positions) => {
+                write_tag(out, 2);
+                color_piece_positions.write_fingerprint(out);
+            }
+            StartingPositions::Placement {
+                first_color,
+                alternation_type,
+                placement_area,
+                piece_limits,
+            } => {
+                write_tag(out, 3);
+                first_color.write_fingerprint(out);
+                alternation_type.write_fingerprint(out);
+                placement_area.write_fingerprint(out);
+                piece_limits.write_fingerprint(out);
+            }
+            StartingPositions::Random {
+                seed,
+                piece_counts,
+                area,
+            } => {
+                write_tag(out, 4);
+                seed.write_fingerprint(out);
+                piece_counts.write_fingerprint(out);
+                area.write_fingerprint(out);
+            }
+        }
+    }
+}
+impl Fingerprint for AlternationType {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            AlternationType::TurnsCount { per_turn_count } => {
+                write_tag(out, 0);
+                per_turn_count.write_fingerprint(out);
+            }
+            AlternationType::TurnsPoints {
+                per_turn_points,
+                hard_limit,
+            } => {
+                write_tag(out, 1);
+                per_turn_points.write_fingerprint(out);
+                hard_limit.write_fingerprint(out);
+            }
+            AlternationType::Points => write_tag(out, 2),
+            AlternationType::WholePlacement => write_tag(out, 3),
+            AlternationType::Hidden => write_tag(out, 4),
+        }
+    }
+}
+impl Fingerprint for PlacementArea {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            PlacementArea::Half => write_tag(out, 0),
+            PlacementArea::MirroredFlipped(positions) => {
+                write_tag(out, 1);
+                positions.write_fingerprint(out);
+            }
+            PlacementArea::MirroredRotated(positions) => {
+                write_tag(out, 2);
+                positions.write_fingerprint(out);
+            }
+            PlacementArea::NonMirrored(color_positions) => {
+                write_tag(out, 3);
+                color_positions.write_fingerprint(out);
+            }
+        }
+    }
+}
+impl Fingerprint for PieceLimit {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            PieceLimit::TotalLimit { limit } => {
+                write_tag(out, 0);
+                limit.write_fingerprint(out);
+            }
+            PieceLimit::TypeCountLimit { limits } => {
+                write_tag(out, 1);
+                limits.write_fingerprint(out);
+            }
+            PieceLimit::PointLimit {
+                point_values,
+                point_limit,
+                square_costs,
+            } => {
+                write_tag(out, 2);
+                point_values.write_fingerprint(out);
+                point_limit.write_fingerprint(out);
+                square_costs.write_fingerprint(out);
+            }
+        }
+    }
+}
+
+impl Fingerprint for StalemateRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_tag(
+            out,
+            match self {
+                StalemateRule::Lose => 0,
+                StalemateRule::Draw => 1,
+                StalemateRule::Pass => 2,
+            },
+        );
+    }
+}
+
+impl Fingerprint for PassRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        write_tag(
+            out,
+            match self {
+                PassRule::Never => 0,
+                PassRule::WhenBlocked => 1,
+                PassRule::Always => 2,
+            },
+        );
+    }
+}
+
+impl Fingerprint for GameLengthRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.max_turns.write_fingerprint(out);
+        write_tag(
+            out,
+            match self.tiebreak {
+                Tiebreak::Draw => 0,
+                Tiebreak::MostPieces => 1,
+                Tiebreak::MostPoints => 2,
+                Tiebreak::ClosestToGoal => 3,
+            },
+        );
+    }
+}
+
+impl Fingerprint for Scoring {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.capture_points.write_fingerprint(out);
+        self.goal_entry_points.write_fingerprint(out);
+    }
+}
+
+impl Fingerprint for ChanceRule {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.faces.write_fingerprint(out);
+    }
+}
+impl Fingerprint for ChanceFace {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        self.pieces.write_fingerprint(out);
+        self.directions.write_fingerprint(out);
+    }
+}
+
+impl Fingerprint for VictoryCondition {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            VictoryCondition::GoalCount {
+                amount,
+                valid_pieces,
+            } => {
+                write_tag(out, 0);
+                amount.write_fingerprint(out);
+                valid_pieces.write_fingerprint(out);
+            }
+            VictoryCondition::AllCaptured => write_tag(out, 1),
+            VictoryCondition::PointDifference(difference) => {
+                write_tag(out, 2);
+                difference.write_fingerprint(out);
+            }
+            VictoryCondition::PiecesInGoal {
+                count,
+                piece_filter,
+            } => {
+                write_tag(out, 3);
+                count.write_fingerprint(out);
+                piece_filter.write_fingerprint(out);
+            }
+            VictoryCondition::CaptureCount {
+                count,
+                piece_filter,
+            } => {
+                write_tag(out, 4);
+                count.write_fingerprint(out);
+                piece_filter.write_fingerprint(out);
+            }
+            VictoryCondition::ScoreAtLeast(points) => {
+                write_tag(out, 5);
+                points.write_fingerprint(out);
+            }
+            VictoryCondition::All(conditions) => {
+                write_tag(out, 6);
+                conditions.write_fingerprint(out);
+            }
+            VictoryCondition::Any(conditions) => {
+                write_tag(out, 7);
+                conditions.write_fingerprint(out);
+            }
+        }
+    }
+}
+
+impl Fingerprint for PieceFilter {
+    fn write_fingerprint(&self, out: &mut Vec<u8>) {
+        match self {
+            PieceFilter::Any => write_tag(out, 0),
+            PieceFilter::Only(indices) => {
+                write_tag(out, 1);
+                indices.write_fingerprint(out);
+            }
+            PieceFilter::Except(indices) => {
+                write_tag(out, 2);
+                indices.write_fingerprint(out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    use crate::collections::{HashMap, HashSet};
+    use crate::coordinate::Coordinate;
+    use crate::game_board::Color;
+    use crate::ruleset::board_type::goal_locations::GoalLocations;
+    use crate::ruleset::board_type::BoardType;
+    use crate::ruleset::fingerprint::Fingerprint;
+    use crate::ruleset::piece_definition::ChainContinuationRule;
+    use crate::ruleset::presets::Preset;
+    use crate::ruleset::standard::standard_pieces;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::ruleset::victory_condition::VictoryCondition;
+    use crate::ruleset::Ruleset;
+
+    fn ruleset(positions: Vec<(usize, Vec<Coordinate>)>) -> Ruleset {
+        let mut victory_conditions = HashSet::new();
+        victory_conditions.insert(VictoryCondition::AllCaptured);
+        Ruleset {
+            pieces: standard_pieces(),
+            board_type: BoardType::Rectangular {
+                rows: 4,
+                columns: 4,
+                goal_depth: 1,
+                red_goals: GoalLocations::Centered { width: 2 },
+                blue_goals: GoalLocations::Centered { width: 2 },
+            },
+            starting_positions: StartingPositions::MirroredRotated(
+                positions.into_iter().collect::<HashMap<_, _>>(),
+            ),
+            victory_conditions,
+            stalemate_rule: Default::default(),
+            pass_rule: Default::default(),
+            scoring: Default::default(),
+            game_length_rule: Default::default(),
+            chance_rule: Default::default(),
+        }
+    }
+
+    #[test]
+    fn order_independent_test() {
+        let first = vec![
+            (0, vec![Coordinate::new(1, 0)]),
+            (1, vec![Coordinate::new(1, 1), Coordinate::new(1, 2)]),
+        ];
+        let mut reversed = first.clone();
+        reversed.reverse();
+        assert_eq!(
+            ruleset(first.clone()).fingerprint(),
+            ruleset(reversed).fingerprint()
+        );
+        assert_eq!(ruleset(first.clone()), ruleset(first.clone()));
+
+        let mut renamed = ruleset(first.clone());
+        renamed.pieces[0].name = "Other".to_string();
+        assert_ne!(renamed.fingerprint(), ruleset(first.clone()).fingerprint());
+        assert_ne!(renamed, ruleset(first));
+    }
+
+    #[test]
+    fn field_test() {
+        let base = ruleset(vec![(0, vec![Coordinate::new(1, 0)])]);
+        let mut scored = base.clone();
+        scored.scoring.capture_points = vec![1];
+        let mut limited = base.clone();
+        limited.game_length_rule.max_turns = Some(1);
+        let mut both = scored.clone();
+        both.game_length_rule = limited.game_length_rule;
+        let fingerprints: HashSet<[u8; 32]> = [&base, &scored, &limited, &both]
+            .iter()
+            .map(|ruleset| ruleset.fingerprint())
+            .collect();
+        assert_eq!(fingerprints.len(), 4);
+
+        let mut first_chains = base.clone();
+        first_chains.pieces[0].chain_rule = ChainContinuationRule::MustCapture;
+        let mut second_chains = base.clone();
+        second_chains.pieces[1].chain_rule = ChainContinuationRule::MustCapture;
+        assert_ne!(first_chains, base);
+        assert_ne!(first_chains, second_chains);
+        assert_ne!(
+            first_chains.pieces[0].fingerprint(),
+            base.pieces[0].fingerprint()
+        );
+    }
+
+    #[test]
+    fn chain_rule_owner_test() {
+        let mini = Preset::Mini.ruleset().unwrap();
+        let mut chains = mini.clone();
+        chains.pieces[0].chain_rule = ChainContinuationRule::MustCapture;
+        let mut owned = mini.clone();
+        owned.pieces[0].owner = Some(Color::Blue);
+        assert_ne!(chains.fingerprint(), owned.fingerprint());
+        assert_ne!(chains, owned);
+        assert_ne!(owned, mini);
+        assert_ne!(
+            chains.pieces[0].fingerprint(),
+            owned.pieces[0].fingerprint()
+        );
+    }
+}
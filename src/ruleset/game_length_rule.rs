@@ -0,0 +1,136 @@
+use core::cmp::Ordering;
+
+use crate::game::GameOutcome;
+use crate::game_board::board::Board;
+use crate::game_board::Color;
+use crate::ruleset::scoring::Scores;
+
+/// How many actions a game may last, and who wins when it runs out.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct GameLengthRule {
+    /// Actions, counting both colors and passes, after which the tiebreak decides the game.
+    /// `None` for no limit
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_turns: Option<usize>,
+    /// How the game is decided at the limit
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tiebreak: Tiebreak,
+}
+impl GameLengthRule {
+    /// Whether a game with `turns` actions played has reached the limit.
+    pub fn is_reached(&self, turns: usize) -> bool {
+        self.max_turns.is_some_and(|max_turns| turns >= max_turns)
+    }
+}
+
+/// Who wins a game stopped by its [`GameLengthRule`], ties are drawn.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum Tiebreak {
+    /// The game is drawn
+    #[default]
+    Draw,
+    /// The color with more pieces on the board wins
+    MostPieces,
+    /// The color with more points under the ruleset's [`Scoring`](crate::ruleset::scoring::Scoring) wins
+    MostPoints,
+    /// The color with a piece fewest single steps from the opponent's goal wins
+    ClosestToGoal,
+}
+impl Tiebreak {
+    /// Decides a game ending on `board` with `scores`.
+    pub fn decide(&self, board: &impl Board, scores: Scores) -> GameOutcome {
+        let ordering = match self {
+            Tiebreak::Draw => Ordering::Equal,
+            Tiebreak::MostPieces => board
+                .pieces_of_color(Color::Red)
+                .len()
+                .cmp(&board.pieces_of_color(Color::Blue).len()),
+            Tiebreak::MostPoints => scores.red.cmp(&scores.blue),
+            Tiebreak::ClosestToGoal => {
+                // Colors without pieces or goals are never closest
+                let closest = |color: Color| {
+                    board
+                        .pieces_of_color(color)
+                        .into_iter()
+                        .filter_map(|(position, _)| board.goal_distance(position, color.opponent()))
+                        .min()
+                        .unwrap_or(usize::MAX)
+                };
+                closest(Color::Blue).cmp(&closest(Color::Red))
+            }
+        };
+        match ordering {
+            Ordering::Greater => GameOutcome::Win(Color::Red),
+            Ordering::Less => GameOutcome::Win(Color::Blue),
+            Ordering::Equal => GameOutcome::Draw,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::coordinate::Coordinate;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::{Game, GameOutcome};
+    use crate::game_board::board::Board;
+    use crate::game_board::{Color, GameBoard, PieceInstance};
+    use crate::ruleset::capabilities::RequiredFeatures;
+    use crate::ruleset::game_length_rule::{GameLengthRule, Tiebreak};
+    use crate::ruleset::scoring::Scores;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn tiebreak_test() {
+        let ruleset = small_ruleset(0);
+        let mut board = GameBoard::from_board_type(&ruleset.board_type);
+        let scores = Scores { red: 1, blue: 3 };
+        let red_goal = board.goals_of_color(Color::Red)[0].0;
+        let blue_goal = board.goals_of_color(Color::Blue)[0].0;
+        *board.piece_mut(red_goal).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(blue_goal).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        let middle = Coordinate::new((red_goal.row + blue_goal.row) / 2, red_goal.column);
+        *board.piece_mut(middle).unwrap() = Some(PieceInstance::new(0, Color::Blue));
+
+        assert_eq!(Tiebreak::Draw.decide(&board, scores), GameOutcome::Draw);
+        assert_eq!(
+            Tiebreak::MostPieces.decide(&board, scores),
+            GameOutcome::Win(Color::Red)
+        );
+        assert_eq!(
+            Tiebreak::MostPoints.decide(&board, scores),
+            GameOutcome::Win(Color::Blue)
+        );
+        assert_eq!(
+            Tiebreak::ClosestToGoal.decide(&board, scores),
+            GameOutcome::Win(Color::Red)
+        );
+        *board.piece_mut(blue_goal).unwrap() = None;
+        assert_eq!(
+            Tiebreak::ClosestToGoal.decide(&board, scores),
+            GameOutcome::Win(Color::Blue)
+        );
+    }
+
+    #[test]
+    fn game_length_rule_test() {
+        let mut ruleset = small_ruleset(2);
+        ruleset.game_length_rule = GameLengthRule {
+            max_turns: Some(3),
+            tiebreak: Tiebreak::ClosestToGoal,
+        };
+        assert!(ruleset
+            .capabilities()
+            .contains(RequiredFeatures::TURN_LIMIT));
+        let mut playout = Game::new(ruleset).unwrap().playout(RandomPolicy::new(1));
+        assert_eq!(playout.by_ref().count(), 3);
+        let game = playout.into_game();
+        assert_eq!(
+            game.result(),
+            Some(Tiebreak::ClosestToGoal.decide(game.board(), game.scores()))
+        );
+    }
+}
@@ -1,22 +1,50 @@
 use core::fmt;
-use std::collections::HashSet;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{Debug, Display, Formatter};
+
+use alloc::vec::Vec;
+
+use crate::collections::HashSet;
+use crate::game_board::{Color, MAX_PIECE_DEFINITIONS};
 
 use crate::ruleset::board_type::{BoardType, BoardTypeVerifyError};
+use crate::ruleset::chance_rule::{ChanceRule, ChanceRuleError};
+use crate::ruleset::fingerprint::Fingerprint;
+use crate::ruleset::game_length_rule::GameLengthRule;
+use crate::ruleset::pass_rule::PassRule;
 use crate::ruleset::piece_definition::{PieceDefinition, PieceDefinitionError};
+use crate::ruleset::scoring::{Scoring, ScoringError};
+use crate::ruleset::stalemate_rule::StalemateRule;
 use crate::ruleset::starting_positions::{StartingPositions, StartingPositionsError};
 use crate::ruleset::victory_condition::{VictoryCondition, VictoryConditionError};
 
 pub mod starting_positions;
 
 pub mod board_type;
+pub mod capabilities;
+pub mod chance_rule;
+pub mod diff;
+pub mod fingerprint;
+pub mod game_length_rule;
+pub mod pass_rule;
 pub mod piece_definition;
+pub mod presets;
+pub mod scoring;
+pub mod stalemate_rule;
 pub mod standard;
+pub mod summary;
+pub mod validation;
+pub mod verified;
 pub mod victory_condition;
 
 /// The ruleset for a game of Kapto
+///
+/// Hash, Eq, and PartialEq are based on the canonical [`Fingerprint`] encoding.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub struct Ruleset {
     /// All possible pieces
     pub pieces: Vec<PieceDefinition>,
@@ -25,20 +53,42 @@ pub struct Ruleset {
     /// Starting position type to use
     pub starting_positions: StartingPositions,
     /// How to win the game
-    /// At least one must be set
+    /// At least one must be set, the mover's are checked before the opponent's after each action
     pub victory_conditions: HashSet<VictoryCondition>,
+    /// What happens when the color to move has no legal action
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stalemate_rule: StalemateRule,
+    /// When the color to move may pass
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pass_rule: PassRule,
+    /// Points scored by captures and goal entries
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scoring: Scoring,
+    /// How many actions the game may last
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub game_length_rule: GameLengthRule,
+    /// The die limiting what may act each turn
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub chance_rule: ChanceRule,
 }
 impl Ruleset {
-    fn verify(&self) -> RulesetResult<()> {
+    /// Checks the ruleset can be played, stopping at the first problem, see
+    /// [`Ruleset::verify_with_context`] for all of them.
+    pub fn verify(&self) -> RulesetResult<()> {
+        if self.pieces.len() > MAX_PIECE_DEFINITIONS {
+            return Err(RulesetError::TooManyPieces(self.pieces.len()));
+        }
         let mut pieces_set = HashSet::with_capacity(self.pieces.len());
         for piece in self.pieces.iter() {
-            piece.verify()?;
+            piece.verify_on(&self.board_type)?;
             if !pieces_set.insert(piece) {
                 return Err(RulesetError::PieceDuplicated(piece.clone()));
             }
         }
         self.board_type.verify()?;
         self.starting_positions.verify(&self.board_type, self)?;
+        self.scoring.verify(self)?;
+        self.chance_rule.verify(self)?;
         for victory_condition in self.victory_conditions.iter() {
             victory_condition.verify(self)?;
         }
@@ -48,29 +98,66 @@ impl Ruleset {
     pub fn get_piece(&self, index: usize) -> Option<&PieceDefinition> {
         self.pieces.get(index)
     }
+    /// The pieces `color` has with their indices, see [`PieceDefinition::owner`].
+    pub fn pieces_for(&self, color: Color) -> impl Iterator<Item = (usize, &PieceDefinition)> + '_ {
+        self.pieces
+            .iter()
+            .enumerate()
+            .filter(move |(_, piece)| piece.is_available_to(color))
+    }
+}
+impl Ruleset {
+    /// SHA-256 of the canonical encoding, stable across `HashMap` iteration orders.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        Fingerprint::fingerprint(self)
+    }
+
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write_fingerprint(&mut out);
+        out
+    }
 }
+impl Hash for Ruleset {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_bytes().hash(state);
+    }
+}
+impl PartialEq for Ruleset {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical_bytes() == other.canonical_bytes()
+    }
+}
+impl Eq for Ruleset {}
 pub type RulesetResult<T> = Result<T, RulesetError>;
 #[derive(Clone, Debug)]
 pub enum RulesetError {
+    /// The ruleset has this many pieces, more than [`MAX_PIECE_DEFINITIONS`].
+    TooManyPieces(usize),
     PieceDuplicated(PieceDefinition),
     PieceDefinitionError(PieceDefinitionError),
     BoardTypeVerifyError(BoardTypeVerifyError),
     StartingPositionsError(StartingPositionsError),
     VictoryConditionError(VictoryConditionError),
+    ScoringError(ScoringError),
+    ChanceRuleError(ChanceRuleError),
 }
 impl Display for RulesetError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for RulesetError {
     fn cause(&self) -> Option<&dyn Error> {
         match self {
-            Self::PieceDuplicated(_) => None,
+            Self::TooManyPieces(_) | Self::PieceDuplicated(_) => None,
             Self::PieceDefinitionError(error) => Some(error),
             Self::BoardTypeVerifyError(error) => Some(error),
             Self::StartingPositionsError(error) => Some(error),
             Self::VictoryConditionError(error) => Some(error),
+            Self::ScoringError(error) => Some(error),
+            Self::ChanceRuleError(error) => Some(error),
         }
     }
 }
@@ -94,3 +181,13 @@ impl From<VictoryConditionError> for RulesetError {
         Self::VictoryConditionError(from)
     }
 }
+impl From<ScoringError> for RulesetError {
+    fn from(from: ScoringError) -> Self {
+        Self::ScoringError(from)
+    }
+}
+impl From<ChanceRuleError> for RulesetError {
+    fn from(from: ChanceRuleError) -> Self {
+        Self::ChanceRuleError(from)
+    }
+}
@@ -0,0 +1,79 @@
+/// When the color to move may play [`ActionType::Pass`](crate::action::ActionType::Pass).
+///
+/// Two passes in a row draw the game.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum PassRule {
+    /// Passing is never legal
+    #[default]
+    Never,
+    /// Passing is legal only without any other legal action
+    WhenBlocked,
+    /// Passing is always legal
+    Always,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::{Action, ActionError};
+    use crate::collections::HashSet;
+    use crate::game::{Game, GameOutcome};
+    use crate::game_board::Color;
+    use crate::ruleset::pass_rule::PassRule;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn pass_rule_test() {
+        let mut ruleset = small_ruleset(0);
+        let mut game = Game::new(ruleset.clone()).unwrap();
+        assert!(!game.legal_actions().contains(&Action::pass()));
+        assert!(matches!(
+            game.apply(&Action::pass()),
+            Err(ActionError::PassNotAllowed)
+        ));
+
+        ruleset.pass_rule = PassRule::Always;
+        let mut game = Game::new(ruleset.clone()).unwrap();
+        assert!(game.legal_actions().contains(&Action::pass()));
+        let board = game.board().board.clone();
+        game.apply(&Action::pass()).unwrap();
+        assert_eq!(game.to_move(), Color::Blue);
+        assert_eq!(game.board().board, board);
+        assert_eq!(game.result(), None);
+        game.apply(&Action::pass()).unwrap();
+        assert_eq!(game.result(), Some(GameOutcome::Draw));
+        assert_eq!(game.record().replay().unwrap().result(), game.result());
+
+        ruleset.pass_rule = PassRule::WhenBlocked;
+        let mut game = Game::new(ruleset.clone()).unwrap();
+        assert!(matches!(
+            game.apply(&Action::pass()),
+            Err(ActionError::PassNotAllowed)
+        ));
+
+        // Blue places nothing, so may only pass
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 1 });
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Blue,
+            alternation_type: AlternationType::WholePlacement,
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        let mut game = Game::new(ruleset).unwrap();
+        game.finish_placing().unwrap();
+        let placement = game.placement().unwrap().legal_placements()[0];
+        game.place(placement.piece_index, placement.position)
+            .unwrap();
+        assert_eq!(game.result(), None);
+        assert_eq!(game.legal_actions(), vec![Action::pass()]);
+        game.apply(&Action::pass()).unwrap();
+        assert_eq!(game.to_move(), Color::Red);
+        assert!(!game.legal_actions().contains(&Action::pass()));
+    }
+}
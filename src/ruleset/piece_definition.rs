@@ -1,16 +1,25 @@
-use std::collections::HashMap;
+use core::fmt;
+use core::fmt::Display;
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
-use std::fmt::Display;
-use std::fmt::{Debug, Formatter};
-use std::hash::{Hash, Hasher};
 
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::collections::HashMap;
 use crate::direction::Directions;
+use crate::game_board::Color;
+use crate::ruleset::board_type::BoardType;
 
 /// Defines a piece
 ///
 /// Hash, Eq, and PartialEq are only defined for `name`
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub struct PieceDefinition {
     /// The name of the piece type
     pub name: String,
@@ -24,19 +33,195 @@ pub struct PieceDefinition {
     pub capture_requirement: CaptureRequirement,
     /// The rule for how many jumps this piece can make
     pub jump_limit: JumpLimit,
+    /// The rule for whether this piece may end a jump chain early
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub chain_rule: ChainContinuationRule,
     /// The rule for how this piece can move
     pub move_rule: MoveRule,
     /// The rule for how this piece moves within a goal
     pub goal_move_rule: GoalMovementRule,
+    /// The only color with this piece, both colors have it when `None`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub owner: Option<Color>,
 }
 impl PieceDefinition {
+    /// Whether `color` has this piece, see [`PieceDefinition::owner`].
+    pub fn is_available_to(&self, color: Color) -> bool {
+        self.owner.is_none_or(|owner| owner == color)
+    }
+
     pub fn verify(&self) -> PieceDefinitionResult<()> {
+        match self.errors(None).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+    /// [`verify`](Self::verify) along with the rules that depend on the board.
+    pub fn verify_on(&self, board_type: &BoardType) -> PieceDefinitionResult<()> {
+        match self.errors(Some(board_type)).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+    /// Every problem with this piece, in the order [`verify`](Self::verify) checks them.
+    pub(crate) fn errors(&self, board_type: Option<&BoardType>) -> Vec<PieceDefinitionError> {
+        let mut out = Vec::new();
         if self.name.is_empty() {
-            return Err(PieceDefinitionError::NameInvalid(self.name.clone()));
+            out.push(PieceDefinitionError::NameInvalid(self.name.clone()));
         }
-        self.jump_limit.verify()?;
-        self.move_rule.verify()?;
-        Ok(())
+        if let Err(error) = self.jump_limit.verify() {
+            out.push(error.into());
+        }
+        if let Err(error) = self.move_rule.verify() {
+            out.push(error.into());
+        }
+        let mut capture_rules: Vec<_> = self.capture_rules.keys().copied().collect();
+        capture_rules.sort_unstable_by_key(|&rule| rule as u8);
+        for rule in capture_rules {
+            match rule {
+                CaptureRule::JumpOver | CaptureRule::JumpOn
+                    if self.jump_limit == JumpLimit::Cannot =>
+                {
+                    out.push(PieceDefinitionError::CaptureWithoutJumps(rule));
+                }
+                CaptureRule::Move if self.move_rule == MoveRule::None => {
+                    out.push(PieceDefinitionError::CaptureWithoutMoves);
+                }
+                _ => {}
+            }
+        }
+        if self.capture_rules.is_empty() && self.capture_requirement != CaptureRequirement::None {
+            out.push(PieceDefinitionError::ForcedWithoutCaptures);
+        }
+        if self.chain_rule == ChainContinuationRule::MustCapture
+            && !self.capture_rules.contains_key(&CaptureRule::JumpOver)
+        {
+            out.push(PieceDefinitionError::ContinuationWithoutCaptures);
+        }
+        if let Some(board_type) = board_type {
+            if self.goal_move_rule != GoalMovementRule::Free && !board_type.has_goal() {
+                out.push(PieceDefinitionError::GoalRuleWithoutGoals(
+                    self.goal_move_rule,
+                ));
+            }
+        }
+        out
+    }
+
+    /// The standard big piece, jumping any number of times.
+    pub fn standard_big() -> Self {
+        Self {
+            name: "Big".into(),
+            capture_rules: vec![(CaptureRule::JumpOver, CaptureTarget::EnemyOnly)]
+                .into_iter()
+                .collect(),
+            jump_rule: JumpRule::NoSameStart,
+            capture_timing_rule: CaptureTimingRule::AfterTurn,
+            capture_requirement: CaptureRequirement::Forced(10),
+            jump_limit: JumpLimit::Unlimited {
+                directions: Directions::ALL,
+            },
+            chain_rule: ChainContinuationRule::MayStop,
+            move_rule: MoveRule::AnyDirection {
+                limit: 1,
+                directions: Directions::ALL,
+            },
+            goal_move_rule: GoalMovementRule::Free,
+            owner: None,
+        }
+    }
+    /// The standard little piece, the big piece jumping once a turn.
+    pub fn standard_small() -> Self {
+        Self::standard_big()
+            .with_name("Little")
+            .with_jump_limit(JumpLimit::Limited {
+                limit: 1,
+                directions: Directions::ALL,
+            })
+    }
+    /// A draughts man stepping and jumping diagonally, capturing each piece as it is jumped.
+    ///
+    /// Directions are the same for both colors so it moves backwards as well.
+    pub fn checker() -> Self {
+        Self {
+            name: "Checker".into(),
+            capture_rules: vec![(CaptureRule::JumpOver, CaptureTarget::EnemyOnly)]
+                .into_iter()
+                .collect(),
+            jump_rule: JumpRule::NoBacktracking,
+            capture_timing_rule: CaptureTimingRule::AfterJump,
+            capture_requirement: CaptureRequirement::Forced(0),
+            jump_limit: JumpLimit::Unlimited {
+                directions: Directions::DIAGONAL,
+            },
+            chain_rule: ChainContinuationRule::MayStop,
+            move_rule: MoveRule::AnyDirection {
+                limit: 1,
+                directions: Directions::DIAGONAL,
+            },
+            goal_move_rule: GoalMovementRule::Free,
+            owner: None,
+        }
+    }
+    /// A draughts king, the [`checker`](Self::checker) moving any distance along a diagonal.
+    pub fn flying_king() -> Self {
+        Self::checker()
+            .with_name("King")
+            .with_move_rule(MoveRule::SameDirection {
+                limit: usize::MAX,
+                directions: Directions::DIAGONAL,
+            })
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+    /// Replaces every capture rule with `capture_rules`.
+    pub fn with_capture_rules(
+        mut self,
+        capture_rules: HashMap<CaptureRule, CaptureTarget>,
+    ) -> Self {
+        self.capture_rules = capture_rules;
+        self
+    }
+    /// Adds or replaces the target of `rule`.
+    pub fn with_capture_rule(mut self, rule: CaptureRule, target: CaptureTarget) -> Self {
+        self.capture_rules.insert(rule, target);
+        self
+    }
+    pub fn with_jump_rule(mut self, jump_rule: JumpRule) -> Self {
+        self.jump_rule = jump_rule;
+        self
+    }
+    pub fn with_capture_timing_rule(mut self, capture_timing_rule: CaptureTimingRule) -> Self {
+        self.capture_timing_rule = capture_timing_rule;
+        self
+    }
+    pub fn with_capture_requirement(mut self, capture_requirement: CaptureRequirement) -> Self {
+        self.capture_requirement = capture_requirement;
+        self
+    }
+    pub fn with_jump_limit(mut self, jump_limit: JumpLimit) -> Self {
+        self.jump_limit = jump_limit;
+        self
+    }
+    pub fn with_chain_rule(mut self, chain_rule: ChainContinuationRule) -> Self {
+        self.chain_rule = chain_rule;
+        self
+    }
+    pub fn with_move_rule(mut self, move_rule: MoveRule) -> Self {
+        self.move_rule = move_rule;
+        self
+    }
+    pub fn with_goal_move_rule(mut self, goal_move_rule: GoalMovementRule) -> Self {
+        self.goal_move_rule = goal_move_rule;
+        self
+    }
+    /// Gives the piece to `owner` only.
+    pub fn with_owner(mut self, owner: Color) -> Self {
+        self.owner = Some(owner);
+        self
     }
 }
 impl Hash for PieceDefinition {
@@ -57,16 +242,47 @@ pub enum PieceDefinitionError {
     NameInvalid(String),
     JumpLimitError(JumpLimitError),
     MoveRuleError(MoveRuleError),
+    /// A capture rule needing jumps on a piece whose [`JumpLimit`] is `Cannot`.
+    CaptureWithoutJumps(CaptureRule),
+    /// [`CaptureRule::Move`] on a piece whose [`MoveRule`] is `None`.
+    CaptureWithoutMoves,
+    /// [`CaptureRequirement::Forced`] or `Huff` on a piece with no capture rules.
+    ForcedWithoutCaptures,
+    /// [`ChainContinuationRule::MustCapture`] on a piece that does not capture by jumping over.
+    ContinuationWithoutCaptures,
+    /// A goal movement rule other than `Free` on a board without goals.
+    GoalRuleWithoutGoals(GoalMovementRule),
+}
+impl PieceDefinitionError {
+    /// The [`PieceDefinition`] field at fault.
+    pub fn field(&self) -> &'static str {
+        match self {
+            PieceDefinitionError::NameInvalid(_) => "name",
+            PieceDefinitionError::JumpLimitError(_) => "jump_limit",
+            PieceDefinitionError::MoveRuleError(_) => "move_rule",
+            PieceDefinitionError::CaptureWithoutJumps(_)
+            | PieceDefinitionError::CaptureWithoutMoves => "capture_rules",
+            PieceDefinitionError::ForcedWithoutCaptures => "capture_requirement",
+            PieceDefinitionError::ContinuationWithoutCaptures => "chain_rule",
+            PieceDefinitionError::GoalRuleWithoutGoals(_) => "goal_move_rule",
+        }
+    }
 }
 impl Display for PieceDefinitionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for PieceDefinitionError {
     fn cause(&self) -> Option<&dyn Error> {
         match self {
-            PieceDefinitionError::NameInvalid(_) => None,
+            PieceDefinitionError::NameInvalid(_)
+            | PieceDefinitionError::CaptureWithoutJumps(_)
+            | PieceDefinitionError::CaptureWithoutMoves
+            | PieceDefinitionError::ForcedWithoutCaptures
+            | PieceDefinitionError::ContinuationWithoutCaptures
+            | PieceDefinitionError::GoalRuleWithoutGoals(_) => None,
             PieceDefinitionError::JumpLimitError(error) => Some(error),
             PieceDefinitionError::MoveRuleError(error) => Some(error),
         }
@@ -85,6 +301,8 @@ impl From<MoveRuleError> for PieceDefinitionError {
 
 /// The rule for how jumps can happen
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum JumpRule {
     /// A piece may not go to any previous space within the same jump
     NoBacktracking,
@@ -95,6 +313,8 @@ pub enum JumpRule {
 }
 /// The rule for how captures can happen
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum CaptureRule {
     /// Can capture by jumping over
     JumpOver,
@@ -105,6 +325,8 @@ pub enum CaptureRule {
 }
 /// The rule for when captures happen during
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum CaptureTimingRule {
     /// Pieces are removed after they are jumped over, an enemy piece cannot be jumped twice
     AfterJump,
@@ -113,6 +335,8 @@ pub enum CaptureTimingRule {
 }
 /// The rule for what can get captured
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum CaptureTarget {
     /// Captures only enemy pieces
     EnemyOnly,
@@ -123,14 +347,36 @@ pub enum CaptureTarget {
 }
 /// The rule for when this piece is forced to capture
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum CaptureRequirement {
     /// Must capture if possible, higher values mean this piece is forced before others
     Forced(isize),
     /// No forced capture
     None,
+    /// Not forced, but the opponent may remove the piece after it ignores a capture, see
+    /// [`Game::huff`](crate::game::Game::huff)
+    Huff,
+}
+/// The rule for when a piece may end a jump chain
+///
+/// Applies within a chain, [`CaptureRequirement`] decides whether a chain is started at all
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum ChainContinuationRule {
+    /// A chain may end after any jump
+    #[default]
+    MayStop,
+    /// A chain may not end while the piece could make another jump that captures
+    MustCapture,
+    /// A chain may not end while the piece could make any other jump
+    MustContinue,
 }
 /// The rule for how a piece jumps
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum JumpLimit {
     /// Piece can jump an unlimited number of times
     Unlimited { directions: Directions },
@@ -170,10 +416,13 @@ impl Display for JumpLimitError {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for JumpLimitError {}
 
 /// The rule for how this piece moves
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum MoveRule {
     /// Piece can move in any one direction from the vec up to the limit amount
     SameDirection {
@@ -215,10 +464,13 @@ impl Display for MoveRuleError {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for MoveRuleError {}
 
-/// The rule for what movement is allowed while in a goal
+/// The rule for what movement is allowed into and while in a goal
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum GoalMovementRule {
     /// Piece is locked in place once it gets to the goal
     Locked,
@@ -226,4 +478,107 @@ pub enum GoalMovementRule {
     OnlyToGoal,
     /// Piece is free to move from the goal
     Free,
+    /// Piece may not enter its own color's goal, only the enemy's, and is free once inside
+    NoOwnGoal,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collections::HashMap;
+    use crate::direction::Directions;
+    use crate::grid::Grid;
+    use crate::ruleset::board_type::BoardType;
+    use crate::ruleset::fingerprint::Fingerprint;
+    use crate::ruleset::piece_definition::{
+        CaptureRequirement, CaptureRule, CaptureTarget, ChainContinuationRule, GoalMovementRule,
+        JumpLimit, MoveRule, PieceDefinition, PieceDefinitionError,
+    };
+    use crate::ruleset::standard::standard_pieces;
+    use crate::ruleset::RulesetError;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn templates_test() {
+        let templates = [
+            PieceDefinition::standard_big(),
+            PieceDefinition::standard_small(),
+            PieceDefinition::checker(),
+            PieceDefinition::flying_king(),
+        ];
+        for template in templates.iter() {
+            template.verify().unwrap();
+        }
+        for (standard, template) in standard_pieces().iter().zip(templates.iter()) {
+            assert_eq!(standard.fingerprint(), template.fingerprint());
+        }
+        assert_eq!(
+            templates[1].jump_limit,
+            JumpLimit::Limited {
+                limit: 1,
+                directions: Directions::ALL
+            }
+        );
+
+        let tank = PieceDefinition::standard_big()
+            .with_name("Tank")
+            .with_capture_rule(CaptureRule::Move, CaptureTarget::All)
+            .with_move_rule(MoveRule::None)
+            .with_goal_move_rule(GoalMovementRule::Locked);
+        assert_eq!(tank.name, "Tank");
+        assert_eq!(tank.capture_rules.len(), 2);
+        assert_eq!(tank.move_rule, MoveRule::None);
+        assert_eq!(tank.jump_rule, templates[0].jump_rule);
+        let restored = tank
+            .with_name("Big")
+            .with_capture_rules(templates[0].capture_rules.clone())
+            .with_move_rule(templates[0].move_rule)
+            .with_goal_move_rule(GoalMovementRule::Free);
+        assert_eq!(restored.fingerprint(), templates[0].fingerprint());
+    }
+
+    #[test]
+    fn interaction_verify_test() {
+        let big = PieceDefinition::standard_big();
+        assert!(matches!(
+            big.clone().with_jump_limit(JumpLimit::Cannot).verify(),
+            Err(PieceDefinitionError::CaptureWithoutJumps(
+                CaptureRule::JumpOver
+            ))
+        ));
+        assert!(matches!(
+            big.clone()
+                .with_capture_rules(HashMap::new())
+                .with_capture_rule(CaptureRule::Move, CaptureTarget::EnemyOnly)
+                .with_move_rule(MoveRule::None)
+                .verify(),
+            Err(PieceDefinitionError::CaptureWithoutMoves)
+        ));
+        let pacifist = big.clone().with_capture_rules(HashMap::new());
+        assert!(matches!(
+            pacifist.verify(),
+            Err(PieceDefinitionError::ForcedWithoutCaptures)
+        ));
+        let pacifist = pacifist.with_capture_requirement(CaptureRequirement::None);
+        pacifist.verify().unwrap();
+        assert!(matches!(
+            pacifist
+                .with_chain_rule(ChainContinuationRule::MustCapture)
+                .verify(),
+            Err(PieceDefinitionError::ContinuationWithoutCaptures)
+        ));
+        big.with_chain_rule(ChainContinuationRule::MustContinue)
+            .verify()
+            .unwrap();
+
+        let mut ruleset = small_ruleset(0);
+        ruleset.pieces[0].goal_move_rule = GoalMovementRule::Locked;
+        assert!(ruleset.verify().is_ok());
+        ruleset.board_type = BoardType::Custom(Grid::new((6, 6)));
+        assert!(matches!(
+            ruleset.verify(),
+            Err(RulesetError::PieceDefinitionError(
+                PieceDefinitionError::GoalRuleWithoutGoals(GoalMovementRule::Locked)
+            ))
+        ));
+    }
 }
@@ -0,0 +1,252 @@
+//! Named rulesets for applications to offer as game modes.
+//!
+//! Every preset verifies, see [`Preset::ruleset`].
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::collections::{HashMap, HashSet};
+use crate::coordinate::Coordinate;
+use crate::direction::Directions;
+use crate::game_board::Color;
+use crate::grid::Grid;
+use crate::ruleset::board_type::goal_locations::GoalLocations;
+use crate::ruleset::board_type::space::Space;
+use crate::ruleset::game_length_rule::{GameLengthRule, Tiebreak};
+use crate::ruleset::piece_definition::{JumpLimit, MoveRule};
+use crate::ruleset::standard::{standard_board, standard_pieces, standard_starting_positions};
+use crate::ruleset::starting_positions::alteration_type::AlternationType;
+use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+use crate::ruleset::starting_positions::placement_area::PlacementArea;
+use crate::ruleset::starting_positions::StartingPositions;
+use crate::ruleset::victory_condition::VictoryCondition;
+use crate::ruleset::{BoardType, Ruleset, RulesetResult};
+
+/// A named ruleset.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, IntoEnumIterator)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Preset {
+    /// A 6×6 board with few pieces for learning the rules.
+    Mini,
+    /// Both colors place pieces bought from a point budget before play.
+    PointsPlacement,
+    /// A hexagonal board where pieces step along its six directions.
+    Hex,
+    /// The standard board won only by capturing every opposing piece.
+    CaptureAll,
+}
+impl Preset {
+    /// The name [`Preset::from_name`] accepts.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Mini => "mini",
+            Preset::PointsPlacement => "points-placement",
+            Preset::Hex => "hex",
+            Preset::CaptureAll => "capture-all",
+        }
+    }
+    /// The preset named `name`, ignoring ASCII case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::into_enum_iter().find(|preset| preset.name().eq_ignore_ascii_case(name))
+    }
+
+    /// This preset's ruleset, erroring only if the preset is broken.
+    pub fn ruleset(&self) -> RulesetResult<Ruleset> {
+        let out = match self {
+            Preset::Mini => mini(),
+            Preset::PointsPlacement => points_placement(),
+            Preset::Hex => hex(),
+            Preset::CaptureAll => capture_all(),
+        };
+        out.verify()?;
+        Ok(out)
+    }
+}
+
+fn ruleset(
+    board_type: BoardType,
+    starting_positions: StartingPositions,
+    victory_conditions: &[VictoryCondition],
+) -> Ruleset {
+    Ruleset {
+        pieces: standard_pieces(),
+        board_type,
+        starting_positions,
+        victory_conditions: victory_conditions.iter().cloned().collect(),
+        stalemate_rule: Default::default(),
+        pass_rule: Default::default(),
+        scoring: Default::default(),
+        game_length_rule: Default::default(),
+        chance_rule: Default::default(),
+    }
+}
+/// A goal or capturing every opposing piece.
+fn goal_or_capture() -> [VictoryCondition; 2] {
+    [
+        VictoryCondition::GoalCount {
+            amount: 1,
+            valid_pieces: vec![0, 1],
+        },
+        VictoryCondition::AllCaptured,
+    ]
+}
+/// Red's pieces as big pieces at `big` and little pieces at `little`.
+fn positions(big: &[(i16, i16)], little: &[(i16, i16)]) -> HashMap<usize, Vec<Coordinate>> {
+    let coordinates = |positions: &[(i16, i16)]| {
+        positions
+            .iter()
+            .map(|&(row, column)| Coordinate::new(row, column))
+            .collect()
+    };
+    let mut out = HashMap::new();
+    out.insert(0, coordinates(big));
+    out.insert(1, coordinates(little));
+    out
+}
+
+fn mini() -> Ruleset {
+    ruleset(
+        BoardType::Rectangular {
+            rows: 6,
+            columns: 6,
+            goal_depth: 1,
+            red_goals: GoalLocations::Centered { width: 2 },
+            blue_goals: GoalLocations::Centered { width: 2 },
+        },
+        StartingPositions::MirroredFlipped(positions(
+            &[(1, 2), (1, 3)],
+            &[(2, 1), (2, 2), (2, 3), (2, 4)],
+        )),
+        &goal_or_capture(),
+    )
+}
+
+fn points_placement() -> Ruleset {
+    let mut piece_limits = HashSet::new();
+    piece_limits.insert(PieceLimit::PointLimit {
+        point_values: vec![(0, 3), (1, 1)].into_iter().collect(),
+        point_limit: 12,
+        square_costs: HashMap::new(),
+    });
+    ruleset(
+        BoardType::Rectangular {
+            rows: 8,
+            columns: 8,
+            goal_depth: 1,
+            red_goals: GoalLocations::Centered { width: 2 },
+            blue_goals: GoalLocations::Centered { width: 2 },
+        },
+        StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::Points,
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        },
+        &goal_or_capture(),
+    )
+}
+
+/// Side length of the [`Preset::Hex`] board.
+const HEX_SIDE: i16 = 4;
+/// The hex board drawn on the square grid by axial coordinates, cutting two corners off.
+///
+/// The six neighbours of each square are to its north, south, east, west, north east,
+/// and south west.
+fn hex() -> Ruleset {
+    let size = (2 * HEX_SIDE - 1) as usize;
+    let mut board = Grid::new((size, size));
+    for row in 0..size {
+        for column in 0..size {
+            let offset = row as i16 + column as i16 - 2 * (HEX_SIDE - 1);
+            if offset.abs() >= HEX_SIDE {
+                board[(row, column)] = Space::Invalid;
+            }
+        }
+    }
+    let last = size - 1;
+    for column in [4, 5] {
+        board[(0, column)] = Space::Goal(Color::Red);
+        board[(last, last - column)] = Space::Goal(Color::Blue);
+    }
+
+    let directions = Directions::NORTH
+        | Directions::SOUTH
+        | Directions::EAST
+        | Directions::WEST
+        | Directions::NORTH_EAST
+        | Directions::SOUTH_WEST;
+    let mut out = ruleset(
+        BoardType::Custom(board),
+        StartingPositions::MirroredRotated(positions(&[(1, 3), (1, 5)], &[(2, 2), (2, 4), (2, 6)])),
+        &goal_or_capture(),
+    );
+    for piece in out.pieces.iter_mut() {
+        if let MoveRule::AnyDirection {
+            directions: piece_directions,
+            ..
+        } = &mut piece.move_rule
+        {
+            *piece_directions = directions;
+        }
+        match &mut piece.jump_limit {
+            JumpLimit::Unlimited {
+                directions: piece_directions,
+            }
+            | JumpLimit::Limited {
+                directions: piece_directions,
+                ..
+            } => *piece_directions = directions,
+            JumpLimit::Cannot => {}
+        }
+    }
+    out
+}
+
+fn capture_all() -> Ruleset {
+    let mut out = ruleset(
+        standard_board(),
+        standard_starting_positions(),
+        &[VictoryCondition::AllCaptured],
+    );
+    // Bounds games where both sides keep their distance
+    out.game_length_rule = GameLengthRule {
+        max_turns: Some(400),
+        tiebreak: Tiebreak::MostPieces,
+    };
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use enum_iterator::IntoEnumIterator;
+
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::ruleset::presets::Preset;
+
+    #[test]
+    fn presets_test() {
+        for preset in Preset::into_enum_iter() {
+            assert_eq!(Preset::from_name(preset.name()), Some(preset));
+            let ruleset = preset.ruleset().unwrap();
+            let mut game = Game::new(ruleset).unwrap();
+            while let Some(placement) = game
+                .placement()
+                .and_then(|phase| phase.legal_placements().first().copied())
+            {
+                game.place(placement.piece_index, placement.position)
+                    .unwrap();
+            }
+            if game.placement().is_some() {
+                game.finish_placing().unwrap();
+            }
+            assert!(!game.legal_actions().is_empty(), "{:?}", preset);
+            let mut playout = game.playout(RandomPolicy::new(0));
+            playout.by_ref().take(500).for_each(drop);
+        }
+        assert_eq!(Preset::from_name("Capture-All"), Some(Preset::CaptureAll));
+        assert_eq!(Preset::from_name("standard"), None);
+    }
+}
@@ -0,0 +1,219 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use alloc::vec::Vec;
+
+use crate::action::{Action, ActionOutcome};
+use crate::game_board::board::Board;
+use crate::game_board::Color;
+use crate::ruleset::Ruleset;
+
+/// Points scored by actions, for [`VictoryCondition::ScoreAtLeast`](crate::ruleset::victory_condition::VictoryCondition::ScoreAtLeast).
+///
+/// Both lists are indexed by piece definition, pieces past the end of a list score nothing.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct Scoring {
+    /// Points for capturing an opponent's piece
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capture_points: Vec<usize>,
+    /// Points for a piece entering the opponent's goal from outside it
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub goal_entry_points: Vec<usize>,
+}
+impl Scoring {
+    pub fn verify(&self, ruleset: &Ruleset) -> ScoringResult<()> {
+        let longest = self.capture_points.len().max(self.goal_entry_points.len());
+        if longest > ruleset.pieces.len() {
+            return Err(ScoringError::PieceNotFound(ruleset.pieces.len()));
+        }
+        Ok(())
+    }
+
+    /// Whether no action can score.
+    pub fn is_empty(&self) -> bool {
+        self.capture_points
+            .iter()
+            .chain(self.goal_entry_points.iter())
+            .all(|&points| points == 0)
+    }
+    pub fn capture_points(&self, definition_index: usize) -> usize {
+        self.capture_points
+            .get(definition_index)
+            .copied()
+            .unwrap_or(0)
+    }
+    pub fn goal_entry_points(&self, definition_index: usize) -> usize {
+        self.goal_entry_points
+            .get(definition_index)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Points scored by the mover for playing `action` on `board`, the board before the action.
+    ///
+    /// Captures of the mover's own pieces score nothing.
+    pub fn points(&self, board: &impl Board, action: &Action, outcome: &ActionOutcome) -> usize {
+        let piece = match board.piece(action.start_pos) {
+            Ok(Some(piece)) if !action.action_type.is_pass() => piece,
+            _ => return 0,
+        };
+        let mover = piece.color();
+        let captures: usize = outcome
+            .captures
+            .iter()
+            .filter(|(_, captured)| captured.color() != mover)
+            .map(|(_, captured)| self.capture_points(captured.definition_index()))
+            .sum();
+        let entered = board.goal_for(action.start_pos) != Some(mover.opponent())
+            && board
+                .destination(action)
+                .is_some_and(|destination| board.goal_for(destination) == Some(mover.opponent()));
+        if entered {
+            captures + self.goal_entry_points(piece.definition_index())
+        } else {
+            captures
+        }
+    }
+}
+
+/// Each color's points so far under the ruleset's [`Scoring`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub struct Scores {
+    pub red: usize,
+    pub blue: usize,
+}
+impl Scores {
+    pub fn color(&self, color: Color) -> usize {
+        match color {
+            Color::Red => self.red,
+            Color::Blue => self.blue,
+        }
+    }
+    pub fn color_mut(&mut self, color: Color) -> &mut usize {
+        match color {
+            Color::Red => &mut self.red,
+            Color::Blue => &mut self.blue,
+        }
+    }
+}
+
+pub type ScoringResult<T> = Result<T, ScoringError>;
+#[derive(Copy, Clone, Debug)]
+pub enum ScoringError {
+    /// Points were given for a piece definition index the ruleset does not have.
+    PieceNotFound(usize),
+}
+impl Display for ScoringError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <Self as Debug>::fmt(self, f)
+    }
+}
+#[cfg(feature = "std")]
+impl Error for ScoringError {}
+
+#[cfg(test)]
+mod test {
+    use enum_iterator::IntoEnumIterator;
+
+    use crate::action::{Action, ActionOutcome, ActionType};
+    use crate::direction::Direction;
+    use crate::game::playout::{Policy, RandomPolicy};
+    use crate::game::{Game, GameError, GameOutcome};
+    use crate::game_board::board::Board;
+    use crate::game_board::{Color, GameBoard, PieceInstance};
+    use crate::ruleset::scoring::{Scoring, ScoringError};
+    use crate::ruleset::victory_condition::{VictoryCondition, VictoryConditionError};
+    use crate::ruleset::RulesetError;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn points_test() {
+        let mut ruleset = small_ruleset(0);
+        ruleset.scoring = Scoring {
+            capture_points: vec![0, 2],
+            goal_entry_points: vec![5],
+        };
+        assert!(ruleset.verify().is_ok());
+        ruleset.scoring.capture_points.push(1);
+        assert!(matches!(
+            ruleset.verify(),
+            Err(RulesetError::ScoringError(ScoringError::PieceNotFound(2)))
+        ));
+        ruleset.scoring.capture_points.pop();
+
+        // A step from beside blue's goal into it
+        let mut board = GameBoard::from_board_type(&ruleset.board_type);
+        let goal = board.goals_of_color(Color::Blue)[0].0;
+        let (start, direction) = Direction::into_enum_iter()
+            .find_map(|direction| {
+                let start = direction.offset() + goal;
+                let (north, east) = direction.components();
+                let back = Direction::from_components(-north, -east)?;
+                (board.is_valid_position(start) && board.goal_for(start).is_none())
+                    .then_some((start, back))
+            })
+            .unwrap();
+        let action = Action {
+            start_pos: start,
+            action_type: ActionType::Move(direction),
+        };
+        let mut outcome = ActionOutcome::default();
+        let scoring = &ruleset.scoring;
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        assert_eq!(scoring.points(&board, &action, &outcome), 5);
+        outcome
+            .captures
+            .push((goal, PieceInstance::new(1, Color::Blue)));
+        outcome
+            .captures
+            .push((goal, PieceInstance::new(1, Color::Red)));
+        assert_eq!(scoring.points(&board, &action, &outcome), 7);
+        *board.piece_mut(start).unwrap() = Some(PieceInstance::new(1, Color::Red));
+        assert_eq!(scoring.points(&board, &action, &outcome), 2);
+        assert_eq!(scoring.points(&board, &Action::pass(), &outcome), 0);
+    }
+
+    #[test]
+    fn score_at_least_test() {
+        let mut ruleset = small_ruleset(0);
+        ruleset
+            .victory_conditions
+            .insert(VictoryCondition::ScoreAtLeast(1));
+        assert!(matches!(
+            Game::new(ruleset.clone()),
+            Err(GameError::RulesetError(
+                RulesetError::VictoryConditionError(VictoryConditionError::NoScoring)
+            ))
+        ));
+
+        // Games end with the first capture
+        ruleset.scoring.capture_points = vec![1, 1];
+        let mut game = Game::new(ruleset).unwrap();
+        let mut policy = RandomPolicy::new(4);
+        while game.result().is_none() {
+            let mover = game.to_move();
+            let actions = game.legal_actions();
+            let index = policy.choose(&game, &actions);
+            let outcome = game.apply(&actions[index]).unwrap();
+            assert_eq!(game.scores().color(mover), outcome.captures.len());
+        }
+        let winner = match game.result() {
+            Some(GameOutcome::Win(color)) => color,
+            result => panic!("{:?}", result),
+        };
+        assert_eq!(
+            game.scores().color(winner),
+            game.statistics().color(winner).score
+        );
+        assert_eq!(game.scores().color(winner.opponent()), 0);
+        let record = game.record();
+        assert_eq!(record.scores, game.scores());
+        assert_eq!(record.replay().unwrap().scores(), game.scores());
+    }
+}
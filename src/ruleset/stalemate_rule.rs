@@ -0,0 +1,85 @@
+/// What happens when the color to move has no legal action.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum StalemateRule {
+    /// The color without a legal action loses
+    #[default]
+    Lose,
+    /// The game is drawn
+    Draw,
+    /// The turn passes to the opponent, drawn if neither color has a legal action
+    Pass,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collections::HashSet;
+    use crate::game::event::GameEvent;
+    use crate::game::{Game, GameOutcome};
+    use crate::game_board::Color;
+    use crate::ruleset::stalemate_rule::StalemateRule;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::ruleset::victory_condition::VictoryCondition;
+    use crate::testing::small_ruleset;
+
+    /// Blue moves first without placing anything, Red places `red_pieces`.
+    fn game(stalemate_rule: StalemateRule, red_pieces: usize) -> Game {
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 3 });
+        let mut ruleset = small_ruleset(0);
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Blue,
+            alternation_type: AlternationType::WholePlacement,
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        ruleset.stalemate_rule = stalemate_rule;
+        // Blue without pieces would have lost to AllCaptured after passing
+        ruleset.victory_conditions = HashSet::new();
+        ruleset
+            .victory_conditions
+            .insert(VictoryCondition::GoalCount {
+                amount: 1,
+                valid_pieces: vec![0, 1],
+            });
+        let mut game = Game::new(ruleset).unwrap();
+        game.finish_placing().unwrap();
+        for _ in 0..red_pieces {
+            let placement = game.placement().unwrap().legal_placements()[0];
+            game.place(placement.piece_index, placement.position)
+                .unwrap();
+        }
+        if game.placement().is_some() {
+            game.finish_placing().unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn stalemate_rule_test() {
+        let lose = game(StalemateRule::Lose, 1);
+        assert_eq!(lose.result(), Some(GameOutcome::Win(Color::Red)));
+
+        let draw = game(StalemateRule::Draw, 1);
+        assert_eq!(draw.result(), Some(GameOutcome::Draw));
+
+        let mut pass = game(StalemateRule::Pass, 1);
+        assert_eq!(pass.result(), None);
+        assert_eq!(pass.to_move(), Color::Red);
+        assert!(!pass.legal_actions().is_empty());
+        assert_eq!(pass.history().len(), 1);
+        assert!(pass.history()[0].0.action_type.is_pass());
+        assert!(pass.huffable().is_empty());
+        assert!(matches!(
+            pass.take_events().last(),
+            Some(GameEvent::Action { action, .. }) if action.action_type.is_pass()
+        ));
+
+        let neither = game(StalemateRule::Pass, 0);
+        assert_eq!(neither.result(), Some(GameOutcome::Draw));
+    }
+}
@@ -1,71 +1,73 @@
-use std::collections::HashMap;
+use alloc::vec;
+use alloc::vec::Vec;
 
-use crate::direction::Directions;
-use crate::ruleset::piece_definition::{
-    CaptureRequirement, CaptureRule, CaptureTarget, CaptureTimingRule, GoalMovementRule, JumpLimit,
-    JumpRule, MoveRule, PieceDefinition,
-};
+use crate::collections::HashMap;
+use crate::coordinate::Coordinate;
+use crate::ruleset::board_type::goal_locations::GoalLocations;
+use crate::ruleset::piece_definition::PieceDefinition;
 use crate::ruleset::starting_positions::StartingPositions;
 use crate::ruleset::{BoardType, Ruleset, RulesetResult};
 
 pub fn standard_rules() -> RulesetResult<Ruleset> {
     let out = Ruleset {
-        pieces: get_pieces(),
-        board_type: get_board(),
-        starting_positions: get_starting_positions(),
+        pieces: standard_pieces(),
+        board_type: standard_board(),
+        starting_positions: standard_starting_positions(),
         victory_conditions: Default::default(),
+        stalemate_rule: Default::default(),
+        pass_rule: Default::default(),
+        scoring: Default::default(),
+        game_length_rule: Default::default(),
+        chance_rule: Default::default(),
     };
     out.verify()?;
     Ok(out)
 }
 
-fn get_pieces() -> Vec<PieceDefinition> {
-    let capture_rules: HashMap<_, _> = vec![(CaptureRule::JumpOver, CaptureTarget::EnemyOnly)]
-        .into_iter()
-        .collect();
-    let big = PieceDefinition {
-        name: "Big".to_string(),
-        capture_rules: capture_rules.clone(),
-        jump_rule: JumpRule::NoSameStart,
-        capture_timing_rule: CaptureTimingRule::AfterTurn,
-        capture_requirement: CaptureRequirement::Forced(10),
-        jump_limit: JumpLimit::Unlimited {
-            directions: Directions::ALL,
-        },
-        move_rule: MoveRule::AnyDirection {
-            limit: 1,
-            directions: Directions::ALL,
-        },
-        goal_move_rule: GoalMovementRule::Free,
-    };
-
-    let small = PieceDefinition {
-        name: "Little".to_string(),
-        capture_rules,
-        jump_rule: JumpRule::NoSameStart,
-        capture_timing_rule: CaptureTimingRule::AfterTurn,
-        capture_requirement: CaptureRequirement::Forced(10),
-        jump_limit: JumpLimit::Limited {
-            limit: 1,
-            directions: Directions::ALL,
-        },
-        move_rule: MoveRule::AnyDirection {
-            limit: 1,
-            directions: Directions::ALL,
-        },
-        goal_move_rule: GoalMovementRule::Free,
-    };
-
-    vec![big, small]
+/// The standard big and little pieces, in that order.
+pub fn standard_pieces() -> Vec<PieceDefinition> {
+    vec![
+        PieceDefinition::standard_big(),
+        PieceDefinition::standard_small(),
+    ]
 }
-fn get_board() -> BoardType {
+/// The 10 by 10 board with two goals centered on each side.
+pub(crate) fn standard_board() -> BoardType {
     BoardType::Rectangular {
         rows: 10,
         columns: 10,
-        goal_locations: [4, 5].iter().cloned().collect(),
+        goal_depth: 1,
+        red_goals: GoalLocations::Centered { width: 2 },
+        blue_goals: GoalLocations::Centered { width: 2 },
     }
 }
-fn get_starting_positions() -> StartingPositions {
-    // StartingPositions::MirroredFlipped()
-    unimplemented!()
+/// Four big pieces in front of eight little ones on the row ahead, mirrored for blue.
+pub(crate) fn standard_starting_positions() -> StartingPositions {
+    let row = |row, columns: core::ops::RangeInclusive<i16>| {
+        columns
+            .map(|column| Coordinate::new(row, column))
+            .collect::<Vec<_>>()
+    };
+    let mut positions = HashMap::new();
+    positions.insert(0, row(1, 3..=6));
+    positions.insert(1, row(2, 1..=8));
+    StartingPositions::MirroredFlipped(positions)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::Game;
+    use crate::ruleset::presets::Preset;
+    use crate::ruleset::standard::standard_rules;
+
+    #[test]
+    fn standard_rules_test() {
+        let ruleset = standard_rules().unwrap();
+        let capture_all = Preset::CaptureAll.ruleset().unwrap();
+        let game = Game::new(ruleset).unwrap();
+        assert_eq!(
+            game.board().board,
+            Game::new(capture_all).unwrap().board().board
+        );
+    }
 }
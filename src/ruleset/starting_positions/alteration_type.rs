@@ -1,13 +1,16 @@
-use std::collections::HashSet;
+use core::fmt;
+use core::fmt::Display;
+use core::fmt::{Debug, Formatter};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
-use std::fmt::Display;
-use std::fmt::{Debug, Formatter};
 
+use crate::collections::HashSet;
 use crate::ruleset::starting_positions::piece_limit::PieceLimit;
 
 /// The alteration for placement
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum AlternationType {
     /// Players alternate placing per_turn_count pieces.
     TurnsCount {
@@ -49,6 +52,7 @@ impl AlternationType {
                 if !piece_limits.contains(&PieceLimit::PointLimit {
                     point_values: Default::default(),
                     point_limit: Default::default(),
+                    square_costs: Default::default(),
                 }) {
                     return Err(AlterationTypeError::NoPointLimitForTurnsPoints);
                 }
@@ -70,4 +74,5 @@ impl Display for AlterationTypeError {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for AlterationTypeError {}
@@ -3,20 +3,24 @@ use core::fmt::{Debug, Display, Formatter};
 use core::option::Option::{None, Some};
 use core::result::Result;
 use core::result::Result::{Err, Ok};
-use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
 use std::error::Error;
 
+use alloc::vec::Vec;
 use enum_iterator::IntoEnumIterator;
 
 use placement_area::PlacementArea;
 
+use crate::collections::{HashMap, HashSet};
 use crate::coordinate::{flip_coordinate, rotate_coordinate, Coordinate};
-use crate::game_board::Color;
+use crate::game_board::{Color, GameBoard, PieceInstance};
+use crate::rng::{ChaChaRng, GameRng, LAYOUT_STREAM, PIECE_COUNT_STREAM};
 use crate::ruleset::board_type::space::Space;
 use crate::ruleset::piece_definition::PieceDefinition;
 use crate::ruleset::starting_positions::alteration_type::{AlterationTypeError, AlternationType};
 use crate::ruleset::starting_positions::piece_limit::{PieceLimit, PieceLimitError};
 use crate::ruleset::starting_positions::placement_area::PlacementAreaError;
+use crate::ruleset::verified::VerifiedRuleset;
 use crate::ruleset::{BoardType, Ruleset};
 
 pub mod alteration_type;
@@ -25,6 +29,8 @@ pub mod placement_area;
 
 /// Defines the starting positions
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum StartingPositions {
     /// Mirrored start positions, only defines a single side.
     /// Mirror will flip about horizontal center.
@@ -49,6 +55,16 @@ pub enum StartingPositions {
         /// The limitations on piece placement.
         piece_limits: HashSet<PieceLimit>,
     },
+    /// Randomized start positions generated deterministically from a seed.
+    /// Mirrored areas produce a symmetric layout.
+    Random {
+        /// The seed for the layout, the same seed always gives the same layout.
+        seed: u64,
+        /// Maps from pieces index to the number of that piece each color gets.
+        piece_counts: HashMap<usize, usize>,
+        /// The area pieces are placed in.
+        area: PlacementArea,
+    },
 }
 impl StartingPositions {
     fn verify_mirrored_flipped(
@@ -63,6 +79,13 @@ impl StartingPositions {
                 None => return Err(StartingPositionsError::PieceIndexNotFound(piece_index)),
                 Some(piece) => piece,
             };
+            // Both colors get every mirrored piece
+            if let Some(owner) = piece.owner {
+                return Err(StartingPositionsError::PieceNotAvailable {
+                    color: owner.opponent(),
+                    piece: piece.clone(),
+                });
+            }
             for &position in positions {
                 // Check already used positions and add to list
                 if !found.insert(position) {
@@ -108,6 +131,13 @@ impl StartingPositions {
                 None => return Err(StartingPositionsError::PieceIndexNotFound(piece_index)),
                 Some(piece) => piece,
             };
+            // Both colors get every mirrored piece
+            if let Some(owner) = piece.owner {
+                return Err(StartingPositionsError::PieceNotAvailable {
+                    color: owner.opponent(),
+                    piece: piece.clone(),
+                });
+            }
             for &position in positions {
                 // Check already used positions and add to list
                 if !found.insert(position) {
@@ -158,6 +188,12 @@ impl StartingPositions {
                     None => return Err(StartingPositionsError::PieceIndexNotFound(piece_index)),
                     Some(piece) => piece,
                 };
+                if !piece.is_available_to(color) {
+                    return Err(StartingPositionsError::PieceNotAvailable {
+                        color,
+                        piece: piece.clone(),
+                    });
+                }
                 for &position in positions {
                     // Check already used positions and add to list
                     if !found.insert(position) {
@@ -196,6 +232,243 @@ impl StartingPositions {
         Ok(())
     }
 
+    fn verify_random(
+        seed: u64,
+        piece_counts: &HashMap<usize, usize>,
+        area: &PlacementArea,
+        board: &BoardType,
+        ruleset: &Ruleset,
+    ) -> StartingPositionsResult<()> {
+        area.verify(board)?;
+        let layout = Self::random_layout(seed, piece_counts, area, board, ruleset)?;
+        Self::verify_not_mirrored(&layout, board, ruleset)
+    }
+
+    /// Generates the layout for a [`StartingPositions::Random`] definition.
+    ///
+    /// Red's pieces are shuffled into its area and mirrored onto Blue's side.
+    /// [`PlacementArea::NonMirrored`] areas are shuffled independently for each color.
+    /// Each color only gets the pieces it has, see [`PieceDefinition::owner`].
+    pub fn random_layout(
+        seed: u64,
+        piece_counts: &HashMap<usize, usize>,
+        area: &PlacementArea,
+        board: &BoardType,
+        ruleset: &Ruleset,
+    ) -> StartingPositionsResult<HashMap<Color, HashMap<usize, Vec<Coordinate>>>> {
+        // Iteration order of the maps is not stable, sort so the seed fully determines the layout
+        let mut pieces: Vec<(usize, usize)> = piece_counts
+            .iter()
+            .map(|(&piece_index, &count)| (piece_index, count))
+            .collect();
+        pieces.sort_unstable();
+        for &(piece_index, _) in pieces.iter() {
+            if ruleset.get_piece(piece_index).is_none() {
+                return Err(StartingPositionsError::PieceIndexNotFound(piece_index));
+            }
+        }
+        let pieces_for = |color: Color| {
+            pieces
+                .iter()
+                .copied()
+                .filter(move |&(piece_index, _)| ruleset.pieces[piece_index].is_available_to(color))
+        };
+        let required = Color::into_enum_iter()
+            .map(|color| pieces_for(color).map(|(_, count)| count).sum())
+            .max()
+            .unwrap_or(0);
+
+        let mut rng = ChaChaRng::with_stream(seed, LAYOUT_STREAM);
+        let mut shuffled = |color: Color| {
+            let mut positions: Vec<Coordinate> =
+                area.positions_for(color, board).into_iter().collect();
+            if positions.len() < required {
+                return Err(StartingPositionsError::NotEnoughSpace {
+                    required,
+                    available: positions.len(),
+                });
+            }
+            positions.sort_unstable();
+            rng.shuffle(&mut positions);
+            Ok(positions)
+        };
+        let red_positions = shuffled(Color::Red)?;
+        let blue_positions = match area {
+            PlacementArea::Half => red_positions
+                .iter()
+                .map(|&position| rotate_coordinate(board, position))
+                .collect(),
+            PlacementArea::MirroredFlipped(_) => red_positions
+                .iter()
+                .map(|&position| flip_coordinate(board, position))
+                .collect(),
+            PlacementArea::MirroredRotated(_) => red_positions
+                .iter()
+                .map(|&position| rotate_coordinate(board, position))
+                .collect(),
+            PlacementArea::NonMirrored(_) => shuffled(Color::Blue)?,
+        };
+
+        let mut out = HashMap::with_capacity(2);
+        for (color, positions) in [(Color::Red, red_positions), (Color::Blue, blue_positions)] {
+            let mut positions = positions.into_iter();
+            let mut piece_positions = HashMap::with_capacity(pieces.len());
+            for (piece_index, count) in pieces_for(color) {
+                piece_positions.insert(piece_index, positions.by_ref().take(count).collect());
+            }
+            out.insert(color, piece_positions);
+        }
+        Ok(out)
+    }
+
+    /// The placement area matching these starting positions.
+    ///
+    /// Fixed layouts become the set of positions they occupy with the same mirroring.
+    pub fn placement_area(&self) -> PlacementArea {
+        fn occupied(piece_positions: &HashMap<usize, Vec<Coordinate>>) -> HashSet<Coordinate> {
+            piece_positions.values().flatten().copied().collect()
+        }
+        match self {
+            StartingPositions::MirroredFlipped(piece_positions) => {
+                PlacementArea::MirroredFlipped(occupied(piece_positions))
+            }
+            StartingPositions::MirroredRotated(piece_positions) => {
+                PlacementArea::MirroredRotated(occupied(piece_positions))
+            }
+            StartingPositions::NotMirrored(color_piece_positions) => PlacementArea::NonMirrored(
+                color_piece_positions
+                    .iter()
+                    .map(|(&color, piece_positions)| (color, occupied(piece_positions)))
+                    .collect(),
+            ),
+            StartingPositions::Placement { placement_area, .. } => placement_area.clone(),
+            StartingPositions::Random { area, .. } => area.clone(),
+        }
+    }
+
+    /// Samples a legal fixed layout for a [`StartingPositions::Placement`] definition.
+    ///
+    /// Piece counts are picked randomly within the piece limits and placed with
+    /// [`StartingPositions::random_layout`], so the same seed always gives the same layout.
+    /// Returns `None` for definitions that are not placements.
+    pub fn sample_from_placement(
+        &self,
+        seed: u64,
+        board: &BoardType,
+        ruleset: &Ruleset,
+    ) -> Option<StartingPositionsResult<StartingPositions>> {
+        let (placement_area, piece_limits) = match self {
+            StartingPositions::Placement {
+                placement_area,
+                piece_limits,
+                ..
+            } => (placement_area, piece_limits),
+            _ => return None,
+        };
+        Some(Self::sample_layout(
+            seed,
+            placement_area,
+            piece_limits,
+            board,
+            ruleset,
+        ))
+    }
+    fn sample_layout(
+        seed: u64,
+        placement_area: &PlacementArea,
+        piece_limits: &HashSet<PieceLimit>,
+        board: &BoardType,
+        ruleset: &Ruleset,
+    ) -> StartingPositionsResult<StartingPositions> {
+        placement_area.verify(board)?;
+        PieceLimit::verify(piece_limits, ruleset)?;
+        let capacity = Color::into_enum_iter()
+            .map(|color| placement_area.positions_for(color, board).len())
+            .min()
+            .unwrap_or(0);
+        let mut rng = ChaChaRng::with_stream(seed, PIECE_COUNT_STREAM);
+        let counts = PieceLimit::sample_counts(piece_limits, ruleset, capacity, &mut rng);
+        let mut layout =
+            Self::random_layout(rng.next_u64(), &counts, placement_area, board, ruleset)?;
+        // Mirrored layouts give both colors the same pieces
+        if ruleset.pieces.iter().any(|piece| piece.owner.is_some()) {
+            return Ok(StartingPositions::NotMirrored(layout));
+        }
+        Ok(match placement_area {
+            PlacementArea::MirroredFlipped(_) => {
+                StartingPositions::MirroredFlipped(layout.remove(&Color::Red).unwrap_or_default())
+            }
+            PlacementArea::Half | PlacementArea::MirroredRotated(_) => {
+                StartingPositions::MirroredRotated(layout.remove(&Color::Red).unwrap_or_default())
+            }
+            PlacementArea::NonMirrored(_) => StartingPositions::NotMirrored(layout),
+        })
+    }
+
+    /// Builds the starting board for these positions.
+    ///
+    /// [`StartingPositions::Placement`] gives an empty board as pieces are placed during the game.
+    pub fn build_board(&self, ruleset: &VerifiedRuleset) -> StartingPositionsResult<GameBoard> {
+        let board = &ruleset.board_type;
+        let layout = match self {
+            StartingPositions::MirroredFlipped(piece_positions) => {
+                Self::mirror_layout(piece_positions, |position| flip_coordinate(board, position))
+            }
+            StartingPositions::MirroredRotated(piece_positions) => {
+                Self::mirror_layout(piece_positions, |position| {
+                    rotate_coordinate(board, position)
+                })
+            }
+            StartingPositions::NotMirrored(color_piece_positions) => color_piece_positions.clone(),
+            StartingPositions::Placement { .. } => HashMap::new(),
+            StartingPositions::Random {
+                seed,
+                piece_counts,
+                area,
+            } => Self::random_layout(*seed, piece_counts, area, board, ruleset)?,
+        };
+
+        let mut game_board = GameBoard::from_board_type(board);
+        game_board.set_piece_rules(&ruleset.pieces);
+        for (&color, piece_positions) in layout.iter() {
+            for (&piece_index, positions) in piece_positions {
+                let definition = ruleset
+                    .get_piece(piece_index)
+                    .ok_or(StartingPositionsError::PieceIndexNotFound(piece_index))?;
+                let piece = PieceInstance::new(piece_index, color);
+                for &position in positions {
+                    let slot = game_board.piece_mut(position).map_err(|_| {
+                        StartingPositionsError::InvalidPositionForBoard {
+                            space: board.get_space(position),
+                            piece: definition.clone(),
+                            position,
+                        }
+                    })?;
+                    *slot = Some(piece);
+                }
+            }
+        }
+        Ok(game_board)
+    }
+    fn mirror_layout(
+        piece_positions: &HashMap<usize, Vec<Coordinate>>,
+        mirror: impl Fn(Coordinate) -> Coordinate,
+    ) -> HashMap<Color, HashMap<usize, Vec<Coordinate>>> {
+        let mirrored = piece_positions
+            .iter()
+            .map(|(&piece_index, positions)| {
+                (
+                    piece_index,
+                    positions.iter().map(|&position| mirror(position)).collect(),
+                )
+            })
+            .collect();
+        let mut out = HashMap::with_capacity(2);
+        out.insert(Color::Red, piece_positions.clone());
+        out.insert(Color::Blue, mirrored);
+        out
+    }
+
     pub fn verify(&self, board: &BoardType, ruleset: &Ruleset) -> StartingPositionsResult<()> {
         match self {
             StartingPositions::MirroredFlipped(self_data) => {
@@ -220,6 +493,11 @@ impl StartingPositions {
                 board,
                 ruleset,
             ),
+            StartingPositions::Random {
+                seed,
+                piece_counts,
+                area,
+            } => Self::verify_random(*seed, piece_counts, area, board, ruleset),
         }
     }
 }
@@ -242,6 +520,16 @@ pub enum StartingPositionsError {
         piece: PieceDefinition,
         position: Coordinate,
     },
+    /// A piece given to a color that does not have it
+    PieceNotAvailable {
+        color: Color,
+        piece: PieceDefinition,
+    },
+    /// Not enough spaces to place all pieces
+    NotEnoughSpace {
+        required: usize,
+        available: usize,
+    },
     AlterationTypeError(AlterationTypeError),
     PlacementAreaError(PlacementAreaError),
     PieceLimitError(PieceLimitError),
@@ -251,6 +539,7 @@ impl Display for StartingPositionsError {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for StartingPositionsError {
     fn cause(&self) -> Option<&dyn Error> {
         match self {
@@ -258,6 +547,8 @@ impl Error for StartingPositionsError {
             StartingPositionsError::PieceIndexNotFound(_) => None,
             StartingPositionsError::DuplicatePosition { .. } => None,
             StartingPositionsError::InvalidPositionForBoard { .. } => None,
+            StartingPositionsError::PieceNotAvailable { .. } => None,
+            StartingPositionsError::NotEnoughSpace { .. } => None,
             StartingPositionsError::AlterationTypeError(error) => Some(error),
             StartingPositionsError::PlacementAreaError(error) => Some(error),
             StartingPositionsError::PieceLimitError(error) => Some(error),
@@ -279,3 +570,206 @@ impl From<PieceLimitError> for StartingPositionsError {
         Self::PieceLimitError(from)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::collections::{HashMap, HashSet};
+    use crate::coordinate::{rotate_coordinate, Coordinate};
+    use crate::game_board::Color;
+    use crate::ruleset::board_type::goal_locations::GoalLocations;
+    use crate::ruleset::board_type::BoardType;
+    use crate::ruleset::standard::standard_pieces;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::{StartingPositions, StartingPositionsError};
+    use crate::ruleset::Ruleset;
+
+    fn ruleset(starting_positions: StartingPositions) -> Ruleset {
+        Ruleset {
+            pieces: standard_pieces(),
+            board_type: BoardType::Rectangular {
+                rows: 6,
+                columns: 6,
+                goal_depth: 1,
+                red_goals: GoalLocations::Centered { width: 2 },
+                blue_goals: GoalLocations::Centered { width: 2 },
+            },
+            starting_positions,
+            victory_conditions: HashSet::new(),
+            stalemate_rule: Default::default(),
+            pass_rule: Default::default(),
+            scoring: Default::default(),
+            game_length_rule: Default::default(),
+            chance_rule: Default::default(),
+        }
+    }
+
+    fn counts() -> HashMap<usize, usize> {
+        [(0, 2), (1, 4)].iter().cloned().collect()
+    }
+
+    fn layout(seed: u64, area: &PlacementArea) -> HashMap<Color, HashMap<usize, Vec<Coordinate>>> {
+        let ruleset = ruleset(StartingPositions::MirroredRotated(HashMap::new()));
+        StartingPositions::random_layout(seed, &counts(), area, &ruleset.board_type, &ruleset)
+            .unwrap()
+    }
+
+    #[test]
+    fn random_deterministic_test() {
+        assert_eq!(
+            layout(3, &PlacementArea::Half),
+            layout(3, &PlacementArea::Half)
+        );
+        assert_ne!(
+            layout(3, &PlacementArea::Half),
+            layout(4, &PlacementArea::Half)
+        );
+    }
+
+    #[test]
+    fn random_symmetric_test() {
+        let ruleset = ruleset(StartingPositions::MirroredRotated(HashMap::new()));
+        let layout = layout(11, &PlacementArea::Half);
+        for (piece_index, positions) in layout[&Color::Red].iter() {
+            assert_eq!(positions.len(), counts()[piece_index]);
+            let mirrored: Vec<_> = positions
+                .iter()
+                .map(|&position| rotate_coordinate(&ruleset.board_type, position))
+                .collect();
+            assert_eq!(&mirrored, &layout[&Color::Blue][piece_index]);
+        }
+    }
+
+    #[test]
+    fn placement_conversion_test() {
+        let limits: HashSet<PieceLimit> = [
+            PieceLimit::TotalLimit { limit: 7 },
+            PieceLimit::TypeCountLimit {
+                limits: [(0, 2)].iter().cloned().collect(),
+            },
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let placement = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::WholePlacement,
+            placement_area: PlacementArea::Half,
+            piece_limits: limits,
+        };
+        let ruleset = ruleset(placement.clone());
+        let fixed = placement
+            .sample_from_placement(9, &ruleset.board_type, &ruleset)
+            .unwrap()
+            .unwrap();
+        assert!(fixed.verify(&ruleset.board_type, &ruleset).is_ok());
+        let positions = match &fixed {
+            StartingPositions::MirroredRotated(positions) => positions,
+            _ => panic!("Half placement should sample a rotated layout"),
+        };
+        assert_eq!(positions.values().map(Vec::len).sum::<usize>(), 7);
+        assert!(positions.get(&0).map(Vec::len).unwrap_or(0) <= 2);
+
+        let area = fixed.placement_area();
+        let occupied: HashSet<Coordinate> = positions.values().flatten().copied().collect();
+        match area {
+            PlacementArea::MirroredRotated(area) => assert_eq!(area, occupied),
+            _ => panic!("Rotated layout should give a rotated area"),
+        }
+    }
+
+    #[test]
+    fn random_verify_test() {
+        let valid = ruleset(StartingPositions::Random {
+            seed: 5,
+            piece_counts: counts(),
+            area: PlacementArea::Half,
+        });
+        assert!(valid
+            .starting_positions
+            .verify(&valid.board_type, &valid)
+            .is_ok());
+
+        let too_many = ruleset(StartingPositions::Random {
+            seed: 5,
+            piece_counts: [(0, 100)].iter().cloned().collect(),
+            area: PlacementArea::Half,
+        });
+        assert!(matches!(
+            too_many
+                .starting_positions
+                .verify(&too_many.board_type, &too_many),
+            Err(StartingPositionsError::NotEnoughSpace { required: 100, .. })
+        ));
+    }
+
+    #[test]
+    fn asymmetric_test() {
+        let mut asymmetric = ruleset(StartingPositions::Random {
+            seed: 5,
+            piece_counts: counts(),
+            area: PlacementArea::Half,
+        });
+        asymmetric.pieces[0].owner = Some(Color::Blue);
+        let verify = |ruleset: &Ruleset| {
+            ruleset
+                .starting_positions
+                .verify(&ruleset.board_type, ruleset)
+        };
+        assert!(verify(&asymmetric).is_ok());
+        let layout = StartingPositions::random_layout(
+            5,
+            &counts(),
+            &PlacementArea::Half,
+            &asymmetric.board_type,
+            &asymmetric,
+        )
+        .unwrap();
+        assert!(!layout[&Color::Red].contains_key(&0));
+        assert_eq!(layout[&Color::Blue][&0].len(), 2);
+        assert_eq!(layout[&Color::Red][&1].len(), 4);
+
+        let mut positions = HashMap::new();
+        positions.insert(0, vec![Coordinate::new(1, 1)]);
+        asymmetric.starting_positions = StartingPositions::MirroredRotated(positions.clone());
+        assert!(matches!(
+            verify(&asymmetric),
+            Err(StartingPositionsError::PieceNotAvailable {
+                color: Color::Red,
+                ..
+            })
+        ));
+        let mut by_color = HashMap::new();
+        by_color.insert(Color::Red, positions);
+        by_color.insert(Color::Blue, HashMap::new());
+        asymmetric.starting_positions = StartingPositions::NotMirrored(by_color);
+        assert!(matches!(
+            verify(&asymmetric),
+            Err(StartingPositionsError::PieceNotAvailable {
+                color: Color::Red,
+                ..
+            })
+        ));
+
+        let placement = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::WholePlacement,
+            placement_area: PlacementArea::Half,
+            piece_limits: [PieceLimit::TotalLimit { limit: 4 }]
+                .iter()
+                .cloned()
+                .collect(),
+        };
+        asymmetric.starting_positions = placement.clone();
+        let fixed = placement
+            .sample_from_placement(9, &asymmetric.board_type, &asymmetric)
+            .unwrap()
+            .unwrap();
+        asymmetric.starting_positions = fixed;
+        assert!(verify(&asymmetric).is_ok());
+    }
+}
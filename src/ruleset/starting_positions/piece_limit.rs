@@ -5,9 +5,14 @@ use core::hash::{Hash, Hasher};
 use core::mem::discriminant;
 use core::result::Result;
 use core::result::Result::{Err, Ok};
-use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
 use std::error::Error;
 
+use alloc::vec::Vec;
+
+use crate::collections::{HashMap, HashSet};
+use crate::coordinate::Coordinate;
+use crate::rng::GameRng;
 use crate::ruleset::piece_definition::PieceDefinition;
 use crate::ruleset::starting_positions::piece_limit::PieceLimitError::PieceHasNoPointValue;
 use crate::ruleset::Ruleset;
@@ -16,6 +21,8 @@ use crate::ruleset::Ruleset;
 ///
 /// Hash, Eq, PartialEq are defined for the discriminant.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum PieceLimit {
     /// Limit to the total count of pieces.
     TotalLimit { limit: usize },
@@ -32,6 +39,11 @@ pub enum PieceLimit {
         point_values: HashMap<usize, usize>,
         /// The total limit for each side.
         point_limit: usize,
+        /// Extra points charged for placing any piece on a square, squares not set cost nothing.
+        /// Maps from extra points to the squares costing them, for both colors.
+        /// All squares must be on the board and appear once.
+        #[cfg_attr(feature = "serde", serde(default))]
+        square_costs: HashMap<usize, HashSet<Coordinate>>,
     },
 }
 impl PieceLimit {
@@ -57,7 +69,21 @@ impl PieceLimit {
                 PieceLimit::PointLimit {
                     point_values,
                     point_limit: _,
+                    square_costs,
                 } => {
+                    let mut found = HashSet::new();
+                    for &position in square_costs.values().flatten() {
+                        if !found.insert(position) {
+                            return Err(PieceLimitError::DuplicateSquareCost(position));
+                        }
+                        if position.row < 0
+                            || position.column < 0
+                            || position.row as usize >= ruleset.board_type.rows()
+                            || position.column as usize >= ruleset.board_type.columns()
+                        {
+                            return Err(PieceLimitError::SquareCostOffBoard(position));
+                        }
+                    }
                     for (piece_index, definition) in ruleset.pieces.iter().enumerate() {
                         match point_values.get(&piece_index) {
                             None => {
@@ -77,7 +103,135 @@ impl PieceLimit {
         }
         Ok(())
     }
+
+    /// Whether one more of `piece_index` can be placed on top of `counts`.
+    pub fn allows(
+        self_set: &HashSet<Self>,
+        counts: &HashMap<usize, usize>,
+        piece_index: usize,
+    ) -> bool {
+        self_set.iter().all(|piece_limit| match piece_limit {
+            PieceLimit::TotalLimit { limit } => counts.values().sum::<usize>() < *limit,
+            PieceLimit::TypeCountLimit { limits } => match limits.get(&piece_index) {
+                None => true,
+                Some(limit) => counts.get(&piece_index).copied().unwrap_or(0) < *limit,
+            },
+            PieceLimit::PointLimit {
+                point_values,
+                point_limit,
+                ..
+            } => {
+                let used: usize = counts
+                    .iter()
+                    .map(|(index, count)| point_values.get(index).copied().unwrap_or(0) * count)
+                    .sum();
+                used + point_values.get(&piece_index).copied().unwrap_or(0) <= *point_limit
+            }
+        })
+    }
+    /// Whether one more of `piece_index` can be placed on `position`, on top of `counts` and
+    /// `square_points` already spent on square costs.
+    pub fn allows_at(
+        self_set: &HashSet<Self>,
+        counts: &HashMap<usize, usize>,
+        square_points: usize,
+        piece_index: usize,
+        position: Coordinate,
+    ) -> bool {
+        Self::allows(self_set, counts, piece_index)
+            && self_set.iter().all(|piece_limit| match piece_limit {
+                PieceLimit::PointLimit {
+                    point_values,
+                    point_limit,
+                    square_costs,
+                } => {
+                    let used: usize = counts
+                        .iter()
+                        .map(|(index, count)| point_values.get(index).copied().unwrap_or(0) * count)
+                        .sum();
+                    used + square_points
+                        + point_values.get(&piece_index).copied().unwrap_or(0)
+                        + cost_of(square_costs, position)
+                        <= *point_limit
+                }
+                _ => true,
+            })
+    }
+    /// The extra points charged for placing on `position`.
+    pub fn square_cost(self_set: &HashSet<Self>, position: Coordinate) -> usize {
+        self_set
+            .iter()
+            .map(|piece_limit| match piece_limit {
+                PieceLimit::PointLimit { square_costs, .. } => cost_of(square_costs, position),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// How many more of `piece_index` can be placed, `None` if no limit applies.
+    /// Square costs are not counted, placing on costly squares leaves fewer.
+    pub fn remaining(
+        self_set: &HashSet<Self>,
+        counts: &HashMap<usize, usize>,
+        piece_index: usize,
+    ) -> Option<usize> {
+        self_set
+            .iter()
+            .filter_map(|piece_limit| match piece_limit {
+                PieceLimit::TotalLimit { limit } => {
+                    Some(limit.saturating_sub(counts.values().sum()))
+                }
+                PieceLimit::TypeCountLimit { limits } => limits.get(&piece_index).map(|limit| {
+                    limit.saturating_sub(counts.get(&piece_index).copied().unwrap_or(0))
+                }),
+                PieceLimit::PointLimit {
+                    point_values,
+                    point_limit,
+                    ..
+                } => {
+                    let points = point_values.get(&piece_index).copied().unwrap_or(0);
+                    if points == 0 {
+                        return None;
+                    }
+                    let used: usize = counts
+                        .iter()
+                        .map(|(index, count)| point_values.get(index).copied().unwrap_or(0) * count)
+                        .sum();
+                    Some(point_limit.saturating_sub(used) / points)
+                }
+            })
+            .min()
+    }
+
+    /// Randomly picks piece counts that respect every limit, placing at most `capacity` pieces.
+    /// Maps from pieces index to count, pieces that were never picked are left out.
+    pub fn sample_counts(
+        self_set: &HashSet<Self>,
+        ruleset: &Ruleset,
+        capacity: usize,
+        rng: &mut impl GameRng,
+    ) -> HashMap<usize, usize> {
+        let mut counts = HashMap::new();
+        for _ in 0..capacity {
+            let candidates: Vec<usize> = (0..ruleset.pieces.len())
+                .filter(|&piece_index| Self::allows(self_set, &counts, piece_index))
+                .collect();
+            if candidates.is_empty() {
+                break;
+            }
+            let piece_index = candidates[rng.below(candidates.len())];
+            *counts.entry(piece_index).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+fn cost_of(square_costs: &HashMap<usize, HashSet<Coordinate>>, position: Coordinate) -> usize {
+    square_costs
+        .iter()
+        .find(|(_, squares)| squares.contains(&position))
+        .map_or(0, |(&cost, _)| cost)
 }
+
 impl Hash for PieceLimit {
     fn hash<H: Hasher>(&self, state: &mut H) {
         discriminant(self).hash(state);
@@ -98,10 +252,13 @@ pub enum PieceLimitError {
     LimitIs0ForPiece(PieceDefinition),
     PointsIs0ForPiece(PieceDefinition),
     PieceHasNoPointValue(PieceDefinition),
+    SquareCostOffBoard(Coordinate),
+    DuplicateSquareCost(Coordinate),
 }
 impl Display for PieceLimitError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for PieceLimitError {}
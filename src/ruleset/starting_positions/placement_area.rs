@@ -1,10 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
-use std::fmt::{Debug, Display, Formatter};
 
 use enum_iterator::IntoEnumIterator;
 
+use crate::collections::{HashMap, HashSet};
 use crate::coordinate::{flip_coordinate, rotate_coordinate, Coordinate};
 use crate::game_board::Color;
 use crate::ruleset::board_type::space::Space;
@@ -12,6 +13,8 @@ use crate::ruleset::board_type::BoardType;
 
 /// Placement area definition.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum PlacementArea {
     /// Players can place on half the board.
     Half,
@@ -37,7 +40,6 @@ impl PlacementArea {
                 } else {
                     rotate_coordinate
                 };
-                let mut found = positions.clone();
                 for &position in positions {
                     if position.row < 0
                         || position.row >= board.rows() as i16
@@ -49,8 +51,7 @@ impl PlacementArea {
                             position,
                         ));
                     }
-                    let opposite = func(board, position);
-                    if !found.insert(position) || found.insert(opposite) {
+                    if positions.contains(&func(board, position)) {
                         return Err(PlacementAreaError::PositionCollision(position));
                     }
                 }
@@ -72,6 +73,44 @@ impl PlacementArea {
         }
         Ok(())
     }
+
+    /// All normal spaces `color` may place on.
+    /// Mirrored sets define Red's side.
+    pub fn positions_for(&self, color: Color, board: &BoardType) -> HashSet<Coordinate> {
+        let positions: HashSet<Coordinate> = match self {
+            Self::Half => {
+                let half = board.rows() / 2;
+                let rows = match color {
+                    Color::Red => 0..half,
+                    Color::Blue => board.rows() - half..board.rows(),
+                };
+                rows.flat_map(|row| {
+                    (0..board.columns())
+                        .map(move |column| Coordinate::new(row as i16, column as i16))
+                })
+                .collect()
+            }
+            Self::MirroredFlipped(positions) | Self::MirroredRotated(positions) => match color {
+                Color::Red => positions.clone(),
+                Color::Blue => {
+                    let func = if let Self::MirroredFlipped(_) = self {
+                        flip_coordinate
+                    } else {
+                        rotate_coordinate
+                    };
+                    positions
+                        .iter()
+                        .map(|&position| func(board, position))
+                        .collect()
+                }
+            },
+            Self::NonMirrored(color_map) => color_map.get(&color).cloned().unwrap_or_default(),
+        };
+        positions
+            .into_iter()
+            .filter(|&position| board.get_space(position) == Space::Normal)
+            .collect()
+    }
 }
 pub type PlacementAreaResult<T> = Result<T, PlacementAreaError>;
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -85,4 +124,5 @@ impl Display for PlacementAreaError {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for PlacementAreaError {}
@@ -0,0 +1,650 @@
+//! Rule sheets generated from rulesets, see [`Ruleset::describe`].
+
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::collections::HashMap;
+use crate::direction::{Direction, Directions};
+use crate::game_board::Color;
+use crate::locale::{text, English, Locale};
+use crate::ruleset::board_type::BoardType;
+use crate::ruleset::game_length_rule::Tiebreak;
+use crate::ruleset::pass_rule::PassRule;
+use crate::ruleset::piece_definition::{
+    CaptureRequirement, CaptureRule, CaptureTarget, CaptureTimingRule, ChainContinuationRule,
+    GoalMovementRule, JumpLimit, JumpRule, MoveRule, PieceDefinition,
+};
+use crate::ruleset::stalemate_rule::StalemateRule;
+use crate::ruleset::starting_positions::alteration_type::AlternationType;
+use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+use crate::ruleset::starting_positions::placement_area::PlacementArea;
+use crate::ruleset::starting_positions::StartingPositions;
+use crate::ruleset::victory_condition::{PieceFilter, VictoryCondition};
+use crate::ruleset::Ruleset;
+
+/// The rules of a ruleset in prose, one sentence per rule.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RulesetSummary {
+    /// The board's size and shape.
+    pub board: String,
+    /// Where each color's goal is.
+    pub goals: String,
+    pub pieces: Vec<PieceSummary>,
+    /// How the pieces are set up before play.
+    pub setup: String,
+    /// One entry per victory condition, sorted.
+    pub victory: Vec<String>,
+    /// Stalemates, passing, scoring, and game length.
+    pub other: Vec<String>,
+}
+
+/// One piece definition in prose.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PieceSummary {
+    pub name: String,
+    pub movement: String,
+    pub jumps: String,
+    pub captures: String,
+}
+
+impl Ruleset {
+    /// The rules as a multi-line rule sheet.
+    pub fn describe(&self) -> String {
+        self.summary().to_string()
+    }
+    /// The rules in prose, see [`Ruleset::describe`].
+    pub fn summary(&self) -> RulesetSummary {
+        RulesetSummary {
+            board: board(&self.board_type),
+            goals: goals(&self.board_type),
+            pieces: self.pieces.iter().map(piece).collect(),
+            setup: setup(self),
+            victory: {
+                let mut victory: Vec<_> = self
+                    .victory_conditions
+                    .iter()
+                    .map(|condition| victory(self, condition))
+                    .collect();
+                victory.sort();
+                victory
+            },
+            other: other(self),
+        }
+    }
+}
+
+impl RulesetSummary {
+    /// The rule sheet with headings and piece names from `locale`, the rules themselves are
+    /// only in English.
+    pub fn describe_in(&self, locale: &dyn Locale) -> String {
+        let mut out = String::new();
+        self.write(&mut out, locale)
+            .expect("writing to a string cannot fail");
+        out
+    }
+
+    fn write(&self, f: &mut impl fmt::Write, locale: &dyn Locale) -> fmt::Result {
+        writeln!(f, "{}: {}", text(locale, "summary.board"), self.board)?;
+        writeln!(f, "{}: {}", text(locale, "summary.goals"), self.goals)?;
+        for piece in self.pieces.iter() {
+            writeln!(f, "{}:", locale.piece_name(&piece.name))?;
+            writeln!(f, "  {}", piece.movement)?;
+            writeln!(f, "  {}", piece.jumps)?;
+            writeln!(f, "  {}", piece.captures)?;
+        }
+        writeln!(f, "{}: {}", text(locale, "summary.setup"), self.setup)?;
+        writeln!(f, "{}:", text(locale, "summary.to_win"))?;
+        for victory in self.victory.iter() {
+            writeln!(f, "  {}", victory)?;
+        }
+        for other in self.other.iter() {
+            writeln!(f, "{}", other)?;
+        }
+        Ok(())
+    }
+}
+impl Display for RulesetSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write(f, &English)
+    }
+}
+
+fn board(board_type: &BoardType) -> String {
+    let (rows, columns) = (board_type.rows(), board_type.columns());
+    let mut out = match board_type {
+        BoardType::Rectangular {
+            rows, goal_depth, ..
+        } => format!(
+            "{} rows of {} columns between {} goal row{} on each side.",
+            rows,
+            columns,
+            goal_depth,
+            plural(*goal_depth as usize)
+        ),
+        BoardType::Squares { .. } => format!("{} rows of {} columns.", rows, columns),
+        BoardType::Custom(_) => format!(
+            "A custom shape within {} rows of {} columns.",
+            rows, columns
+        ),
+    };
+    if board_type.has_terrain() {
+        out += " Some squares are blocked, sticky, or portals.";
+    }
+    out
+}
+
+fn goals(board_type: &BoardType) -> String {
+    if !board_type.has_goal() {
+        return "There are no goals.".to_string();
+    }
+    let squares = |color| {
+        let mut squares = board_type.goal_spaces(color);
+        squares.sort();
+        squares
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    format!(
+        "Red's goal is {} and Blue's is {}, each color attacking the opponent's.",
+        squares(Color::Red),
+        squares(Color::Blue)
+    )
+}
+
+fn piece(piece: &PieceDefinition) -> PieceSummary {
+    let movement = match piece.move_rule {
+        MoveRule::SameDirection {
+            limit: usize::MAX,
+            directions,
+        } => format!(
+            "Moves any number of squares in a straight line {}.",
+            directions_prose(directions)
+        ),
+        MoveRule::SameDirection { limit, directions } => format!(
+            "Moves up to {} square{} in a straight line {}.",
+            limit,
+            plural(limit),
+            directions_prose(directions)
+        ),
+        MoveRule::AnyDirection {
+            limit: 1,
+            directions,
+        } => {
+            format!("Moves one square {}.", directions_prose(directions))
+        }
+        MoveRule::AnyDirection { limit, directions } => format!(
+            "Moves up to {} squares, turning freely, {}.",
+            limit,
+            directions_prose(directions)
+        ),
+        MoveRule::None => "Cannot move without jumping.".to_string(),
+    };
+    let movement = match piece.goal_move_rule {
+        GoalMovementRule::Locked => movement + " Cannot leave a goal once in one.",
+        GoalMovementRule::OnlyToGoal => movement + " Only moves between goal squares once in one.",
+        GoalMovementRule::Free => movement,
+        GoalMovementRule::NoOwnGoal => movement + " Cannot enter its own goal.",
+    };
+    let movement = match piece.owner {
+        Some(owner) => format!("Only {} has this piece. {}", color_name(owner), movement),
+        None => movement,
+    };
+
+    let chain = match piece.jump_limit {
+        JumpLimit::Unlimited { directions } => {
+            Some(("any number of jumps".to_string(), directions))
+        }
+        JumpLimit::Limited {
+            limit: 1,
+            directions,
+        } => Some(("one jump".to_string(), directions)),
+        JumpLimit::Limited { limit, directions } => {
+            Some((format!("up to {} jumps", limit), directions))
+        }
+        JumpLimit::Cannot => None,
+    };
+    let jumps = match chain {
+        Some((count, directions)) => {
+            let rule = match piece.jump_rule {
+                JumpRule::NoBacktracking => " without landing on a square twice",
+                JumpRule::NoSameStart => " without ending where it started",
+                JumpRule::Open => "",
+            };
+            let continuation = match piece.chain_rule {
+                ChainContinuationRule::MayStop => "",
+                ChainContinuationRule::MustCapture => " It keeps jumping while it can capture.",
+                ChainContinuationRule::MustContinue => " It keeps jumping while it can.",
+            };
+            format!(
+                "Jumps over adjacent pieces {}, chaining {} a turn{}.{}",
+                directions_prose(directions),
+                count,
+                rule,
+                continuation
+            )
+        }
+        None => "Cannot jump.".to_string(),
+    };
+
+    let mut rules: Vec<_> = piece
+        .capture_rules
+        .iter()
+        .map(|(rule, target)| {
+            let rule = match rule {
+                CaptureRule::JumpOver => "jumping over",
+                CaptureRule::JumpOn => "jumping onto",
+                CaptureRule::Move => "moving onto",
+            };
+            let target = match target {
+                CaptureTarget::EnemyOnly => "enemy pieces",
+                CaptureTarget::OwnOnly => "its own pieces",
+                CaptureTarget::All => "any piece",
+            };
+            format!("{} by {}", target, rule)
+        })
+        .collect();
+    rules.sort();
+    let captures = if rules.is_empty() {
+        "Cannot capture.".to_string()
+    } else {
+        let timing = match piece.capture_timing_rule {
+            CaptureTimingRule::AfterJump => "as they are jumped",
+            CaptureTimingRule::AfterTurn => "at the end of the turn",
+        };
+        let requirement = match piece.capture_requirement {
+            CaptureRequirement::Forced(priority) => {
+                format!(" Capturing is forced, with priority {}.", priority)
+            }
+            CaptureRequirement::None => String::new(),
+            CaptureRequirement::Huff => {
+                " Ignoring a capture lets the opponent remove it.".to_string()
+            }
+        };
+        format!(
+            "Captures {}, removed {}.{}",
+            rules.join(" and "),
+            timing,
+            requirement
+        )
+    };
+
+    PieceSummary {
+        name: piece.name.clone(),
+        movement,
+        jumps,
+        captures,
+    }
+}
+
+fn setup(ruleset: &Ruleset) -> String {
+    let name = |index: usize| piece_name(ruleset, index);
+    let counts = |counts: &mut dyn Iterator<Item = (usize, usize)>| {
+        let mut counts: Vec<_> = counts.collect();
+        counts.sort_unstable();
+        counts
+            .into_iter()
+            .map(|(index, count)| format!("{} {}", count, name(index)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let fixed = |positions: &HashMap<usize, Vec<_>>| {
+        counts(
+            &mut positions
+                .iter()
+                .map(|(&index, positions)| (index, positions.len())),
+        )
+    };
+    match &ruleset.starting_positions {
+        StartingPositions::MirroredFlipped(positions) => format!(
+            "Each color starts with {} on fixed squares, Blue's mirroring Red's across the middle.",
+            fixed(positions)
+        ),
+        StartingPositions::MirroredRotated(positions) => format!(
+            "Each color starts with {} on fixed squares, Blue's rotating Red's about the center.",
+            fixed(positions)
+        ),
+        StartingPositions::NotMirrored(positions) => format!(
+            "Red starts with {} and Blue with {} on fixed squares.",
+            positions.get(&Color::Red).map(fixed).unwrap_or_default(),
+            positions.get(&Color::Blue).map(fixed).unwrap_or_default()
+        ),
+        StartingPositions::Random {
+            seed,
+            piece_counts,
+            area,
+        } => {
+            let counts_for = |color: Color| {
+                counts(
+                    &mut piece_counts
+                        .iter()
+                        .map(|(&index, &count)| (index, count))
+                        .filter(|&(index, _)| {
+                            ruleset
+                                .get_piece(index)
+                                .is_some_and(|piece| piece.is_available_to(color))
+                        }),
+                )
+            };
+            let (red, blue) = (counts_for(Color::Red), counts_for(Color::Blue));
+            if red == blue {
+                format!(
+                    "Each color starts with {} placed randomly from seed {} {}.",
+                    red,
+                    seed,
+                    area_prose(area)
+                )
+            } else {
+                format!(
+                    "Red starts with {} and Blue with {} placed randomly from seed {} {}.",
+                    red,
+                    blue,
+                    seed,
+                    area_prose(area)
+                )
+            }
+        }
+        StartingPositions::Placement {
+            first_color,
+            alternation_type,
+            placement_area,
+            piece_limits,
+        } => {
+            let turns = match alternation_type {
+                AlternationType::TurnsCount { per_turn_count } => {
+                    format!(
+                        "taking turns of {} piece{}",
+                        per_turn_count,
+                        plural(*per_turn_count)
+                    )
+                }
+                AlternationType::TurnsPoints {
+                    per_turn_points, ..
+                } => format!("taking turns of {} points", per_turn_points),
+                AlternationType::Points => "the color with fewer points placing next".to_string(),
+                AlternationType::WholePlacement => "each placing a whole side in turn".to_string(),
+                AlternationType::Hidden => "hidden from each other".to_string(),
+            };
+            let mut limits: Vec<String> = piece_limits
+                .iter()
+                .map(|limit| match limit {
+                    PieceLimit::TotalLimit { limit } => format!("at most {} pieces", limit),
+                    PieceLimit::TypeCountLimit { limits } => format!(
+                        "at most {}",
+                        counts(&mut limits.iter().map(|(&index, &count)| (index, count)))
+                    ),
+                    PieceLimit::PointLimit {
+                        point_values,
+                        point_limit,
+                        square_costs,
+                    } => {
+                        let mut values: Vec<_> = point_values.iter().collect();
+                        values.sort_unstable();
+                        let values = values
+                            .into_iter()
+                            .map(|(&index, points)| format!("{} costs {}", name(index), points))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let squares = if square_costs.is_empty() {
+                            ""
+                        } else {
+                            ", some squares cost extra"
+                        };
+                        format!("{} points where {}{}", point_limit, values, squares)
+                    }
+                })
+                .collect();
+            limits.sort();
+            format!(
+                "Players place pieces {} before play, {} first and {}. Each color may place {}.",
+                area_prose(placement_area),
+                color_name(*first_color),
+                turns,
+                limits.join(" and ")
+            )
+        }
+    }
+}
+
+fn victory(ruleset: &Ruleset, condition: &VictoryCondition) -> String {
+    let filter = |piece_filter: &PieceFilter| match piece_filter {
+        PieceFilter::Any => "pieces".to_string(),
+        PieceFilter::Only(indices) => names(ruleset, indices),
+        PieceFilter::Except(indices) => format!("pieces other than {}", names(ruleset, indices)),
+    };
+    match condition {
+        VictoryCondition::GoalCount {
+            amount,
+            valid_pieces,
+        } => format!(
+            "Occupy {} of the opponent's goal squares with {}.",
+            amount,
+            names(ruleset, valid_pieces)
+        ),
+        VictoryCondition::AllCaptured => "Capture every opposing piece.".to_string(),
+        VictoryCondition::PointDifference(difference) => {
+            format!("Have {} more pieces than the opponent.", difference)
+        }
+        VictoryCondition::PiecesInGoal {
+            count,
+            piece_filter,
+        } => format!(
+            "Have {} {} in the opponent's goal at once.",
+            count,
+            filter(piece_filter)
+        ),
+        VictoryCondition::CaptureCount {
+            count,
+            piece_filter,
+        } => format!("Have {} opposing {} captured.", count, filter(piece_filter)),
+        VictoryCondition::ScoreAtLeast(points) => format!("Score {} points.", points),
+        VictoryCondition::All(conditions) | VictoryCondition::Any(conditions) => {
+            let joiner = match condition {
+                VictoryCondition::All(_) => " and ",
+                _ => " or ",
+            };
+            let parts: Vec<String> = conditions
+                .iter()
+                .map(|condition| {
+                    let part = victory(ruleset, condition);
+                    format!("({})", part.trim_end_matches('.'))
+                })
+                .collect();
+            parts.join(joiner) + "."
+        }
+    }
+}
+
+fn other(ruleset: &Ruleset) -> Vec<String> {
+    let mut out = Vec::new();
+    out.push(
+        match ruleset.stalemate_rule {
+            StalemateRule::Lose => "A color without a legal action loses.",
+            StalemateRule::Draw => "A color without a legal action draws.",
+            StalemateRule::Pass => "A color without a legal action passes.",
+        }
+        .to_string(),
+    );
+    match ruleset.pass_rule {
+        PassRule::Never => {}
+        PassRule::WhenBlocked => out.push("Passing is allowed only when blocked.".to_string()),
+        PassRule::Always => out.push("Passing is always allowed.".to_string()),
+    }
+    if !ruleset.scoring.is_empty() {
+        let points = |points: &[usize]| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(_, &points)| points > 0)
+                .map(|(index, points)| format!("{} for {}", points, piece_name(ruleset, index)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut scoring = Vec::new();
+        if ruleset
+            .scoring
+            .capture_points
+            .iter()
+            .any(|&points| points > 0)
+        {
+            scoring.push(format!(
+                "capturing scores {}",
+                points(&ruleset.scoring.capture_points)
+            ));
+        }
+        if ruleset
+            .scoring
+            .goal_entry_points
+            .iter()
+            .any(|&points| points > 0)
+        {
+            scoring.push(format!(
+                "entering the opponent's goal scores {}",
+                points(&ruleset.scoring.goal_entry_points)
+            ));
+        }
+        out.push(format!("Points: {}.", scoring.join(", ")));
+    }
+    if let Some(max_turns) = ruleset.game_length_rule.max_turns {
+        let tiebreak = match ruleset.game_length_rule.tiebreak {
+            Tiebreak::Draw => "the game is drawn",
+            Tiebreak::MostPieces => "the color with more pieces wins",
+            Tiebreak::MostPoints => "the color with more points wins",
+            Tiebreak::ClosestToGoal => "the color closest to the opponent's goal wins",
+        };
+        out.push(format!("After {} actions {}.", max_turns, tiebreak));
+    }
+    if ruleset.chance_rule.is_enabled() {
+        let faces: Vec<String> = ruleset
+            .chance_rule
+            .faces
+            .iter()
+            .map(|face| {
+                let pieces = match &face.pieces {
+                    Some(pieces) => names(ruleset, pieces),
+                    None => "any piece".to_string(),
+                };
+                match face.directions {
+                    Some(directions) => format!("{} {}", pieces, directions_prose(directions)),
+                    None => pieces,
+                }
+            })
+            .collect();
+        out.push(format!(
+            "A die is rolled each turn, its faces let act: {}. Rolls that allow no action are ignored.",
+            faces.join("; ")
+        ));
+    }
+    out
+}
+
+fn directions_prose(directions: Directions) -> String {
+    if directions == Directions::ALL {
+        return "in any direction".to_string();
+    }
+    if directions == Directions::CARDINAL {
+        return "orthogonally".to_string();
+    }
+    if directions == Directions::DIAGONAL {
+        return "diagonally".to_string();
+    }
+    let names: Vec<&str> = directions
+        .into_iter()
+        .map(|direction| match direction {
+            Direction::North => "north",
+            Direction::South => "south",
+            Direction::East => "east",
+            Direction::West => "west",
+            Direction::NorthWest => "north west",
+            Direction::NorthEast => "north east",
+            Direction::SouthWest => "south west",
+            Direction::SouthEast => "south east",
+        })
+        .collect();
+    format!("to the {}", names.join(", "))
+}
+fn area_prose(area: &PlacementArea) -> &'static str {
+    match area {
+        PlacementArea::Half => "in their half of the board",
+        _ => "on their placement squares",
+    }
+}
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Red => "Red",
+        Color::Blue => "Blue",
+    }
+}
+fn piece_name(ruleset: &Ruleset, index: usize) -> String {
+    match ruleset.get_piece(index) {
+        Some(piece) => piece.name.clone(),
+        None => format!("piece {}", index),
+    }
+}
+fn names(ruleset: &Ruleset, indices: &[usize]) -> String {
+    indices
+        .iter()
+        .map(|&index| piece_name(ruleset, index))
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use enum_iterator::IntoEnumIterator;
+
+    use crate::ruleset::presets::Preset;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn describe_test() {
+        let summary = small_ruleset(0).summary();
+        assert_eq!(summary.pieces.len(), 2);
+        assert_eq!(summary.pieces[0].name, "Big");
+        assert_eq!(
+            summary.pieces[0].movement,
+            "Moves one square in any direction."
+        );
+        assert_eq!(
+            summary.pieces[1].jumps,
+            "Jumps over adjacent pieces in any direction, chaining one jump a turn \
+             without ending where it started."
+        );
+        assert_eq!(
+            summary.pieces[1].captures,
+            "Captures enemy pieces by jumping over, removed at the end of the turn. \
+             Capturing is forced, with priority 10."
+        );
+        assert!(summary.board.starts_with("6 rows of 6 columns"));
+        assert!(summary.setup.contains("2 Big, 3 Little"));
+        assert_eq!(summary.victory, vec!["Capture every opposing piece."]);
+
+        for preset in Preset::into_enum_iter() {
+            let text = preset.ruleset().unwrap().describe();
+            assert!(text.contains("To win:"), "{}", text);
+        }
+        let hex = Preset::Hex.ruleset().unwrap().summary();
+        assert!(hex.board.starts_with("A custom shape"));
+        assert!(hex.pieces[0]
+            .movement
+            .contains("to the north, south, east, west, north east, south west"));
+        let placement = Preset::PointsPlacement.ruleset().unwrap().summary();
+        assert!(placement
+            .setup
+            .contains("12 points where Big costs 3, Little costs 1"));
+    }
+}
@@ -0,0 +1,168 @@
+//! Verification reporting every problem in a ruleset, see [`Ruleset::verify_with_context`].
+
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::collections::HashSet;
+use crate::game_board::MAX_PIECE_DEFINITIONS;
+use crate::ruleset::{Ruleset, RulesetError, RulesetResult};
+
+/// Every problem found in a ruleset.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+    /// The first issue's error, the one [`Ruleset::verify`] would return.
+    pub fn into_result(self) -> RulesetResult<()> {
+        match self.issues.into_iter().next() {
+            Some(issue) => Err(issue.error),
+            None => Ok(()),
+        }
+    }
+}
+impl Display for ValidationReport {
+    /// One issue per line.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for issue in self.issues.iter() {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem and where in the ruleset it is.
+#[derive(Clone, Debug)]
+pub struct ValidationIssue {
+    /// The offending element as a field path such as `pieces[2].jump_limit`.
+    pub path: String,
+    pub error: RulesetError,
+}
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.error)
+    }
+}
+
+impl Ruleset {
+    /// Every problem [`Ruleset::verify`] checks for with the path to each.
+    ///
+    /// Starting positions are only checked on a valid board.
+    pub fn verify_with_context(&self) -> ValidationReport {
+        let mut out = ValidationReport::default();
+        let mut push =
+            |path: String, error: RulesetError| out.issues.push(ValidationIssue { path, error });
+        if self.pieces.len() > MAX_PIECE_DEFINITIONS {
+            push(
+                "pieces".to_string(),
+                RulesetError::TooManyPieces(self.pieces.len()),
+            );
+        }
+        let mut pieces_set = HashSet::with_capacity(self.pieces.len());
+        for (index, piece) in self.pieces.iter().enumerate() {
+            for error in piece.errors(Some(&self.board_type)) {
+                push(format!("pieces[{}].{}", index, error.field()), error.into());
+            }
+            if !pieces_set.insert(piece) {
+                push(
+                    format!("pieces[{}].name", index),
+                    RulesetError::PieceDuplicated(piece.clone()),
+                );
+            }
+        }
+        match self.board_type.verify() {
+            Ok(()) => {
+                if let Err(error) = self.starting_positions.verify(&self.board_type, self) {
+                    push("starting_positions".to_string(), error.into());
+                }
+            }
+            Err(error) => push("board_type".to_string(), error.into()),
+        }
+        if let Err(error) = self.scoring.verify(self) {
+            push("scoring".to_string(), error.into());
+        }
+        if let Err(error) = self.chance_rule.verify(self) {
+            push("chance_rule".to_string(), error.into());
+        }
+        let mut victory_conditions: Vec<_> = self.victory_conditions.iter().collect();
+        victory_conditions.sort_by_cached_key(|condition| format!("{:?}", condition));
+        for condition in victory_conditions {
+            if let Err(error) = condition.verify(self) {
+                push(format!("victory_conditions[{:?}]", condition), error.into());
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collections::HashMap;
+    use crate::direction::Directions;
+    use crate::ruleset::board_type::goal_squares::GoalSquares;
+    use crate::ruleset::piece_definition::{
+        CaptureRequirement, JumpLimit, MoveRule, PieceDefinitionError,
+    };
+    use crate::ruleset::victory_condition::VictoryCondition;
+    use crate::ruleset::{BoardType, RulesetError};
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn report_test() {
+        let mut ruleset = small_ruleset(0);
+        assert!(ruleset.verify_with_context().is_valid());
+
+        ruleset.pieces[1].move_rule = MoveRule::AnyDirection {
+            limit: 0,
+            directions: Directions::ALL,
+        };
+        ruleset.pieces[1].jump_limit = JumpLimit::Cannot;
+        ruleset.pieces[1].capture_requirement = CaptureRequirement::None;
+        ruleset.pieces.push(ruleset.pieces[0].clone());
+        ruleset
+            .victory_conditions
+            .insert(VictoryCondition::ScoreAtLeast(0));
+        let paths: Vec<_> = ruleset
+            .verify_with_context()
+            .issues
+            .into_iter()
+            .map(|issue| issue.path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "pieces[1].move_rule",
+                "pieces[1].capture_rules",
+                "pieces[2].name",
+                "victory_conditions[ScoreAtLeast(0)]",
+            ]
+        );
+        assert!(matches!(
+            ruleset.verify_with_context().into_result(),
+            Err(RulesetError::PieceDefinitionError(
+                PieceDefinitionError::MoveRuleError(_)
+            ))
+        ));
+
+        ruleset.board_type = BoardType::Squares {
+            rows: 0,
+            columns: 6,
+            goals: GoalSquares::NonMirrored(HashMap::new()),
+        };
+        let report = ruleset.verify_with_context();
+        assert!(report.issues.iter().any(|issue| issue.path == "board_type"
+            && matches!(issue.error, RulesetError::BoardTypeVerifyError(_))));
+        assert!(report
+            .issues
+            .iter()
+            .all(|issue| issue.path != "starting_positions"));
+        assert!(report.to_string().contains("board_type: "));
+    }
+}
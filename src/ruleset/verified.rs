@@ -0,0 +1,114 @@
+//! Rulesets known to have passed verification.
+
+use core::convert::TryFrom;
+use core::ops::Deref;
+
+use alloc::sync::Arc;
+
+use crate::ruleset::{Ruleset, RulesetError, RulesetResult};
+
+/// A [`Ruleset`] that passed verification, only constructed by verifying one.
+///
+/// Deserializing verifies as well.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Ruleset", into = "Ruleset")
+)]
+pub struct VerifiedRuleset(Ruleset);
+impl VerifiedRuleset {
+    pub fn new(ruleset: Ruleset) -> RulesetResult<Self> {
+        ruleset.verify()?;
+        Ok(Self(ruleset))
+    }
+    /// The ruleset back for editing, it must be verified again to be used.
+    pub fn into_inner(self) -> Ruleset {
+        self.0
+    }
+}
+impl Deref for VerifiedRuleset {
+    type Target = Ruleset;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl AsRef<Ruleset> for VerifiedRuleset {
+    fn as_ref(&self) -> &Ruleset {
+        &self.0
+    }
+}
+impl TryFrom<Ruleset> for VerifiedRuleset {
+    type Error = RulesetError;
+
+    fn try_from(ruleset: Ruleset) -> Result<Self, Self::Error> {
+        Self::new(ruleset)
+    }
+}
+impl From<VerifiedRuleset> for Ruleset {
+    fn from(from: VerifiedRuleset) -> Self {
+        from.0
+    }
+}
+
+/// A shared [`VerifiedRuleset`], cheap to clone and send between threads.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RulesetHandle(Arc<VerifiedRuleset>);
+impl RulesetHandle {
+    /// Verifies `ruleset` and shares it.
+    pub fn new(ruleset: Ruleset) -> RulesetResult<Self> {
+        VerifiedRuleset::new(ruleset).map(Self::from)
+    }
+    /// Whether both handles share the same ruleset, not just equal ones.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl Deref for RulesetHandle {
+    type Target = VerifiedRuleset;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl AsRef<Ruleset> for RulesetHandle {
+    fn as_ref(&self) -> &Ruleset {
+        &self.0
+    }
+}
+impl From<VerifiedRuleset> for RulesetHandle {
+    fn from(from: VerifiedRuleset) -> Self {
+        Self(Arc::new(from))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::Game;
+    use crate::ruleset::verified::{RulesetHandle, VerifiedRuleset};
+    use crate::ruleset::RulesetError;
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn handle_test() {
+        fn shareable<T: Send + Sync>(_: &T) {}
+
+        let handle = RulesetHandle::new(small_ruleset(0)).unwrap();
+        shareable(&handle);
+        let game = Game::from_handle(handle.clone()).unwrap();
+        let copy = game.clone();
+        assert!(copy.ruleset_handle().ptr_eq(&handle));
+        assert_eq!(*game.ruleset(), small_ruleset(0));
+        assert!(!RulesetHandle::new(small_ruleset(0))
+            .unwrap()
+            .ptr_eq(&handle));
+
+        let mut broken = small_ruleset(0);
+        broken.pieces.push(broken.pieces[0].clone());
+        assert!(matches!(
+            VerifiedRuleset::new(broken),
+            Err(RulesetError::PieceDuplicated(_))
+        ));
+    }
+}
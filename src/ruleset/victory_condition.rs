@@ -1,17 +1,27 @@
 use core::cmp::{Eq, PartialEq};
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
 use core::hash::{Hash, Hasher};
 use core::mem::discriminant;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
-use std::fmt::{Debug, Display, Formatter};
-use std::vec::Vec;
 
+use alloc::vec;
+use alloc::vec::Vec;
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::game_board::board::Board;
+use crate::game_board::{Color, GameBoard, PieceInstance};
+use crate::ruleset::scoring::Scores;
 use crate::ruleset::Ruleset;
 
 /// How the game is won.
 ///
 /// Hash, Eq, and PartialEq are based on the discriminate.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
 pub enum VictoryCondition {
     /// Victory can be achieved by having a certain number of goals owned by pieces.
     /// Condition becomes impossible if player has less than amount pieces left.
@@ -22,9 +32,30 @@ pub enum VictoryCondition {
         valid_pieces: Vec<usize>,
     },
     /// Victory can be achieved by capturing all of your opponents pieces.
+    #[cfg_attr(feature = "serde", serde(alias = "CaptureAll"))]
     AllCaptured,
     /// Victory can be achieved by having a non-captured point difference.
     PointDifference(usize),
+    /// Victory can be achieved by having `count` pieces in the opponent's goal at once.
+    /// Will error if either goal has fewer than `count` spaces.
+    PiecesInGoal {
+        count: usize,
+        /// Pieces that count towards `count`.
+        piece_filter: PieceFilter,
+    },
+    /// Victory can be achieved by `count` of your opponent's pieces having been captured.
+    CaptureCount {
+        count: usize,
+        /// Captured pieces that count towards `count`.
+        piece_filter: PieceFilter,
+    },
+    /// Victory can be achieved by scoring at least this many points under the ruleset's
+    /// [`Scoring`](crate::ruleset::scoring::Scoring).
+    ScoreAtLeast(usize),
+    /// Victory can be achieved by meeting every one of these conditions at once.
+    All(Vec<VictoryCondition>),
+    /// Victory can be achieved by meeting any one of these conditions.
+    Any(Vec<VictoryCondition>),
 }
 impl VictoryCondition {
     pub fn verify(&self, ruleset: &Ruleset) -> VictoryConditionResult<()> {
@@ -40,10 +71,9 @@ impl VictoryCondition {
                     return Err(VictoryConditionError::NoValidPieces);
                 }
                 for &piece_index in valid_pieces {
-                    match ruleset.get_piece(piece_index) {
-                        None => return Err(VictoryConditionError::PieceNotFound(piece_index)),
-                        Some(_) => {}
-                    };
+                    if ruleset.get_piece(piece_index).is_none() {
+                        return Err(VictoryConditionError::PieceNotFound(piece_index));
+                    }
                 }
                 if !ruleset.board_type.has_goal() {
                     return Err(VictoryConditionError::BoardHasNoGoal);
@@ -55,9 +85,200 @@ impl VictoryCondition {
                     return Err(VictoryConditionError::PointDifferenceIs0);
                 }
             }
+            VictoryCondition::PiecesInGoal {
+                count,
+                piece_filter,
+            } => {
+                if *count == 0 {
+                    return Err(VictoryConditionError::AmountIs0);
+                }
+                piece_filter.verify(ruleset)?;
+                if !ruleset.board_type.has_goal() {
+                    return Err(VictoryConditionError::BoardHasNoGoal);
+                }
+                let board = GameBoard::from_board_type(&ruleset.board_type);
+                for color in Color::into_enum_iter() {
+                    let goals = board.goals_of_color(color).len();
+                    if goals < *count {
+                        return Err(VictoryConditionError::CountExceedsGoals {
+                            goal_for: color,
+                            goals,
+                        });
+                    }
+                }
+            }
+            VictoryCondition::CaptureCount {
+                count,
+                piece_filter,
+            } => {
+                if *count == 0 {
+                    return Err(VictoryConditionError::AmountIs0);
+                }
+                piece_filter.verify(ruleset)?;
+            }
+            VictoryCondition::ScoreAtLeast(points) => {
+                if *points == 0 {
+                    return Err(VictoryConditionError::AmountIs0);
+                }
+                if ruleset.scoring.is_empty() {
+                    return Err(VictoryConditionError::NoScoring);
+                }
+            }
+            VictoryCondition::All(conditions) | VictoryCondition::Any(conditions) => {
+                if conditions.is_empty() {
+                    return Err(VictoryConditionError::EmptyComposite);
+                }
+                for condition in conditions {
+                    condition.verify(ruleset)?;
+                }
+            }
         }
         Ok(())
     }
+
+    /// Every condition that is not [`VictoryCondition::All`] or [`VictoryCondition::Any`]
+    /// inside this one, in evaluation order.
+    pub fn leaves(&self) -> Vec<&VictoryCondition> {
+        match self {
+            VictoryCondition::All(conditions) | VictoryCondition::Any(conditions) => conditions
+                .iter()
+                .flat_map(VictoryCondition::leaves)
+                .collect(),
+            condition => vec![condition],
+        }
+    }
+
+    /// Whether `color` has achieved this condition on `board` before any captures or points.
+    pub fn is_met(&self, board: &GameBoard, color: Color) -> bool {
+        self.is_met_in(&VictoryState::new(board), color)
+    }
+    /// Whether `color` has achieved this condition in `state`.
+    ///
+    /// Goals are occupied by entering the opponent's goal spaces.
+    /// Each piece counts as one point for [`VictoryCondition::PointDifference`].
+    /// Captures count for [`VictoryCondition::CaptureCount`] whoever made them.
+    /// Composites check their conditions in order and stop at the first that decides them.
+    pub fn is_met_in(&self, state: &VictoryState<'_, impl Board>, color: Color) -> bool {
+        let board = state.board;
+        match self {
+            VictoryCondition::GoalCount {
+                amount,
+                valid_pieces,
+            } => {
+                let occupied = board
+                    .goals_of_color(color.opponent())
+                    .into_iter()
+                    .filter_map(|(_, piece)| piece)
+                    .filter(|piece| {
+                        piece.color() == color && valid_pieces.contains(&piece.definition_index())
+                    })
+                    .count();
+                occupied >= *amount
+            }
+            VictoryCondition::AllCaptured => board.pieces_of_color(color.opponent()).is_empty(),
+            VictoryCondition::PointDifference(difference) => {
+                let own = board.pieces_of_color(color).len();
+                let opponent = board.pieces_of_color(color.opponent()).len();
+                own >= opponent + difference
+            }
+            VictoryCondition::PiecesInGoal {
+                count,
+                piece_filter,
+            } => {
+                let occupied = board
+                    .goals_of_color(color.opponent())
+                    .into_iter()
+                    .filter_map(|(_, piece)| piece)
+                    .filter(|piece| {
+                        piece.color() == color && piece_filter.matches(piece.definition_index())
+                    })
+                    .count();
+                occupied >= *count
+            }
+            VictoryCondition::CaptureCount {
+                count,
+                piece_filter,
+            } => {
+                let captured = state
+                    .captured
+                    .iter()
+                    .filter(|piece| {
+                        piece.color() != color && piece_filter.matches(piece.definition_index())
+                    })
+                    .count();
+                captured >= *count
+            }
+            VictoryCondition::ScoreAtLeast(points) => state.scores.color(color) >= *points,
+            VictoryCondition::All(conditions) => conditions
+                .iter()
+                .all(|condition| condition.is_met_in(state, color)),
+            VictoryCondition::Any(conditions) => conditions
+                .iter()
+                .any(|condition| condition.is_met_in(state, color)),
+        }
+    }
+}
+
+/// What [`VictoryCondition::is_met_in`] checks.
+#[derive(Debug)]
+pub struct VictoryState<'a, B = GameBoard> {
+    pub board: &'a B,
+    /// Every piece captured so far in the game.
+    pub captured: &'a [PieceInstance],
+    pub scores: Scores,
+}
+impl<B> Copy for VictoryState<'_, B> {}
+impl<B> Clone for VictoryState<'_, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'a, B> VictoryState<'a, B> {
+    /// `board` without any captures or points.
+    pub fn new(board: &'a B) -> Self {
+        Self {
+            board,
+            captured: &[],
+            scores: Scores::default(),
+        }
+    }
+}
+
+/// Which pieces a [`VictoryCondition`] counts.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "formats", derive(schemars::JsonSchema))]
+pub enum PieceFilter {
+    Any,
+    /// Only pieces of these definition indices.
+    Only(Vec<usize>),
+    /// Every piece but those of these definition indices.
+    Except(Vec<usize>),
+}
+impl PieceFilter {
+    pub fn verify(&self, ruleset: &Ruleset) -> VictoryConditionResult<()> {
+        let indices = match self {
+            PieceFilter::Any => return Ok(()),
+            PieceFilter::Only(indices) | PieceFilter::Except(indices) => indices,
+        };
+        for &piece_index in indices {
+            if ruleset.get_piece(piece_index).is_none() {
+                return Err(VictoryConditionError::PieceNotFound(piece_index));
+            }
+        }
+        if !(0..ruleset.pieces.len()).any(|index| self.matches(index)) {
+            return Err(VictoryConditionError::NoValidPieces);
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, definition_index: usize) -> bool {
+        match self {
+            PieceFilter::Any => true,
+            PieceFilter::Only(indices) => indices.contains(&definition_index),
+            PieceFilter::Except(indices) => !indices.contains(&definition_index),
+        }
+    }
 }
 impl Hash for VictoryCondition {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -79,10 +300,233 @@ pub enum VictoryConditionError {
     BoardHasNoGoal,
     PieceNotFound(usize),
     PointDifferenceIs0,
+    /// The ruleset's scoring gives no points to reach.
+    NoScoring,
+    /// A [`VictoryCondition::All`] or [`VictoryCondition::Any`] without conditions.
+    EmptyComposite,
+    /// The goal of `goal_for` has fewer spaces than the pieces needed in it.
+    CountExceedsGoals {
+        goal_for: Color,
+        goals: usize,
+    },
 }
 impl Display for VictoryConditionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         <Self as Debug>::fmt(self, f)
     }
 }
+#[cfg(feature = "std")]
 impl Error for VictoryConditionError {}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use crate::collections::HashSet;
+    use crate::coordinate::Coordinate;
+    use crate::game::playout::{Policy, RandomPolicy};
+    use crate::game::Game;
+    use crate::game_board::board::Board;
+    use crate::game_board::{Color, GameBoard, PieceInstance};
+    use crate::ruleset::board_type::goal_squares::GoalSquares;
+    use crate::ruleset::board_type::BoardType;
+    use crate::ruleset::scoring::Scores;
+    use crate::ruleset::victory_condition::{
+        PieceFilter, VictoryCondition, VictoryConditionError, VictoryState,
+    };
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn goal_count_depth_test() {
+        let board_type = BoardType::Rectangular {
+            rows: 2,
+            columns: 2,
+            goal_depth: 2,
+            red_goals: [0, 1].iter().cloned().collect::<HashSet<_>>().into(),
+            blue_goals: [0, 1].iter().cloned().collect::<HashSet<_>>().into(),
+        };
+        let mut board = GameBoard::from_board_type(&board_type);
+        let condition = VictoryCondition::GoalCount {
+            amount: 2,
+            valid_pieces: vec![0, 1],
+        };
+        *board.piece_mut(Coordinate::new(0, 0)).unwrap() = Some(PieceInstance::new(1, Color::Blue));
+        assert!(!condition.is_met(&board, Color::Blue));
+        *board.piece_mut(Coordinate::new(1, 1)).unwrap() = Some(PieceInstance::new(0, Color::Blue));
+        assert!(condition.is_met(&board, Color::Blue));
+        assert!(!condition.is_met(&board, Color::Red));
+        assert!(VictoryCondition::AllCaptured.is_met(&board, Color::Blue));
+    }
+
+    #[test]
+    fn goal_count_squares_test() {
+        let board_type = BoardType::Squares {
+            rows: 4,
+            columns: 4,
+            goals: GoalSquares::MirroredFlipped(
+                [Coordinate::new(1, 0), Coordinate::new(1, 3)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+        };
+        let mut board = GameBoard::from_board_type(&board_type);
+        let condition = VictoryCondition::GoalCount {
+            amount: 2,
+            valid_pieces: vec![0],
+        };
+        *board.piece_mut(Coordinate::new(1, 0)).unwrap() = Some(PieceInstance::new(0, Color::Blue));
+        *board.piece_mut(Coordinate::new(2, 3)).unwrap() = Some(PieceInstance::new(0, Color::Blue));
+        assert!(!condition.is_met(&board, Color::Blue));
+        *board.piece_mut(Coordinate::new(1, 3)).unwrap() = Some(PieceInstance::new(0, Color::Blue));
+        assert!(condition.is_met(&board, Color::Blue));
+        *board.piece_mut(Coordinate::new(2, 0)).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        assert!(!condition.is_met(&board, Color::Red));
+    }
+
+    #[test]
+    fn pieces_in_goal_test() {
+        let mut ruleset = small_ruleset(0);
+        let goals = GameBoard::from_board_type(&ruleset.board_type)
+            .goals_of_color(Color::Red)
+            .len();
+        let condition = |count, piece_filter| VictoryCondition::PiecesInGoal {
+            count,
+            piece_filter,
+        };
+        assert!(condition(goals, PieceFilter::Any).verify(&ruleset).is_ok());
+        assert!(matches!(
+            condition(goals + 1, PieceFilter::Any).verify(&ruleset),
+            Err(VictoryConditionError::CountExceedsGoals { goals: found, .. }) if found == goals
+        ));
+        assert!(matches!(
+            condition(0, PieceFilter::Any).verify(&ruleset),
+            Err(VictoryConditionError::AmountIs0)
+        ));
+        assert!(matches!(
+            condition(1, PieceFilter::Only(vec![9])).verify(&ruleset),
+            Err(VictoryConditionError::PieceNotFound(9))
+        ));
+        let all = (0..ruleset.pieces.len()).collect();
+        assert!(matches!(
+            condition(1, PieceFilter::Except(all)).verify(&ruleset),
+            Err(VictoryConditionError::NoValidPieces)
+        ));
+
+        let mut board = GameBoard::from_board_type(&ruleset.board_type);
+        let blue_goals = board.goals_of_color(Color::Blue);
+        let condition = condition(2, PieceFilter::Except(vec![1]));
+        *board.piece_mut(blue_goals[0].0).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        *board.piece_mut(blue_goals[1].0).unwrap() = Some(PieceInstance::new(1, Color::Red));
+        assert!(!condition.is_met(&board, Color::Red));
+        *board.piece_mut(blue_goals[1].0).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        assert!(condition.is_met(&board, Color::Red));
+        assert!(!condition.is_met(&board, Color::Blue));
+
+        ruleset.victory_conditions.insert(condition);
+        assert!(ruleset.verify().is_ok());
+    }
+
+    #[test]
+    fn capture_count_test() {
+        let ruleset = small_ruleset(0);
+        let condition = VictoryCondition::CaptureCount {
+            count: 2,
+            piece_filter: PieceFilter::Only(vec![1]),
+        };
+        assert!(condition.verify(&ruleset).is_ok());
+        assert!(matches!(
+            VictoryCondition::CaptureCount {
+                count: 0,
+                piece_filter: PieceFilter::Any,
+            }
+            .verify(&ruleset),
+            Err(VictoryConditionError::AmountIs0)
+        ));
+
+        let board = GameBoard::from_board_type(&ruleset.board_type);
+        let mut captured = vec![
+            PieceInstance::new(1, Color::Blue),
+            PieceInstance::new(0, Color::Blue),
+            PieceInstance::new(1, Color::Red),
+        ];
+        let state = VictoryState {
+            board: &board,
+            captured: &captured,
+            scores: Scores::default(),
+        };
+        assert!(!condition.is_met_in(&state, Color::Red));
+        assert!(!condition.is_met(&board, Color::Red));
+        captured.push(PieceInstance::new(1, Color::Blue));
+        let state = VictoryState {
+            board: &board,
+            captured: &captured,
+            scores: Scores::default(),
+        };
+        assert!(condition.is_met_in(&state, Color::Red));
+        assert!(!condition.is_met_in(&state, Color::Blue));
+
+        // Games end with the first capture
+        let mut ruleset = ruleset;
+        ruleset
+            .victory_conditions
+            .insert(VictoryCondition::CaptureCount {
+                count: 1,
+                piece_filter: PieceFilter::Any,
+            });
+        let mut game = Game::new(ruleset).unwrap();
+        let mut policy = RandomPolicy::new(4);
+        while game.result().is_none() {
+            let actions = game.legal_actions();
+            let index = policy.choose(&game, &actions);
+            let outcome = game.apply(&actions[index]).unwrap();
+            assert_eq!(game.result().is_some(), !outcome.captures.is_empty());
+        }
+    }
+
+    #[test]
+    fn composite_test() {
+        let ruleset = small_ruleset(0);
+        assert!(matches!(
+            VictoryCondition::Any(vec![]).verify(&ruleset),
+            Err(VictoryConditionError::EmptyComposite)
+        ));
+        assert!(matches!(
+            VictoryCondition::All(vec![
+                VictoryCondition::AllCaptured,
+                VictoryCondition::Any(vec![VictoryCondition::PointDifference(0)]),
+            ])
+            .verify(&ruleset),
+            Err(VictoryConditionError::PointDifferenceIs0)
+        ));
+
+        // Two pieces ahead, or one ahead with a piece in the goal
+        let in_goal = VictoryCondition::PiecesInGoal {
+            count: 1,
+            piece_filter: PieceFilter::Any,
+        };
+        let condition = VictoryCondition::Any(vec![
+            VictoryCondition::PointDifference(2),
+            VictoryCondition::All(vec![VictoryCondition::PointDifference(1), in_goal.clone()]),
+        ]);
+        assert!(condition.verify(&ruleset).is_ok());
+        assert_eq!(condition.leaves().len(), 3);
+        assert!(matches!(
+            condition.leaves()[2],
+            VictoryCondition::PiecesInGoal { .. }
+        ));
+
+        let mut board = GameBoard::from_board_type(&ruleset.board_type);
+        let blue_goals = board.goals_of_color(Color::Blue);
+        let red_goals = board.goals_of_color(Color::Red);
+        *board.piece_mut(blue_goals[0].0).unwrap() = Some(PieceInstance::new(0, Color::Red));
+        assert!(condition.is_met(&board, Color::Red));
+        assert!(!condition.is_met(&board, Color::Blue));
+        *board.piece_mut(red_goals[0].0).unwrap() = Some(PieceInstance::new(0, Color::Blue));
+        assert!(in_goal.is_met(&board, Color::Red));
+        assert!(!condition.is_met(&board, Color::Red));
+        assert!(!condition.is_met(&board, Color::Blue));
+        *board.piece_mut(blue_goals[1].0).unwrap() = Some(PieceInstance::new(1, Color::Red));
+        assert!(condition.is_met(&board, Color::Red));
+    }
+}
@@ -0,0 +1,186 @@
+//! Games between players that answer asynchronously.
+//!
+//! [`run_game_async`] only needs [`core::future`], so it runs on any executor. Servers can keep
+//! many games on a few threads while players wait on the network or on a person.
+
+use core::future::{ready, Future};
+
+use crate::action::Action;
+use crate::engine::{Engine, PlacementPlanner};
+use crate::game::placement::{Placement, PlacementPhase};
+use crate::game::record::GameRecord;
+use crate::game::{Game, GameOutcome, GameResult};
+use crate::game_board::Color;
+use crate::ruleset::Ruleset;
+
+/// An [`Engine`] whose answers may arrive later.
+///
+/// Answers are `Send` so games of `Send` players can be spawned on multithreaded executors.
+pub trait AsyncEngine {
+    /// Called before each new game.
+    fn new_game(&mut self, _game: &Game) {}
+    /// Resolves to the action to play for [`Game::to_move`], `None` resigns.
+    fn choose_action(&mut self, game: &Game) -> impl Future<Output = Option<Action>> + Send;
+    /// Resolves to the piece to place, see [`Engine::choose_placement`].
+    fn choose_placement(&mut self, game: &Game) -> impl Future<Output = Option<Placement>> + Send {
+        ready(PlacementPlanner::default().choose(game))
+    }
+}
+impl<T: AsyncEngine + ?Sized> AsyncEngine for &mut T {
+    fn new_game(&mut self, game: &Game) {
+        (**self).new_game(game)
+    }
+    fn choose_action(&mut self, game: &Game) -> impl Future<Output = Option<Action>> + Send {
+        (**self).choose_action(game)
+    }
+    fn choose_placement(&mut self, game: &Game) -> impl Future<Output = Option<Placement>> + Send {
+        (**self).choose_placement(game)
+    }
+}
+
+/// An [`Engine`] answering as soon as it is asked.
+#[derive(Copy, Clone, Debug)]
+pub struct Immediate<E>(pub E);
+impl<E: Engine> AsyncEngine for Immediate<E> {
+    fn new_game(&mut self, game: &Game) {
+        self.0.new_game(game)
+    }
+    fn choose_action(&mut self, game: &Game) -> impl Future<Output = Option<Action>> + Send {
+        ready(self.0.choose_action(game))
+    }
+    fn choose_placement(&mut self, game: &Game) -> impl Future<Output = Option<Placement>> + Send {
+        ready(self.0.choose_placement(game))
+    }
+}
+
+/// Plays one game of `ruleset` between `red` and `blue`, drawn after `max_plies`.
+///
+/// A player resigning or playing an illegal action or placement loses.
+pub async fn run_game_async(
+    mut red: impl AsyncEngine,
+    mut blue: impl AsyncEngine,
+    ruleset: Ruleset,
+    max_plies: usize,
+) -> GameResult<GameRecord> {
+    let mut game = Game::new(ruleset)?;
+    red.new_game(&game);
+    blue.new_game(&game);
+
+    let result = loop {
+        if let Some(outcome) = game.result() {
+            break outcome;
+        }
+        if let Some(color) = game.placement().and_then(PlacementPhase::to_place) {
+            let placement = match color {
+                Color::Red => red.choose_placement(&game).await,
+                Color::Blue => blue.choose_placement(&game).await,
+            };
+            let placed = match placement {
+                Some(placement) => game.place(placement.piece_index, placement.position),
+                None => game.finish_placing(),
+            };
+            if placed.is_err() {
+                break GameOutcome::Win(color.opponent());
+            }
+            continue;
+        }
+        if game.history().len() >= max_plies {
+            break GameOutcome::Draw;
+        }
+        let to_move = game.to_move();
+        let action = match to_move {
+            Color::Red => red.choose_action(&game).await,
+            Color::Blue => blue.choose_action(&game).await,
+        };
+        match action {
+            Some(action) if game.apply(&action).is_ok() => {}
+            _ => break GameOutcome::Win(to_move.opponent()),
+        }
+    };
+
+    let mut record = game.record();
+    record.result = Some(result);
+    Ok(record)
+}
+
+#[cfg(test)]
+mod test {
+    use core::future::Future;
+    use core::pin::{pin, Pin};
+    use core::task::{Context, Poll, Waker};
+
+    use crate::action::Action;
+    use crate::engine::{Engine, RandomPlayer};
+    use crate::game::{Game, GameOutcome};
+    use crate::runner::{run_game_async, AsyncEngine, Immediate};
+    use crate::testing::small_ruleset;
+
+    /// Answers after being polled once, like a player on the network.
+    struct Remote(RandomPlayer);
+    struct Later(Option<Option<Action>>, bool);
+    impl Future for Later {
+        type Output = Option<Action>;
+
+        fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+            if !self.1 {
+                self.1 = true;
+                context.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            Poll::Ready(self.0.take().unwrap())
+        }
+    }
+    impl AsyncEngine for Remote {
+        fn choose_action(&mut self, game: &Game) -> impl Future<Output = Option<Action>> + Send {
+            Later(Some(self.0.choose_action(game)), false)
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        let mut pending = 0;
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return (output, pending),
+                Poll::Pending => pending += 1,
+            }
+        }
+    }
+
+    #[test]
+    fn run_game_async_test() {
+        let mut remote = Remote(RandomPlayer::new(2));
+        let (record, pending) = block_on(run_game_async(
+            Immediate(RandomPlayer::new(1)),
+            &mut remote,
+            small_ruleset(3),
+            200,
+        ));
+        let record = record.unwrap();
+        assert!(record.result.is_some());
+        assert!(pending > 0);
+        let replayed = record.replay().unwrap();
+        assert_eq!(replayed.history().len(), record.actions.len());
+
+        let (record, _) = block_on(run_game_async(
+            Immediate(RandomPlayer::new(1)),
+            Immediate(RandomPlayer::new(2)),
+            small_ruleset(3),
+            0,
+        ));
+        assert_eq!(record.unwrap().result, Some(GameOutcome::Draw));
+    }
+
+    #[test]
+    fn send_test() {
+        fn assert_send(_: &impl Send) {}
+        let mut remote = Remote(RandomPlayer::new(2));
+        assert_send(&run_game_async(
+            Immediate(RandomPlayer::new(1)),
+            &mut remote,
+            small_ruleset(3),
+            200,
+        ));
+    }
+}
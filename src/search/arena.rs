@@ -0,0 +1,187 @@
+//! Reusable storage for search nodes.
+
+use std::convert::TryFrom;
+use std::ops::{Index, IndexMut};
+use std::vec::Vec;
+
+/// A value's place in an [`Arena`], it no longer finds anything once the value is removed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ArenaId {
+    index: u32,
+    generation: u32,
+}
+
+/// A slab of values addressed by [`ArenaId`]s.
+///
+/// Removed values leave their slot for the next insert, and [`Arena::clear`] keeps every slot,
+/// so an arena reused between searches stops allocating once it has grown to the largest tree.
+#[derive(Clone, Debug)]
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    /// Empty slots, reused last in first out.
+    free: Vec<u32>,
+    len: usize,
+}
+#[derive(Clone, Debug)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+    /// Values in the arena.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Values the arena holds before allocating.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    pub fn insert(&mut self, value: T) -> ArenaId {
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.value = Some(value);
+                ArenaId {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                let index =
+                    u32::try_from(self.slots.len()).expect("Arenas hold up to u32::MAX values");
+                self.slots.push(Slot {
+                    generation: 0,
+                    value: Some(value),
+                });
+                ArenaId {
+                    index,
+                    generation: 0,
+                }
+            }
+        }
+    }
+    pub fn get(&self, id: ArenaId) -> Option<&T> {
+        self.slots
+            .get(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.value.as_ref())
+    }
+    pub fn get_mut(&mut self, id: ArenaId) -> Option<&mut T> {
+        self.slots
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.value.as_mut())
+    }
+    pub fn contains(&self, id: ArenaId) -> bool {
+        self.get(id).is_some()
+    }
+    /// Takes the value out, `None` if it was already removed.
+    pub fn remove(&mut self, id: ArenaId) -> Option<T> {
+        let slot = self
+            .slots
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)?;
+        let out = slot.value.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        self.len -= 1;
+        Some(out)
+    }
+    /// Removes every value, keeping the slots for reuse.
+    pub fn clear(&mut self) {
+        self.free.clear();
+        for (index, slot) in self.slots.iter_mut().enumerate().rev() {
+            if slot.value.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+            self.free.push(index as u32);
+        }
+        self.len = 0;
+    }
+    /// Every value with its id, in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (ArenaId, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let value = slot.value.as_ref()?;
+            Some((
+                ArenaId {
+                    index: index as u32,
+                    generation: slot.generation,
+                },
+                value,
+            ))
+        })
+    }
+}
+impl<T> Index<ArenaId> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, id: ArenaId) -> &Self::Output {
+        self.get(id)
+            .expect("Arena ids must not be used after removal")
+    }
+}
+impl<T> IndexMut<ArenaId> for Arena<T> {
+    fn index_mut(&mut self, id: ArenaId) -> &mut Self::Output {
+        self.get_mut(id)
+            .expect("Arena ids must not be used after removal")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::search::arena::Arena;
+
+    #[test]
+    fn arena_test() {
+        let mut arena = Arena::new();
+        let a = arena.insert("a");
+        let b = arena.insert("b");
+        assert_eq!(arena.len(), 2);
+        assert_eq!(arena[a], "a");
+
+        assert_eq!(arena.remove(a), Some("a"));
+        assert_eq!(arena.remove(a), None);
+        assert!(!arena.contains(a));
+        let c = arena.insert("c");
+        assert_ne!(a, c);
+        assert_eq!(arena.get(a), None);
+        assert_eq!(arena[c], "c");
+
+        let capacity = arena.capacity();
+        arena.clear();
+        assert!(arena.is_empty());
+        assert!(!arena.contains(b) && !arena.contains(c));
+        let ids: Vec<_> = ["d", "e"]
+            .iter()
+            .map(|&value| arena.insert(value))
+            .collect();
+        assert_eq!(arena.capacity(), capacity);
+        assert_eq!(
+            arena
+                .iter()
+                .map(|(id, value)| (id, *value))
+                .collect::<Vec<_>>(),
+            vec![(ids[0], "d"), (ids[1], "e")]
+        );
+    }
+}
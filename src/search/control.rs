@@ -0,0 +1,317 @@
+//! Searching on another thread, stopping early, and pondering.
+//!
+//! [`Engine::start_search`] moves an engine to a new thread, the returned [`SearchHandle`] can
+//! stop it, read its best action so far, or be awaited. [`Engine::start_pondering`] searches the
+//! position after the expected reply on the opponent's time without any limit until
+//! [`SearchHandle::ponder_hit`], when the opponent played that reply and the engine's usual
+//! limits apply again, counting the work already done.
+//!
+//! Searches also report [`SearchInfo`] as they go to a callback set with
+//! [`SearchControl::on_info`], use a channel's sender in it to receive lines on another thread.
+
+use std::boxed::Box;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::vec::Vec;
+
+use crate::action::Action;
+use crate::engine::Engine;
+use crate::game::Game;
+
+/// A snapshot of a running search, like the info lines of chess engines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchInfo {
+    /// Deepest ply the search reached.
+    pub depth: usize,
+    /// Expected result for the color to move, between `-1.0` for a loss and `1.0` for a win.
+    pub score: f32,
+    /// The line the search expects, starting with its best action.
+    pub pv: Vec<Action>,
+    /// Positions in the search tree.
+    pub nodes: usize,
+    /// Nodes per second.
+    pub nps: u64,
+    /// Time since the search started.
+    pub time: Duration,
+}
+
+type InfoCallback = Box<dyn FnMut(&SearchInfo) + Send>;
+
+/// Shared between a search and whoever started it, see [`Engine::think`].
+#[derive(Default)]
+pub struct SearchControl {
+    stop: AtomicBool,
+    pondering: AtomicBool,
+    finished: AtomicBool,
+    best: Mutex<Option<Action>>,
+    info: Mutex<Option<InfoCallback>>,
+    waker: Mutex<Option<Waker>>,
+}
+impl SearchControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// A control for pondering, limits are ignored until [`SearchControl::ponder_hit`].
+    pub fn pondering() -> Self {
+        Self {
+            pondering: AtomicBool::new(true),
+            ..Self::default()
+        }
+    }
+
+    /// Calls `callback` with every [`SearchInfo`] the search reports.
+    pub fn on_info(self, callback: impl FnMut(&SearchInfo) + Send + 'static) -> Self {
+        *self.info.lock().unwrap() = Some(Box::new(callback));
+        self
+    }
+
+    /// Asks the search to return as soon as possible.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+    pub fn should_stop(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+    /// The expected move was played, the search continues under its usual limits.
+    pub fn ponder_hit(&self) {
+        self.pondering.store(false, Ordering::Relaxed);
+    }
+    pub fn is_pondering(&self) -> bool {
+        self.pondering.load(Ordering::Relaxed)
+    }
+
+    /// Called by searches whenever their best action changes.
+    pub fn report_best(&self, action: &Action) {
+        let mut best = self.best.lock().unwrap();
+        if best.as_ref() != Some(action) {
+            *best = Some(action.clone());
+        }
+    }
+    pub fn best_so_far(&self) -> Option<Action> {
+        self.best.lock().unwrap().clone()
+    }
+    /// Whether [`SearchControl::report_info`] goes anywhere, so searches can skip building it.
+    pub fn wants_info(&self) -> bool {
+        self.info.lock().unwrap().is_some()
+    }
+    /// Called by searches when their depth or principal variation changes.
+    pub fn report_info(&self, info: &SearchInfo) {
+        if let Some(callback) = self.info.lock().unwrap().as_mut() {
+            callback(info);
+        }
+    }
+
+    fn finish(&self) {
+        self.finished.store(true, Ordering::Release);
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A search running on its own thread.
+///
+/// Awaiting the handle gives the same result as [`SearchHandle::wait`] without blocking.
+/// Dropping it stops the search and leaves the thread to finish on its own.
+#[derive(Debug)]
+pub struct SearchHandle<E> {
+    control: Arc<SearchControl>,
+    thread: Option<JoinHandle<(E, Option<Action>)>>,
+}
+impl Debug for SearchControl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchControl")
+            .field("stop", &self.stop)
+            .field("pondering", &self.pondering)
+            .field("finished", &self.finished)
+            .field("best", &self.best)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E: Engine + Send + 'static> SearchHandle<E> {
+    pub(crate) fn spawn(mut engine: E, game: &Game, control: SearchControl) -> Self {
+        let control = Arc::new(control);
+        let game = game.clone();
+        let thread = {
+            let control = control.clone();
+            thread::spawn(move || {
+                let action = engine.think(&game, &control);
+                if let Some(action) = &action {
+                    control.report_best(action);
+                }
+                control.finish();
+                (engine, action)
+            })
+        };
+        Self {
+            control,
+            thread: Some(thread),
+        }
+    }
+}
+impl<E> SearchHandle<E> {
+    /// The best action found so far, `None` until the engine reports one.
+    pub fn best_so_far(&self) -> Option<Action> {
+        self.control.best_so_far()
+    }
+    pub fn is_finished(&self) -> bool {
+        self.control.finished.load(Ordering::Acquire)
+    }
+    pub fn is_pondering(&self) -> bool {
+        self.control.is_pondering()
+    }
+    /// See [`SearchControl::ponder_hit`].
+    pub fn ponder_hit(&self) {
+        self.control.ponder_hit()
+    }
+
+    /// Stops the search and returns the engine with the action it chose.
+    pub fn stop(self) -> (E, Option<Action>) {
+        self.control.stop();
+        self.wait()
+    }
+    /// Waits for the search to end under its own limits.
+    ///
+    /// Pondering searches have no limit and must be stopped or hit first.
+    pub fn wait(mut self) -> (E, Option<Action>) {
+        self.join()
+    }
+
+    fn join(&mut self) -> (E, Option<Action>) {
+        self.thread
+            .take()
+            .expect("Searches are joined once")
+            .join()
+            .expect("Search thread panicked")
+    }
+}
+impl<E> Drop for SearchHandle<E> {
+    fn drop(&mut self) {
+        if self.thread.take().is_some() {
+            self.control.stop();
+        }
+    }
+}
+impl<E> Future for SearchHandle<E> {
+    type Output = (E, Option<Action>);
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        *this.control.waker.lock().unwrap() = Some(context.waker().clone());
+        if this.is_finished() {
+            Poll::Ready(this.join())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc;
+    use std::task::{Context, Poll, Waker};
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::engine::Engine;
+    use crate::eval::ParamEvaluator;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::search::control::SearchControl;
+    use crate::search::mcts::{EvaluatorModel, Mcts, MctsConfig};
+    use crate::testing::small_ruleset;
+
+    fn mcts(simulations: usize) -> Mcts<EvaluatorModel<ParamEvaluator>> {
+        Mcts::new(
+            MctsConfig {
+                simulations,
+                ..MctsConfig::default()
+            },
+            EvaluatorModel::new(ParamEvaluator::default()),
+        )
+    }
+
+    #[test]
+    fn stop_test() {
+        let game = Game::new(small_ruleset(1)).unwrap();
+        let handle = mcts(usize::MAX).start_search(&game);
+        while handle.best_so_far().is_none() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(!handle.is_finished());
+        let (_, action) = handle.stop();
+        assert!(game.legal_actions().contains(&action.unwrap()));
+    }
+
+    #[test]
+    fn drop_test() {
+        let game = Game::new(small_ruleset(1)).unwrap();
+        let handle = mcts(usize::MAX).start_pondering(&game);
+        let control = handle.control.clone();
+        drop(handle);
+        assert!(control.should_stop());
+        while !control.finished.load(Ordering::Acquire) {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn ponder_test() {
+        let game = Game::new(small_ruleset(1)).unwrap();
+        let handle = mcts(16).start_pondering(&game);
+        thread::sleep(Duration::from_millis(20));
+        assert!(handle.is_pondering());
+        assert!(!handle.is_finished());
+        handle.ponder_hit();
+        let (_, action) = handle.wait();
+        assert!(game.legal_actions().contains(&action.unwrap()));
+    }
+
+    #[test]
+    fn await_test() {
+        let game = Game::new(small_ruleset(2)).unwrap();
+        let mut handle = pin!(RandomPolicy::new(3).start_search(&game));
+        let mut context = Context::from_waker(Waker::noop());
+        let action = loop {
+            if let Poll::Ready((_, action)) = handle.as_mut().poll(&mut context) {
+                break action;
+            }
+            thread::yield_now();
+        };
+        assert!(game.legal_actions().contains(&action.unwrap()));
+    }
+
+    #[test]
+    fn info_test() {
+        let game = Game::new(small_ruleset(1)).unwrap();
+        let (sender, receiver) = mpsc::channel();
+        let control = SearchControl::new().on_info(move |info| {
+            let _ = sender.send(info.clone());
+        });
+        let (_, action) = mcts(200).start_search_with(&game, control).wait();
+        let infos: Vec<_> = receiver.try_iter().collect();
+        assert!(!infos.is_empty());
+        assert!(infos.windows(2).all(|pair| pair[0].depth <= pair[1].depth
+            && pair[0].nodes <= pair[1].nodes
+            && pair[0].time <= pair[1].time));
+        let last = infos.last().unwrap();
+        assert!(last.depth > 1);
+        assert_eq!(last.pv.first(), action.as_ref());
+        let mut line = game.clone();
+        for action in last.pv.iter() {
+            line.apply(action).unwrap();
+        }
+    }
+}
@@ -0,0 +1,612 @@
+//! Monte Carlo tree search guided by an [`ExternalEvaluator`], in the style of AlphaZero.
+//!
+//! Leaves are collected into batches using virtual losses, so a model is called once per
+//! [`MctsConfig::batch_size`] simulations instead of once per simulation.
+
+use std::cmp::Reverse;
+use std::time::{Duration, Instant};
+use std::vec;
+use std::vec::Vec;
+
+use crate::action::Action;
+use crate::engine::{Candidate, Engine};
+use crate::eval::{Evaluator, ExternalEvaluator, WIN_SCORE};
+use crate::game::{Game, GameOutcome};
+use crate::game_board::Color;
+use crate::search::arena::{Arena, ArenaId};
+use crate::search::control::{SearchControl, SearchInfo};
+
+/// How long and how widely [`Mcts`] searches.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MctsConfig {
+    /// Leaves evaluated per search.
+    pub simulations: usize,
+    /// Most leaves sent to the model at once.
+    pub batch_size: usize,
+    /// Weight of the priors against the values found so far, higher explores more.
+    pub exploration: f32,
+}
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            simulations: 800,
+            batch_size: 8,
+            exploration: 1.5,
+        }
+    }
+}
+
+/// Searches with PUCT selection, the move played is the root child visited most.
+#[derive(Clone, Debug)]
+pub struct Mcts<M> {
+    pub config: MctsConfig,
+    pub model: M,
+    /// Node storage kept between searches, see [`Mcts::recycle`].
+    arena: Arena<Node>,
+}
+impl<M: ExternalEvaluator> Mcts<M> {
+    pub fn new(config: MctsConfig, model: M) -> Self {
+        Self {
+            config,
+            model,
+            arena: Arena::new(),
+        }
+    }
+    /// Keeps the storage of a tree no longer needed for the next search.
+    pub fn recycle(&mut self, tree: SearchTree) {
+        if tree.nodes.capacity() > self.arena.capacity() {
+            self.arena = tree.nodes;
+        }
+    }
+
+    /// Runs [`MctsConfig::simulations`] simulations from `game`.
+    pub fn search(&mut self, game: &Game) -> SearchTree {
+        self.search_controlled(game, &SearchControl::new())
+    }
+    /// [`Mcts::search`] that stops early and ponders as `control` asks.
+    ///
+    /// Pondering searches keep going past [`MctsConfig::simulations`] until hit or stopped.
+    pub fn search_controlled(&mut self, game: &Game, control: &SearchControl) -> SearchTree {
+        let start = Instant::now();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("mcts", simulations = self.config.simulations).entered();
+        #[cfg(feature = "tracing")]
+        let recycled = self.arena.capacity();
+        let mut tree = SearchTree::new(game.clone(), core::mem::take(&mut self.arena));
+        let mut simulations = 0;
+        let mut depth = 0;
+        let mut reported = None;
+        while !control.should_stop()
+            && (control.is_pondering() || simulations < self.config.simulations)
+        {
+            let limit = self
+                .config
+                .batch_size
+                .max(1)
+                .min(self.config.simulations.saturating_sub(simulations).max(1));
+            let mut leaves: Vec<Vec<ArenaId>> = Vec::new();
+            for _ in 0..limit {
+                let path = tree.select(self.config.exploration);
+                let leaf = *path.last().unwrap();
+                if leaves.iter().any(|other| other.last() == Some(&leaf)) {
+                    // Every path leads to leaves already in the batch
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(batch = leaves.len(), limit, "batch cut short");
+                    break;
+                }
+                simulations += 1;
+                #[cfg(feature = "tracing")]
+                if path.len() - 1 > depth {
+                    tracing::trace!(depth = path.len() - 1, simulations, "reached depth");
+                }
+                depth = depth.max(path.len() - 1);
+                let node = &tree.nodes[leaf];
+                if let Some(result) = node.game.result() {
+                    let value = outcome_value(result, node.game.to_move());
+                    tree.backpropagate(&path, value, false);
+                } else if node.expanded {
+                    // No legal actions without a result, only possible in custom rulesets
+                    tree.backpropagate(&path, 0.0, false);
+                } else {
+                    tree.add_virtual_loss(&path);
+                    leaves.push(path);
+                }
+            }
+            if !leaves.is_empty() {
+                let games: Vec<Game> = leaves
+                    .iter()
+                    .map(|path| tree.nodes[*path.last().unwrap()].game.clone())
+                    .collect();
+                let predictions = self.model.evaluate_batch(&games);
+                for (path, (policy, value)) in leaves.iter().zip(predictions) {
+                    tree.expand(*path.last().unwrap(), policy);
+                    tree.backpropagate(path, value.clamp(-1.0, 1.0), true);
+                }
+            }
+
+            let best = tree.best_action();
+            if let Some(best) = &best {
+                control.report_best(best);
+            }
+            if control.wants_info() && reported != Some((depth, best.clone())) {
+                control.report_info(&tree.info(depth, start.elapsed()));
+                reported = Some((depth, best));
+            }
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            simulations,
+            depth,
+            nodes = tree.len(),
+            recycled,
+            elapsed_ms = start.elapsed().as_millis() as u64,
+            "search finished"
+        );
+        tree
+    }
+}
+impl<M: ExternalEvaluator> Engine for Mcts<M> {
+    fn choose_action(&mut self, game: &Game) -> Option<Action> {
+        let tree = self.search(game);
+        let out = tree.best_action();
+        self.recycle(tree);
+        out
+    }
+    fn analyze(&mut self, game: &Game, multipv: usize) -> Vec<Candidate> {
+        let tree = self.search(game);
+        let out = tree.candidates(multipv);
+        self.recycle(tree);
+        out
+    }
+    fn think(&mut self, game: &Game, control: &SearchControl) -> Option<Action> {
+        let tree = self.search_controlled(game, control);
+        let out = tree.best_action();
+        self.recycle(tree);
+        out
+    }
+}
+
+/// The tree built by one [`Mcts::search`].
+#[derive(Clone, Debug)]
+pub struct SearchTree {
+    nodes: Arena<Node>,
+    root: ArenaId,
+}
+impl SearchTree {
+    /// A tree of only `game`, stored in `nodes` after clearing it.
+    fn new(game: Game, mut nodes: Arena<Node>) -> Self {
+        nodes.clear();
+        let root = nodes.insert(Node {
+            mover: game.to_move().opponent(),
+            game,
+            action: None,
+            children: Vec::new(),
+            prior: 1.0,
+            visits: 0,
+            value_sum: 0.0,
+            virtual_loss: 0,
+            expanded: false,
+        });
+        Self { nodes, root }
+    }
+
+    /// The most visited action from the root, `None` if the root has no legal actions.
+    pub fn best_action(&self) -> Option<Action> {
+        self.root_children()
+            .into_iter()
+            .max_by_key(|child| child.visits)
+            .map(|child| child.action)
+    }
+    /// Statistics of every action from the root in legal action order.
+    pub fn root_children(&self) -> Vec<ChildStats> {
+        self.nodes[self.root]
+            .children
+            .iter()
+            .map(|&child| {
+                let node = &self.nodes[child];
+                ChildStats {
+                    action: node.action.clone().expect("Only the root has no action"),
+                    prior: node.prior,
+                    visits: node.visits,
+                    value: node.mean_value(),
+                }
+            })
+            .collect()
+    }
+    /// The most visited line from the root.
+    pub fn principal_variation(&self) -> Vec<Action> {
+        self.line_from(self.root, Vec::new())
+    }
+    /// The `count` most visited actions from the root, each with its most visited line.
+    pub fn candidates(&self, count: usize) -> Vec<Candidate> {
+        let mut children = self.nodes[self.root].children.clone();
+        children.sort_by_key(|&child| Reverse(self.nodes[child].visits));
+        children
+            .into_iter()
+            .take(count)
+            .map(|child| {
+                let node = &self.nodes[child];
+                let action = node.action.clone().expect("Only the root has no action");
+                Candidate {
+                    pv: self.line_from(child, vec![action.clone()]),
+                    action,
+                    score: node.mean_value(),
+                }
+            })
+            .collect()
+    }
+    /// `out` followed by the most visited line from the node at `current`.
+    fn line_from(&self, mut current: ArenaId, mut out: Vec<Action>) -> Vec<Action> {
+        while let Some(&child) = self.nodes[current]
+            .children
+            .iter()
+            .filter(|&&child| self.nodes[child].visits > 0)
+            .max_by_key(|&&child| self.nodes[child].visits)
+        {
+            out.push(
+                self.nodes[child]
+                    .action
+                    .clone()
+                    .expect("Only the root has no action"),
+            );
+            current = child;
+        }
+        out
+    }
+    /// Nodes up to `depth` actions from the root breadth first, the root first.
+    ///
+    /// Children are in legal action order, unvisited ones included.
+    pub fn nodes_to_depth(&self, depth: usize) -> Vec<TreeNode> {
+        let mut out = Vec::new();
+        let mut queue = vec![(self.root, None, 0)];
+        let mut next = 0;
+        while let Some(&(id, parent, node_depth)) = queue.get(next) {
+            next += 1;
+            let node = &self.nodes[id];
+            out.push(TreeNode {
+                id: out.len(),
+                parent,
+                depth: node_depth,
+                action: node.action.clone(),
+                mover: node.mover,
+                prior: node.prior,
+                visits: node.visits,
+                value: node.mean_value(),
+            });
+            if node_depth < depth {
+                let index = out.len() - 1;
+                queue.extend(
+                    node.children
+                        .iter()
+                        .map(|&child| (child, Some(index), node_depth + 1)),
+                );
+            }
+        }
+        out
+    }
+    /// Nodes in the tree, including the root.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn info(&self, depth: usize, time: Duration) -> SearchInfo {
+        let pv = self.principal_variation();
+        let score = self
+            .root_children()
+            .into_iter()
+            .find(|child| Some(&child.action) == pv.first())
+            .map_or(0.0, |child| child.value);
+        SearchInfo {
+            depth,
+            score,
+            pv,
+            nodes: self.len(),
+            nps: (self.len() as f64 / time.as_secs_f64().max(1e-9)) as u64,
+            time,
+        }
+    }
+
+    /// The path from the root to the leaf with the best PUCT score at every step.
+    fn select(&self, exploration: f32) -> Vec<ArenaId> {
+        let mut path = vec![self.root];
+        let mut current = self.root;
+        while !self.nodes[current].children.is_empty() {
+            let parent = &self.nodes[current];
+            let total = (parent.visits + parent.virtual_loss) as f32;
+            current = *parent
+                .children
+                .iter()
+                .max_by(|&&a, &&b| {
+                    let score = |id: ArenaId| {
+                        let node = &self.nodes[id];
+                        let visits = (node.visits + node.virtual_loss) as f32;
+                        let value = if visits == 0.0 {
+                            0.0
+                        } else {
+                            // Virtual losses count as lost simulations
+                            (node.value_sum - node.virtual_loss as f32) / visits
+                        };
+                        value + exploration * node.prior * total.sqrt() / (1.0 + visits)
+                    };
+                    score(a).total_cmp(&score(b))
+                })
+                .unwrap();
+            path.push(current);
+        }
+        path
+    }
+    fn add_virtual_loss(&mut self, path: &[ArenaId]) {
+        for &id in path {
+            self.nodes[id].virtual_loss += 1;
+        }
+    }
+    /// Adds `value`, which is for the color to move at the end of `path`, to every node on it.
+    fn backpropagate(&mut self, path: &[ArenaId], value: f32, virtual_loss: bool) {
+        let color = self.nodes[*path.last().unwrap()].game.to_move();
+        for &id in path {
+            let node = &mut self.nodes[id];
+            if virtual_loss {
+                node.virtual_loss -= 1;
+            }
+            node.visits += 1;
+            node.value_sum += if node.mover == color { value } else { -value };
+        }
+    }
+    fn expand(&mut self, id: ArenaId, policy: Vec<f32>) {
+        let game = self.nodes[id].game.clone();
+        let actions = game.legal_actions();
+        let mut priors: Vec<f32> = if policy.len() == actions.len() {
+            policy.into_iter().map(|prior| prior.max(0.0)).collect()
+        } else {
+            vec![1.0; actions.len()]
+        };
+        let sum: f32 = priors.iter().sum();
+        if sum > 0.0 {
+            priors.iter_mut().for_each(|prior| *prior /= sum);
+        }
+
+        self.nodes[id].expanded = true;
+        for (action, prior) in actions.into_iter().zip(priors) {
+            let mut child = game.clone();
+            if child.apply(&action).is_err() {
+                continue;
+            }
+            let child_id = self.nodes.insert(Node {
+                mover: game.to_move(),
+                game: child,
+                action: Some(action),
+                children: Vec::new(),
+                prior,
+                visits: 0,
+                value_sum: 0.0,
+                virtual_loss: 0,
+                expanded: false,
+            });
+            self.nodes[id].children.push(child_id);
+        }
+    }
+}
+
+/// What the search found about one action from the root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChildStats {
+    pub action: Action,
+    pub prior: f32,
+    pub visits: u32,
+    /// Mean value for the color to move at the root, between `-1.0` and `1.0`.
+    pub value: f32,
+}
+
+/// A node of a [`SearchTree`], see [`SearchTree::nodes_to_depth`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeNode {
+    /// The node's index in [`SearchTree::nodes_to_depth`].
+    pub id: usize,
+    pub parent: Option<usize>,
+    /// Actions from the root.
+    pub depth: usize,
+    /// The action played to reach the node, `None` at the root.
+    pub action: Option<Action>,
+    /// The color that played `action`, `value` is from its point of view.
+    pub mover: Color,
+    pub prior: f32,
+    pub visits: u32,
+    /// Mean value between `-1.0` and `1.0`.
+    pub value: f32,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    /// The position after `action`.
+    game: Game,
+    /// The color that played `action`, values are from its point of view.
+    mover: Color,
+    action: Option<Action>,
+    children: Vec<ArenaId>,
+    prior: f32,
+    visits: u32,
+    value_sum: f32,
+    /// Simulations through this node waiting for the model.
+    virtual_loss: u32,
+    expanded: bool,
+}
+impl Node {
+    fn mean_value(&self) -> f32 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.value_sum / self.visits as f32
+        }
+    }
+}
+
+fn outcome_value(outcome: GameOutcome, color: Color) -> f32 {
+    match outcome {
+        GameOutcome::Win(winner) if winner == color => 1.0,
+        GameOutcome::Win(_) => -1.0,
+        GameOutcome::Draw => 0.0,
+    }
+}
+
+/// Uses an [`Evaluator`] as a model with uniform priors, for searching without a network.
+#[derive(Copy, Clone, Debug)]
+pub struct EvaluatorModel<E> {
+    pub evaluator: E,
+    /// Scores of this size map to values of about `±0.76`.
+    pub scale: f32,
+}
+impl<E: Evaluator> EvaluatorModel<E> {
+    pub fn new(evaluator: E) -> Self {
+        Self {
+            evaluator,
+            scale: 300.0,
+        }
+    }
+}
+impl<E: Evaluator> ExternalEvaluator for EvaluatorModel<E> {
+    fn evaluate_batch(&mut self, games: &[Game]) -> Vec<(Vec<f32>, f32)> {
+        games
+            .iter()
+            .map(|game| {
+                let score = self.evaluator.evaluate(game, game.to_move());
+                let value = if score.abs() >= WIN_SCORE {
+                    score.signum() as f32
+                } else {
+                    (score as f32 / self.scale).tanh()
+                };
+                (Vec::new(), value)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::action::Action;
+    use crate::collections::HashSet;
+    use crate::coordinate::Coordinate;
+    use crate::engine::Engine;
+    use crate::eval::{ExternalEvaluator, ParamEvaluator};
+    use crate::game::{Game, GameOutcome};
+    use crate::game_board::Color;
+    use crate::ruleset::starting_positions::alteration_type::AlternationType;
+    use crate::ruleset::starting_positions::piece_limit::PieceLimit;
+    use crate::ruleset::starting_positions::placement_area::PlacementArea;
+    use crate::ruleset::starting_positions::StartingPositions;
+    use crate::search::mcts::{EvaluatorModel, Mcts, MctsConfig};
+    use crate::testing::small_ruleset;
+
+    /// Uniform priors and even values, counting batches.
+    struct CountingModel {
+        batches: Vec<usize>,
+    }
+    impl ExternalEvaluator for CountingModel {
+        fn evaluate_batch(&mut self, games: &[Game]) -> Vec<(Vec<f32>, f32)> {
+            self.batches.push(games.len());
+            games.iter().map(|_| (Vec::new(), 0.0)).collect()
+        }
+    }
+
+    #[test]
+    fn batch_test() {
+        let game = Game::new(small_ruleset(1)).unwrap();
+        let mut mcts = Mcts::new(
+            MctsConfig {
+                simulations: 64,
+                batch_size: 8,
+                ..MctsConfig::default()
+            },
+            CountingModel {
+                batches: Vec::new(),
+            },
+        );
+        let tree = mcts.search(&game);
+        let visits: u32 = tree.root_children().iter().map(|child| child.visits).sum();
+        assert_eq!(visits, 63);
+        assert!(mcts.model.batches.iter().all(|&batch| batch <= 8));
+        assert!(mcts.model.batches.iter().any(|&batch| batch > 1));
+        let legal = game.legal_actions();
+        assert!(legal.contains(&tree.best_action().unwrap()));
+    }
+
+    #[test]
+    fn finds_win_test() {
+        let mut piece_limits = HashSet::new();
+        piece_limits.insert(PieceLimit::TotalLimit { limit: 1 });
+        let mut ruleset = small_ruleset(0);
+        ruleset.starting_positions = StartingPositions::Placement {
+            first_color: Color::Red,
+            alternation_type: AlternationType::TurnsCount { per_turn_count: 1 },
+            placement_area: PlacementArea::Half,
+            piece_limits,
+        };
+        let mut game = Game::new(ruleset).unwrap();
+        let half = game.board().board.rows as i16 / 2;
+        game.place(0, Coordinate::new(half - 1, 2)).unwrap();
+        game.place(1, Coordinate::new(half, 2)).unwrap();
+
+        let mut mcts = Mcts::new(
+            MctsConfig {
+                simulations: 200,
+                ..MctsConfig::default()
+            },
+            EvaluatorModel::new(ParamEvaluator::default()),
+        );
+        let action: Action = mcts.choose_action(&game).unwrap();
+        game.apply(&action).unwrap();
+        assert_eq!(game.result(), Some(GameOutcome::Win(Color::Red)));
+    }
+
+    #[test]
+    fn analyze_test() {
+        let game = Game::new(small_ruleset(1)).unwrap();
+        let mut mcts = Mcts::new(
+            MctsConfig {
+                simulations: 300,
+                ..MctsConfig::default()
+            },
+            EvaluatorModel::new(ParamEvaluator::default()),
+        );
+        let candidates = mcts.analyze(&game, 3);
+        assert_eq!(candidates.len(), 3.min(game.legal_actions().len()));
+        let mut actions: Vec<_> = candidates
+            .iter()
+            .map(|candidate| &candidate.action)
+            .collect();
+        actions.dedup();
+        assert_eq!(actions.len(), candidates.len());
+        for candidate in candidates.iter() {
+            assert_eq!(candidate.pv.first(), Some(&candidate.action));
+            let mut line = game.clone();
+            for action in candidate.pv.iter() {
+                line.apply(action).unwrap();
+            }
+        }
+        assert!(mcts.analyze(&game, 0).is_empty());
+    }
+
+    #[test]
+    fn recycle_test() {
+        let game = Game::new(small_ruleset(2)).unwrap();
+        let mut mcts = Mcts::new(
+            MctsConfig {
+                simulations: 16,
+                ..MctsConfig::default()
+            },
+            EvaluatorModel::new(ParamEvaluator::default()),
+        );
+        let first = mcts.search(&game);
+        let (nodes, best) = (first.len(), first.best_action());
+        mcts.recycle(first);
+        let capacity = mcts.arena.capacity();
+        assert!(capacity >= nodes);
+
+        let second = mcts.search(&game);
+        assert_eq!(second.len(), nodes);
+        assert_eq!(second.best_action(), best);
+        assert_eq!(second.nodes.capacity(), capacity);
+    }
+}
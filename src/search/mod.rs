@@ -0,0 +1,140 @@
+//! Engines that search the game tree.
+
+use std::fmt::Write;
+use std::string::String;
+
+use crate::notation::{format_action, CoordinateScheme};
+use crate::search::mcts::SearchTree;
+
+pub mod arena;
+pub mod control;
+pub mod mcts;
+
+/// A graph format for [`export_tree`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TreeFormat {
+    /// Graphviz, labeled with each action, its visits and its mean value.
+    Dot,
+    /// GraphML with the action, mover, prior, visits and mean value as node data.
+    GraphMl,
+}
+
+/// The nodes of `tree` up to `depth` actions from the root, as a graph in `format`.
+///
+/// Actions are in algebraic notation and values are from the point of view of the color that
+/// played the action, see [`SearchTree::nodes_to_depth`].
+pub fn export_tree(tree: &SearchTree, depth: usize, format: TreeFormat) -> String {
+    let nodes = tree.nodes_to_depth(depth);
+    let action = |index: usize| {
+        nodes[index].action.as_ref().map_or_else(
+            || String::from("root"),
+            |action| format_action(action, CoordinateScheme::Algebraic),
+        )
+    };
+    let mut out = String::new();
+    match format {
+        TreeFormat::Dot => {
+            out.push_str("digraph search {\n    node [shape=box];\n");
+            for node in nodes.iter() {
+                let _ = writeln!(
+                    out,
+                    "    n{} [label=\"{}\\nN={} Q={:.3}\"];",
+                    node.id,
+                    action(node.id).replace('"', "\\\""),
+                    node.visits,
+                    node.value
+                );
+                if let Some(parent) = node.parent {
+                    let _ = writeln!(out, "    n{} -> n{};", parent, node.id);
+                }
+            }
+            out.push_str("}\n");
+        }
+        TreeFormat::GraphMl => {
+            out.push_str(concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+                "  <key id=\"action\" for=\"node\" attr.name=\"action\" attr.type=\"string\"/>\n",
+                "  <key id=\"mover\" for=\"node\" attr.name=\"mover\" attr.type=\"string\"/>\n",
+                "  <key id=\"prior\" for=\"node\" attr.name=\"prior\" attr.type=\"double\"/>\n",
+                "  <key id=\"visits\" for=\"node\" attr.name=\"visits\" attr.type=\"int\"/>\n",
+                "  <key id=\"value\" for=\"node\" attr.name=\"value\" attr.type=\"double\"/>\n",
+                "  <graph id=\"search\" edgedefault=\"directed\">\n",
+            ));
+            for node in nodes.iter() {
+                let _ = writeln!(
+                    out,
+                    concat!(
+                        "    <node id=\"n{}\"><data key=\"action\">{}</data>",
+                        "<data key=\"mover\">{:?}</data><data key=\"prior\">{}</data>",
+                        "<data key=\"visits\">{}</data><data key=\"value\">{}</data></node>"
+                    ),
+                    node.id,
+                    escape_xml(&action(node.id)),
+                    node.mover,
+                    node.prior,
+                    node.visits,
+                    node.value
+                );
+            }
+            for node in nodes.iter() {
+                if let Some(parent) = node.parent {
+                    let _ = writeln!(
+                        out,
+                        "    <edge source=\"n{}\" target=\"n{}\"/>",
+                        parent, node.id
+                    );
+                }
+            }
+            out.push_str("  </graph>\n</graphml>\n");
+        }
+    }
+    out
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::eval::ParamEvaluator;
+    use crate::game::Game;
+    use crate::search::mcts::{EvaluatorModel, Mcts, MctsConfig};
+    use crate::search::{export_tree, TreeFormat};
+    use crate::testing::small_ruleset;
+
+    #[test]
+    fn export_tree_test() {
+        let game = Game::new(small_ruleset(0)).unwrap();
+        let mut mcts = Mcts::new(
+            MctsConfig {
+                simulations: 32,
+                ..MctsConfig::default()
+            },
+            EvaluatorModel::new(ParamEvaluator::default()),
+        );
+        let tree = mcts.search(&game);
+        let nodes = tree.nodes_to_depth(1);
+        assert_eq!(nodes.len(), 1 + tree.root_children().len());
+        assert!(nodes[1..].iter().all(|node| node.parent == Some(0)));
+        assert_eq!(nodes[0].visits, 32);
+
+        let dot = export_tree(&tree, 1, TreeFormat::Dot);
+        assert!(dot.starts_with("digraph search {"));
+        assert_eq!(dot.matches(" -> ").count(), nodes.len() - 1);
+        let graphml = export_tree(&tree, 2, TreeFormat::GraphMl);
+        assert_eq!(
+            graphml.matches("<node ").count(),
+            tree.nodes_to_depth(2).len()
+        );
+        assert_eq!(
+            graphml.matches("<edge ").count(),
+            tree.nodes_to_depth(2).len() - 1
+        );
+    }
+}
@@ -0,0 +1,387 @@
+//! Games of an engine against itself for generating training data.
+//!
+//! [`SelfPlay`] keeps several games going on their own threads and hands every finished
+//! [`GameRecord`] to a [`RecordSink`], such as a [`Dataset`](crate::dataset::Dataset) writer.
+//! Actions are sampled from [`Engine::analyze`] so games differ even between deterministic
+//! engines.
+
+use std::f64::consts::PI;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::vec::Vec;
+
+use crate::engine::{play_placement, Candidate, Engine};
+use crate::game::placement::PlacementPhase;
+use crate::game::record::GameRecord;
+use crate::game::{Game, GameOutcome, GameResult};
+use crate::rng::{ChaChaRng, GameRng};
+use crate::ruleset::Ruleset;
+
+/// How [`SelfPlay`] plays its games.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelfPlayConfig {
+    /// Games to play in total.
+    pub games: usize,
+    /// Games played at the same time, each on its own thread with its own engine.
+    pub concurrency: usize,
+    /// Games reaching this many plies are drawn.
+    pub max_plies: usize,
+    /// Candidates asked from [`Engine::analyze`] for every action.
+    pub multipv: usize,
+    /// Candidates are sampled by the softmax of their scores divided by this,
+    /// `0.0` always plays the most likely.
+    pub temperature: f64,
+    /// Plies sampled at `temperature`, later actions are always the most likely.
+    pub temperature_plies: usize,
+    pub noise: Option<DirichletNoise>,
+    /// Game `index` samples from a generator seeded with `seed + index`.
+    pub seed: u64,
+}
+impl Default for SelfPlayConfig {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            concurrency: 4,
+            max_plies: 500,
+            multipv: 16,
+            temperature: 1.0,
+            temperature_plies: 30,
+            noise: Some(DirichletNoise::default()),
+            seed: 0,
+        }
+    }
+}
+
+/// Dirichlet noise mixed into the sampling probabilities of every action.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirichletNoise {
+    /// Concentration, lower values put the noise on fewer candidates.
+    pub alpha: f64,
+    /// Share of the probabilities replaced by noise, between `0.0` and `1.0`.
+    pub fraction: f64,
+}
+impl Default for DirichletNoise {
+    fn default() -> Self {
+        Self {
+            alpha: 0.3,
+            fraction: 0.25,
+        }
+    }
+}
+
+/// Receives the records of finished games.
+pub trait RecordSink {
+    fn accept(&mut self, record: GameRecord);
+}
+impl RecordSink for Vec<GameRecord> {
+    fn accept(&mut self, record: GameRecord) {
+        self.push(record);
+    }
+}
+impl<F: FnMut(GameRecord)> RecordSink for F {
+    fn accept(&mut self, record: GameRecord) {
+        self(record)
+    }
+}
+
+/// Plays batches of games between copies of an engine.
+///
+/// Engines make placements with [`Engine::choose_placement`], placements are not sampled.
+#[derive(Clone, Debug)]
+pub struct SelfPlay {
+    ruleset: Ruleset,
+    config: SelfPlayConfig,
+}
+impl SelfPlay {
+    pub fn new(ruleset: Ruleset, config: SelfPlayConfig) -> Self {
+        Self { ruleset, config }
+    }
+
+    pub fn config(&self) -> &SelfPlayConfig {
+        &self.config
+    }
+
+    /// Plays every game, returning how many records went to `sink`.
+    ///
+    /// `engines` makes the engine for each thread from its number. Records reach `sink` as
+    /// games finish, so their order can change between runs even though every game is
+    /// reproducible from the seed with deterministic engines.
+    pub fn run<E, F>(&self, engines: F, sink: &mut impl RecordSink) -> GameResult<usize>
+    where
+        E: Engine,
+        F: Fn(usize) -> E + Sync,
+    {
+        let start = Game::new(self.ruleset.clone())?;
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for worker in 0..self.config.concurrency.max(1) {
+                let (sender, next, start, engines) = (sender.clone(), &next, &start, &engines);
+                scope.spawn(move || {
+                    let mut engine = engines(worker);
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= self.config.games {
+                            break;
+                        }
+                        let record = self.play_game(&mut engine, start.clone(), index);
+                        if sender.send(record).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+            let mut count = 0;
+            for record in receiver {
+                sink.accept(record);
+                count += 1;
+            }
+            Ok(count)
+        })
+    }
+
+    /// Plays game `index` from `game`, a resignation or an illegal action loses.
+    fn play_game(&self, engine: &mut impl Engine, mut game: Game, index: usize) -> GameRecord {
+        let seed = self.config.seed.wrapping_add(index as u64);
+        let mut rng = ChaChaRng::new(seed);
+        game.set_seed(seed);
+        engine.new_game(&game);
+        let result = loop {
+            if let Some(outcome) = game.result() {
+                break outcome;
+            }
+            if let Some(color) = game.placement().and_then(PlacementPhase::to_place) {
+                if play_placement(engine, &mut game).is_err() {
+                    break GameOutcome::Win(color.opponent());
+                }
+                continue;
+            }
+            let ply = game.history().len();
+            if ply >= self.config.max_plies {
+                break GameOutcome::Draw;
+            }
+            let temperature = if ply < self.config.temperature_plies {
+                self.config.temperature
+            } else {
+                0.0
+            };
+            let mut candidates = engine.analyze(&game, self.config.multipv);
+            let chosen = sample_candidate(&candidates, temperature, self.config.noise, &mut rng);
+            match chosen.map(|chosen| candidates.swap_remove(chosen).action) {
+                Some(action) if game.apply(&action).is_ok() => {}
+                _ => break GameOutcome::Win(game.to_move().opponent()),
+            }
+        };
+        let mut record = game.record();
+        record.result = Some(result);
+        record
+    }
+}
+
+/// Picks the index of one of `candidates` as [`SelfPlay`] does, `None` if there are none.
+pub fn sample_candidate(
+    candidates: &[Candidate],
+    temperature: f64,
+    noise: Option<DirichletNoise>,
+    rng: &mut impl GameRng,
+) -> Option<usize> {
+    if candidates.is_empty() {
+        return None;
+    }
+    let best = candidates
+        .iter()
+        .map(|candidate| candidate.score as f64)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let mut probabilities: Vec<f64> = if temperature > 0.0 {
+        let weights: Vec<f64> = candidates
+            .iter()
+            .map(|candidate| ((candidate.score as f64 - best) / temperature).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+        weights.into_iter().map(|weight| weight / total).collect()
+    } else {
+        let first = candidates
+            .iter()
+            .position(|candidate| candidate.score as f64 == best)
+            .unwrap_or(0);
+        (0..candidates.len())
+            .map(|index| if index == first { 1.0 } else { 0.0 })
+            .collect()
+    };
+    if let Some(noise) = noise {
+        for (probability, sample) in
+            probabilities
+                .iter_mut()
+                .zip(dirichlet(noise.alpha, candidates.len(), rng))
+        {
+            *probability = (1.0 - noise.fraction) * *probability + noise.fraction * sample;
+        }
+    }
+
+    if temperature > 0.0 {
+        let mut remaining = rng.next_f64();
+        for (index, probability) in probabilities.iter().enumerate() {
+            remaining -= probability;
+            if remaining < 0.0 {
+                return Some(index);
+            }
+        }
+        Some(candidates.len() - 1)
+    } else {
+        (0..candidates.len()).reduce(|a, b| {
+            if probabilities[b] > probabilities[a] {
+                b
+            } else {
+                a
+            }
+        })
+    }
+}
+
+/// A sample of `count` values from a symmetric Dirichlet distribution, they sum to `1.0`.
+fn dirichlet(alpha: f64, count: usize, rng: &mut impl GameRng) -> Vec<f64> {
+    let samples: Vec<f64> = (0..count).map(|_| gamma(alpha, rng)).collect();
+    let total: f64 = samples.iter().sum();
+    if total > 0.0 {
+        samples.into_iter().map(|sample| sample / total).collect()
+    } else {
+        vec![1.0 / count as f64; count]
+    }
+}
+
+/// A sample of the Gamma distribution with shape `alpha` and scale `1.0` by Marsaglia and Tsang's
+/// method.
+fn gamma(alpha: f64, rng: &mut impl GameRng) -> f64 {
+    if alpha < 1.0 {
+        // Boosts the shape above 1 then scales back down
+        return gamma(alpha + 1.0, rng) * (1.0 - rng.next_f64()).powf(1.0 / alpha);
+    }
+    let d = alpha - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        // Standard normal by Box-Muller
+        let normal =
+            (-2.0 * (1.0 - rng.next_f64()).ln()).sqrt() * (2.0 * PI * rng.next_f64()).cos();
+        let v = (1.0 + c * normal).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u = 1.0 - rng.next_f64();
+        if u.ln() < 0.5 * normal * normal + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+
+    use crate::action::Action;
+    use crate::engine::{Candidate, Engine};
+    use crate::game::record::GameRecord;
+    use crate::game::Game;
+    use crate::rng::ChaChaRng;
+    use crate::selfplay::{sample_candidate, DirichletNoise, SelfPlay, SelfPlayConfig};
+    use crate::testing::small_ruleset;
+
+    /// Prefers earlier legal actions.
+    struct Ordered;
+    impl Engine for Ordered {
+        fn choose_action(&mut self, game: &Game) -> Option<Action> {
+            game.legal_actions().into_iter().next()
+        }
+        fn analyze(&mut self, game: &Game, multipv: usize) -> Vec<Candidate> {
+            game.legal_actions()
+                .into_iter()
+                .take(multipv)
+                .enumerate()
+                .map(|(index, action)| Candidate {
+                    pv: vec![action.clone()],
+                    action,
+                    score: -(index as f32) / 10.0,
+                })
+                .collect()
+        }
+    }
+
+    fn candidates(scores: &[f32]) -> Vec<Candidate> {
+        let action = Game::new(small_ruleset(0)).unwrap().legal_actions()[0].clone();
+        scores
+            .iter()
+            .map(|&score| Candidate {
+                action: action.clone(),
+                score,
+                pv: Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sample_candidate_test() {
+        let mut rng = ChaChaRng::new(0);
+        assert_eq!(sample_candidate(&[], 1.0, None, &mut rng), None);
+        let candidates = candidates(&[0.2, 0.9, -0.5]);
+        for _ in 0..20 {
+            assert_eq!(sample_candidate(&candidates, 0.0, None, &mut rng), Some(1));
+        }
+
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            counts[sample_candidate(&candidates, 0.5, None, &mut rng).unwrap()] += 1;
+        }
+        assert!(counts[1] > counts[0] && counts[0] > counts[2] && counts[2] > 0);
+
+        // Noise alone is spread over every candidate
+        let noise = DirichletNoise {
+            alpha: 0.3,
+            fraction: 1.0,
+        };
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            counts[sample_candidate(&candidates, 0.0, Some(noise), &mut rng).unwrap()] += 1;
+        }
+        assert!(counts.iter().all(|&count| count > 500));
+    }
+
+    #[test]
+    fn selfplay_test() {
+        let config = SelfPlayConfig {
+            games: 6,
+            concurrency: 3,
+            max_plies: 60,
+            ..SelfPlayConfig::default()
+        };
+        let selfplay = SelfPlay::new(small_ruleset(3), config);
+        let mut records = Vec::new();
+        assert_eq!(selfplay.run(|_| Ordered, &mut records).unwrap(), 6);
+        assert_eq!(records.len(), 6);
+        for record in &records {
+            assert!(record.result.is_some());
+            assert!(record.actions.len() <= 60);
+            assert_eq!(
+                record.replay().unwrap().history().len(),
+                record.actions.len()
+            );
+        }
+        // Sampling makes games differ
+        assert!(records
+            .iter()
+            .any(|record| record.actions != records[0].actions));
+
+        let mut again = Vec::new();
+        selfplay
+            .run(|_| Ordered, &mut |record| again.push(record))
+            .unwrap();
+        let sorted = |records: &mut Vec<GameRecord>| {
+            records.sort_by_key(|record| format!("{:?}", record.actions));
+        };
+        sorted(&mut records);
+        sorted(&mut again);
+        assert_eq!(records, again);
+    }
+}
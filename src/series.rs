@@ -0,0 +1,385 @@
+//! Best-of-N series between two players.
+//!
+//! Any [`Engine`] can take part, a human interface or a remote connection plays by implementing
+//! it and returning the action its player chose. [`MatchObserver`]s see every game's events and
+//! the standing after each game.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Formatter};
+
+use crate::engine::{play_placement, Engine};
+use crate::game::event::GameEvent;
+use crate::game::placement::PlacementPhase;
+use crate::game::record::GameRecord;
+use crate::game::{Game, GameOutcome, GameResult};
+use crate::game_board::Color;
+use crate::ruleset::Ruleset;
+
+/// How a [`Match`] is played.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchConfig {
+    /// Games in the series, it ends early once a player cannot be caught.
+    pub games: usize,
+    /// Games reaching this many plies are drawn.
+    pub max_plies: usize,
+    /// Players swap colors every game, otherwise the first player is always Red.
+    pub swap_colors: bool,
+    /// What happens when the series ends level.
+    pub tiebreak: Tiebreak,
+}
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            games: 3,
+            max_plies: 500,
+            swap_colors: true,
+            tiebreak: Tiebreak::Draw,
+        }
+    }
+}
+
+/// How a level series is decided.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tiebreak {
+    /// The match is drawn.
+    Draw,
+    /// Extra games are played until one is won, the match is drawn after `max_games` of them.
+    SuddenDeath { max_games: usize },
+}
+
+/// The standing of a [`Match`], players are `0` for the first and `1` for the second.
+#[derive(Clone, Debug, Default)]
+pub struct MatchState {
+    pub records: Vec<GameRecord>,
+    /// Games won by each player.
+    pub wins: [usize; 2],
+    pub draws: usize,
+    /// Games lost by playing an illegal action, included in `wins`.
+    pub forfeits: [usize; 2],
+    /// Set once the match is over, `Some(None)` for a drawn match.
+    pub result: Option<Option<usize>>,
+}
+impl MatchState {
+    pub fn games(&self) -> usize {
+        self.records.len()
+    }
+    /// Points of `player`, a draw is worth half a win.
+    pub fn points(&self, player: usize) -> f64 {
+        self.wins[player] as f64 + self.draws as f64 / 2.0
+    }
+    pub fn is_finished(&self) -> bool {
+        self.result.is_some()
+    }
+    /// The player who won the match, `None` while playing or when it was drawn.
+    pub fn winner(&self) -> Option<usize> {
+        self.result.flatten()
+    }
+}
+
+/// Hooks called while a [`Match`] is played, every method defaults to doing nothing.
+pub trait MatchObserver {
+    /// Game `index` is about to start with `red` as the player with Red.
+    fn game_started(&mut self, _index: usize, _red: usize, _game: &Game) {}
+    /// Something happened in game `index`, see [`Game::take_events`].
+    fn event(&mut self, _index: usize, _event: &GameEvent) {}
+    /// Game `index` ended, `state` already counts it.
+    fn game_finished(&mut self, _index: usize, _state: &MatchState) {}
+    fn match_finished(&mut self, _state: &MatchState) {}
+}
+impl<T: MatchObserver + ?Sized> MatchObserver for &mut T {
+    fn game_started(&mut self, index: usize, red: usize, game: &Game) {
+        (**self).game_started(index, red, game)
+    }
+    fn event(&mut self, index: usize, event: &GameEvent) {
+        (**self).event(index, event)
+    }
+    fn game_finished(&mut self, index: usize, state: &MatchState) {
+        (**self).game_finished(index, state)
+    }
+    fn match_finished(&mut self, state: &MatchState) {
+        (**self).match_finished(state)
+    }
+}
+
+/// A series of games between two players.
+///
+/// Players make placements with [`Engine::choose_placement`], an illegal one forfeits.
+pub struct Match<'a> {
+    ruleset: Ruleset,
+    config: MatchConfig,
+    players: [Box<dyn Engine + 'a>; 2],
+    observers: Vec<Box<dyn MatchObserver + 'a>>,
+    state: MatchState,
+}
+impl<'a> Match<'a> {
+    pub fn new(
+        ruleset: Ruleset,
+        config: MatchConfig,
+        first: Box<dyn Engine + 'a>,
+        second: Box<dyn Engine + 'a>,
+    ) -> Self {
+        Self {
+            ruleset,
+            config,
+            players: [first, second],
+            observers: Vec::new(),
+            state: MatchState::default(),
+        }
+    }
+    pub fn with_observer(mut self, observer: Box<dyn MatchObserver + 'a>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    pub fn config(&self) -> &MatchConfig {
+        &self.config
+    }
+    pub fn state(&self) -> &MatchState {
+        &self.state
+    }
+    pub fn into_state(self) -> MatchState {
+        self.state
+    }
+    /// The player with Red in game `index`.
+    pub fn red_player(&self, index: usize) -> usize {
+        if self.config.swap_colors {
+            index % 2
+        } else {
+            0
+        }
+    }
+
+    /// Plays games until the match is over.
+    pub fn run(&mut self) -> GameResult<&MatchState> {
+        while !self.state.is_finished() {
+            self.play_game()?;
+        }
+        Ok(&self.state)
+    }
+    /// Plays the next game, returning its record, `None` if the match is already over.
+    pub fn play_game(&mut self) -> GameResult<Option<&GameRecord>> {
+        if self.state.is_finished() {
+            return Ok(None);
+        }
+        let index = self.state.games();
+        let red = self.red_player(index);
+        let mut game = Game::new(self.ruleset.clone())?;
+        for player in self.players.iter_mut() {
+            player.new_game(&game);
+        }
+        for observer in self.observers.iter_mut() {
+            observer.game_started(index, red, &game);
+        }
+
+        let mut forfeit = false;
+        let outcome = loop {
+            for event in game.take_events() {
+                for observer in self.observers.iter_mut() {
+                    observer.event(index, &event);
+                }
+            }
+            if let Some(outcome) = game.result() {
+                break outcome;
+            }
+            if let Some(color) = game.placement().and_then(PlacementPhase::to_place) {
+                let player = if color == Color::Red { red } else { 1 - red };
+                if play_placement(&mut *self.players[player], &mut game).is_err() {
+                    forfeit = true;
+                    break GameOutcome::Win(color.opponent());
+                }
+                continue;
+            }
+            if game.history().len() >= self.config.max_plies {
+                break GameOutcome::Draw;
+            }
+            let to_move = game.to_move();
+            let player = if to_move == Color::Red { red } else { 1 - red };
+            match self.players[player].choose_action(&game) {
+                Some(action) => {
+                    if game.apply(&action).is_err() {
+                        forfeit = true;
+                        break GameOutcome::Win(to_move.opponent());
+                    }
+                }
+                None => break GameOutcome::Win(to_move.opponent()),
+            }
+        };
+
+        match outcome {
+            GameOutcome::Win(color) => {
+                let winner = if color == Color::Red { red } else { 1 - red };
+                self.state.wins[winner] += 1;
+                if forfeit {
+                    self.state.forfeits[1 - winner] += 1;
+                }
+            }
+            GameOutcome::Draw => self.state.draws += 1,
+        }
+        let mut record = game.record();
+        record.result = Some(outcome);
+        self.state.records.push(record);
+        self.state.result = self.decide();
+
+        for observer in self.observers.iter_mut() {
+            observer.game_finished(index, &self.state);
+        }
+        if self.state.is_finished() {
+            for observer in self.observers.iter_mut() {
+                observer.match_finished(&self.state);
+            }
+        }
+        Ok(self.state.records.last())
+    }
+
+    /// The match result if it is over after the games so far.
+    fn decide(&self) -> Option<Option<usize>> {
+        let state = &self.state;
+        let played = state.games();
+        let leader = match state.wins[0].cmp(&state.wins[1]) {
+            core::cmp::Ordering::Greater => Some(0),
+            core::cmp::Ordering::Less => Some(1),
+            core::cmp::Ordering::Equal => None,
+        };
+        if played < self.config.games {
+            // The trailing player cannot catch up even winning every game left
+            let left = (self.config.games - played) as f64;
+            return leader
+                .filter(|&leader| state.points(leader) > state.points(1 - leader) + left)
+                .map(Some);
+        }
+        match (leader, self.config.tiebreak) {
+            (Some(leader), _) => Some(Some(leader)),
+            (None, Tiebreak::Draw) => Some(None),
+            (None, Tiebreak::SuddenDeath { max_games }) => {
+                if played >= self.config.games + max_games {
+                    Some(None)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+impl Debug for Match<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Match")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::boxed::Box;
+    use alloc::rc::Rc;
+    use alloc::vec::Vec;
+    use core::cell::RefCell;
+
+    use crate::action::Action;
+    use crate::engine::Engine;
+    use crate::game::event::GameEvent;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::series::{Match, MatchConfig, MatchObserver, MatchState, Tiebreak};
+    use crate::testing::small_ruleset;
+
+    /// Resigns every game.
+    struct Resigner;
+    impl Engine for Resigner {
+        fn choose_action(&mut self, _game: &Game) -> Option<Action> {
+            None
+        }
+    }
+
+    #[derive(Default)]
+    struct Log {
+        started: Vec<usize>,
+        actions: usize,
+        finished: Vec<(f64, f64)>,
+        result: Option<Option<usize>>,
+    }
+    struct Observer(Rc<RefCell<Log>>);
+    impl MatchObserver for Observer {
+        fn game_started(&mut self, _index: usize, red: usize, _game: &Game) {
+            self.0.borrow_mut().started.push(red);
+        }
+        fn event(&mut self, _index: usize, event: &GameEvent) {
+            if let GameEvent::Action { .. } = event {
+                self.0.borrow_mut().actions += 1;
+            }
+        }
+        fn game_finished(&mut self, _index: usize, state: &MatchState) {
+            self.0
+                .borrow_mut()
+                .finished
+                .push((state.points(0), state.points(1)));
+        }
+        fn match_finished(&mut self, state: &MatchState) {
+            self.0.borrow_mut().result = state.result;
+        }
+    }
+
+    #[test]
+    fn early_finish_test() {
+        let log = Rc::new(RefCell::new(Log::default()));
+        let mut series = Match::new(
+            small_ruleset(0),
+            MatchConfig {
+                games: 5,
+                ..MatchConfig::default()
+            },
+            Box::new(RandomPolicy::new(1)),
+            Box::new(Resigner),
+        )
+        .with_observer(Box::new(Observer(log.clone())));
+        let state = series.run().unwrap();
+        // Three wins out of five cannot be caught
+        assert_eq!(state.games(), 3);
+        assert_eq!(state.winner(), Some(0));
+        assert!(series.play_game().unwrap().is_none());
+
+        let log = log.borrow();
+        assert_eq!(log.started, vec![0, 1, 0]);
+        // The random player moves first in one of the first two games
+        assert!(log.actions > 0);
+        assert_eq!(log.finished, vec![(1.0, 0.0), (2.0, 0.0), (3.0, 0.0)]);
+        assert_eq!(log.result, Some(Some(0)));
+    }
+
+    #[test]
+    fn tiebreak_test() {
+        let config = MatchConfig {
+            games: 2,
+            max_plies: 0,
+            swap_colors: false,
+            tiebreak: Tiebreak::Draw,
+        };
+        let mut series = Match::new(
+            small_ruleset(0),
+            config,
+            Box::new(RandomPolicy::new(1)),
+            Box::new(RandomPolicy::new(2)),
+        );
+        let state = series.run().unwrap();
+        assert_eq!(state.draws, 2);
+        assert_eq!(state.result, Some(None));
+
+        let mut series = Match::new(
+            small_ruleset(0),
+            MatchConfig {
+                tiebreak: Tiebreak::SuddenDeath { max_games: 3 },
+                ..config
+            },
+            Box::new(RandomPolicy::new(1)),
+            Box::new(RandomPolicy::new(2)),
+        );
+        let state = series.run().unwrap();
+        assert_eq!(state.games(), 5);
+        assert_eq!(state.result, Some(None));
+    }
+}
@@ -0,0 +1,411 @@
+//! Proptest strategies and invariant checkers for fuzzing code built on kapto.
+//!
+//! Enabled by the `testing` feature.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use enum_iterator::IntoEnumIterator;
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::action::{Action, ActionType};
+use crate::collections::{HashMap, HashSet};
+use crate::coordinate::Coordinate;
+use crate::direction::{Direction, Directions};
+use crate::game_board::board::Board;
+use crate::game_board::{Color, GameBoard, PieceInstance};
+use crate::ruleset::board_type::goal_locations::GoalLocations;
+use crate::ruleset::board_type::BoardType;
+use crate::ruleset::pass_rule::PassRule;
+use crate::ruleset::piece_definition::{
+    CaptureRequirement, CaptureRule, CaptureTarget, CaptureTimingRule, ChainContinuationRule,
+    GoalMovementRule, JumpLimit, JumpRule, MoveRule, PieceDefinition,
+};
+use crate::ruleset::stalemate_rule::StalemateRule;
+use crate::ruleset::standard::standard_pieces;
+use crate::ruleset::starting_positions::placement_area::PlacementArea;
+use crate::ruleset::starting_positions::StartingPositions;
+use crate::ruleset::victory_condition::VictoryCondition;
+use crate::ruleset::Ruleset;
+
+pub fn arb_direction() -> impl Strategy<Value = Direction> {
+    let directions: Vec<Direction> = Direction::into_enum_iter().collect();
+    proptest::sample::select(directions)
+}
+/// Any non-empty set of directions.
+pub fn arb_directions() -> impl Strategy<Value = Directions> {
+    (1..=u8::MAX).prop_map(Directions::from_bits_truncate)
+}
+/// A piece of one of the standard definitions or a third custom one.
+pub fn arb_piece() -> impl Strategy<Value = PieceInstance> {
+    (0..3usize, prop_oneof![Just(Color::Red), Just(Color::Blue)])
+        .prop_map(|(definition_index, color)| PieceInstance::new(definition_index, color))
+}
+/// A coordinate within a board of `rows` by `columns`.
+pub fn arb_coordinate(rows: usize, columns: usize) -> impl Strategy<Value = Coordinate> {
+    (0..rows as i16, 0..columns as i16).prop_map(|(row, column)| Coordinate::new(row, column))
+}
+
+/// A move, a pass, or a jump chain of up to `max_jumps` starting anywhere on a `rows` by `columns` board.
+pub fn arb_action(rows: usize, columns: usize, max_jumps: usize) -> impl Strategy<Value = Action> {
+    let action_type = prop_oneof![
+        arb_direction().prop_map(ActionType::Move),
+        vec(arb_direction(), 1..=max_jumps.max(1))
+            .prop_map(|directions| ActionType::Jump(directions.into())),
+        Just(ActionType::Pass),
+    ];
+    (arb_coordinate(rows, columns), action_type).prop_map(|(start_pos, action_type)| Action {
+        start_pos,
+        action_type,
+    })
+}
+
+/// A board of up to `max_rows` by `max_columns` playable spaces with random pieces.
+pub fn arb_game_board(max_rows: usize, max_columns: usize) -> impl Strategy<Value = GameBoard> {
+    (1..=max_rows.max(1), 2..=max_columns.max(2))
+        .prop_flat_map(|(rows, columns)| {
+            let spaces = (rows + 2) * columns;
+            (
+                Just((rows, columns)),
+                vec(0..columns, 1..=columns),
+                vec(proptest::option::weighted(0.4, arb_piece()), spaces),
+            )
+        })
+        .prop_map(|(size, goal_pos, pieces)| {
+            let mut board = GameBoard::new(size, &goal_pos);
+            for (space, piece) in board.board.values.iter_mut().zip(pieces) {
+                if let Some(slot) = space.piece_mut() {
+                    *slot = piece
+                }
+            }
+            board
+        })
+}
+
+/// A board paired with an action that starts on it.
+pub fn arb_board_and_action(
+    max_rows: usize,
+    max_columns: usize,
+    max_jumps: usize,
+) -> impl Strategy<Value = (GameBoard, Action)> {
+    arb_game_board(max_rows, max_columns).prop_flat_map(move |board| {
+        let (rows, columns) = (board.board.rows, board.board.columns);
+        (Just(board), arb_action(rows, columns, max_jumps))
+    })
+}
+
+pub fn arb_goal_locations(columns: u8) -> impl Strategy<Value = GoalLocations> {
+    prop_oneof![
+        vec(0..columns, 0..=columns as usize)
+            .prop_map(|columns| GoalLocations::Columns(columns.into_iter().collect())),
+        (1..=columns).prop_map(|width| GoalLocations::Centered { width }),
+        (0..columns).prop_flat_map(move |start| {
+            (1..=columns - start).prop_map(move |width| GoalLocations::Strip { start, width })
+        }),
+    ]
+}
+/// A valid rectangular board of up to `max_rows` by `max_columns` playable spaces.
+pub fn arb_board_type(max_rows: u8, max_columns: u8) -> impl Strategy<Value = BoardType> {
+    (1..=max_rows.max(1), 2..=max_columns.max(2)).prop_flat_map(|(rows, columns)| {
+        (
+            1..=2u8,
+            arb_goal_locations(columns),
+            arb_goal_locations(columns),
+        )
+            .prop_map(
+                move |(goal_depth, red_goals, blue_goals)| BoardType::Rectangular {
+                    rows,
+                    columns,
+                    goal_depth,
+                    red_goals,
+                    blue_goals,
+                },
+            )
+    })
+}
+
+fn arb_capture_rule() -> impl Strategy<Value = CaptureRule> {
+    prop_oneof![
+        Just(CaptureRule::JumpOver),
+        Just(CaptureRule::JumpOn),
+        Just(CaptureRule::Move),
+    ]
+}
+fn arb_capture_target() -> impl Strategy<Value = CaptureTarget> {
+    prop_oneof![
+        Just(CaptureTarget::EnemyOnly),
+        Just(CaptureTarget::OwnOnly),
+        Just(CaptureTarget::All),
+    ]
+}
+fn arb_jump_limit() -> impl Strategy<Value = JumpLimit> {
+    prop_oneof![
+        arb_directions().prop_map(|directions| JumpLimit::Unlimited { directions }),
+        (1..5usize, arb_directions())
+            .prop_map(|(limit, directions)| JumpLimit::Limited { limit, directions }),
+        Just(JumpLimit::Cannot),
+    ]
+}
+fn arb_move_rule() -> impl Strategy<Value = MoveRule> {
+    prop_oneof![
+        (1..5usize, arb_directions())
+            .prop_map(|(limit, directions)| MoveRule::SameDirection { limit, directions }),
+        (1..5usize, arb_directions())
+            .prop_map(|(limit, directions)| MoveRule::AnyDirection { limit, directions }),
+        Just(MoveRule::None),
+    ]
+}
+
+/// A valid piece definition with the given name.
+pub fn arb_piece_definition(name: String) -> impl Strategy<Value = PieceDefinition> {
+    (
+        vec((arb_capture_rule(), arb_capture_target()), 0..3),
+        prop_oneof![
+            Just(JumpRule::NoBacktracking),
+            Just(JumpRule::NoSameStart),
+            Just(JumpRule::Open),
+        ],
+        prop_oneof![
+            Just(CaptureTimingRule::AfterJump),
+            Just(CaptureTimingRule::AfterTurn),
+        ],
+        prop_oneof![
+            (-10..10isize).prop_map(CaptureRequirement::Forced),
+            Just(CaptureRequirement::None),
+            Just(CaptureRequirement::Huff),
+        ],
+        arb_jump_limit(),
+        prop_oneof![
+            Just(ChainContinuationRule::MayStop),
+            Just(ChainContinuationRule::MustCapture),
+            Just(ChainContinuationRule::MustContinue),
+        ],
+        arb_move_rule(),
+        prop_oneof![
+            Just(GoalMovementRule::Locked),
+            Just(GoalMovementRule::OnlyToGoal),
+            Just(GoalMovementRule::Free),
+            Just(GoalMovementRule::NoOwnGoal),
+        ],
+    )
+        .prop_map(
+            move |(
+                capture_rules,
+                jump_rule,
+                capture_timing_rule,
+                capture_requirement,
+                jump_limit,
+                chain_rule,
+                move_rule,
+                goal_move_rule,
+            )| {
+                // Drop the capture rules the piece has no way to use
+                let capture_rules: HashMap<_, _> = capture_rules
+                    .into_iter()
+                    .filter(|(rule, _)| match rule {
+                        CaptureRule::JumpOver | CaptureRule::JumpOn => {
+                            jump_limit != JumpLimit::Cannot
+                        }
+                        CaptureRule::Move => move_rule != MoveRule::None,
+                    })
+                    .collect();
+                let capture_requirement = if capture_rules.is_empty() {
+                    CaptureRequirement::None
+                } else {
+                    capture_requirement
+                };
+                let chain_rule = if chain_rule == ChainContinuationRule::MustCapture
+                    && !capture_rules.contains_key(&CaptureRule::JumpOver)
+                {
+                    ChainContinuationRule::MayStop
+                } else {
+                    chain_rule
+                };
+                PieceDefinition {
+                    name: name.clone(),
+                    capture_rules,
+                    jump_rule,
+                    capture_timing_rule,
+                    capture_requirement,
+                    jump_limit,
+                    chain_rule,
+                    move_rule,
+                    goal_move_rule,
+                    owner: None,
+                }
+            },
+        )
+}
+
+/// A ruleset that passes verification, using random starting positions.
+pub fn arb_ruleset() -> impl Strategy<Value = Ruleset> {
+    (1..=3usize)
+        .prop_flat_map(|piece_count| {
+            let pieces: Vec<_> = (0..piece_count)
+                .map(|index| arb_piece_definition(alloc::format!("PieceInstance {}", index)))
+                .collect();
+            (
+                pieces,
+                arb_board_type(8, 8),
+                any::<u64>(),
+                vec(0..3usize, piece_count),
+                prop_oneof![
+                    Just(StalemateRule::Lose),
+                    Just(StalemateRule::Draw),
+                    Just(StalemateRule::Pass),
+                ],
+                prop_oneof![
+                    Just(PassRule::Never),
+                    Just(PassRule::WhenBlocked),
+                    Just(PassRule::Always),
+                ],
+            )
+        })
+        .prop_map(
+            |(pieces, board_type, seed, counts, stalemate_rule, pass_rule)| {
+                let piece_counts: HashMap<usize, usize> = counts.into_iter().enumerate().collect();
+                let mut victory_conditions = HashSet::new();
+                victory_conditions.insert(VictoryCondition::AllCaptured);
+                Ruleset {
+                    pieces,
+                    board_type,
+                    starting_positions: StartingPositions::Random {
+                        seed,
+                        piece_counts,
+                        area: PlacementArea::Half,
+                    },
+                    victory_conditions,
+                    stalemate_rule,
+                    pass_rule,
+                    scoring: Default::default(),
+                    game_length_rule: Default::default(),
+                    chance_rule: Default::default(),
+                }
+            },
+        )
+        .prop_filter("Ruleset must verify", |ruleset| ruleset.verify().is_ok())
+}
+
+/// A small playable ruleset with the standard pieces placed randomly from `seed`.
+pub fn small_ruleset(seed: u64) -> Ruleset {
+    let mut victory_conditions = HashSet::new();
+    victory_conditions.insert(VictoryCondition::AllCaptured);
+    let mut piece_counts = HashMap::new();
+    piece_counts.insert(0, 2);
+    piece_counts.insert(1, 3);
+    Ruleset {
+        pieces: standard_pieces(),
+        board_type: BoardType::Rectangular {
+            rows: 6,
+            columns: 6,
+            goal_depth: 1,
+            red_goals: GoalLocations::Centered { width: 2 },
+            blue_goals: GoalLocations::Centered { width: 2 },
+        },
+        starting_positions: StartingPositions::Random {
+            seed,
+            piece_counts,
+            area: PlacementArea::Half,
+        },
+        victory_conditions,
+        stalemate_rule: Default::default(),
+        pass_rule: Default::default(),
+        scoring: Default::default(),
+        game_length_rule: Default::default(),
+        chance_rule: Default::default(),
+    }
+}
+
+/// Panics if the board's storage or piece bookkeeping is inconsistent.
+pub fn assert_board_consistent(board: &GameBoard) {
+    assert_eq!(
+        board.board.values.len(),
+        board.board.rows * board.board.columns,
+        "Board storage does not match its dimensions"
+    );
+    let pieces = board
+        .board
+        .values
+        .iter()
+        .filter(|space| matches!(space.piece(), Some(Some(_))))
+        .count();
+    let by_color: usize = Color::into_enum_iter()
+        .map(|color| board.pieces_of_color(color).len())
+        .sum();
+    let definitions = Color::into_enum_iter()
+        .flat_map(|color| board.pieces_of_color(color))
+        .map(|(_, piece)| piece.definition_index() + 1)
+        .max()
+        .unwrap_or(0);
+    let by_definition: usize = (0..definitions)
+        .map(|definition_index| board.pieces_of_definition(definition_index).len())
+        .sum();
+    assert_eq!(pieces, by_color, "Pieces by color do not add up");
+    assert_eq!(pieces, by_definition, "Pieces by definition do not add up");
+}
+
+/// Applies `action` and undoes it from the reported captures, panicking if the result differs.
+///
+/// Invalid actions must leave the board untouched.
+pub fn assert_apply_round_trip(board: &GameBoard, action: &Action) {
+    let (applied, outcome) = match board.apply_action(action) {
+        Ok(applied) => applied,
+        Err(_) => {
+            assert!(board.is_valid_action(action).is_err());
+            return;
+        }
+    };
+    assert_board_consistent(&applied);
+
+    if action.action_type.is_pass() {
+        assert_eq!(applied.board, board.board, "Passing changed the board");
+        return;
+    }
+    let end_pos = board
+        .destination(action)
+        .expect("Applied actions have a destination");
+    let mut undone = applied;
+    let piece = undone
+        .piece_mut(end_pos)
+        .expect("End position must be valid")
+        .take()
+        .expect("PieceInstance must be at the end position");
+    *undone.piece_mut(action.start_pos).unwrap() = Some(piece);
+    for (position, captured) in outcome.captures {
+        let slot = undone.piece_mut(position).unwrap();
+        assert!(slot.is_none(), "Captured position still has a piece");
+        *slot = Some(captured);
+    }
+    assert_eq!(
+        undone.board, board.board,
+        "Undoing the action did not restore the board"
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::testing::{
+        arb_board_and_action, arb_game_board, arb_ruleset, assert_apply_round_trip,
+        assert_board_consistent,
+    };
+
+    proptest! {
+        #[test]
+        fn board_consistent_test(board in arb_game_board(8, 8)) {
+            assert_board_consistent(&board);
+        }
+
+        #[test]
+        fn apply_round_trip_test((board, action) in arb_board_and_action(6, 6, 4)) {
+            assert_apply_round_trip(&board, &action);
+        }
+
+        #[test]
+        fn ruleset_verifies_test(ruleset in arb_ruleset()) {
+            prop_assert!(ruleset.verify().is_ok());
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Matches between two engines for measuring strength.
+
+use std::vec::Vec;
+
+use crate::action::Action;
+use crate::engine::{play_placement, Engine};
+use crate::game::placement::PlacementPhase;
+use crate::game::record::GameRecord;
+use crate::game::{Game, GameError, GameOutcome, GameResult};
+use crate::game_board::Color;
+use crate::ruleset::Ruleset;
+
+/// How a tournament is played.
+#[derive(Clone, Debug)]
+pub struct TournamentConfig {
+    /// Most games to play, colors alternate every game.
+    pub games: usize,
+    /// Games reaching this many plies are drawn.
+    pub max_plies: usize,
+    /// Actions played before the engines take over.
+    /// Each opening is played twice in a row so both engines get both colors.
+    pub openings: Vec<Vec<Action>>,
+    /// Stops early once the result is statistically clear.
+    pub sprt: Option<Sprt>,
+}
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            max_plies: 500,
+            openings: Vec::new(),
+            sprt: None,
+        }
+    }
+}
+
+/// Sequential probability ratio test between two Elo hypotheses for the first engine.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sprt {
+    /// Elo difference of the null hypothesis.
+    pub elo0: f64,
+    /// Elo difference of the alternative hypothesis.
+    pub elo1: f64,
+    /// Chance of accepting `elo1` when `elo0` holds.
+    pub alpha: f64,
+    /// Chance of accepting `elo0` when `elo1` holds.
+    pub beta: f64,
+}
+impl Sprt {
+    /// The log likelihood ratio of `elo1` against `elo0` from the first engine's results.
+    ///
+    /// Uses the normal approximation to the trinomial distribution.
+    pub fn llr(&self, wins: usize, draws: usize, losses: usize) -> f64 {
+        let games = (wins + draws + losses) as f64;
+        if wins == 0 || losses == 0 {
+            // Variance is degenerate until both results have happened
+            return 0.0;
+        }
+        let score = (wins as f64 + draws as f64 / 2.0) / games;
+        let variance = (wins as f64 * (1.0 - score).powi(2)
+            + draws as f64 * (0.5 - score).powi(2)
+            + losses as f64 * score.powi(2))
+            / games;
+        let score0 = expected_score(self.elo0);
+        let score1 = expected_score(self.elo1);
+        games * (score1 - score0) * (2.0 * score - score0 - score1) / (2.0 * variance)
+    }
+
+    pub fn decision(&self, wins: usize, draws: usize, losses: usize) -> Option<SprtDecision> {
+        let llr = self.llr(wins, draws, losses);
+        if llr >= ((1.0 - self.beta) / self.alpha).ln() {
+            Some(SprtDecision::AcceptElo1)
+        } else if llr <= (self.beta / (1.0 - self.alpha)).ln() {
+            Some(SprtDecision::AcceptElo0)
+        } else {
+            None
+        }
+    }
+}
+fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SprtDecision {
+    AcceptElo0,
+    AcceptElo1,
+}
+
+/// The results of a tournament, counted from the first engine's perspective.
+#[derive(Clone, Debug, Default)]
+pub struct TournamentReport {
+    pub records: Vec<GameRecord>,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// Games lost by playing an illegal action, included in `wins` and `losses`.
+    pub forfeits: usize,
+    /// Set when the tournament stopped early.
+    pub sprt: Option<SprtDecision>,
+}
+impl TournamentReport {
+    pub fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+    /// Points per game for the first engine, a draw is worth half a win.
+    pub fn score(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games() as f64
+    }
+}
+
+/// Plays `first` against `second` under `config`.
+pub fn run(
+    ruleset: &Ruleset,
+    first: &mut impl Engine,
+    second: &mut impl Engine,
+    config: &TournamentConfig,
+) -> GameResult<TournamentReport> {
+    let start = Game::new(ruleset.clone())?;
+    let mut report = TournamentReport::default();
+    for index in 0..config.games {
+        let first_color = if index % 2 == 0 {
+            Color::Red
+        } else {
+            Color::Blue
+        };
+        let opening = match config.openings.len() {
+            0 => &[][..],
+            count => &config.openings[(index / 2) % count][..],
+        };
+        let (record, forfeit) = play_game(
+            &start,
+            opening,
+            first_color,
+            first,
+            second,
+            config.max_plies,
+        )?;
+
+        match record.result {
+            Some(GameOutcome::Win(color)) if color == first_color => report.wins += 1,
+            Some(GameOutcome::Win(_)) => report.losses += 1,
+            Some(GameOutcome::Draw) | None => report.draws += 1,
+        }
+        if forfeit {
+            report.forfeits += 1;
+        }
+        report.records.push(record);
+
+        if let Some(sprt) = &config.sprt {
+            report.sprt = sprt.decision(report.wins, report.draws, report.losses);
+            if report.sprt.is_some() {
+                break;
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Plays one game, returning its record and whether it ended by forfeit.
+fn play_game(
+    start: &Game,
+    opening: &[Action],
+    first_color: Color,
+    first: &mut impl Engine,
+    second: &mut impl Engine,
+    max_plies: usize,
+) -> GameResult<(GameRecord, bool)> {
+    let mut game = start.clone();
+    for (ply, action) in opening.iter().enumerate() {
+        game.apply(action)
+            .map_err(|error| GameError::IllegalAction { ply, error })?;
+    }
+    first.new_game(&game);
+    second.new_game(&game);
+
+    let mut forfeit = false;
+    let result = loop {
+        if let Some(outcome) = game.result() {
+            break outcome;
+        }
+        if let Some(color) = game.placement().and_then(PlacementPhase::to_place) {
+            let placed = if color == first_color {
+                play_placement(first, &mut game)
+            } else {
+                play_placement(second, &mut game)
+            };
+            if placed.is_err() {
+                forfeit = true;
+                break GameOutcome::Win(color.opponent());
+            }
+            continue;
+        }
+        if game.history().len() >= max_plies {
+            break GameOutcome::Draw;
+        }
+        let to_move = game.to_move();
+        let action = if to_move == first_color {
+            first.choose_action(&game)
+        } else {
+            second.choose_action(&game)
+        };
+        match action {
+            Some(action) => {
+                if game.apply(&action).is_err() {
+                    forfeit = true;
+                    break GameOutcome::Win(to_move.opponent());
+                }
+            }
+            None => break GameOutcome::Win(to_move.opponent()),
+        }
+    };
+
+    let mut record = game.record();
+    record.result = Some(result);
+    Ok((record, forfeit))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::playout::RandomPolicy;
+    use crate::testing::small_ruleset;
+    use crate::tournament::{run, Sprt, SprtDecision, TournamentConfig};
+
+    #[test]
+    fn random_tournament_test() {
+        let config = TournamentConfig {
+            games: 6,
+            max_plies: 200,
+            ..TournamentConfig::default()
+        };
+        let report = run(
+            &small_ruleset(4),
+            &mut RandomPolicy::new(1),
+            &mut RandomPolicy::new(2),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(report.games(), 6);
+        assert_eq!(report.records.len(), 6);
+        assert_eq!(report.forfeits, 0);
+        assert!(report.records.iter().all(|record| record.result.is_some()));
+        assert!(report.sprt.is_none());
+    }
+
+    #[test]
+    fn sprt_test() {
+        let sprt = Sprt {
+            elo0: 0.0,
+            elo1: 10.0,
+            alpha: 0.05,
+            beta: 0.05,
+        };
+        assert_eq!(sprt.decision(1, 0, 1), None);
+        assert_eq!(sprt.decision(300, 50, 100), Some(SprtDecision::AcceptElo1));
+        assert_eq!(sprt.decision(100, 50, 300), Some(SprtDecision::AcceptElo0));
+    }
+}
@@ -0,0 +1,184 @@
+//! Offline tuning of [`EvalParams`] from finished games.
+//!
+//! Texel style: every position of every game is labeled with the game's result,
+//! the weights are then searched to minimize the mean squared error
+//! between the labels and the logistic of the evaluation.
+
+use std::vec::Vec;
+
+use crate::eval::{EvalParams, ParamEvaluator};
+use crate::game::record::GameRecord;
+use crate::game::{GameError, GameOutcome, GameResult};
+use crate::game_board::Color;
+
+/// How [`tune`] searches.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TunerConfig {
+    /// Multiplies evaluations before the logistic, smaller treats scores as less decisive.
+    pub scale: f64,
+    /// Most passes over every weight.
+    pub max_iterations: usize,
+    /// Amount each weight is first moved by, halved whenever a pass makes no progress.
+    pub initial_step: i32,
+}
+impl Default for TunerConfig {
+    fn default() -> Self {
+        Self {
+            scale: 0.005,
+            max_iterations: 200,
+            initial_step: 16,
+        }
+    }
+}
+
+/// A position from a finished game.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TrainingPosition {
+    /// Red's [`ParamEvaluator::features`] minus Blue's.
+    pub features: [i32; 5],
+    /// Red's score in the game, `1.0` for a win, `0.5` for a draw, `0.0` for a loss.
+    pub result: f64,
+}
+
+/// The best parameters found by [`tune`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TuneReport {
+    pub params: EvalParams,
+    /// Mean squared error of `params`.
+    pub error: f64,
+    pub positions: usize,
+    pub iterations: usize,
+}
+
+/// Every position before each action of the finished games in `records`.
+///
+/// Games without a result are skipped.
+pub fn positions(records: &[GameRecord]) -> GameResult<Vec<TrainingPosition>> {
+    let mut out = Vec::new();
+    for record in records {
+        let result = match record.result {
+            Some(GameOutcome::Win(Color::Red)) => 1.0,
+            Some(GameOutcome::Win(Color::Blue)) => 0.0,
+            Some(GameOutcome::Draw) => 0.5,
+            None => continue,
+        };
+        let mut game = GameRecord {
+            actions: Vec::new(),
+            ..record.clone()
+        }
+        .replay()?;
+        for (ply, action) in record.actions.iter().enumerate() {
+            let red = ParamEvaluator::features(game.board(), Color::Red);
+            let blue = ParamEvaluator::features(game.board(), Color::Blue);
+            let mut features = [0; 5];
+            for (index, feature) in features.iter_mut().enumerate() {
+                *feature = red[index] - blue[index];
+            }
+            out.push(TrainingPosition { features, result });
+            game.apply(action)
+                .map_err(|error| GameError::IllegalAction { ply, error })?;
+        }
+    }
+    Ok(out)
+}
+
+/// Mean squared error of `params` predicting the results of `positions`.
+pub fn error(positions: &[TrainingPosition], params: &EvalParams, scale: f64) -> f64 {
+    if positions.is_empty() {
+        return 0.0;
+    }
+    let weights = params.weights();
+    let total: f64 = positions
+        .iter()
+        .map(|position| {
+            let score: f64 = weights
+                .iter()
+                .zip(position.features.iter())
+                .map(|(&weight, &feature)| weight as f64 * feature as f64)
+                .sum();
+            let predicted = 1.0 / (1.0 + (-scale * score).exp());
+            (position.result - predicted).powi(2)
+        })
+        .sum();
+    total / positions.len() as f64
+}
+
+/// Searches for the weights that best predict the results of `records`, starting from `initial`.
+pub fn tune(
+    records: &[GameRecord],
+    initial: EvalParams,
+    config: &TunerConfig,
+) -> GameResult<TuneReport> {
+    let positions = positions(records)?;
+    let mut weights = initial.weights();
+    let mut best = error(&positions, &initial, config.scale);
+    let mut step = config.initial_step.max(1);
+    let mut iterations = 0;
+    while iterations < config.max_iterations {
+        iterations += 1;
+        let mut improved = false;
+        for index in 0..weights.len() {
+            for delta in [step, -step] {
+                let mut candidate = weights;
+                candidate[index] += delta;
+                let candidate_error = error(
+                    &positions,
+                    &EvalParams::from_weights(candidate),
+                    config.scale,
+                );
+                if candidate_error < best {
+                    best = candidate_error;
+                    weights = candidate;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        if !improved {
+            if step == 1 {
+                break;
+            }
+            step /= 2;
+        }
+    }
+    Ok(TuneReport {
+        params: EvalParams::from_weights(weights),
+        error: best,
+        positions: positions.len(),
+        iterations,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::eval::EvalParams;
+    use crate::game::playout::RandomPolicy;
+    use crate::game::Game;
+    use crate::testing::small_ruleset;
+    use crate::tuner::{error, positions, tune, TunerConfig};
+
+    #[test]
+    fn tune_test() {
+        let records: Vec<_> = (0..8)
+            .map(|seed| {
+                let mut playout = Game::new(small_ruleset(seed))
+                    .unwrap()
+                    .playout(RandomPolicy::new(seed));
+                playout.by_ref().take(400).for_each(drop);
+                playout.game().record()
+            })
+            .collect();
+        let positions = positions(&records).unwrap();
+        assert!(!positions.is_empty());
+
+        let config = TunerConfig::default();
+        let initial = EvalParams::from_weights([0; 5]);
+        let report = tune(&records, initial, &config).unwrap();
+        assert_eq!(report.positions, positions.len());
+        assert!(report.error <= error(&positions, &initial, config.scale));
+        assert_eq!(
+            report.error,
+            error(&positions, &report.params, config.scale)
+        );
+    }
+}